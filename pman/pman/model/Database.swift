@@ -121,7 +121,7 @@ struct Database: Equatable, Identifiable {
             let hash2 = if secondPassword == nil { nil as Data? } else {Data(SHA256.hash(data: secondPassword!.data(using: .utf8)!))}
             try preOpen(databaseId: dbId!, passwordHash: hash1, password2Hash: hash2, keyFileContents: keyData)
             try open(databaseId: dbId!)
-            users = try getUsers(databaseId: dbId!)
+            users = try getUsers(databaseId: dbId!).mapValues { $0.name }
             try refreshGroups()
             isOpened = true
         } catch PmanError.ErrorMessage(let e) {
@@ -44,7 +44,7 @@ data class DBEntity(val id: UInt, val entity: DatabaseEntity?) {
     }
 
     fun getPropertyNames() {
-        propertyNames = entity?.getPropertyNames(0U) ?: mapOf()
+        propertyNames = entity?.getPropertyNames(0U)?.associate { it.name to it.id } ?: mapOf()
         propertyFields = propertyNames.map {
             it.value.toInt() to
                     Pair(
@@ -216,7 +216,7 @@ data class Database(
             val dbGroups = uniffi.pman_lib.getGroups(id)
             groups = dbGroups.map { DBGroup(it.getId(), it.getName(), it.getEntitiesCount()) }
                 .sortedBy { it.name }.toMutableStateList()
-            users = uniffi.pman_lib.getUsers(id)
+            users = uniffi.pman_lib.getUsers(id).mapValues { it.value.name }
         } catch (e: PmanException) {
             return e.toString()
         }
@@ -235,7 +235,8 @@ data class Database(
                 } else {
                     entity.urlField.value.value
                 },
-                entity.buildProperties()
+                entity.buildProperties(),
+                null
             )
             val dbGroups = uniffi.pman_lib.getGroups(id)
             groups = dbGroups.map { DBGroup(it.getId(), it.getName(), it.getEntitiesCount()) }
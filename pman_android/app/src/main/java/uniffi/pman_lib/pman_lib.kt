@@ -385,22 +385,40 @@ internal interface _UniFFILib : Library {
 
     fun uniffi_pman_lib_fn_free_databaseentity(`ptr`: Pointer,_uniffi_out_err: RustCallStatus, 
     ): Unit
+    fun uniffi_pman_lib_fn_method_databaseentity_get_attachment_names(`ptr`: Pointer,`version`: Int,_uniffi_out_err: RustCallStatus, 
+    ): RustBuffer.ByValue
     fun uniffi_pman_lib_fn_method_databaseentity_get_created_at(`ptr`: Pointer,`version`: Int,_uniffi_out_err: RustCallStatus, 
     ): Long
+    fun uniffi_pman_lib_fn_method_databaseentity_get_expires_at(`ptr`: Pointer,`version`: Int,_uniffi_out_err: RustCallStatus, 
+    ): RustBuffer.ByValue
     fun uniffi_pman_lib_fn_method_databaseentity_get_group_id(`ptr`: Pointer,`version`: Int,_uniffi_out_err: RustCallStatus, 
     ): Int
+    fun uniffi_pman_lib_fn_method_databaseentity_get_history_length(`ptr`: Pointer,_uniffi_out_err: RustCallStatus, 
+    ): RustBuffer.ByValue
+    fun uniffi_pman_lib_fn_method_databaseentity_get_kind(`ptr`: Pointer,_uniffi_out_err: RustCallStatus, 
+    ): RustBuffer.ByValue
+    fun uniffi_pman_lib_fn_method_databaseentity_get_max_age_days(`ptr`: Pointer,`version`: Int,_uniffi_out_err: RustCallStatus, 
+    ): RustBuffer.ByValue
     fun uniffi_pman_lib_fn_method_databaseentity_get_max_version(`ptr`: Pointer,_uniffi_out_err: RustCallStatus, 
     ): Int
     fun uniffi_pman_lib_fn_method_databaseentity_get_name(`ptr`: Pointer,_uniffi_out_err: RustCallStatus, 
     ): RustBuffer.ByValue
+    fun uniffi_pman_lib_fn_method_databaseentity_get_otp_code(`ptr`: Pointer,`version`: Int,`timestamp`: Long,_uniffi_out_err: RustCallStatus, 
+    ): RustBuffer.ByValue
     fun uniffi_pman_lib_fn_method_databaseentity_get_password(`ptr`: Pointer,`version`: Int,_uniffi_out_err: RustCallStatus, 
     ): RustBuffer.ByValue
     fun uniffi_pman_lib_fn_method_databaseentity_get_property_names(`ptr`: Pointer,`version`: Int,_uniffi_out_err: RustCallStatus, 
     ): RustBuffer.ByValue
     fun uniffi_pman_lib_fn_method_databaseentity_get_property_value(`ptr`: Pointer,`version`: Int,`id`: Int,_uniffi_out_err: RustCallStatus, 
     ): RustBuffer.ByValue
+    fun uniffi_pman_lib_fn_method_databaseentity_get_tag_ids(`ptr`: Pointer,`version`: Int,_uniffi_out_err: RustCallStatus, 
+    ): RustBuffer.ByValue
+    fun uniffi_pman_lib_fn_method_databaseentity_get_template_id(`ptr`: Pointer,`version`: Int,_uniffi_out_err: RustCallStatus, 
+    ): RustBuffer.ByValue
     fun uniffi_pman_lib_fn_method_databaseentity_get_url(`ptr`: Pointer,`version`: Int,_uniffi_out_err: RustCallStatus, 
     ): RustBuffer.ByValue
+    fun uniffi_pman_lib_fn_method_databaseentity_get_urls(`ptr`: Pointer,`version`: Int,_uniffi_out_err: RustCallStatus, 
+    ): RustBuffer.ByValue
     fun uniffi_pman_lib_fn_method_databaseentity_get_user_id(`ptr`: Pointer,`version`: Int,_uniffi_out_err: RustCallStatus, 
     ): Int
     fun uniffi_pman_lib_fn_free_databasegroup(`ptr`: Pointer,_uniffi_out_err: RustCallStatus, 
@@ -409,58 +427,182 @@ internal interface _UniFFILib : Library {
     ): Int
     fun uniffi_pman_lib_fn_method_databasegroup_get_id(`ptr`: Pointer,_uniffi_out_err: RustCallStatus, 
     ): Int
+    fun uniffi_pman_lib_fn_method_databasegroup_get_max_age_days(`ptr`: Pointer,_uniffi_out_err: RustCallStatus, 
+    ): RustBuffer.ByValue
     fun uniffi_pman_lib_fn_method_databasegroup_get_name(`ptr`: Pointer,_uniffi_out_err: RustCallStatus, 
     ): RustBuffer.ByValue
-    fun uniffi_pman_lib_fn_func_add_entity(`databaseId`: Long,`name`: RustBuffer.ByValue,`groupId`: Int,`userId`: Int,`password`: RustBuffer.ByValue,`url`: RustBuffer.ByValue,`properties`: RustBuffer.ByValue,_uniffi_out_err: RustCallStatus, 
+    fun uniffi_pman_lib_fn_method_databasegroup_get_parent_id(`ptr`: Pointer,_uniffi_out_err: RustCallStatus, 
+    ): RustBuffer.ByValue
+    fun uniffi_pman_lib_fn_method_databasegroup_get_total_entities_count(`ptr`: Pointer,_uniffi_out_err: RustCallStatus, 
+    ): Int
+    fun uniffi_pman_lib_fn_func_add_attachment(`databaseId`: Long,`entityId`: Int,`name`: RustBuffer.ByValue,`data`: RustBuffer.ByValue,_uniffi_out_err: RustCallStatus, 
+    ): Int
+    fun uniffi_pman_lib_fn_func_add_child_group(`databaseId`: Long,`parentId`: Int,`name`: RustBuffer.ByValue,_uniffi_out_err: RustCallStatus, 
+    ): Int
+    fun uniffi_pman_lib_fn_func_add_entity(`databaseId`: Long,`name`: RustBuffer.ByValue,`groupId`: Int,`userId`: Int,`password`: RustBuffer.ByValue,`url`: RustBuffer.ByValue,`properties`: RustBuffer.ByValue,`templateId`: RustBuffer.ByValue,_uniffi_out_err: RustCallStatus, 
     ): Int
     fun uniffi_pman_lib_fn_func_add_group(`databaseId`: Long,`name`: RustBuffer.ByValue,_uniffi_out_err: RustCallStatus, 
     ): Int
+    fun uniffi_pman_lib_fn_func_add_key_file_slot(`databaseId`: Long,`keyFile`: RustBuffer.ByValue,_uniffi_out_err: RustCallStatus, 
+    ): Int
+    fun uniffi_pman_lib_fn_func_add_member(`databaseId`: Long,`name`: RustBuffer.ByValue,`publicKey`: RustBuffer.ByValue,_uniffi_out_err: RustCallStatus, 
+    ): Int
+    fun uniffi_pman_lib_fn_func_add_note(`databaseId`: Long,`name`: RustBuffer.ByValue,`groupId`: Int,`body`: RustBuffer.ByValue,_uniffi_out_err: RustCallStatus, 
+    ): Int
+    fun uniffi_pman_lib_fn_func_add_recovery_key(`databaseId`: Long,_uniffi_out_err: RustCallStatus, 
+    ): RustBuffer.ByValue
+    fun uniffi_pman_lib_fn_func_add_tag(`databaseId`: Long,`entityId`: Int,`tag`: RustBuffer.ByValue,_uniffi_out_err: RustCallStatus, 
+    ): Int
+    fun uniffi_pman_lib_fn_func_add_template(`databaseId`: Long,`name`: RustBuffer.ByValue,`fields`: RustBuffer.ByValue,_uniffi_out_err: RustCallStatus, 
+    ): Int
     fun uniffi_pman_lib_fn_func_add_user(`databaseId`: Long,`name`: RustBuffer.ByValue,_uniffi_out_err: RustCallStatus, 
     ): Int
+    fun uniffi_pman_lib_fn_func_build_totp_uri(`secret`: RustBuffer.ByValue,`algorithm`: RustBuffer.ByValue,`digits`: Int,`period`: Long,_uniffi_out_err: RustCallStatus, 
+    ): RustBuffer.ByValue
+    fun uniffi_pman_lib_fn_func_check_integrity(`databaseId`: Long,`repair`: Byte,_uniffi_out_err: RustCallStatus, 
+    ): RustBuffer.ByValue
     fun uniffi_pman_lib_fn_func_close(`databaseId`: Long,_uniffi_out_err: RustCallStatus, 
     ): Unit
     fun uniffi_pman_lib_fn_func_create(`databaseType`: RustBuffer.ByValue,`passwordHash`: RustBuffer.ByValue,`password2Hash`: RustBuffer.ByValue,`keyFileContents`: RustBuffer.ByValue,`fileName`: RustBuffer.ByValue,_uniffi_out_err: RustCallStatus, 
     ): Long
+    fun uniffi_pman_lib_fn_func_disable_core_dumps(_uniffi_out_err: RustCallStatus, 
+    ): Unit
+    fun uniffi_pman_lib_fn_func_empty_trash(`databaseId`: Long,_uniffi_out_err: RustCallStatus, 
+    ): Unit
+    fun uniffi_pman_lib_fn_func_export_to_new_database(`databaseId`: Long,`groupIds`: RustBuffer.ByValue,`passwordHash`: RustBuffer.ByValue,`password2Hash`: RustBuffer.ByValue,`keyFileContents`: RustBuffer.ByValue,`fileName`: RustBuffer.ByValue,_uniffi_out_err: RustCallStatus, 
+    ): Long
+    fun uniffi_pman_lib_fn_func_find_by_url(`databaseId`: Long,`url`: RustBuffer.ByValue,_uniffi_out_err: RustCallStatus, 
+    ): RustBuffer.ByValue
+    fun uniffi_pman_lib_fn_func_generate_member_key_pair(_uniffi_out_err: RustCallStatus, 
+    ): RustBuffer.ByValue
+    fun uniffi_pman_lib_fn_func_get_attachment(`databaseId`: Long,`entityId`: Int,`version`: Int,`attachmentId`: Int,_uniffi_out_err: RustCallStatus, 
+    ): RustBuffer.ByValue
+    fun uniffi_pman_lib_fn_func_get_audit_log(`databaseId`: Long,`since`: RustBuffer.ByValue,`objectId`: RustBuffer.ByValue,_uniffi_out_err: RustCallStatus, 
+    ): RustBuffer.ByValue
+    fun uniffi_pman_lib_fn_func_get_audit_settings(`databaseId`: Long,_uniffi_out_err: RustCallStatus, 
+    ): RustBuffer.ByValue
+    fun uniffi_pman_lib_fn_func_get_database_version(`databaseId`: Long,_uniffi_out_err: RustCallStatus, 
+    ): RustBuffer.ByValue
     fun uniffi_pman_lib_fn_func_get_entities(`databaseId`: Long,`groupId`: Int,_uniffi_out_err: RustCallStatus, 
     ): RustBuffer.ByValue
+    fun uniffi_pman_lib_fn_func_get_entities_by_tags(`databaseId`: Long,`tagIds`: RustBuffer.ByValue,_uniffi_out_err: RustCallStatus, 
+    ): RustBuffer.ByValue
+    fun uniffi_pman_lib_fn_func_get_expiring(`databaseId`: Long,`days`: Int,_uniffi_out_err: RustCallStatus, 
+    ): RustBuffer.ByValue
     fun uniffi_pman_lib_fn_func_get_groups(`databaseId`: Long,_uniffi_out_err: RustCallStatus, 
     ): RustBuffer.ByValue
+    fun uniffi_pman_lib_fn_func_get_history_length(`databaseId`: Long,_uniffi_out_err: RustCallStatus, 
+    ): Int
+    fun uniffi_pman_lib_fn_func_get_key_slots(`databaseId`: Long,_uniffi_out_err: RustCallStatus, 
+    ): RustBuffer.ByValue
+    fun uniffi_pman_lib_fn_func_get_members(`databaseId`: Long,_uniffi_out_err: RustCallStatus, 
+    ): RustBuffer.ByValue
+    fun uniffi_pman_lib_fn_func_get_tags(`databaseId`: Long,_uniffi_out_err: RustCallStatus, 
+    ): RustBuffer.ByValue
+    fun uniffi_pman_lib_fn_func_get_templates(`databaseId`: Long,_uniffi_out_err: RustCallStatus, 
+    ): RustBuffer.ByValue
+    fun uniffi_pman_lib_fn_func_get_trash(`databaseId`: Long,_uniffi_out_err: RustCallStatus, 
+    ): RustBuffer.ByValue
+    fun uniffi_pman_lib_fn_func_get_trash_retention(`databaseId`: Long,_uniffi_out_err: RustCallStatus, 
+    ): Int
     fun uniffi_pman_lib_fn_func_get_users(`databaseId`: Long,_uniffi_out_err: RustCallStatus, 
     ): RustBuffer.ByValue
+    fun uniffi_pman_lib_fn_func_is_audit_pending(`databaseId`: Long,_uniffi_out_err: RustCallStatus, 
+    ): Byte
     fun uniffi_pman_lib_fn_func_is_read_only(`databaseId`: Long,_uniffi_out_err: RustCallStatus, 
     ): Byte
     fun uniffi_pman_lib_fn_func_lib_init(_uniffi_out_err: RustCallStatus, 
     ): Unit
+    fun uniffi_pman_lib_fn_func_list_entities(`databaseId`: Long,`groupId`: RustBuffer.ByValue,`sortKey`: RustBuffer.ByValue,`descending`: Byte,`offset`: Int,`limit`: Int,_uniffi_out_err: RustCallStatus, 
+    ): RustBuffer.ByValue
+    fun uniffi_pman_lib_fn_func_merge_groups(`databaseId`: Long,`id`: Int,`targetId`: Int,`rewriteHistory`: Byte,_uniffi_out_err: RustCallStatus, 
+    ): Unit
     fun uniffi_pman_lib_fn_func_modify_entity(`databaseId`: Long,`entityId`: Int,`newGroupId`: RustBuffer.ByValue,`newUserId`: RustBuffer.ByValue,`newPassword`: RustBuffer.ByValue,`newUrl`: RustBuffer.ByValue,`changeUrl`: Byte,`newProperties`: RustBuffer.ByValue,`modifiedProperties`: RustBuffer.ByValue,_uniffi_out_err: RustCallStatus, 
     ): Unit
+    fun uniffi_pman_lib_fn_func_move_group(`databaseId`: Long,`id`: Int,`newParentId`: RustBuffer.ByValue,_uniffi_out_err: RustCallStatus, 
+    ): Unit
+    fun uniffi_pman_lib_fn_func_next_hotp_code(`databaseId`: Long,`entityId`: Int,_uniffi_out_err: RustCallStatus, 
+    ): RustBuffer.ByValue
     fun uniffi_pman_lib_fn_func_open(`databaseId`: Long,_uniffi_out_err: RustCallStatus, 
     ): Unit
     fun uniffi_pman_lib_fn_func_pre_open(`databaseId`: Long,`passwordHash`: RustBuffer.ByValue,`password2Hash`: RustBuffer.ByValue,`keyFileContents`: RustBuffer.ByValue,_uniffi_out_err: RustCallStatus, 
     ): Unit
+    fun uniffi_pman_lib_fn_func_pre_open_with_key_slot(`databaseId`: Long,`slotType`: RustBuffer.ByValue,`secret`: RustBuffer.ByValue,`keyFileContents`: RustBuffer.ByValue,_uniffi_out_err: RustCallStatus, 
+    ): Unit
     fun uniffi_pman_lib_fn_func_prepare(`data`: RustBuffer.ByValue,`fileName`: RustBuffer.ByValue,_uniffi_out_err: RustCallStatus, 
     ): Long
+    fun uniffi_pman_lib_fn_func_purge_entity_history(`databaseId`: Long,`entityId`: Int,_uniffi_out_err: RustCallStatus, 
+    ): Unit
     fun uniffi_pman_lib_fn_func_remove(`databaseId`: Long,_uniffi_out_err: RustCallStatus, 
     ): Unit
+    fun uniffi_pman_lib_fn_func_remove_attachment(`databaseId`: Long,`entityId`: Int,`attachmentId`: Int,_uniffi_out_err: RustCallStatus, 
+    ): Unit
+    fun uniffi_pman_lib_fn_func_remove_data_file_backups(`databaseId`: Long,_uniffi_out_err: RustCallStatus, 
+    ): Unit
     fun uniffi_pman_lib_fn_func_remove_entity(`databaseId`: Long,`id`: Int,_uniffi_out_err: RustCallStatus, 
     ): Unit
     fun uniffi_pman_lib_fn_func_remove_group(`databaseId`: Long,`id`: Int,_uniffi_out_err: RustCallStatus, 
     ): Unit
+    fun uniffi_pman_lib_fn_func_remove_key_slot(`databaseId`: Long,`id`: Int,_uniffi_out_err: RustCallStatus, 
+    ): Unit
+    fun uniffi_pman_lib_fn_func_remove_tag(`databaseId`: Long,`entityId`: Int,`tagId`: Int,_uniffi_out_err: RustCallStatus, 
+    ): Unit
+    fun uniffi_pman_lib_fn_func_remove_template(`databaseId`: Long,`templateId`: Int,_uniffi_out_err: RustCallStatus, 
+    ): Unit
     fun uniffi_pman_lib_fn_func_remove_user(`databaseId`: Long,`id`: Int,_uniffi_out_err: RustCallStatus, 
     ): Unit
     fun uniffi_pman_lib_fn_func_rename_entity(`databaseId`: Long,`entityId`: Int,`newName`: RustBuffer.ByValue,_uniffi_out_err: RustCallStatus, 
     ): Unit
     fun uniffi_pman_lib_fn_func_rename_group(`databaseId`: Long,`id`: Int,`newName`: RustBuffer.ByValue,_uniffi_out_err: RustCallStatus, 
     ): Unit
+    fun uniffi_pman_lib_fn_func_rename_user(`databaseId`: Long,`id`: Int,`newName`: RustBuffer.ByValue,_uniffi_out_err: RustCallStatus, 
+    ): Unit
+    fun uniffi_pman_lib_fn_func_replace_user(`databaseId`: Long,`id`: Int,`replacementId`: Int,`rewriteHistory`: Byte,_uniffi_out_err: RustCallStatus, 
+    ): Unit
+    fun uniffi_pman_lib_fn_func_restore_entity(`databaseId`: Long,`entityId`: Int,_uniffi_out_err: RustCallStatus, 
+    ): Unit
+    fun uniffi_pman_lib_fn_func_restore_entity_version(`databaseId`: Long,`entityId`: Int,`version`: Int,_uniffi_out_err: RustCallStatus, 
+    ): Unit
+    fun uniffi_pman_lib_fn_func_revoke_member(`databaseId`: Long,`memberId`: Int,_uniffi_out_err: RustCallStatus, 
+    ): RustBuffer.ByValue
     fun uniffi_pman_lib_fn_func_save(`databaseId`: Long,_uniffi_out_err: RustCallStatus, 
     ): RustBuffer.ByValue
     fun uniffi_pman_lib_fn_func_search(`databaseId`: Long,`searchString`: RustBuffer.ByValue,_uniffi_out_err: RustCallStatus, 
     ): RustBuffer.ByValue
+    fun uniffi_pman_lib_fn_func_search_entities(`databaseId`: Long,`searchString`: RustBuffer.ByValue,`sortKey`: RustBuffer.ByValue,`descending`: Byte,`offset`: Int,`limit`: Int,_uniffi_out_err: RustCallStatus, 
+    ): RustBuffer.ByValue
     fun uniffi_pman_lib_fn_func_set_argon2(`databaseId`: Long,`hashId`: Long,`iterations`: Long,`parallelism`: Long,`memory`: Long,_uniffi_out_err: RustCallStatus, 
     ): Unit
+    fun uniffi_pman_lib_fn_func_set_audit_settings(`databaseId`: Long,`settings`: RustBuffer.ByValue,_uniffi_out_err: RustCallStatus, 
+    ): Unit
+    fun uniffi_pman_lib_fn_func_set_device_label(`databaseId`: Long,`label`: RustBuffer.ByValue,_uniffi_out_err: RustCallStatus, 
+    ): Unit
+    fun uniffi_pman_lib_fn_func_set_encryption(`databaseId`: Long,`layerId`: Long,`engines`: RustBuffer.ByValue,_uniffi_out_err: RustCallStatus, 
+    ): Unit
+    fun uniffi_pman_lib_fn_func_set_entity_expiry(`databaseId`: Long,`entityId`: Int,`expiresAt`: RustBuffer.ByValue,`maxAgeDays`: RustBuffer.ByValue,_uniffi_out_err: RustCallStatus, 
+    ): Unit
+    fun uniffi_pman_lib_fn_func_set_entity_history_length(`databaseId`: Long,`entityId`: Int,`length`: RustBuffer.ByValue,_uniffi_out_err: RustCallStatus, 
+    ): Unit
+    fun uniffi_pman_lib_fn_func_set_entity_otp(`databaseId`: Long,`entityId`: Int,`otp`: RustBuffer.ByValue,_uniffi_out_err: RustCallStatus, 
+    ): Unit
+    fun uniffi_pman_lib_fn_func_set_entity_urls(`databaseId`: Long,`entityId`: Int,`urls`: RustBuffer.ByValue,_uniffi_out_err: RustCallStatus, 
+    ): Unit
     fun uniffi_pman_lib_fn_func_set_file1_location_qs3(`databaseId`: Long,`fileName`: RustBuffer.ByValue,`s3Key`: RustBuffer.ByValue,_uniffi_out_err: RustCallStatus, 
     ): Unit
     fun uniffi_pman_lib_fn_func_set_file2_location_qs3(`databaseId`: Long,`fileName`: RustBuffer.ByValue,`s3Key`: RustBuffer.ByValue,_uniffi_out_err: RustCallStatus, 
     ): Unit
+    fun uniffi_pman_lib_fn_func_set_group_max_age(`databaseId`: Long,`groupId`: Int,`maxAgeDays`: RustBuffer.ByValue,_uniffi_out_err: RustCallStatus, 
+    ): Unit
+    fun uniffi_pman_lib_fn_func_set_history_length(`databaseId`: Long,`length`: Int,_uniffi_out_err: RustCallStatus, 
+    ): Unit
+    fun uniffi_pman_lib_fn_func_set_passwords(`databaseId`: Long,`passwordHash`: RustBuffer.ByValue,`password2Hash`: RustBuffer.ByValue,_uniffi_out_err: RustCallStatus, 
+    ): Unit
+    fun uniffi_pman_lib_fn_func_set_property_layout(`databaseId`: Long,`entityId`: Int,`layout`: RustBuffer.ByValue,_uniffi_out_err: RustCallStatus, 
+    ): Unit
+    fun uniffi_pman_lib_fn_func_set_trash_retention(`databaseId`: Long,`days`: Int,_uniffi_out_err: RustCallStatus, 
+    ): Unit
+    fun uniffi_pman_lib_fn_func_update_user(`databaseId`: Long,`id`: Int,`user`: RustBuffer.ByValue,_uniffi_out_err: RustCallStatus, 
+    ): Unit
     fun ffi_pman_lib_rustbuffer_alloc(`size`: Int,_uniffi_out_err: RustCallStatus, 
     ): RustBuffer.ByValue
     fun ffi_pman_lib_rustbuffer_from_bytes(`bytes`: ForeignBytes.ByValue,_uniffi_out_err: RustCallStatus, 
@@ -575,80 +717,222 @@ internal interface _UniFFILib : Library {
     ): Unit
     fun ffi_pman_lib_rust_future_complete_void(`handle`: Pointer,_uniffi_out_err: RustCallStatus, 
     ): Unit
+    fun uniffi_pman_lib_checksum_func_add_attachment(
+    ): Short
+    fun uniffi_pman_lib_checksum_func_add_child_group(
+    ): Short
     fun uniffi_pman_lib_checksum_func_add_entity(
     ): Short
     fun uniffi_pman_lib_checksum_func_add_group(
     ): Short
+    fun uniffi_pman_lib_checksum_func_add_key_file_slot(
+    ): Short
+    fun uniffi_pman_lib_checksum_func_add_member(
+    ): Short
+    fun uniffi_pman_lib_checksum_func_add_note(
+    ): Short
+    fun uniffi_pman_lib_checksum_func_add_recovery_key(
+    ): Short
+    fun uniffi_pman_lib_checksum_func_add_tag(
+    ): Short
+    fun uniffi_pman_lib_checksum_func_add_template(
+    ): Short
     fun uniffi_pman_lib_checksum_func_add_user(
     ): Short
+    fun uniffi_pman_lib_checksum_func_build_totp_uri(
+    ): Short
+    fun uniffi_pman_lib_checksum_func_check_integrity(
+    ): Short
     fun uniffi_pman_lib_checksum_func_close(
     ): Short
     fun uniffi_pman_lib_checksum_func_create(
     ): Short
+    fun uniffi_pman_lib_checksum_func_disable_core_dumps(
+    ): Short
+    fun uniffi_pman_lib_checksum_func_empty_trash(
+    ): Short
+    fun uniffi_pman_lib_checksum_func_export_to_new_database(
+    ): Short
+    fun uniffi_pman_lib_checksum_func_find_by_url(
+    ): Short
+    fun uniffi_pman_lib_checksum_func_generate_member_key_pair(
+    ): Short
+    fun uniffi_pman_lib_checksum_func_get_attachment(
+    ): Short
+    fun uniffi_pman_lib_checksum_func_get_audit_log(
+    ): Short
+    fun uniffi_pman_lib_checksum_func_get_audit_settings(
+    ): Short
+    fun uniffi_pman_lib_checksum_func_get_database_version(
+    ): Short
     fun uniffi_pman_lib_checksum_func_get_entities(
     ): Short
+    fun uniffi_pman_lib_checksum_func_get_entities_by_tags(
+    ): Short
+    fun uniffi_pman_lib_checksum_func_get_expiring(
+    ): Short
     fun uniffi_pman_lib_checksum_func_get_groups(
     ): Short
+    fun uniffi_pman_lib_checksum_func_get_history_length(
+    ): Short
+    fun uniffi_pman_lib_checksum_func_get_key_slots(
+    ): Short
+    fun uniffi_pman_lib_checksum_func_get_members(
+    ): Short
+    fun uniffi_pman_lib_checksum_func_get_tags(
+    ): Short
+    fun uniffi_pman_lib_checksum_func_get_templates(
+    ): Short
+    fun uniffi_pman_lib_checksum_func_get_trash(
+    ): Short
+    fun uniffi_pman_lib_checksum_func_get_trash_retention(
+    ): Short
     fun uniffi_pman_lib_checksum_func_get_users(
     ): Short
+    fun uniffi_pman_lib_checksum_func_is_audit_pending(
+    ): Short
     fun uniffi_pman_lib_checksum_func_is_read_only(
     ): Short
     fun uniffi_pman_lib_checksum_func_lib_init(
     ): Short
+    fun uniffi_pman_lib_checksum_func_list_entities(
+    ): Short
+    fun uniffi_pman_lib_checksum_func_merge_groups(
+    ): Short
     fun uniffi_pman_lib_checksum_func_modify_entity(
     ): Short
+    fun uniffi_pman_lib_checksum_func_move_group(
+    ): Short
+    fun uniffi_pman_lib_checksum_func_next_hotp_code(
+    ): Short
     fun uniffi_pman_lib_checksum_func_open(
     ): Short
     fun uniffi_pman_lib_checksum_func_pre_open(
     ): Short
+    fun uniffi_pman_lib_checksum_func_pre_open_with_key_slot(
+    ): Short
     fun uniffi_pman_lib_checksum_func_prepare(
     ): Short
+    fun uniffi_pman_lib_checksum_func_purge_entity_history(
+    ): Short
     fun uniffi_pman_lib_checksum_func_remove(
     ): Short
+    fun uniffi_pman_lib_checksum_func_remove_attachment(
+    ): Short
+    fun uniffi_pman_lib_checksum_func_remove_data_file_backups(
+    ): Short
     fun uniffi_pman_lib_checksum_func_remove_entity(
     ): Short
     fun uniffi_pman_lib_checksum_func_remove_group(
     ): Short
+    fun uniffi_pman_lib_checksum_func_remove_key_slot(
+    ): Short
+    fun uniffi_pman_lib_checksum_func_remove_tag(
+    ): Short
+    fun uniffi_pman_lib_checksum_func_remove_template(
+    ): Short
     fun uniffi_pman_lib_checksum_func_remove_user(
     ): Short
     fun uniffi_pman_lib_checksum_func_rename_entity(
     ): Short
     fun uniffi_pman_lib_checksum_func_rename_group(
     ): Short
+    fun uniffi_pman_lib_checksum_func_rename_user(
+    ): Short
+    fun uniffi_pman_lib_checksum_func_replace_user(
+    ): Short
+    fun uniffi_pman_lib_checksum_func_restore_entity(
+    ): Short
+    fun uniffi_pman_lib_checksum_func_restore_entity_version(
+    ): Short
+    fun uniffi_pman_lib_checksum_func_revoke_member(
+    ): Short
     fun uniffi_pman_lib_checksum_func_save(
     ): Short
     fun uniffi_pman_lib_checksum_func_search(
     ): Short
+    fun uniffi_pman_lib_checksum_func_search_entities(
+    ): Short
     fun uniffi_pman_lib_checksum_func_set_argon2(
     ): Short
+    fun uniffi_pman_lib_checksum_func_set_audit_settings(
+    ): Short
+    fun uniffi_pman_lib_checksum_func_set_device_label(
+    ): Short
+    fun uniffi_pman_lib_checksum_func_set_encryption(
+    ): Short
+    fun uniffi_pman_lib_checksum_func_set_entity_expiry(
+    ): Short
+    fun uniffi_pman_lib_checksum_func_set_entity_history_length(
+    ): Short
+    fun uniffi_pman_lib_checksum_func_set_entity_otp(
+    ): Short
+    fun uniffi_pman_lib_checksum_func_set_entity_urls(
+    ): Short
     fun uniffi_pman_lib_checksum_func_set_file1_location_qs3(
     ): Short
     fun uniffi_pman_lib_checksum_func_set_file2_location_qs3(
     ): Short
+    fun uniffi_pman_lib_checksum_func_set_group_max_age(
+    ): Short
+    fun uniffi_pman_lib_checksum_func_set_history_length(
+    ): Short
+    fun uniffi_pman_lib_checksum_func_set_passwords(
+    ): Short
+    fun uniffi_pman_lib_checksum_func_set_property_layout(
+    ): Short
+    fun uniffi_pman_lib_checksum_func_set_trash_retention(
+    ): Short
+    fun uniffi_pman_lib_checksum_func_update_user(
+    ): Short
+    fun uniffi_pman_lib_checksum_method_databaseentity_get_attachment_names(
+    ): Short
     fun uniffi_pman_lib_checksum_method_databaseentity_get_created_at(
     ): Short
+    fun uniffi_pman_lib_checksum_method_databaseentity_get_expires_at(
+    ): Short
     fun uniffi_pman_lib_checksum_method_databaseentity_get_group_id(
     ): Short
+    fun uniffi_pman_lib_checksum_method_databaseentity_get_history_length(
+    ): Short
+    fun uniffi_pman_lib_checksum_method_databaseentity_get_kind(
+    ): Short
+    fun uniffi_pman_lib_checksum_method_databaseentity_get_max_age_days(
+    ): Short
     fun uniffi_pman_lib_checksum_method_databaseentity_get_max_version(
     ): Short
     fun uniffi_pman_lib_checksum_method_databaseentity_get_name(
     ): Short
+    fun uniffi_pman_lib_checksum_method_databaseentity_get_otp_code(
+    ): Short
     fun uniffi_pman_lib_checksum_method_databaseentity_get_password(
     ): Short
     fun uniffi_pman_lib_checksum_method_databaseentity_get_property_names(
     ): Short
     fun uniffi_pman_lib_checksum_method_databaseentity_get_property_value(
     ): Short
+    fun uniffi_pman_lib_checksum_method_databaseentity_get_tag_ids(
+    ): Short
+    fun uniffi_pman_lib_checksum_method_databaseentity_get_template_id(
+    ): Short
     fun uniffi_pman_lib_checksum_method_databaseentity_get_url(
     ): Short
+    fun uniffi_pman_lib_checksum_method_databaseentity_get_urls(
+    ): Short
     fun uniffi_pman_lib_checksum_method_databaseentity_get_user_id(
     ): Short
     fun uniffi_pman_lib_checksum_method_databasegroup_get_entities_count(
     ): Short
     fun uniffi_pman_lib_checksum_method_databasegroup_get_id(
     ): Short
+    fun uniffi_pman_lib_checksum_method_databasegroup_get_max_age_days(
+    ): Short
     fun uniffi_pman_lib_checksum_method_databasegroup_get_name(
     ): Short
+    fun uniffi_pman_lib_checksum_method_databasegroup_get_parent_id(
+    ): Short
+    fun uniffi_pman_lib_checksum_method_databasegroup_get_total_entities_count(
+    ): Short
     fun ffi_pman_lib_uniffi_contract_version(
     ): Int
     
@@ -666,28 +950,115 @@ private fun uniffiCheckContractApiVersion(lib: _UniFFILib) {
 
 @Suppress("UNUSED_PARAMETER")
 private fun uniffiCheckApiChecksums(lib: _UniFFILib) {
-    if (lib.uniffi_pman_lib_checksum_func_add_entity() != 54311.toShort()) {
+    if (lib.uniffi_pman_lib_checksum_func_add_attachment() != 63855.toShort()) {
+        throw RuntimeException("UniFFI API checksum mismatch: try cleaning and rebuilding your project")
+    }
+    if (lib.uniffi_pman_lib_checksum_func_add_child_group() != 65332.toShort()) {
+        throw RuntimeException("UniFFI API checksum mismatch: try cleaning and rebuilding your project")
+    }
+    if (lib.uniffi_pman_lib_checksum_func_add_entity() != 62562.toShort()) {
         throw RuntimeException("UniFFI API checksum mismatch: try cleaning and rebuilding your project")
     }
     if (lib.uniffi_pman_lib_checksum_func_add_group() != 62187.toShort()) {
         throw RuntimeException("UniFFI API checksum mismatch: try cleaning and rebuilding your project")
     }
+    if (lib.uniffi_pman_lib_checksum_func_add_key_file_slot() != 53088.toShort()) {
+        throw RuntimeException("UniFFI API checksum mismatch: try cleaning and rebuilding your project")
+    }
+    if (lib.uniffi_pman_lib_checksum_func_add_member() != 32193.toShort()) {
+        throw RuntimeException("UniFFI API checksum mismatch: try cleaning and rebuilding your project")
+    }
+    if (lib.uniffi_pman_lib_checksum_func_add_note() != 29676.toShort()) {
+        throw RuntimeException("UniFFI API checksum mismatch: try cleaning and rebuilding your project")
+    }
+    if (lib.uniffi_pman_lib_checksum_func_add_recovery_key() != 2356.toShort()) {
+        throw RuntimeException("UniFFI API checksum mismatch: try cleaning and rebuilding your project")
+    }
+    if (lib.uniffi_pman_lib_checksum_func_add_tag() != 25158.toShort()) {
+        throw RuntimeException("UniFFI API checksum mismatch: try cleaning and rebuilding your project")
+    }
+    if (lib.uniffi_pman_lib_checksum_func_add_template() != 17693.toShort()) {
+        throw RuntimeException("UniFFI API checksum mismatch: try cleaning and rebuilding your project")
+    }
     if (lib.uniffi_pman_lib_checksum_func_add_user() != 42676.toShort()) {
         throw RuntimeException("UniFFI API checksum mismatch: try cleaning and rebuilding your project")
     }
+    if (lib.uniffi_pman_lib_checksum_func_build_totp_uri() != 43415.toShort()) {
+        throw RuntimeException("UniFFI API checksum mismatch: try cleaning and rebuilding your project")
+    }
+    if (lib.uniffi_pman_lib_checksum_func_check_integrity() != 8378.toShort()) {
+        throw RuntimeException("UniFFI API checksum mismatch: try cleaning and rebuilding your project")
+    }
     if (lib.uniffi_pman_lib_checksum_func_close() != 57699.toShort()) {
         throw RuntimeException("UniFFI API checksum mismatch: try cleaning and rebuilding your project")
     }
     if (lib.uniffi_pman_lib_checksum_func_create() != 52861.toShort()) {
         throw RuntimeException("UniFFI API checksum mismatch: try cleaning and rebuilding your project")
     }
+    if (lib.uniffi_pman_lib_checksum_func_disable_core_dumps() != 12971.toShort()) {
+        throw RuntimeException("UniFFI API checksum mismatch: try cleaning and rebuilding your project")
+    }
+    if (lib.uniffi_pman_lib_checksum_func_empty_trash() != 23060.toShort()) {
+        throw RuntimeException("UniFFI API checksum mismatch: try cleaning and rebuilding your project")
+    }
+    if (lib.uniffi_pman_lib_checksum_func_export_to_new_database() != 766.toShort()) {
+        throw RuntimeException("UniFFI API checksum mismatch: try cleaning and rebuilding your project")
+    }
+    if (lib.uniffi_pman_lib_checksum_func_find_by_url() != 9578.toShort()) {
+        throw RuntimeException("UniFFI API checksum mismatch: try cleaning and rebuilding your project")
+    }
+    if (lib.uniffi_pman_lib_checksum_func_generate_member_key_pair() != 16566.toShort()) {
+        throw RuntimeException("UniFFI API checksum mismatch: try cleaning and rebuilding your project")
+    }
+    if (lib.uniffi_pman_lib_checksum_func_get_attachment() != 50598.toShort()) {
+        throw RuntimeException("UniFFI API checksum mismatch: try cleaning and rebuilding your project")
+    }
+    if (lib.uniffi_pman_lib_checksum_func_get_audit_log() != 35397.toShort()) {
+        throw RuntimeException("UniFFI API checksum mismatch: try cleaning and rebuilding your project")
+    }
+    if (lib.uniffi_pman_lib_checksum_func_get_audit_settings() != 46383.toShort()) {
+        throw RuntimeException("UniFFI API checksum mismatch: try cleaning and rebuilding your project")
+    }
+    if (lib.uniffi_pman_lib_checksum_func_get_database_version() != 28071.toShort()) {
+        throw RuntimeException("UniFFI API checksum mismatch: try cleaning and rebuilding your project")
+    }
     if (lib.uniffi_pman_lib_checksum_func_get_entities() != 64141.toShort()) {
         throw RuntimeException("UniFFI API checksum mismatch: try cleaning and rebuilding your project")
     }
+    if (lib.uniffi_pman_lib_checksum_func_get_entities_by_tags() != 38681.toShort()) {
+        throw RuntimeException("UniFFI API checksum mismatch: try cleaning and rebuilding your project")
+    }
+    if (lib.uniffi_pman_lib_checksum_func_get_expiring() != 53753.toShort()) {
+        throw RuntimeException("UniFFI API checksum mismatch: try cleaning and rebuilding your project")
+    }
     if (lib.uniffi_pman_lib_checksum_func_get_groups() != 19082.toShort()) {
         throw RuntimeException("UniFFI API checksum mismatch: try cleaning and rebuilding your project")
     }
-    if (lib.uniffi_pman_lib_checksum_func_get_users() != 46894.toShort()) {
+    if (lib.uniffi_pman_lib_checksum_func_get_history_length() != 10775.toShort()) {
+        throw RuntimeException("UniFFI API checksum mismatch: try cleaning and rebuilding your project")
+    }
+    if (lib.uniffi_pman_lib_checksum_func_get_key_slots() != 43643.toShort()) {
+        throw RuntimeException("UniFFI API checksum mismatch: try cleaning and rebuilding your project")
+    }
+    if (lib.uniffi_pman_lib_checksum_func_get_members() != 16355.toShort()) {
+        throw RuntimeException("UniFFI API checksum mismatch: try cleaning and rebuilding your project")
+    }
+    if (lib.uniffi_pman_lib_checksum_func_get_tags() != 40737.toShort()) {
+        throw RuntimeException("UniFFI API checksum mismatch: try cleaning and rebuilding your project")
+    }
+    if (lib.uniffi_pman_lib_checksum_func_get_templates() != 6254.toShort()) {
+        throw RuntimeException("UniFFI API checksum mismatch: try cleaning and rebuilding your project")
+    }
+    if (lib.uniffi_pman_lib_checksum_func_get_trash() != 63810.toShort()) {
+        throw RuntimeException("UniFFI API checksum mismatch: try cleaning and rebuilding your project")
+    }
+    if (lib.uniffi_pman_lib_checksum_func_get_trash_retention() != 51138.toShort()) {
+        throw RuntimeException("UniFFI API checksum mismatch: try cleaning and rebuilding your project")
+    }
+    if (lib.uniffi_pman_lib_checksum_func_get_users() != 40448.toShort()) {
+        throw RuntimeException("UniFFI API checksum mismatch: try cleaning and rebuilding your project")
+    }
+    if (lib.uniffi_pman_lib_checksum_func_is_audit_pending() != 35437.toShort()) {
         throw RuntimeException("UniFFI API checksum mismatch: try cleaning and rebuilding your project")
     }
     if (lib.uniffi_pman_lib_checksum_func_is_read_only() != 65416.toShort()) {
@@ -696,27 +1067,60 @@ private fun uniffiCheckApiChecksums(lib: _UniFFILib) {
     if (lib.uniffi_pman_lib_checksum_func_lib_init() != 48264.toShort()) {
         throw RuntimeException("UniFFI API checksum mismatch: try cleaning and rebuilding your project")
     }
+    if (lib.uniffi_pman_lib_checksum_func_list_entities() != 4183.toShort()) {
+        throw RuntimeException("UniFFI API checksum mismatch: try cleaning and rebuilding your project")
+    }
+    if (lib.uniffi_pman_lib_checksum_func_merge_groups() != 45460.toShort()) {
+        throw RuntimeException("UniFFI API checksum mismatch: try cleaning and rebuilding your project")
+    }
     if (lib.uniffi_pman_lib_checksum_func_modify_entity() != 49681.toShort()) {
         throw RuntimeException("UniFFI API checksum mismatch: try cleaning and rebuilding your project")
     }
+    if (lib.uniffi_pman_lib_checksum_func_move_group() != 52404.toShort()) {
+        throw RuntimeException("UniFFI API checksum mismatch: try cleaning and rebuilding your project")
+    }
+    if (lib.uniffi_pman_lib_checksum_func_next_hotp_code() != 13662.toShort()) {
+        throw RuntimeException("UniFFI API checksum mismatch: try cleaning and rebuilding your project")
+    }
     if (lib.uniffi_pman_lib_checksum_func_open() != 58724.toShort()) {
         throw RuntimeException("UniFFI API checksum mismatch: try cleaning and rebuilding your project")
     }
     if (lib.uniffi_pman_lib_checksum_func_pre_open() != 44629.toShort()) {
         throw RuntimeException("UniFFI API checksum mismatch: try cleaning and rebuilding your project")
     }
+    if (lib.uniffi_pman_lib_checksum_func_pre_open_with_key_slot() != 48949.toShort()) {
+        throw RuntimeException("UniFFI API checksum mismatch: try cleaning and rebuilding your project")
+    }
     if (lib.uniffi_pman_lib_checksum_func_prepare() != 43921.toShort()) {
         throw RuntimeException("UniFFI API checksum mismatch: try cleaning and rebuilding your project")
     }
+    if (lib.uniffi_pman_lib_checksum_func_purge_entity_history() != 24760.toShort()) {
+        throw RuntimeException("UniFFI API checksum mismatch: try cleaning and rebuilding your project")
+    }
     if (lib.uniffi_pman_lib_checksum_func_remove() != 24111.toShort()) {
         throw RuntimeException("UniFFI API checksum mismatch: try cleaning and rebuilding your project")
     }
+    if (lib.uniffi_pman_lib_checksum_func_remove_attachment() != 32921.toShort()) {
+        throw RuntimeException("UniFFI API checksum mismatch: try cleaning and rebuilding your project")
+    }
+    if (lib.uniffi_pman_lib_checksum_func_remove_data_file_backups() != 14857.toShort()) {
+        throw RuntimeException("UniFFI API checksum mismatch: try cleaning and rebuilding your project")
+    }
     if (lib.uniffi_pman_lib_checksum_func_remove_entity() != 46671.toShort()) {
         throw RuntimeException("UniFFI API checksum mismatch: try cleaning and rebuilding your project")
     }
     if (lib.uniffi_pman_lib_checksum_func_remove_group() != 42780.toShort()) {
         throw RuntimeException("UniFFI API checksum mismatch: try cleaning and rebuilding your project")
     }
+    if (lib.uniffi_pman_lib_checksum_func_remove_key_slot() != 61200.toShort()) {
+        throw RuntimeException("UniFFI API checksum mismatch: try cleaning and rebuilding your project")
+    }
+    if (lib.uniffi_pman_lib_checksum_func_remove_tag() != 56511.toShort()) {
+        throw RuntimeException("UniFFI API checksum mismatch: try cleaning and rebuilding your project")
+    }
+    if (lib.uniffi_pman_lib_checksum_func_remove_template() != 57486.toShort()) {
+        throw RuntimeException("UniFFI API checksum mismatch: try cleaning and rebuilding your project")
+    }
     if (lib.uniffi_pman_lib_checksum_func_remove_user() != 24445.toShort()) {
         throw RuntimeException("UniFFI API checksum mismatch: try cleaning and rebuilding your project")
     }
@@ -726,64 +1130,177 @@ private fun uniffiCheckApiChecksums(lib: _UniFFILib) {
     if (lib.uniffi_pman_lib_checksum_func_rename_group() != 37906.toShort()) {
         throw RuntimeException("UniFFI API checksum mismatch: try cleaning and rebuilding your project")
     }
+    if (lib.uniffi_pman_lib_checksum_func_rename_user() != 33359.toShort()) {
+        throw RuntimeException("UniFFI API checksum mismatch: try cleaning and rebuilding your project")
+    }
+    if (lib.uniffi_pman_lib_checksum_func_replace_user() != 32422.toShort()) {
+        throw RuntimeException("UniFFI API checksum mismatch: try cleaning and rebuilding your project")
+    }
+    if (lib.uniffi_pman_lib_checksum_func_restore_entity() != 33792.toShort()) {
+        throw RuntimeException("UniFFI API checksum mismatch: try cleaning and rebuilding your project")
+    }
+    if (lib.uniffi_pman_lib_checksum_func_restore_entity_version() != 62642.toShort()) {
+        throw RuntimeException("UniFFI API checksum mismatch: try cleaning and rebuilding your project")
+    }
+    if (lib.uniffi_pman_lib_checksum_func_revoke_member() != 62591.toShort()) {
+        throw RuntimeException("UniFFI API checksum mismatch: try cleaning and rebuilding your project")
+    }
     if (lib.uniffi_pman_lib_checksum_func_save() != 31149.toShort()) {
         throw RuntimeException("UniFFI API checksum mismatch: try cleaning and rebuilding your project")
     }
     if (lib.uniffi_pman_lib_checksum_func_search() != 2378.toShort()) {
         throw RuntimeException("UniFFI API checksum mismatch: try cleaning and rebuilding your project")
     }
+    if (lib.uniffi_pman_lib_checksum_func_search_entities() != 19248.toShort()) {
+        throw RuntimeException("UniFFI API checksum mismatch: try cleaning and rebuilding your project")
+    }
     if (lib.uniffi_pman_lib_checksum_func_set_argon2() != 13956.toShort()) {
         throw RuntimeException("UniFFI API checksum mismatch: try cleaning and rebuilding your project")
     }
-    if (lib.uniffi_pman_lib_checksum_func_set_file1_location_qs3() != 37036.toShort()) {
+    if (lib.uniffi_pman_lib_checksum_func_set_audit_settings() != 89.toShort()) {
         throw RuntimeException("UniFFI API checksum mismatch: try cleaning and rebuilding your project")
     }
-    if (lib.uniffi_pman_lib_checksum_func_set_file2_location_qs3() != 1497.toShort()) {
+    if (lib.uniffi_pman_lib_checksum_func_set_device_label() != 22152.toShort()) {
         throw RuntimeException("UniFFI API checksum mismatch: try cleaning and rebuilding your project")
     }
-    if (lib.uniffi_pman_lib_checksum_method_databaseentity_get_created_at() != 25075.toShort()) {
+    if (lib.uniffi_pman_lib_checksum_func_set_encryption() != 4595.toShort()) {
         throw RuntimeException("UniFFI API checksum mismatch: try cleaning and rebuilding your project")
     }
-    if (lib.uniffi_pman_lib_checksum_method_databaseentity_get_group_id() != 55072.toShort()) {
+    if (lib.uniffi_pman_lib_checksum_func_set_entity_expiry() != 55155.toShort()) {
         throw RuntimeException("UniFFI API checksum mismatch: try cleaning and rebuilding your project")
     }
-    if (lib.uniffi_pman_lib_checksum_method_databaseentity_get_max_version() != 36769.toShort()) {
+    if (lib.uniffi_pman_lib_checksum_func_set_entity_history_length() != 47595.toShort()) {
         throw RuntimeException("UniFFI API checksum mismatch: try cleaning and rebuilding your project")
     }
-    if (lib.uniffi_pman_lib_checksum_method_databaseentity_get_name() != 15454.toShort()) {
+    if (lib.uniffi_pman_lib_checksum_func_set_entity_otp() != 51831.toShort()) {
         throw RuntimeException("UniFFI API checksum mismatch: try cleaning and rebuilding your project")
     }
-    if (lib.uniffi_pman_lib_checksum_method_databaseentity_get_password() != 34637.toShort()) {
+    if (lib.uniffi_pman_lib_checksum_func_set_entity_urls() != 43364.toShort()) {
         throw RuntimeException("UniFFI API checksum mismatch: try cleaning and rebuilding your project")
     }
-    if (lib.uniffi_pman_lib_checksum_method_databaseentity_get_property_names() != 37718.toShort()) {
+    if (lib.uniffi_pman_lib_checksum_func_set_file1_location_qs3() != 37036.toShort()) {
         throw RuntimeException("UniFFI API checksum mismatch: try cleaning and rebuilding your project")
     }
-    if (lib.uniffi_pman_lib_checksum_method_databaseentity_get_property_value() != 53055.toShort()) {
+    if (lib.uniffi_pman_lib_checksum_func_set_file2_location_qs3() != 1497.toShort()) {
         throw RuntimeException("UniFFI API checksum mismatch: try cleaning and rebuilding your project")
     }
-    if (lib.uniffi_pman_lib_checksum_method_databaseentity_get_url() != 59540.toShort()) {
+    if (lib.uniffi_pman_lib_checksum_func_set_group_max_age() != 50945.toShort()) {
         throw RuntimeException("UniFFI API checksum mismatch: try cleaning and rebuilding your project")
     }
-    if (lib.uniffi_pman_lib_checksum_method_databaseentity_get_user_id() != 37031.toShort()) {
+    if (lib.uniffi_pman_lib_checksum_func_set_history_length() != 37337.toShort()) {
         throw RuntimeException("UniFFI API checksum mismatch: try cleaning and rebuilding your project")
     }
-    if (lib.uniffi_pman_lib_checksum_method_databasegroup_get_entities_count() != 14862.toShort()) {
+    if (lib.uniffi_pman_lib_checksum_func_set_passwords() != 52871.toShort()) {
         throw RuntimeException("UniFFI API checksum mismatch: try cleaning and rebuilding your project")
     }
-    if (lib.uniffi_pman_lib_checksum_method_databasegroup_get_id() != 42627.toShort()) {
+    if (lib.uniffi_pman_lib_checksum_func_set_property_layout() != 21472.toShort()) {
         throw RuntimeException("UniFFI API checksum mismatch: try cleaning and rebuilding your project")
     }
-    if (lib.uniffi_pman_lib_checksum_method_databasegroup_get_name() != 18037.toShort()) {
+    if (lib.uniffi_pman_lib_checksum_func_set_trash_retention() != 8661.toShort()) {
         throw RuntimeException("UniFFI API checksum mismatch: try cleaning and rebuilding your project")
     }
-}
-
-// Async support
-
-// Public interface members begin here.
-
-
+    if (lib.uniffi_pman_lib_checksum_func_update_user() != 61538.toShort()) {
+        throw RuntimeException("UniFFI API checksum mismatch: try cleaning and rebuilding your project")
+    }
+    if (lib.uniffi_pman_lib_checksum_method_databaseentity_get_attachment_names() != 16996.toShort()) {
+        throw RuntimeException("UniFFI API checksum mismatch: try cleaning and rebuilding your project")
+    }
+    if (lib.uniffi_pman_lib_checksum_method_databaseentity_get_created_at() != 25075.toShort()) {
+        throw RuntimeException("UniFFI API checksum mismatch: try cleaning and rebuilding your project")
+    }
+    if (lib.uniffi_pman_lib_checksum_method_databaseentity_get_expires_at() != 18325.toShort()) {
+        throw RuntimeException("UniFFI API checksum mismatch: try cleaning and rebuilding your project")
+    }
+    if (lib.uniffi_pman_lib_checksum_method_databaseentity_get_group_id() != 55072.toShort()) {
+        throw RuntimeException("UniFFI API checksum mismatch: try cleaning and rebuilding your project")
+    }
+    if (lib.uniffi_pman_lib_checksum_method_databaseentity_get_history_length() != 37150.toShort()) {
+        throw RuntimeException("UniFFI API checksum mismatch: try cleaning and rebuilding your project")
+    }
+    if (lib.uniffi_pman_lib_checksum_method_databaseentity_get_kind() != 5609.toShort()) {
+        throw RuntimeException("UniFFI API checksum mismatch: try cleaning and rebuilding your project")
+    }
+    if (lib.uniffi_pman_lib_checksum_method_databaseentity_get_max_age_days() != 47314.toShort()) {
+        throw RuntimeException("UniFFI API checksum mismatch: try cleaning and rebuilding your project")
+    }
+    if (lib.uniffi_pman_lib_checksum_method_databaseentity_get_max_version() != 36769.toShort()) {
+        throw RuntimeException("UniFFI API checksum mismatch: try cleaning and rebuilding your project")
+    }
+    if (lib.uniffi_pman_lib_checksum_method_databaseentity_get_name() != 15454.toShort()) {
+        throw RuntimeException("UniFFI API checksum mismatch: try cleaning and rebuilding your project")
+    }
+    if (lib.uniffi_pman_lib_checksum_method_databaseentity_get_otp_code() != 43141.toShort()) {
+        throw RuntimeException("UniFFI API checksum mismatch: try cleaning and rebuilding your project")
+    }
+    if (lib.uniffi_pman_lib_checksum_method_databaseentity_get_password() != 34637.toShort()) {
+        throw RuntimeException("UniFFI API checksum mismatch: try cleaning and rebuilding your project")
+    }
+    if (lib.uniffi_pman_lib_checksum_method_databaseentity_get_property_names() != 20185.toShort()) {
+        throw RuntimeException("UniFFI API checksum mismatch: try cleaning and rebuilding your project")
+    }
+    if (lib.uniffi_pman_lib_checksum_method_databaseentity_get_property_value() != 53055.toShort()) {
+        throw RuntimeException("UniFFI API checksum mismatch: try cleaning and rebuilding your project")
+    }
+    if (lib.uniffi_pman_lib_checksum_method_databaseentity_get_tag_ids() != 4721.toShort()) {
+        throw RuntimeException("UniFFI API checksum mismatch: try cleaning and rebuilding your project")
+    }
+    if (lib.uniffi_pman_lib_checksum_method_databaseentity_get_template_id() != 39960.toShort()) {
+        throw RuntimeException("UniFFI API checksum mismatch: try cleaning and rebuilding your project")
+    }
+    if (lib.uniffi_pman_lib_checksum_method_databaseentity_get_url() != 59540.toShort()) {
+        throw RuntimeException("UniFFI API checksum mismatch: try cleaning and rebuilding your project")
+    }
+    if (lib.uniffi_pman_lib_checksum_method_databaseentity_get_urls() != 39124.toShort()) {
+        throw RuntimeException("UniFFI API checksum mismatch: try cleaning and rebuilding your project")
+    }
+    if (lib.uniffi_pman_lib_checksum_method_databaseentity_get_user_id() != 37031.toShort()) {
+        throw RuntimeException("UniFFI API checksum mismatch: try cleaning and rebuilding your project")
+    }
+    if (lib.uniffi_pman_lib_checksum_method_databasegroup_get_entities_count() != 14862.toShort()) {
+        throw RuntimeException("UniFFI API checksum mismatch: try cleaning and rebuilding your project")
+    }
+    if (lib.uniffi_pman_lib_checksum_method_databasegroup_get_id() != 42627.toShort()) {
+        throw RuntimeException("UniFFI API checksum mismatch: try cleaning and rebuilding your project")
+    }
+    if (lib.uniffi_pman_lib_checksum_method_databasegroup_get_max_age_days() != 62640.toShort()) {
+        throw RuntimeException("UniFFI API checksum mismatch: try cleaning and rebuilding your project")
+    }
+    if (lib.uniffi_pman_lib_checksum_method_databasegroup_get_name() != 18037.toShort()) {
+        throw RuntimeException("UniFFI API checksum mismatch: try cleaning and rebuilding your project")
+    }
+    if (lib.uniffi_pman_lib_checksum_method_databasegroup_get_parent_id() != 6845.toShort()) {
+        throw RuntimeException("UniFFI API checksum mismatch: try cleaning and rebuilding your project")
+    }
+    if (lib.uniffi_pman_lib_checksum_method_databasegroup_get_total_entities_count() != 30086.toShort()) {
+        throw RuntimeException("UniFFI API checksum mismatch: try cleaning and rebuilding your project")
+    }
+}
+
+// Async support
+
+// Public interface members begin here.
+
+
+public object FfiConverterUShort: FfiConverter<UShort, Short> {
+    override fun lift(value: Short): UShort {
+        return value.toUShort()
+    }
+
+    override fun read(buf: ByteBuffer): UShort {
+        return lift(buf.getShort())
+    }
+
+    override fun lower(value: UShort): Short {
+        return value.toShort()
+    }
+
+    override fun allocationSize(value: UShort) = 2
+
+    override fun write(value: UShort, buf: ByteBuffer) {
+        buf.putShort(value.toShort())
+    }
+}
+
 public object FfiConverterUInt: FfiConverter<UInt, Int> {
     override fun lift(value: Int): UInt {
         return value.toUInt()
@@ -1079,14 +1596,23 @@ abstract class FFIObject(
 
 public interface DatabaseEntityInterface {
     @Throws(PmanException::class)
+    fun `getAttachmentNames`(`version`: UInt): Map<String, UInt>@Throws(PmanException::class)
     fun `getCreatedAt`(`version`: UInt): ULong@Throws(PmanException::class)
+    fun `getExpiresAt`(`version`: UInt): ULong?@Throws(PmanException::class)
     fun `getGroupId`(`version`: UInt): UInt
+    fun `getHistoryLength`(): UInt?
+    fun `getKind`(): EntityKind@Throws(PmanException::class)
+    fun `getMaxAgeDays`(`version`: UInt): UInt?
     fun `getMaxVersion`(): UInt@Throws(PmanException::class)
     fun `getName`(): String@Throws(PmanException::class)
+    fun `getOtpCode`(`version`: UInt, `timestamp`: ULong): OtpCode?@Throws(PmanException::class)
     fun `getPassword`(`version`: UInt): String@Throws(PmanException::class)
-    fun `getPropertyNames`(`version`: UInt): Map<String, UInt>@Throws(PmanException::class)
+    fun `getPropertyNames`(`version`: UInt): List<PropertyInfo>@Throws(PmanException::class)
     fun `getPropertyValue`(`version`: UInt, `id`: UInt): String@Throws(PmanException::class)
+    fun `getTagIds`(`version`: UInt): List<UInt>@Throws(PmanException::class)
+    fun `getTemplateId`(`version`: UInt): UInt?@Throws(PmanException::class)
     fun `getUrl`(`version`: UInt): String?@Throws(PmanException::class)
+    fun `getUrls`(`version`: UInt): List<EntityUrl>@Throws(PmanException::class)
     fun `getUserId`(`version`: UInt): UInt
     companion object
 }
@@ -1110,6 +1636,18 @@ class DatabaseEntity(
     }
 
     
+    @Throws(PmanException::class)override fun `getAttachmentNames`(`version`: UInt): Map<String, UInt> =
+        callWithPointer {
+    rustCallWithError(PmanException) { _status ->
+    _UniFFILib.INSTANCE.uniffi_pman_lib_fn_method_databaseentity_get_attachment_names(it,
+        FfiConverterUInt.lower(`version`),
+        _status)
+}
+        }.let {
+            FfiConverterMapStringUInt.lift(it)
+        }
+    
+    
     @Throws(PmanException::class)override fun `getCreatedAt`(`version`: UInt): ULong =
         callWithPointer {
     rustCallWithError(PmanException) { _status ->
@@ -1122,6 +1660,18 @@ class DatabaseEntity(
         }
     
     
+    @Throws(PmanException::class)override fun `getExpiresAt`(`version`: UInt): ULong? =
+        callWithPointer {
+    rustCallWithError(PmanException) { _status ->
+    _UniFFILib.INSTANCE.uniffi_pman_lib_fn_method_databaseentity_get_expires_at(it,
+        FfiConverterUInt.lower(`version`),
+        _status)
+}
+        }.let {
+            FfiConverterOptionalULong.lift(it)
+        }
+    
+    
     @Throws(PmanException::class)override fun `getGroupId`(`version`: UInt): UInt =
         callWithPointer {
     rustCallWithError(PmanException) { _status ->
@@ -1133,6 +1683,40 @@ class DatabaseEntity(
             FfiConverterUInt.lift(it)
         }
     
+    override fun `getHistoryLength`(): UInt? =
+        callWithPointer {
+    rustCall() { _status ->
+    _UniFFILib.INSTANCE.uniffi_pman_lib_fn_method_databaseentity_get_history_length(it,
+        
+        _status)
+}
+        }.let {
+            FfiConverterOptionalUInt.lift(it)
+        }
+    
+    override fun `getKind`(): EntityKind =
+        callWithPointer {
+    rustCall() { _status ->
+    _UniFFILib.INSTANCE.uniffi_pman_lib_fn_method_databaseentity_get_kind(it,
+        
+        _status)
+}
+        }.let {
+            FfiConverterTypeEntityKind.lift(it)
+        }
+    
+    
+    @Throws(PmanException::class)override fun `getMaxAgeDays`(`version`: UInt): UInt? =
+        callWithPointer {
+    rustCallWithError(PmanException) { _status ->
+    _UniFFILib.INSTANCE.uniffi_pman_lib_fn_method_databaseentity_get_max_age_days(it,
+        FfiConverterUInt.lower(`version`),
+        _status)
+}
+        }.let {
+            FfiConverterOptionalUInt.lift(it)
+        }
+    
     override fun `getMaxVersion`(): UInt =
         callWithPointer {
     rustCall() { _status ->
@@ -1157,6 +1741,18 @@ class DatabaseEntity(
         }
     
     
+    @Throws(PmanException::class)override fun `getOtpCode`(`version`: UInt, `timestamp`: ULong): OtpCode? =
+        callWithPointer {
+    rustCallWithError(PmanException) { _status ->
+    _UniFFILib.INSTANCE.uniffi_pman_lib_fn_method_databaseentity_get_otp_code(it,
+        FfiConverterUInt.lower(`version`),FfiConverterULong.lower(`timestamp`),
+        _status)
+}
+        }.let {
+            FfiConverterOptionalTypeOtpCode.lift(it)
+        }
+    
+    
     @Throws(PmanException::class)override fun `getPassword`(`version`: UInt): String =
         callWithPointer {
     rustCallWithError(PmanException) { _status ->
@@ -1169,7 +1765,7 @@ class DatabaseEntity(
         }
     
     
-    @Throws(PmanException::class)override fun `getPropertyNames`(`version`: UInt): Map<String, UInt> =
+    @Throws(PmanException::class)override fun `getPropertyNames`(`version`: UInt): List<PropertyInfo> =
         callWithPointer {
     rustCallWithError(PmanException) { _status ->
     _UniFFILib.INSTANCE.uniffi_pman_lib_fn_method_databaseentity_get_property_names(it,
@@ -1177,7 +1773,7 @@ class DatabaseEntity(
         _status)
 }
         }.let {
-            FfiConverterMapStringUInt.lift(it)
+            FfiConverterSequenceTypePropertyInfo.lift(it)
         }
     
     
@@ -1193,6 +1789,30 @@ class DatabaseEntity(
         }
     
     
+    @Throws(PmanException::class)override fun `getTagIds`(`version`: UInt): List<UInt> =
+        callWithPointer {
+    rustCallWithError(PmanException) { _status ->
+    _UniFFILib.INSTANCE.uniffi_pman_lib_fn_method_databaseentity_get_tag_ids(it,
+        FfiConverterUInt.lower(`version`),
+        _status)
+}
+        }.let {
+            FfiConverterSequenceUInt.lift(it)
+        }
+    
+    
+    @Throws(PmanException::class)override fun `getTemplateId`(`version`: UInt): UInt? =
+        callWithPointer {
+    rustCallWithError(PmanException) { _status ->
+    _UniFFILib.INSTANCE.uniffi_pman_lib_fn_method_databaseentity_get_template_id(it,
+        FfiConverterUInt.lower(`version`),
+        _status)
+}
+        }.let {
+            FfiConverterOptionalUInt.lift(it)
+        }
+    
+    
     @Throws(PmanException::class)override fun `getUrl`(`version`: UInt): String? =
         callWithPointer {
     rustCallWithError(PmanException) { _status ->
@@ -1205,6 +1825,18 @@ class DatabaseEntity(
         }
     
     
+    @Throws(PmanException::class)override fun `getUrls`(`version`: UInt): List<EntityUrl> =
+        callWithPointer {
+    rustCallWithError(PmanException) { _status ->
+    _UniFFILib.INSTANCE.uniffi_pman_lib_fn_method_databaseentity_get_urls(it,
+        FfiConverterUInt.lower(`version`),
+        _status)
+}
+        }.let {
+            FfiConverterSequenceTypeEntityUrl.lift(it)
+        }
+    
+    
     @Throws(PmanException::class)override fun `getUserId`(`version`: UInt): UInt =
         callWithPointer {
     rustCallWithError(PmanException) { _status ->
@@ -1252,7 +1884,10 @@ public interface DatabaseGroupInterface {
     
     fun `getEntitiesCount`(): UInt
     fun `getId`(): UInt
+    fun `getMaxAgeDays`(): UInt?
     fun `getName`(): String
+    fun `getParentId`(): UInt?
+    fun `getTotalEntitiesCount`(): UInt
     companion object
 }
 
@@ -1296,6 +1931,17 @@ class DatabaseGroup(
             FfiConverterUInt.lift(it)
         }
     
+    override fun `getMaxAgeDays`(): UInt? =
+        callWithPointer {
+    rustCall() { _status ->
+    _UniFFILib.INSTANCE.uniffi_pman_lib_fn_method_databasegroup_get_max_age_days(it,
+        
+        _status)
+}
+        }.let {
+            FfiConverterOptionalUInt.lift(it)
+        }
+    
     override fun `getName`(): String =
         callWithPointer {
     rustCall() { _status ->
@@ -1307,6 +1953,28 @@ class DatabaseGroup(
             FfiConverterString.lift(it)
         }
     
+    override fun `getParentId`(): UInt? =
+        callWithPointer {
+    rustCall() { _status ->
+    _UniFFILib.INSTANCE.uniffi_pman_lib_fn_method_databasegroup_get_parent_id(it,
+        
+        _status)
+}
+        }.let {
+            FfiConverterOptionalUInt.lift(it)
+        }
+    
+    override fun `getTotalEntitiesCount`(): UInt =
+        callWithPointer {
+    rustCall() { _status ->
+    _UniFFILib.INSTANCE.uniffi_pman_lib_fn_method_databasegroup_get_total_entities_count(it,
+        
+        _status)
+}
+        }.let {
+            FfiConverterUInt.lift(it)
+        }
+    
     
 
     
@@ -1339,102 +2007,930 @@ public object FfiConverterTypeDatabaseGroup: FfiConverter<DatabaseGroup, Pointer
 
 
 
-enum class CryptoEngine {
-    AES,CHACHA20;
+data class AuditEvent (
+    var `timestamp`: ULong, 
+    var `eventType`: AuditEventType, 
+    var `objectType`: AuditObjectType, 
+    var `objectId`: UInt, 
+    var `objectName`: String, 
+    var `details`: String, 
+    var `deviceLabel`: String
+) {
+    
     companion object
 }
 
-public object FfiConverterTypeCryptoEngine: FfiConverterRustBuffer<CryptoEngine> {
-    override fun read(buf: ByteBuffer) = try {
-        CryptoEngine.values()[buf.getInt() - 1]
-    } catch (e: IndexOutOfBoundsException) {
-        throw RuntimeException("invalid enum value, something is very wrong!!", e)
+public object FfiConverterTypeAuditEvent: FfiConverterRustBuffer<AuditEvent> {
+    override fun read(buf: ByteBuffer): AuditEvent {
+        return AuditEvent(
+            FfiConverterULong.read(buf),
+            FfiConverterTypeAuditEventType.read(buf),
+            FfiConverterTypeAuditObjectType.read(buf),
+            FfiConverterUInt.read(buf),
+            FfiConverterString.read(buf),
+            FfiConverterString.read(buf),
+            FfiConverterString.read(buf),
+        )
+    }
+
+    override fun allocationSize(value: AuditEvent) = (
+            FfiConverterULong.allocationSize(value.`timestamp`) +
+            FfiConverterTypeAuditEventType.allocationSize(value.`eventType`) +
+            FfiConverterTypeAuditObjectType.allocationSize(value.`objectType`) +
+            FfiConverterUInt.allocationSize(value.`objectId`) +
+            FfiConverterString.allocationSize(value.`objectName`) +
+            FfiConverterString.allocationSize(value.`details`) +
+            FfiConverterString.allocationSize(value.`deviceLabel`)
+    )
+
+    override fun write(value: AuditEvent, buf: ByteBuffer) {
+            FfiConverterULong.write(value.`timestamp`, buf)
+            FfiConverterTypeAuditEventType.write(value.`eventType`, buf)
+            FfiConverterTypeAuditObjectType.write(value.`objectType`, buf)
+            FfiConverterUInt.write(value.`objectId`, buf)
+            FfiConverterString.write(value.`objectName`, buf)
+            FfiConverterString.write(value.`details`, buf)
+            FfiConverterString.write(value.`deviceLabel`, buf)
     }
+}
 
-    override fun allocationSize(value: CryptoEngine) = 4
 
-    override fun write(value: CryptoEngine, buf: ByteBuffer) {
-        buf.putInt(value.ordinal + 1)
-    }
+
+
+data class AuditSettings (
+    var `maxRecords`: UInt, 
+    var `logReveals`: Boolean
+) {
+    
+    companion object
 }
 
+public object FfiConverterTypeAuditSettings: FfiConverterRustBuffer<AuditSettings> {
+    override fun read(buf: ByteBuffer): AuditSettings {
+        return AuditSettings(
+            FfiConverterUInt.read(buf),
+            FfiConverterBoolean.read(buf),
+        )
+    }
+
+    override fun allocationSize(value: AuditSettings) = (
+            FfiConverterUInt.allocationSize(value.`maxRecords`) +
+            FfiConverterBoolean.allocationSize(value.`logReveals`)
+    )
 
+    override fun write(value: AuditSettings, buf: ByteBuffer) {
+            FfiConverterUInt.write(value.`maxRecords`, buf)
+            FfiConverterBoolean.write(value.`logReveals`, buf)
+    }
+}
 
 
 
 
-enum class HashAlgorithm {
-    ARGON2;
+data class DatabaseMember (
+    var `name`: String, 
+    var `publicKey`: ByteArray
+) {
+    
     companion object
 }
 
-public object FfiConverterTypeHashAlgorithm: FfiConverterRustBuffer<HashAlgorithm> {
-    override fun read(buf: ByteBuffer) = try {
-        HashAlgorithm.values()[buf.getInt() - 1]
-    } catch (e: IndexOutOfBoundsException) {
-        throw RuntimeException("invalid enum value, something is very wrong!!", e)
+public object FfiConverterTypeDatabaseMember: FfiConverterRustBuffer<DatabaseMember> {
+    override fun read(buf: ByteBuffer): DatabaseMember {
+        return DatabaseMember(
+            FfiConverterString.read(buf),
+            FfiConverterByteArray.read(buf),
+        )
     }
 
-    override fun allocationSize(value: HashAlgorithm) = 4
+    override fun allocationSize(value: DatabaseMember) = (
+            FfiConverterString.allocationSize(value.`name`) +
+            FfiConverterByteArray.allocationSize(value.`publicKey`)
+    )
 
-    override fun write(value: HashAlgorithm, buf: ByteBuffer) {
-        buf.putInt(value.ordinal + 1)
+    override fun write(value: DatabaseMember, buf: ByteBuffer) {
+            FfiConverterString.write(value.`name`, buf)
+            FfiConverterByteArray.write(value.`publicKey`, buf)
     }
 }
 
 
 
 
-
-
-enum class PasswordDatabaseType {
-    KEE_PASS,PMAN;
+data class DatabaseUser (
+    var `name`: String, 
+    var `email`: String, 
+    var `displayName`: String, 
+    var `fields`: Map<String, String>
+) {
+    
     companion object
 }
 
-public object FfiConverterTypePasswordDatabaseType: FfiConverterRustBuffer<PasswordDatabaseType> {
-    override fun read(buf: ByteBuffer) = try {
-        PasswordDatabaseType.values()[buf.getInt() - 1]
-    } catch (e: IndexOutOfBoundsException) {
-        throw RuntimeException("invalid enum value, something is very wrong!!", e)
+public object FfiConverterTypeDatabaseUser: FfiConverterRustBuffer<DatabaseUser> {
+    override fun read(buf: ByteBuffer): DatabaseUser {
+        return DatabaseUser(
+            FfiConverterString.read(buf),
+            FfiConverterString.read(buf),
+            FfiConverterString.read(buf),
+            FfiConverterMapStringString.read(buf),
+        )
+    }
+
+    override fun allocationSize(value: DatabaseUser) = (
+            FfiConverterString.allocationSize(value.`name`) +
+            FfiConverterString.allocationSize(value.`email`) +
+            FfiConverterString.allocationSize(value.`displayName`) +
+            FfiConverterMapStringString.allocationSize(value.`fields`)
+    )
+
+    override fun write(value: DatabaseUser, buf: ByteBuffer) {
+            FfiConverterString.write(value.`name`, buf)
+            FfiConverterString.write(value.`email`, buf)
+            FfiConverterString.write(value.`displayName`, buf)
+            FfiConverterMapStringString.write(value.`fields`, buf)
     }
+}
 
-    override fun allocationSize(value: PasswordDatabaseType) = 4
 
-    override fun write(value: PasswordDatabaseType, buf: ByteBuffer) {
-        buf.putInt(value.ordinal + 1)
-    }
+
+
+data class DatabaseVersion (
+    var `storedVersion`: UShort, 
+    var `currentVersion`: UShort
+) {
+    
+    companion object
 }
 
+public object FfiConverterTypeDatabaseVersion: FfiConverterRustBuffer<DatabaseVersion> {
+    override fun read(buf: ByteBuffer): DatabaseVersion {
+        return DatabaseVersion(
+            FfiConverterUShort.read(buf),
+            FfiConverterUShort.read(buf),
+        )
+    }
 
+    override fun allocationSize(value: DatabaseVersion) = (
+            FfiConverterUShort.allocationSize(value.`storedVersion`) +
+            FfiConverterUShort.allocationSize(value.`currentVersion`)
+    )
 
+    override fun write(value: DatabaseVersion, buf: ByteBuffer) {
+            FfiConverterUShort.write(value.`storedVersion`, buf)
+            FfiConverterUShort.write(value.`currentVersion`, buf)
+    }
+}
 
 
 
 
-sealed class PmanException: Exception() {
-    // Each variant is a nested class
+data class EntityListItem (
+    var `id`: UInt, 
+    var `entity`: DatabaseEntity
+) : Disposable {
     
-    class ErrorMessage(
-        val `errorText`: String
-        ) : PmanException() {
-        override val message
-            get() = "errorText=${ `errorText` }"
+    @Suppress("UNNECESSARY_SAFE_CALL") // codegen is much simpler if we unconditionally emit safe calls here
+    override fun destroy() {
+        
+    Disposable.destroy(
+        this.`id`, 
+        this.`entity`)
     }
     
+    companion object
+}
 
-    companion object ErrorHandler : CallStatusErrorHandler<PmanException> {
-        override fun lift(error_buf: RustBuffer.ByValue): PmanException = FfiConverterTypePmanError.lift(error_buf)
+public object FfiConverterTypeEntityListItem: FfiConverterRustBuffer<EntityListItem> {
+    override fun read(buf: ByteBuffer): EntityListItem {
+        return EntityListItem(
+            FfiConverterUInt.read(buf),
+            FfiConverterTypeDatabaseEntity.read(buf),
+        )
     }
 
-    
+    override fun allocationSize(value: EntityListItem) = (
+            FfiConverterUInt.allocationSize(value.`id`) +
+            FfiConverterTypeDatabaseEntity.allocationSize(value.`entity`)
+    )
+
+    override fun write(value: EntityListItem, buf: ByteBuffer) {
+            FfiConverterUInt.write(value.`id`, buf)
+            FfiConverterTypeDatabaseEntity.write(value.`entity`, buf)
+    }
 }
 
-public object FfiConverterTypePmanError : FfiConverterRustBuffer<PmanException> {
-    override fun read(buf: ByteBuffer): PmanException {
-        
 
-        return when(buf.getInt()) {
+
+
+data class EntityPage (
+    var `total`: UInt, 
+    var `items`: List<EntityListItem>
+) : Disposable {
+    
+    @Suppress("UNNECESSARY_SAFE_CALL") // codegen is much simpler if we unconditionally emit safe calls here
+    override fun destroy() {
+        
+    Disposable.destroy(
+        this.`total`, 
+        this.`items`)
+    }
+    
+    companion object
+}
+
+public object FfiConverterTypeEntityPage: FfiConverterRustBuffer<EntityPage> {
+    override fun read(buf: ByteBuffer): EntityPage {
+        return EntityPage(
+            FfiConverterUInt.read(buf),
+            FfiConverterSequenceTypeEntityListItem.read(buf),
+        )
+    }
+
+    override fun allocationSize(value: EntityPage) = (
+            FfiConverterUInt.allocationSize(value.`total`) +
+            FfiConverterSequenceTypeEntityListItem.allocationSize(value.`items`)
+    )
+
+    override fun write(value: EntityPage, buf: ByteBuffer) {
+            FfiConverterUInt.write(value.`total`, buf)
+            FfiConverterSequenceTypeEntityListItem.write(value.`items`, buf)
+    }
+}
+
+
+
+
+data class EntityTemplate (
+    var `id`: UInt, 
+    var `name`: String, 
+    var `builtIn`: Boolean, 
+    var `fields`: List<TemplateField>
+) {
+    
+    companion object
+}
+
+public object FfiConverterTypeEntityTemplate: FfiConverterRustBuffer<EntityTemplate> {
+    override fun read(buf: ByteBuffer): EntityTemplate {
+        return EntityTemplate(
+            FfiConverterUInt.read(buf),
+            FfiConverterString.read(buf),
+            FfiConverterBoolean.read(buf),
+            FfiConverterSequenceTypeTemplateField.read(buf),
+        )
+    }
+
+    override fun allocationSize(value: EntityTemplate) = (
+            FfiConverterUInt.allocationSize(value.`id`) +
+            FfiConverterString.allocationSize(value.`name`) +
+            FfiConverterBoolean.allocationSize(value.`builtIn`) +
+            FfiConverterSequenceTypeTemplateField.allocationSize(value.`fields`)
+    )
+
+    override fun write(value: EntityTemplate, buf: ByteBuffer) {
+            FfiConverterUInt.write(value.`id`, buf)
+            FfiConverterString.write(value.`name`, buf)
+            FfiConverterBoolean.write(value.`builtIn`, buf)
+            FfiConverterSequenceTypeTemplateField.write(value.`fields`, buf)
+    }
+}
+
+
+
+
+data class EntityUrl (
+    var `url`: String, 
+    var `matchMode`: UrlMatchMode
+) {
+    
+    companion object
+}
+
+public object FfiConverterTypeEntityUrl: FfiConverterRustBuffer<EntityUrl> {
+    override fun read(buf: ByteBuffer): EntityUrl {
+        return EntityUrl(
+            FfiConverterString.read(buf),
+            FfiConverterTypeUrlMatchMode.read(buf),
+        )
+    }
+
+    override fun allocationSize(value: EntityUrl) = (
+            FfiConverterString.allocationSize(value.`url`) +
+            FfiConverterTypeUrlMatchMode.allocationSize(value.`matchMode`)
+    )
+
+    override fun write(value: EntityUrl, buf: ByteBuffer) {
+            FfiConverterString.write(value.`url`, buf)
+            FfiConverterTypeUrlMatchMode.write(value.`matchMode`, buf)
+    }
+}
+
+
+
+
+data class ExpiringEntity (
+    var `entityId`: UInt, 
+    var `passwordChangedAt`: ULong, 
+    var `expiresAt`: ULong
+) {
+    
+    companion object
+}
+
+public object FfiConverterTypeExpiringEntity: FfiConverterRustBuffer<ExpiringEntity> {
+    override fun read(buf: ByteBuffer): ExpiringEntity {
+        return ExpiringEntity(
+            FfiConverterUInt.read(buf),
+            FfiConverterULong.read(buf),
+            FfiConverterULong.read(buf),
+        )
+    }
+
+    override fun allocationSize(value: ExpiringEntity) = (
+            FfiConverterUInt.allocationSize(value.`entityId`) +
+            FfiConverterULong.allocationSize(value.`passwordChangedAt`) +
+            FfiConverterULong.allocationSize(value.`expiresAt`)
+    )
+
+    override fun write(value: ExpiringEntity, buf: ByteBuffer) {
+            FfiConverterUInt.write(value.`entityId`, buf)
+            FfiConverterULong.write(value.`passwordChangedAt`, buf)
+            FfiConverterULong.write(value.`expiresAt`, buf)
+    }
+}
+
+
+
+
+data class IntegrityIssue (
+    var `issueType`: IntegrityIssueType, 
+    var `recordId`: UInt, 
+    var `description`: String
+) {
+    
+    companion object
+}
+
+public object FfiConverterTypeIntegrityIssue: FfiConverterRustBuffer<IntegrityIssue> {
+    override fun read(buf: ByteBuffer): IntegrityIssue {
+        return IntegrityIssue(
+            FfiConverterTypeIntegrityIssueType.read(buf),
+            FfiConverterUInt.read(buf),
+            FfiConverterString.read(buf),
+        )
+    }
+
+    override fun allocationSize(value: IntegrityIssue) = (
+            FfiConverterTypeIntegrityIssueType.allocationSize(value.`issueType`) +
+            FfiConverterUInt.allocationSize(value.`recordId`) +
+            FfiConverterString.allocationSize(value.`description`)
+    )
+
+    override fun write(value: IntegrityIssue, buf: ByteBuffer) {
+            FfiConverterTypeIntegrityIssueType.write(value.`issueType`, buf)
+            FfiConverterUInt.write(value.`recordId`, buf)
+            FfiConverterString.write(value.`description`, buf)
+    }
+}
+
+
+
+
+data class MemberKeyPair (
+    var `privateKey`: ByteArray, 
+    var `publicKey`: ByteArray
+) {
+    
+    companion object
+}
+
+public object FfiConverterTypeMemberKeyPair: FfiConverterRustBuffer<MemberKeyPair> {
+    override fun read(buf: ByteBuffer): MemberKeyPair {
+        return MemberKeyPair(
+            FfiConverterByteArray.read(buf),
+            FfiConverterByteArray.read(buf),
+        )
+    }
+
+    override fun allocationSize(value: MemberKeyPair) = (
+            FfiConverterByteArray.allocationSize(value.`privateKey`) +
+            FfiConverterByteArray.allocationSize(value.`publicKey`)
+    )
+
+    override fun write(value: MemberKeyPair, buf: ByteBuffer) {
+            FfiConverterByteArray.write(value.`privateKey`, buf)
+            FfiConverterByteArray.write(value.`publicKey`, buf)
+    }
+}
+
+
+
+
+data class OtpCode (
+    var `code`: String, 
+    var `validFor`: ULong?
+) {
+    
+    companion object
+}
+
+public object FfiConverterTypeOtpCode: FfiConverterRustBuffer<OtpCode> {
+    override fun read(buf: ByteBuffer): OtpCode {
+        return OtpCode(
+            FfiConverterString.read(buf),
+            FfiConverterOptionalULong.read(buf),
+        )
+    }
+
+    override fun allocationSize(value: OtpCode) = (
+            FfiConverterString.allocationSize(value.`code`) +
+            FfiConverterOptionalULong.allocationSize(value.`validFor`)
+    )
+
+    override fun write(value: OtpCode, buf: ByteBuffer) {
+            FfiConverterString.write(value.`code`, buf)
+            FfiConverterOptionalULong.write(value.`validFor`, buf)
+    }
+}
+
+
+
+
+data class PropertyInfo (
+    var `id`: UInt, 
+    var `name`: String, 
+    var `fieldType`: FieldType, 
+    var `secret`: Boolean
+) {
+    
+    companion object
+}
+
+public object FfiConverterTypePropertyInfo: FfiConverterRustBuffer<PropertyInfo> {
+    override fun read(buf: ByteBuffer): PropertyInfo {
+        return PropertyInfo(
+            FfiConverterUInt.read(buf),
+            FfiConverterString.read(buf),
+            FfiConverterTypeFieldType.read(buf),
+            FfiConverterBoolean.read(buf),
+        )
+    }
+
+    override fun allocationSize(value: PropertyInfo) = (
+            FfiConverterUInt.allocationSize(value.`id`) +
+            FfiConverterString.allocationSize(value.`name`) +
+            FfiConverterTypeFieldType.allocationSize(value.`fieldType`) +
+            FfiConverterBoolean.allocationSize(value.`secret`)
+    )
+
+    override fun write(value: PropertyInfo, buf: ByteBuffer) {
+            FfiConverterUInt.write(value.`id`, buf)
+            FfiConverterString.write(value.`name`, buf)
+            FfiConverterTypeFieldType.write(value.`fieldType`, buf)
+            FfiConverterBoolean.write(value.`secret`, buf)
+    }
+}
+
+
+
+
+data class PropertyLayout (
+    var `id`: UInt, 
+    var `fieldType`: FieldType, 
+    var `secret`: Boolean
+) {
+    
+    companion object
+}
+
+public object FfiConverterTypePropertyLayout: FfiConverterRustBuffer<PropertyLayout> {
+    override fun read(buf: ByteBuffer): PropertyLayout {
+        return PropertyLayout(
+            FfiConverterUInt.read(buf),
+            FfiConverterTypeFieldType.read(buf),
+            FfiConverterBoolean.read(buf),
+        )
+    }
+
+    override fun allocationSize(value: PropertyLayout) = (
+            FfiConverterUInt.allocationSize(value.`id`) +
+            FfiConverterTypeFieldType.allocationSize(value.`fieldType`) +
+            FfiConverterBoolean.allocationSize(value.`secret`)
+    )
+
+    override fun write(value: PropertyLayout, buf: ByteBuffer) {
+            FfiConverterUInt.write(value.`id`, buf)
+            FfiConverterTypeFieldType.write(value.`fieldType`, buf)
+            FfiConverterBoolean.write(value.`secret`, buf)
+    }
+}
+
+
+
+
+data class TemplateField (
+    var `name`: String, 
+    var `fieldType`: FieldType, 
+    var `secret`: Boolean, 
+    var `required`: Boolean
+) {
+    
+    companion object
+}
+
+public object FfiConverterTypeTemplateField: FfiConverterRustBuffer<TemplateField> {
+    override fun read(buf: ByteBuffer): TemplateField {
+        return TemplateField(
+            FfiConverterString.read(buf),
+            FfiConverterTypeFieldType.read(buf),
+            FfiConverterBoolean.read(buf),
+            FfiConverterBoolean.read(buf),
+        )
+    }
+
+    override fun allocationSize(value: TemplateField) = (
+            FfiConverterString.allocationSize(value.`name`) +
+            FfiConverterTypeFieldType.allocationSize(value.`fieldType`) +
+            FfiConverterBoolean.allocationSize(value.`secret`) +
+            FfiConverterBoolean.allocationSize(value.`required`)
+    )
+
+    override fun write(value: TemplateField, buf: ByteBuffer) {
+            FfiConverterString.write(value.`name`, buf)
+            FfiConverterTypeFieldType.write(value.`fieldType`, buf)
+            FfiConverterBoolean.write(value.`secret`, buf)
+            FfiConverterBoolean.write(value.`required`, buf)
+    }
+}
+
+
+
+
+data class TrashedEntity (
+    var `entityId`: UInt, 
+    var `name`: String, 
+    var `groupId`: UInt, 
+    var `deletedAt`: ULong
+) {
+    
+    companion object
+}
+
+public object FfiConverterTypeTrashedEntity: FfiConverterRustBuffer<TrashedEntity> {
+    override fun read(buf: ByteBuffer): TrashedEntity {
+        return TrashedEntity(
+            FfiConverterUInt.read(buf),
+            FfiConverterString.read(buf),
+            FfiConverterUInt.read(buf),
+            FfiConverterULong.read(buf),
+        )
+    }
+
+    override fun allocationSize(value: TrashedEntity) = (
+            FfiConverterUInt.allocationSize(value.`entityId`) +
+            FfiConverterString.allocationSize(value.`name`) +
+            FfiConverterUInt.allocationSize(value.`groupId`) +
+            FfiConverterULong.allocationSize(value.`deletedAt`)
+    )
+
+    override fun write(value: TrashedEntity, buf: ByteBuffer) {
+            FfiConverterUInt.write(value.`entityId`, buf)
+            FfiConverterString.write(value.`name`, buf)
+            FfiConverterUInt.write(value.`groupId`, buf)
+            FfiConverterULong.write(value.`deletedAt`, buf)
+    }
+}
+
+
+
+
+data class UrlMatch (
+    var `entityId`: UInt, 
+    var `groupId`: UInt, 
+    var `url`: String, 
+    var `matchMode`: UrlMatchMode
+) {
+    
+    companion object
+}
+
+public object FfiConverterTypeUrlMatch: FfiConverterRustBuffer<UrlMatch> {
+    override fun read(buf: ByteBuffer): UrlMatch {
+        return UrlMatch(
+            FfiConverterUInt.read(buf),
+            FfiConverterUInt.read(buf),
+            FfiConverterString.read(buf),
+            FfiConverterTypeUrlMatchMode.read(buf),
+        )
+    }
+
+    override fun allocationSize(value: UrlMatch) = (
+            FfiConverterUInt.allocationSize(value.`entityId`) +
+            FfiConverterUInt.allocationSize(value.`groupId`) +
+            FfiConverterString.allocationSize(value.`url`) +
+            FfiConverterTypeUrlMatchMode.allocationSize(value.`matchMode`)
+    )
+
+    override fun write(value: UrlMatch, buf: ByteBuffer) {
+            FfiConverterUInt.write(value.`entityId`, buf)
+            FfiConverterUInt.write(value.`groupId`, buf)
+            FfiConverterString.write(value.`url`, buf)
+            FfiConverterTypeUrlMatchMode.write(value.`matchMode`, buf)
+    }
+}
+
+
+
+
+enum class AuditEventType {
+    ADD,MODIFY,RENAME,REMOVE,RESTORE,REVEAL;
+    companion object
+}
+
+public object FfiConverterTypeAuditEventType: FfiConverterRustBuffer<AuditEventType> {
+    override fun read(buf: ByteBuffer) = try {
+        AuditEventType.values()[buf.getInt() - 1]
+    } catch (e: IndexOutOfBoundsException) {
+        throw RuntimeException("invalid enum value, something is very wrong!!", e)
+    }
+
+    override fun allocationSize(value: AuditEventType) = 4
+
+    override fun write(value: AuditEventType, buf: ByteBuffer) {
+        buf.putInt(value.ordinal + 1)
+    }
+}
+
+
+
+
+
+
+enum class AuditObjectType {
+    GROUP,USER,ENTITY,DATABASE;
+    companion object
+}
+
+public object FfiConverterTypeAuditObjectType: FfiConverterRustBuffer<AuditObjectType> {
+    override fun read(buf: ByteBuffer) = try {
+        AuditObjectType.values()[buf.getInt() - 1]
+    } catch (e: IndexOutOfBoundsException) {
+        throw RuntimeException("invalid enum value, something is very wrong!!", e)
+    }
+
+    override fun allocationSize(value: AuditObjectType) = 4
+
+    override fun write(value: AuditObjectType, buf: ByteBuffer) {
+        buf.putInt(value.ordinal + 1)
+    }
+}
+
+
+
+
+
+
+enum class CryptoEngine {
+    AES,CHACHA20,AES_CTR;
+    companion object
+}
+
+public object FfiConverterTypeCryptoEngine: FfiConverterRustBuffer<CryptoEngine> {
+    override fun read(buf: ByteBuffer) = try {
+        CryptoEngine.values()[buf.getInt() - 1]
+    } catch (e: IndexOutOfBoundsException) {
+        throw RuntimeException("invalid enum value, something is very wrong!!", e)
+    }
+
+    override fun allocationSize(value: CryptoEngine) = 4
+
+    override fun write(value: CryptoEngine, buf: ByteBuffer) {
+        buf.putInt(value.ordinal + 1)
+    }
+}
+
+
+
+
+
+
+enum class EntityKind {
+    PASSWORD,NOTE;
+    companion object
+}
+
+public object FfiConverterTypeEntityKind: FfiConverterRustBuffer<EntityKind> {
+    override fun read(buf: ByteBuffer) = try {
+        EntityKind.values()[buf.getInt() - 1]
+    } catch (e: IndexOutOfBoundsException) {
+        throw RuntimeException("invalid enum value, something is very wrong!!", e)
+    }
+
+    override fun allocationSize(value: EntityKind) = 4
+
+    override fun write(value: EntityKind, buf: ByteBuffer) {
+        buf.putInt(value.ordinal + 1)
+    }
+}
+
+
+
+
+
+
+enum class EntitySortKey {
+    NAME,CREATED_AT,MODIFIED_AT,GROUP;
+    companion object
+}
+
+public object FfiConverterTypeEntitySortKey: FfiConverterRustBuffer<EntitySortKey> {
+    override fun read(buf: ByteBuffer) = try {
+        EntitySortKey.values()[buf.getInt() - 1]
+    } catch (e: IndexOutOfBoundsException) {
+        throw RuntimeException("invalid enum value, something is very wrong!!", e)
+    }
+
+    override fun allocationSize(value: EntitySortKey) = 4
+
+    override fun write(value: EntitySortKey, buf: ByteBuffer) {
+        buf.putInt(value.ordinal + 1)
+    }
+}
+
+
+
+
+
+
+enum class FieldType {
+    TEXT,URL,EMAIL,PHONE,DATE,MULTILINE;
+    companion object
+}
+
+public object FfiConverterTypeFieldType: FfiConverterRustBuffer<FieldType> {
+    override fun read(buf: ByteBuffer) = try {
+        FieldType.values()[buf.getInt() - 1]
+    } catch (e: IndexOutOfBoundsException) {
+        throw RuntimeException("invalid enum value, something is very wrong!!", e)
+    }
+
+    override fun allocationSize(value: FieldType) = 4
+
+    override fun write(value: FieldType, buf: ByteBuffer) {
+        buf.putInt(value.ordinal + 1)
+    }
+}
+
+
+
+
+
+
+enum class HashAlgorithm {
+    ARGON2;
+    companion object
+}
+
+public object FfiConverterTypeHashAlgorithm: FfiConverterRustBuffer<HashAlgorithm> {
+    override fun read(buf: ByteBuffer) = try {
+        HashAlgorithm.values()[buf.getInt() - 1]
+    } catch (e: IndexOutOfBoundsException) {
+        throw RuntimeException("invalid enum value, something is very wrong!!", e)
+    }
+
+    override fun allocationSize(value: HashAlgorithm) = 4
+
+    override fun write(value: HashAlgorithm, buf: ByteBuffer) {
+        buf.putInt(value.ordinal + 1)
+    }
+}
+
+
+
+
+
+
+enum class IntegrityIssueType {
+    DANGLING_REFERENCE,ORPHANED_RECORD,DUPLICATE_NAME,UNDECRYPTABLE_RECORD;
+    companion object
+}
+
+public object FfiConverterTypeIntegrityIssueType: FfiConverterRustBuffer<IntegrityIssueType> {
+    override fun read(buf: ByteBuffer) = try {
+        IntegrityIssueType.values()[buf.getInt() - 1]
+    } catch (e: IndexOutOfBoundsException) {
+        throw RuntimeException("invalid enum value, something is very wrong!!", e)
+    }
+
+    override fun allocationSize(value: IntegrityIssueType) = 4
+
+    override fun write(value: IntegrityIssueType, buf: ByteBuffer) {
+        buf.putInt(value.ordinal + 1)
+    }
+}
+
+
+
+
+
+
+enum class KeySlotType {
+    PASSWORDS,RECOVERY_KEY,KEY_FILE,MEMBER;
+    companion object
+}
+
+public object FfiConverterTypeKeySlotType: FfiConverterRustBuffer<KeySlotType> {
+    override fun read(buf: ByteBuffer) = try {
+        KeySlotType.values()[buf.getInt() - 1]
+    } catch (e: IndexOutOfBoundsException) {
+        throw RuntimeException("invalid enum value, something is very wrong!!", e)
+    }
+
+    override fun allocationSize(value: KeySlotType) = 4
+
+    override fun write(value: KeySlotType, buf: ByteBuffer) {
+        buf.putInt(value.ordinal + 1)
+    }
+}
+
+
+
+
+
+
+enum class OtpAlgorithm {
+    SHA1,SHA256,SHA512;
+    companion object
+}
+
+public object FfiConverterTypeOtpAlgorithm: FfiConverterRustBuffer<OtpAlgorithm> {
+    override fun read(buf: ByteBuffer) = try {
+        OtpAlgorithm.values()[buf.getInt() - 1]
+    } catch (e: IndexOutOfBoundsException) {
+        throw RuntimeException("invalid enum value, something is very wrong!!", e)
+    }
+
+    override fun allocationSize(value: OtpAlgorithm) = 4
+
+    override fun write(value: OtpAlgorithm, buf: ByteBuffer) {
+        buf.putInt(value.ordinal + 1)
+    }
+}
+
+
+
+
+
+
+enum class PasswordDatabaseType {
+    KEE_PASS,PMAN;
+    companion object
+}
+
+public object FfiConverterTypePasswordDatabaseType: FfiConverterRustBuffer<PasswordDatabaseType> {
+    override fun read(buf: ByteBuffer) = try {
+        PasswordDatabaseType.values()[buf.getInt() - 1]
+    } catch (e: IndexOutOfBoundsException) {
+        throw RuntimeException("invalid enum value, something is very wrong!!", e)
+    }
+
+    override fun allocationSize(value: PasswordDatabaseType) = 4
+
+    override fun write(value: PasswordDatabaseType, buf: ByteBuffer) {
+        buf.putInt(value.ordinal + 1)
+    }
+}
+
+
+
+
+
+
+
+sealed class PmanException: Exception() {
+    // Each variant is a nested class
+    
+    class ErrorMessage(
+        val `errorText`: String
+        ) : PmanException() {
+        override val message
+            get() = "errorText=${ `errorText` }"
+    }
+    
+
+    companion object ErrorHandler : CallStatusErrorHandler<PmanException> {
+        override fun lift(error_buf: RustBuffer.ByValue): PmanException = FfiConverterTypePmanError.lift(error_buf)
+    }
+
+    
+}
+
+public object FfiConverterTypePmanError : FfiConverterRustBuffer<PmanException> {
+    override fun read(buf: ByteBuffer): PmanException {
+        
+
+        return when(buf.getInt()) {
             1 -> PmanException.ErrorMessage(
                 FfiConverterString.read(buf),
                 )
@@ -1442,53 +2938,368 @@ public object FfiConverterTypePmanError : FfiConverterRustBuffer<PmanException>
         }
     }
 
-    override fun allocationSize(value: PmanException): Int {
-        return when(value) {
-            is PmanException.ErrorMessage -> (
-                // Add the size for the Int that specifies the variant plus the size needed for all fields
-                4
-                + FfiConverterString.allocationSize(value.`errorText`)
-            )
+    override fun allocationSize(value: PmanException): Int {
+        return when(value) {
+            is PmanException.ErrorMessage -> (
+                // Add the size for the Int that specifies the variant plus the size needed for all fields
+                4
+                + FfiConverterString.allocationSize(value.`errorText`)
+            )
+        }
+    }
+
+    override fun write(value: PmanException, buf: ByteBuffer) {
+        when(value) {
+            is PmanException.ErrorMessage -> {
+                buf.putInt(1)
+                FfiConverterString.write(value.`errorText`, buf)
+                Unit
+            }
+        }.let { /* this makes the `when` an expression, which ensures it is exhaustive */ }
+    }
+
+}
+
+
+
+
+enum class UrlMatchMode {
+    EXACT,HOST,BASE_DOMAIN,PREFIX,REGEX;
+    companion object
+}
+
+public object FfiConverterTypeUrlMatchMode: FfiConverterRustBuffer<UrlMatchMode> {
+    override fun read(buf: ByteBuffer) = try {
+        UrlMatchMode.values()[buf.getInt() - 1]
+    } catch (e: IndexOutOfBoundsException) {
+        throw RuntimeException("invalid enum value, something is very wrong!!", e)
+    }
+
+    override fun allocationSize(value: UrlMatchMode) = 4
+
+    override fun write(value: UrlMatchMode, buf: ByteBuffer) {
+        buf.putInt(value.ordinal + 1)
+    }
+}
+
+
+
+
+
+
+public object FfiConverterOptionalUInt: FfiConverterRustBuffer<UInt?> {
+    override fun read(buf: ByteBuffer): UInt? {
+        if (buf.get().toInt() == 0) {
+            return null
+        }
+        return FfiConverterUInt.read(buf)
+    }
+
+    override fun allocationSize(value: UInt?): Int {
+        if (value == null) {
+            return 1
+        } else {
+            return 1 + FfiConverterUInt.allocationSize(value)
+        }
+    }
+
+    override fun write(value: UInt?, buf: ByteBuffer) {
+        if (value == null) {
+            buf.put(0)
+        } else {
+            buf.put(1)
+            FfiConverterUInt.write(value, buf)
+        }
+    }
+}
+
+
+
+
+public object FfiConverterOptionalULong: FfiConverterRustBuffer<ULong?> {
+    override fun read(buf: ByteBuffer): ULong? {
+        if (buf.get().toInt() == 0) {
+            return null
+        }
+        return FfiConverterULong.read(buf)
+    }
+
+    override fun allocationSize(value: ULong?): Int {
+        if (value == null) {
+            return 1
+        } else {
+            return 1 + FfiConverterULong.allocationSize(value)
+        }
+    }
+
+    override fun write(value: ULong?, buf: ByteBuffer) {
+        if (value == null) {
+            buf.put(0)
+        } else {
+            buf.put(1)
+            FfiConverterULong.write(value, buf)
+        }
+    }
+}
+
+
+
+
+public object FfiConverterOptionalString: FfiConverterRustBuffer<String?> {
+    override fun read(buf: ByteBuffer): String? {
+        if (buf.get().toInt() == 0) {
+            return null
+        }
+        return FfiConverterString.read(buf)
+    }
+
+    override fun allocationSize(value: String?): Int {
+        if (value == null) {
+            return 1
+        } else {
+            return 1 + FfiConverterString.allocationSize(value)
+        }
+    }
+
+    override fun write(value: String?, buf: ByteBuffer) {
+        if (value == null) {
+            buf.put(0)
+        } else {
+            buf.put(1)
+            FfiConverterString.write(value, buf)
+        }
+    }
+}
+
+
+
+
+public object FfiConverterOptionalByteArray: FfiConverterRustBuffer<ByteArray?> {
+    override fun read(buf: ByteBuffer): ByteArray? {
+        if (buf.get().toInt() == 0) {
+            return null
+        }
+        return FfiConverterByteArray.read(buf)
+    }
+
+    override fun allocationSize(value: ByteArray?): Int {
+        if (value == null) {
+            return 1
+        } else {
+            return 1 + FfiConverterByteArray.allocationSize(value)
+        }
+    }
+
+    override fun write(value: ByteArray?, buf: ByteBuffer) {
+        if (value == null) {
+            buf.put(0)
+        } else {
+            buf.put(1)
+            FfiConverterByteArray.write(value, buf)
+        }
+    }
+}
+
+
+
+
+public object FfiConverterOptionalTypeOtpCode: FfiConverterRustBuffer<OtpCode?> {
+    override fun read(buf: ByteBuffer): OtpCode? {
+        if (buf.get().toInt() == 0) {
+            return null
+        }
+        return FfiConverterTypeOtpCode.read(buf)
+    }
+
+    override fun allocationSize(value: OtpCode?): Int {
+        if (value == null) {
+            return 1
+        } else {
+            return 1 + FfiConverterTypeOtpCode.allocationSize(value)
+        }
+    }
+
+    override fun write(value: OtpCode?, buf: ByteBuffer) {
+        if (value == null) {
+            buf.put(0)
+        } else {
+            buf.put(1)
+            FfiConverterTypeOtpCode.write(value, buf)
+        }
+    }
+}
+
+
+
+
+public object FfiConverterSequenceUInt: FfiConverterRustBuffer<List<UInt>> {
+    override fun read(buf: ByteBuffer): List<UInt> {
+        val len = buf.getInt()
+        return List<UInt>(len) {
+            FfiConverterUInt.read(buf)
+        }
+    }
+
+    override fun allocationSize(value: List<UInt>): Int {
+        val sizeForLength = 4
+        val sizeForItems = value.map { FfiConverterUInt.allocationSize(it) }.sum()
+        return sizeForLength + sizeForItems
+    }
+
+    override fun write(value: List<UInt>, buf: ByteBuffer) {
+        buf.putInt(value.size)
+        value.forEach {
+            FfiConverterUInt.write(it, buf)
+        }
+    }
+}
+
+
+
+
+public object FfiConverterSequenceTypeDatabaseGroup: FfiConverterRustBuffer<List<DatabaseGroup>> {
+    override fun read(buf: ByteBuffer): List<DatabaseGroup> {
+        val len = buf.getInt()
+        return List<DatabaseGroup>(len) {
+            FfiConverterTypeDatabaseGroup.read(buf)
+        }
+    }
+
+    override fun allocationSize(value: List<DatabaseGroup>): Int {
+        val sizeForLength = 4
+        val sizeForItems = value.map { FfiConverterTypeDatabaseGroup.allocationSize(it) }.sum()
+        return sizeForLength + sizeForItems
+    }
+
+    override fun write(value: List<DatabaseGroup>, buf: ByteBuffer) {
+        buf.putInt(value.size)
+        value.forEach {
+            FfiConverterTypeDatabaseGroup.write(it, buf)
+        }
+    }
+}
+
+
+
+
+public object FfiConverterSequenceTypeAuditEvent: FfiConverterRustBuffer<List<AuditEvent>> {
+    override fun read(buf: ByteBuffer): List<AuditEvent> {
+        val len = buf.getInt()
+        return List<AuditEvent>(len) {
+            FfiConverterTypeAuditEvent.read(buf)
+        }
+    }
+
+    override fun allocationSize(value: List<AuditEvent>): Int {
+        val sizeForLength = 4
+        val sizeForItems = value.map { FfiConverterTypeAuditEvent.allocationSize(it) }.sum()
+        return sizeForLength + sizeForItems
+    }
+
+    override fun write(value: List<AuditEvent>, buf: ByteBuffer) {
+        buf.putInt(value.size)
+        value.forEach {
+            FfiConverterTypeAuditEvent.write(it, buf)
+        }
+    }
+}
+
+
+
+
+public object FfiConverterSequenceTypeEntityListItem: FfiConverterRustBuffer<List<EntityListItem>> {
+    override fun read(buf: ByteBuffer): List<EntityListItem> {
+        val len = buf.getInt()
+        return List<EntityListItem>(len) {
+            FfiConverterTypeEntityListItem.read(buf)
+        }
+    }
+
+    override fun allocationSize(value: List<EntityListItem>): Int {
+        val sizeForLength = 4
+        val sizeForItems = value.map { FfiConverterTypeEntityListItem.allocationSize(it) }.sum()
+        return sizeForLength + sizeForItems
+    }
+
+    override fun write(value: List<EntityListItem>, buf: ByteBuffer) {
+        buf.putInt(value.size)
+        value.forEach {
+            FfiConverterTypeEntityListItem.write(it, buf)
+        }
+    }
+}
+
+
+
+
+public object FfiConverterSequenceTypeEntityTemplate: FfiConverterRustBuffer<List<EntityTemplate>> {
+    override fun read(buf: ByteBuffer): List<EntityTemplate> {
+        val len = buf.getInt()
+        return List<EntityTemplate>(len) {
+            FfiConverterTypeEntityTemplate.read(buf)
         }
     }
 
-    override fun write(value: PmanException, buf: ByteBuffer) {
-        when(value) {
-            is PmanException.ErrorMessage -> {
-                buf.putInt(1)
-                FfiConverterString.write(value.`errorText`, buf)
-                Unit
-            }
-        }.let { /* this makes the `when` an expression, which ensures it is exhaustive */ }
+    override fun allocationSize(value: List<EntityTemplate>): Int {
+        val sizeForLength = 4
+        val sizeForItems = value.map { FfiConverterTypeEntityTemplate.allocationSize(it) }.sum()
+        return sizeForLength + sizeForItems
     }
 
+    override fun write(value: List<EntityTemplate>, buf: ByteBuffer) {
+        buf.putInt(value.size)
+        value.forEach {
+            FfiConverterTypeEntityTemplate.write(it, buf)
+        }
+    }
 }
 
 
 
 
-public object FfiConverterOptionalUInt: FfiConverterRustBuffer<UInt?> {
-    override fun read(buf: ByteBuffer): UInt? {
-        if (buf.get().toInt() == 0) {
-            return null
+public object FfiConverterSequenceTypeEntityUrl: FfiConverterRustBuffer<List<EntityUrl>> {
+    override fun read(buf: ByteBuffer): List<EntityUrl> {
+        val len = buf.getInt()
+        return List<EntityUrl>(len) {
+            FfiConverterTypeEntityUrl.read(buf)
         }
-        return FfiConverterUInt.read(buf)
     }
 
-    override fun allocationSize(value: UInt?): Int {
-        if (value == null) {
-            return 1
-        } else {
-            return 1 + FfiConverterUInt.allocationSize(value)
+    override fun allocationSize(value: List<EntityUrl>): Int {
+        val sizeForLength = 4
+        val sizeForItems = value.map { FfiConverterTypeEntityUrl.allocationSize(it) }.sum()
+        return sizeForLength + sizeForItems
+    }
+
+    override fun write(value: List<EntityUrl>, buf: ByteBuffer) {
+        buf.putInt(value.size)
+        value.forEach {
+            FfiConverterTypeEntityUrl.write(it, buf)
         }
     }
+}
 
-    override fun write(value: UInt?, buf: ByteBuffer) {
-        if (value == null) {
-            buf.put(0)
-        } else {
-            buf.put(1)
-            FfiConverterUInt.write(value, buf)
+
+
+
+public object FfiConverterSequenceTypeExpiringEntity: FfiConverterRustBuffer<List<ExpiringEntity>> {
+    override fun read(buf: ByteBuffer): List<ExpiringEntity> {
+        val len = buf.getInt()
+        return List<ExpiringEntity>(len) {
+            FfiConverterTypeExpiringEntity.read(buf)
+        }
+    }
+
+    override fun allocationSize(value: List<ExpiringEntity>): Int {
+        val sizeForLength = 4
+        val sizeForItems = value.map { FfiConverterTypeExpiringEntity.allocationSize(it) }.sum()
+        return sizeForLength + sizeForItems
+    }
+
+    override fun write(value: List<ExpiringEntity>, buf: ByteBuffer) {
+        buf.putInt(value.size)
+        value.forEach {
+            FfiConverterTypeExpiringEntity.write(it, buf)
         }
     }
 }
@@ -1496,28 +3307,49 @@ public object FfiConverterOptionalUInt: FfiConverterRustBuffer<UInt?> {
 
 
 
-public object FfiConverterOptionalString: FfiConverterRustBuffer<String?> {
-    override fun read(buf: ByteBuffer): String? {
-        if (buf.get().toInt() == 0) {
-            return null
+public object FfiConverterSequenceTypeIntegrityIssue: FfiConverterRustBuffer<List<IntegrityIssue>> {
+    override fun read(buf: ByteBuffer): List<IntegrityIssue> {
+        val len = buf.getInt()
+        return List<IntegrityIssue>(len) {
+            FfiConverterTypeIntegrityIssue.read(buf)
         }
-        return FfiConverterString.read(buf)
     }
 
-    override fun allocationSize(value: String?): Int {
-        if (value == null) {
-            return 1
-        } else {
-            return 1 + FfiConverterString.allocationSize(value)
+    override fun allocationSize(value: List<IntegrityIssue>): Int {
+        val sizeForLength = 4
+        val sizeForItems = value.map { FfiConverterTypeIntegrityIssue.allocationSize(it) }.sum()
+        return sizeForLength + sizeForItems
+    }
+
+    override fun write(value: List<IntegrityIssue>, buf: ByteBuffer) {
+        buf.putInt(value.size)
+        value.forEach {
+            FfiConverterTypeIntegrityIssue.write(it, buf)
         }
     }
+}
 
-    override fun write(value: String?, buf: ByteBuffer) {
-        if (value == null) {
-            buf.put(0)
-        } else {
-            buf.put(1)
-            FfiConverterString.write(value, buf)
+
+
+
+public object FfiConverterSequenceTypePropertyInfo: FfiConverterRustBuffer<List<PropertyInfo>> {
+    override fun read(buf: ByteBuffer): List<PropertyInfo> {
+        val len = buf.getInt()
+        return List<PropertyInfo>(len) {
+            FfiConverterTypePropertyInfo.read(buf)
+        }
+    }
+
+    override fun allocationSize(value: List<PropertyInfo>): Int {
+        val sizeForLength = 4
+        val sizeForItems = value.map { FfiConverterTypePropertyInfo.allocationSize(it) }.sum()
+        return sizeForLength + sizeForItems
+    }
+
+    override fun write(value: List<PropertyInfo>, buf: ByteBuffer) {
+        buf.putInt(value.size)
+        value.forEach {
+            FfiConverterTypePropertyInfo.write(it, buf)
         }
     }
 }
@@ -1525,123 +3357,324 @@ public object FfiConverterOptionalString: FfiConverterRustBuffer<String?> {
 
 
 
-public object FfiConverterOptionalByteArray: FfiConverterRustBuffer<ByteArray?> {
-    override fun read(buf: ByteBuffer): ByteArray? {
-        if (buf.get().toInt() == 0) {
-            return null
+public object FfiConverterSequenceTypePropertyLayout: FfiConverterRustBuffer<List<PropertyLayout>> {
+    override fun read(buf: ByteBuffer): List<PropertyLayout> {
+        val len = buf.getInt()
+        return List<PropertyLayout>(len) {
+            FfiConverterTypePropertyLayout.read(buf)
         }
-        return FfiConverterByteArray.read(buf)
     }
 
-    override fun allocationSize(value: ByteArray?): Int {
-        if (value == null) {
-            return 1
-        } else {
-            return 1 + FfiConverterByteArray.allocationSize(value)
+    override fun allocationSize(value: List<PropertyLayout>): Int {
+        val sizeForLength = 4
+        val sizeForItems = value.map { FfiConverterTypePropertyLayout.allocationSize(it) }.sum()
+        return sizeForLength + sizeForItems
+    }
+
+    override fun write(value: List<PropertyLayout>, buf: ByteBuffer) {
+        buf.putInt(value.size)
+        value.forEach {
+            FfiConverterTypePropertyLayout.write(it, buf)
+        }
+    }
+}
+
+
+
+
+public object FfiConverterSequenceTypeTemplateField: FfiConverterRustBuffer<List<TemplateField>> {
+    override fun read(buf: ByteBuffer): List<TemplateField> {
+        val len = buf.getInt()
+        return List<TemplateField>(len) {
+            FfiConverterTypeTemplateField.read(buf)
+        }
+    }
+
+    override fun allocationSize(value: List<TemplateField>): Int {
+        val sizeForLength = 4
+        val sizeForItems = value.map { FfiConverterTypeTemplateField.allocationSize(it) }.sum()
+        return sizeForLength + sizeForItems
+    }
+
+    override fun write(value: List<TemplateField>, buf: ByteBuffer) {
+        buf.putInt(value.size)
+        value.forEach {
+            FfiConverterTypeTemplateField.write(it, buf)
+        }
+    }
+}
+
+
+
+
+public object FfiConverterSequenceTypeTrashedEntity: FfiConverterRustBuffer<List<TrashedEntity>> {
+    override fun read(buf: ByteBuffer): List<TrashedEntity> {
+        val len = buf.getInt()
+        return List<TrashedEntity>(len) {
+            FfiConverterTypeTrashedEntity.read(buf)
+        }
+    }
+
+    override fun allocationSize(value: List<TrashedEntity>): Int {
+        val sizeForLength = 4
+        val sizeForItems = value.map { FfiConverterTypeTrashedEntity.allocationSize(it) }.sum()
+        return sizeForLength + sizeForItems
+    }
+
+    override fun write(value: List<TrashedEntity>, buf: ByteBuffer) {
+        buf.putInt(value.size)
+        value.forEach {
+            FfiConverterTypeTrashedEntity.write(it, buf)
+        }
+    }
+}
+
+
+
+
+public object FfiConverterSequenceTypeUrlMatch: FfiConverterRustBuffer<List<UrlMatch>> {
+    override fun read(buf: ByteBuffer): List<UrlMatch> {
+        val len = buf.getInt()
+        return List<UrlMatch>(len) {
+            FfiConverterTypeUrlMatch.read(buf)
+        }
+    }
+
+    override fun allocationSize(value: List<UrlMatch>): Int {
+        val sizeForLength = 4
+        val sizeForItems = value.map { FfiConverterTypeUrlMatch.allocationSize(it) }.sum()
+        return sizeForLength + sizeForItems
+    }
+
+    override fun write(value: List<UrlMatch>, buf: ByteBuffer) {
+        buf.putInt(value.size)
+        value.forEach {
+            FfiConverterTypeUrlMatch.write(it, buf)
+        }
+    }
+}
+
+
+
+
+public object FfiConverterSequenceTypeCryptoEngine: FfiConverterRustBuffer<List<CryptoEngine>> {
+    override fun read(buf: ByteBuffer): List<CryptoEngine> {
+        val len = buf.getInt()
+        return List<CryptoEngine>(len) {
+            FfiConverterTypeCryptoEngine.read(buf)
+        }
+    }
+
+    override fun allocationSize(value: List<CryptoEngine>): Int {
+        val sizeForLength = 4
+        val sizeForItems = value.map { FfiConverterTypeCryptoEngine.allocationSize(it) }.sum()
+        return sizeForLength + sizeForItems
+    }
+
+    override fun write(value: List<CryptoEngine>, buf: ByteBuffer) {
+        buf.putInt(value.size)
+        value.forEach {
+            FfiConverterTypeCryptoEngine.write(it, buf)
+        }
+    }
+}
+
+
+
+
+public object FfiConverterSequenceTypeKeySlotType: FfiConverterRustBuffer<List<KeySlotType>> {
+    override fun read(buf: ByteBuffer): List<KeySlotType> {
+        val len = buf.getInt()
+        return List<KeySlotType>(len) {
+            FfiConverterTypeKeySlotType.read(buf)
+        }
+    }
+
+    override fun allocationSize(value: List<KeySlotType>): Int {
+        val sizeForLength = 4
+        val sizeForItems = value.map { FfiConverterTypeKeySlotType.allocationSize(it) }.sum()
+        return sizeForLength + sizeForItems
+    }
+
+    override fun write(value: List<KeySlotType>, buf: ByteBuffer) {
+        buf.putInt(value.size)
+        value.forEach {
+            FfiConverterTypeKeySlotType.write(it, buf)
+        }
+    }
+}
+
+
+
+public object FfiConverterMapUIntString: FfiConverterRustBuffer<Map<UInt, String>> {
+    override fun read(buf: ByteBuffer): Map<UInt, String> {
+        val len = buf.getInt()
+        return buildMap<UInt, String>(len) {
+            repeat(len) {
+                val k = FfiConverterUInt.read(buf)
+                val v = FfiConverterString.read(buf)
+                this[k] = v
+            }
+        }
+    }
+
+    override fun allocationSize(value: Map<UInt, String>): Int {
+        val spaceForMapSize = 4
+        val spaceForChildren = value.map { (k, v) ->
+            FfiConverterUInt.allocationSize(k) +
+            FfiConverterString.allocationSize(v)
+        }.sum()
+        return spaceForMapSize + spaceForChildren
+    }
+
+    override fun write(value: Map<UInt, String>, buf: ByteBuffer) {
+        buf.putInt(value.size)
+        // The parens on `(k, v)` here ensure we're calling the right method,
+        // which is important for compatibility with older android devices.
+        // Ref https://blog.danlew.net/2017/03/16/kotlin-puzzler-whose-line-is-it-anyways/
+        value.forEach { (k, v) ->
+            FfiConverterUInt.write(k, buf)
+            FfiConverterString.write(v, buf)
+        }
+    }
+}
+
+
+
+public object FfiConverterMapUIntTypeDatabaseEntity: FfiConverterRustBuffer<Map<UInt, DatabaseEntity>> {
+    override fun read(buf: ByteBuffer): Map<UInt, DatabaseEntity> {
+        val len = buf.getInt()
+        return buildMap<UInt, DatabaseEntity>(len) {
+            repeat(len) {
+                val k = FfiConverterUInt.read(buf)
+                val v = FfiConverterTypeDatabaseEntity.read(buf)
+                this[k] = v
+            }
         }
     }
 
-    override fun write(value: ByteArray?, buf: ByteBuffer) {
-        if (value == null) {
-            buf.put(0)
-        } else {
-            buf.put(1)
-            FfiConverterByteArray.write(value, buf)
+    override fun allocationSize(value: Map<UInt, DatabaseEntity>): Int {
+        val spaceForMapSize = 4
+        val spaceForChildren = value.map { (k, v) ->
+            FfiConverterUInt.allocationSize(k) +
+            FfiConverterTypeDatabaseEntity.allocationSize(v)
+        }.sum()
+        return spaceForMapSize + spaceForChildren
+    }
+
+    override fun write(value: Map<UInt, DatabaseEntity>, buf: ByteBuffer) {
+        buf.putInt(value.size)
+        // The parens on `(k, v)` here ensure we're calling the right method,
+        // which is important for compatibility with older android devices.
+        // Ref https://blog.danlew.net/2017/03/16/kotlin-puzzler-whose-line-is-it-anyways/
+        value.forEach { (k, v) ->
+            FfiConverterUInt.write(k, buf)
+            FfiConverterTypeDatabaseEntity.write(v, buf)
         }
     }
 }
 
 
 
-
-public object FfiConverterSequenceTypeDatabaseGroup: FfiConverterRustBuffer<List<DatabaseGroup>> {
-    override fun read(buf: ByteBuffer): List<DatabaseGroup> {
+public object FfiConverterMapUIntTypeDatabaseMember: FfiConverterRustBuffer<Map<UInt, DatabaseMember>> {
+    override fun read(buf: ByteBuffer): Map<UInt, DatabaseMember> {
         val len = buf.getInt()
-        return List<DatabaseGroup>(len) {
-            FfiConverterTypeDatabaseGroup.read(buf)
+        return buildMap<UInt, DatabaseMember>(len) {
+            repeat(len) {
+                val k = FfiConverterUInt.read(buf)
+                val v = FfiConverterTypeDatabaseMember.read(buf)
+                this[k] = v
+            }
         }
     }
 
-    override fun allocationSize(value: List<DatabaseGroup>): Int {
-        val sizeForLength = 4
-        val sizeForItems = value.map { FfiConverterTypeDatabaseGroup.allocationSize(it) }.sum()
-        return sizeForLength + sizeForItems
+    override fun allocationSize(value: Map<UInt, DatabaseMember>): Int {
+        val spaceForMapSize = 4
+        val spaceForChildren = value.map { (k, v) ->
+            FfiConverterUInt.allocationSize(k) +
+            FfiConverterTypeDatabaseMember.allocationSize(v)
+        }.sum()
+        return spaceForMapSize + spaceForChildren
     }
 
-    override fun write(value: List<DatabaseGroup>, buf: ByteBuffer) {
+    override fun write(value: Map<UInt, DatabaseMember>, buf: ByteBuffer) {
         buf.putInt(value.size)
-        value.forEach {
-            FfiConverterTypeDatabaseGroup.write(it, buf)
+        // The parens on `(k, v)` here ensure we're calling the right method,
+        // which is important for compatibility with older android devices.
+        // Ref https://blog.danlew.net/2017/03/16/kotlin-puzzler-whose-line-is-it-anyways/
+        value.forEach { (k, v) ->
+            FfiConverterUInt.write(k, buf)
+            FfiConverterTypeDatabaseMember.write(v, buf)
         }
     }
 }
 
 
 
-public object FfiConverterMapUIntString: FfiConverterRustBuffer<Map<UInt, String>> {
-    override fun read(buf: ByteBuffer): Map<UInt, String> {
+public object FfiConverterMapUIntTypeDatabaseUser: FfiConverterRustBuffer<Map<UInt, DatabaseUser>> {
+    override fun read(buf: ByteBuffer): Map<UInt, DatabaseUser> {
         val len = buf.getInt()
-        return buildMap<UInt, String>(len) {
+        return buildMap<UInt, DatabaseUser>(len) {
             repeat(len) {
                 val k = FfiConverterUInt.read(buf)
-                val v = FfiConverterString.read(buf)
+                val v = FfiConverterTypeDatabaseUser.read(buf)
                 this[k] = v
             }
         }
     }
 
-    override fun allocationSize(value: Map<UInt, String>): Int {
+    override fun allocationSize(value: Map<UInt, DatabaseUser>): Int {
         val spaceForMapSize = 4
         val spaceForChildren = value.map { (k, v) ->
             FfiConverterUInt.allocationSize(k) +
-            FfiConverterString.allocationSize(v)
+            FfiConverterTypeDatabaseUser.allocationSize(v)
         }.sum()
         return spaceForMapSize + spaceForChildren
     }
 
-    override fun write(value: Map<UInt, String>, buf: ByteBuffer) {
+    override fun write(value: Map<UInt, DatabaseUser>, buf: ByteBuffer) {
         buf.putInt(value.size)
         // The parens on `(k, v)` here ensure we're calling the right method,
         // which is important for compatibility with older android devices.
         // Ref https://blog.danlew.net/2017/03/16/kotlin-puzzler-whose-line-is-it-anyways/
         value.forEach { (k, v) ->
             FfiConverterUInt.write(k, buf)
-            FfiConverterString.write(v, buf)
+            FfiConverterTypeDatabaseUser.write(v, buf)
         }
     }
 }
 
 
 
-public object FfiConverterMapUIntTypeDatabaseEntity: FfiConverterRustBuffer<Map<UInt, DatabaseEntity>> {
-    override fun read(buf: ByteBuffer): Map<UInt, DatabaseEntity> {
+public object FfiConverterMapUIntTypeKeySlotType: FfiConverterRustBuffer<Map<UInt, KeySlotType>> {
+    override fun read(buf: ByteBuffer): Map<UInt, KeySlotType> {
         val len = buf.getInt()
-        return buildMap<UInt, DatabaseEntity>(len) {
+        return buildMap<UInt, KeySlotType>(len) {
             repeat(len) {
                 val k = FfiConverterUInt.read(buf)
-                val v = FfiConverterTypeDatabaseEntity.read(buf)
+                val v = FfiConverterTypeKeySlotType.read(buf)
                 this[k] = v
             }
         }
     }
 
-    override fun allocationSize(value: Map<UInt, DatabaseEntity>): Int {
+    override fun allocationSize(value: Map<UInt, KeySlotType>): Int {
         val spaceForMapSize = 4
         val spaceForChildren = value.map { (k, v) ->
             FfiConverterUInt.allocationSize(k) +
-            FfiConverterTypeDatabaseEntity.allocationSize(v)
+            FfiConverterTypeKeySlotType.allocationSize(v)
         }.sum()
         return spaceForMapSize + spaceForChildren
     }
 
-    override fun write(value: Map<UInt, DatabaseEntity>, buf: ByteBuffer) {
+    override fun write(value: Map<UInt, KeySlotType>, buf: ByteBuffer) {
         buf.putInt(value.size)
         // The parens on `(k, v)` here ensure we're calling the right method,
         // which is important for compatibility with older android devices.
         // Ref https://blog.danlew.net/2017/03/16/kotlin-puzzler-whose-line-is-it-anyways/
         value.forEach { (k, v) ->
             FfiConverterUInt.write(k, buf)
-            FfiConverterTypeDatabaseEntity.write(v, buf)
+            FfiConverterTypeKeySlotType.write(v, buf)
         }
     }
 }
@@ -1774,84 +3807,335 @@ public object FfiConverterMapStringString: FfiConverterRustBuffer<Map<String, St
         return spaceForMapSize + spaceForChildren
     }
 
-    override fun write(value: Map<String, String>, buf: ByteBuffer) {
-        buf.putInt(value.size)
-        // The parens on `(k, v)` here ensure we're calling the right method,
-        // which is important for compatibility with older android devices.
-        // Ref https://blog.danlew.net/2017/03/16/kotlin-puzzler-whose-line-is-it-anyways/
-        value.forEach { (k, v) ->
-            FfiConverterString.write(k, buf)
-            FfiConverterString.write(v, buf)
-        }
-    }
+    override fun write(value: Map<String, String>, buf: ByteBuffer) {
+        buf.putInt(value.size)
+        // The parens on `(k, v)` here ensure we're calling the right method,
+        // which is important for compatibility with older android devices.
+        // Ref https://blog.danlew.net/2017/03/16/kotlin-puzzler-whose-line-is-it-anyways/
+        value.forEach { (k, v) ->
+            FfiConverterString.write(k, buf)
+            FfiConverterString.write(v, buf)
+        }
+    }
+}
+@Throws(PmanException::class)
+
+fun `addAttachment`(`databaseId`: ULong, `entityId`: UInt, `name`: String, `data`: ByteArray): UInt {
+    return FfiConverterUInt.lift(
+    rustCallWithError(PmanException) { _status ->
+    _UniFFILib.INSTANCE.uniffi_pman_lib_fn_func_add_attachment(FfiConverterULong.lower(`databaseId`),FfiConverterUInt.lower(`entityId`),FfiConverterString.lower(`name`),FfiConverterByteArray.lower(`data`),_status)
+})
+}
+
+@Throws(PmanException::class)
+
+fun `addChildGroup`(`databaseId`: ULong, `parentId`: UInt, `name`: String): UInt {
+    return FfiConverterUInt.lift(
+    rustCallWithError(PmanException) { _status ->
+    _UniFFILib.INSTANCE.uniffi_pman_lib_fn_func_add_child_group(FfiConverterULong.lower(`databaseId`),FfiConverterUInt.lower(`parentId`),FfiConverterString.lower(`name`),_status)
+})
+}
+
+@Throws(PmanException::class)
+
+fun `addEntity`(`databaseId`: ULong, `name`: String, `groupId`: UInt, `userId`: UInt, `password`: String, `url`: String?, `properties`: Map<String, String>, `templateId`: UInt?): UInt {
+    return FfiConverterUInt.lift(
+    rustCallWithError(PmanException) { _status ->
+    _UniFFILib.INSTANCE.uniffi_pman_lib_fn_func_add_entity(FfiConverterULong.lower(`databaseId`),FfiConverterString.lower(`name`),FfiConverterUInt.lower(`groupId`),FfiConverterUInt.lower(`userId`),FfiConverterString.lower(`password`),FfiConverterOptionalString.lower(`url`),FfiConverterMapStringString.lower(`properties`),FfiConverterOptionalUInt.lower(`templateId`),_status)
+})
+}
+
+@Throws(PmanException::class)
+
+fun `addGroup`(`databaseId`: ULong, `name`: String): UInt {
+    return FfiConverterUInt.lift(
+    rustCallWithError(PmanException) { _status ->
+    _UniFFILib.INSTANCE.uniffi_pman_lib_fn_func_add_group(FfiConverterULong.lower(`databaseId`),FfiConverterString.lower(`name`),_status)
+})
+}
+
+@Throws(PmanException::class)
+
+fun `addKeyFileSlot`(`databaseId`: ULong, `keyFile`: ByteArray): UInt {
+    return FfiConverterUInt.lift(
+    rustCallWithError(PmanException) { _status ->
+    _UniFFILib.INSTANCE.uniffi_pman_lib_fn_func_add_key_file_slot(FfiConverterULong.lower(`databaseId`),FfiConverterByteArray.lower(`keyFile`),_status)
+})
+}
+
+@Throws(PmanException::class)
+
+fun `addMember`(`databaseId`: ULong, `name`: String, `publicKey`: ByteArray): UInt {
+    return FfiConverterUInt.lift(
+    rustCallWithError(PmanException) { _status ->
+    _UniFFILib.INSTANCE.uniffi_pman_lib_fn_func_add_member(FfiConverterULong.lower(`databaseId`),FfiConverterString.lower(`name`),FfiConverterByteArray.lower(`publicKey`),_status)
+})
+}
+
+@Throws(PmanException::class)
+
+fun `addNote`(`databaseId`: ULong, `name`: String, `groupId`: UInt, `body`: String): UInt {
+    return FfiConverterUInt.lift(
+    rustCallWithError(PmanException) { _status ->
+    _UniFFILib.INSTANCE.uniffi_pman_lib_fn_func_add_note(FfiConverterULong.lower(`databaseId`),FfiConverterString.lower(`name`),FfiConverterUInt.lower(`groupId`),FfiConverterString.lower(`body`),_status)
+})
+}
+
+@Throws(PmanException::class)
+
+fun `addRecoveryKey`(`databaseId`: ULong): ByteArray {
+    return FfiConverterByteArray.lift(
+    rustCallWithError(PmanException) { _status ->
+    _UniFFILib.INSTANCE.uniffi_pman_lib_fn_func_add_recovery_key(FfiConverterULong.lower(`databaseId`),_status)
+})
+}
+
+@Throws(PmanException::class)
+
+fun `addTag`(`databaseId`: ULong, `entityId`: UInt, `tag`: String): UInt {
+    return FfiConverterUInt.lift(
+    rustCallWithError(PmanException) { _status ->
+    _UniFFILib.INSTANCE.uniffi_pman_lib_fn_func_add_tag(FfiConverterULong.lower(`databaseId`),FfiConverterUInt.lower(`entityId`),FfiConverterString.lower(`tag`),_status)
+})
+}
+
+@Throws(PmanException::class)
+
+fun `addTemplate`(`databaseId`: ULong, `name`: String, `fields`: List<TemplateField>): UInt {
+    return FfiConverterUInt.lift(
+    rustCallWithError(PmanException) { _status ->
+    _UniFFILib.INSTANCE.uniffi_pman_lib_fn_func_add_template(FfiConverterULong.lower(`databaseId`),FfiConverterString.lower(`name`),FfiConverterSequenceTypeTemplateField.lower(`fields`),_status)
+})
+}
+
+@Throws(PmanException::class)
+
+fun `addUser`(`databaseId`: ULong, `name`: String): UInt {
+    return FfiConverterUInt.lift(
+    rustCallWithError(PmanException) { _status ->
+    _UniFFILib.INSTANCE.uniffi_pman_lib_fn_func_add_user(FfiConverterULong.lower(`databaseId`),FfiConverterString.lower(`name`),_status)
+})
+}
+
+@Throws(PmanException::class)
+
+fun `buildTotpUri`(`secret`: String, `algorithm`: OtpAlgorithm, `digits`: UInt, `period`: ULong): String {
+    return FfiConverterString.lift(
+    rustCallWithError(PmanException) { _status ->
+    _UniFFILib.INSTANCE.uniffi_pman_lib_fn_func_build_totp_uri(FfiConverterString.lower(`secret`),FfiConverterTypeOtpAlgorithm.lower(`algorithm`),FfiConverterUInt.lower(`digits`),FfiConverterULong.lower(`period`),_status)
+})
+}
+
+@Throws(PmanException::class)
+
+fun `checkIntegrity`(`databaseId`: ULong, `repair`: Boolean): List<IntegrityIssue> {
+    return FfiConverterSequenceTypeIntegrityIssue.lift(
+    rustCallWithError(PmanException) { _status ->
+    _UniFFILib.INSTANCE.uniffi_pman_lib_fn_func_check_integrity(FfiConverterULong.lower(`databaseId`),FfiConverterBoolean.lower(`repair`),_status)
+})
+}
+
+@Throws(PmanException::class)
+
+fun `close`(`databaseId`: ULong) =
+    
+    rustCallWithError(PmanException) { _status ->
+    _UniFFILib.INSTANCE.uniffi_pman_lib_fn_func_close(FfiConverterULong.lower(`databaseId`),_status)
+}
+
+
+@Throws(PmanException::class)
+
+fun `create`(`databaseType`: PasswordDatabaseType, `passwordHash`: ByteArray, `password2Hash`: ByteArray?, `keyFileContents`: ByteArray?, `fileName`: String): ULong {
+    return FfiConverterULong.lift(
+    rustCallWithError(PmanException) { _status ->
+    _UniFFILib.INSTANCE.uniffi_pman_lib_fn_func_create(FfiConverterTypePasswordDatabaseType.lower(`databaseType`),FfiConverterByteArray.lower(`passwordHash`),FfiConverterOptionalByteArray.lower(`password2Hash`),FfiConverterOptionalByteArray.lower(`keyFileContents`),FfiConverterString.lower(`fileName`),_status)
+})
+}
+
+@Throws(PmanException::class)
+
+fun `disableCoreDumps`() =
+    
+    rustCallWithError(PmanException) { _status ->
+    _UniFFILib.INSTANCE.uniffi_pman_lib_fn_func_disable_core_dumps(_status)
+}
+
+
+@Throws(PmanException::class)
+
+fun `emptyTrash`(`databaseId`: ULong) =
+    
+    rustCallWithError(PmanException) { _status ->
+    _UniFFILib.INSTANCE.uniffi_pman_lib_fn_func_empty_trash(FfiConverterULong.lower(`databaseId`),_status)
+}
+
+
+@Throws(PmanException::class)
+
+fun `exportToNewDatabase`(`databaseId`: ULong, `groupIds`: List<UInt>, `passwordHash`: ByteArray, `password2Hash`: ByteArray, `keyFileContents`: ByteArray, `fileName`: String): ULong {
+    return FfiConverterULong.lift(
+    rustCallWithError(PmanException) { _status ->
+    _UniFFILib.INSTANCE.uniffi_pman_lib_fn_func_export_to_new_database(FfiConverterULong.lower(`databaseId`),FfiConverterSequenceUInt.lower(`groupIds`),FfiConverterByteArray.lower(`passwordHash`),FfiConverterByteArray.lower(`password2Hash`),FfiConverterByteArray.lower(`keyFileContents`),FfiConverterString.lower(`fileName`),_status)
+})
+}
+
+@Throws(PmanException::class)
+
+fun `findByUrl`(`databaseId`: ULong, `url`: String): List<UrlMatch> {
+    return FfiConverterSequenceTypeUrlMatch.lift(
+    rustCallWithError(PmanException) { _status ->
+    _UniFFILib.INSTANCE.uniffi_pman_lib_fn_func_find_by_url(FfiConverterULong.lower(`databaseId`),FfiConverterString.lower(`url`),_status)
+})
+}
+
+
+fun `generateMemberKeyPair`(): MemberKeyPair {
+    return FfiConverterTypeMemberKeyPair.lift(
+    rustCall() { _status ->
+    _UniFFILib.INSTANCE.uniffi_pman_lib_fn_func_generate_member_key_pair(_status)
+})
+}
+
+@Throws(PmanException::class)
+
+fun `getAttachment`(`databaseId`: ULong, `entityId`: UInt, `version`: UInt, `attachmentId`: UInt): ByteArray {
+    return FfiConverterByteArray.lift(
+    rustCallWithError(PmanException) { _status ->
+    _UniFFILib.INSTANCE.uniffi_pman_lib_fn_func_get_attachment(FfiConverterULong.lower(`databaseId`),FfiConverterUInt.lower(`entityId`),FfiConverterUInt.lower(`version`),FfiConverterUInt.lower(`attachmentId`),_status)
+})
+}
+
+@Throws(PmanException::class)
+
+fun `getAuditLog`(`databaseId`: ULong, `since`: ULong?, `objectId`: UInt?): List<AuditEvent> {
+    return FfiConverterSequenceTypeAuditEvent.lift(
+    rustCallWithError(PmanException) { _status ->
+    _UniFFILib.INSTANCE.uniffi_pman_lib_fn_func_get_audit_log(FfiConverterULong.lower(`databaseId`),FfiConverterOptionalULong.lower(`since`),FfiConverterOptionalUInt.lower(`objectId`),_status)
+})
+}
+
+@Throws(PmanException::class)
+
+fun `getAuditSettings`(`databaseId`: ULong): AuditSettings {
+    return FfiConverterTypeAuditSettings.lift(
+    rustCallWithError(PmanException) { _status ->
+    _UniFFILib.INSTANCE.uniffi_pman_lib_fn_func_get_audit_settings(FfiConverterULong.lower(`databaseId`),_status)
+})
+}
+
+@Throws(PmanException::class)
+
+fun `getDatabaseVersion`(`databaseId`: ULong): DatabaseVersion {
+    return FfiConverterTypeDatabaseVersion.lift(
+    rustCallWithError(PmanException) { _status ->
+    _UniFFILib.INSTANCE.uniffi_pman_lib_fn_func_get_database_version(FfiConverterULong.lower(`databaseId`),_status)
+})
+}
+
+@Throws(PmanException::class)
+
+fun `getEntities`(`databaseId`: ULong, `groupId`: UInt): Map<UInt, DatabaseEntity> {
+    return FfiConverterMapUIntTypeDatabaseEntity.lift(
+    rustCallWithError(PmanException) { _status ->
+    _UniFFILib.INSTANCE.uniffi_pman_lib_fn_func_get_entities(FfiConverterULong.lower(`databaseId`),FfiConverterUInt.lower(`groupId`),_status)
+})
+}
+
+@Throws(PmanException::class)
+
+fun `getEntitiesByTags`(`databaseId`: ULong, `tagIds`: List<UInt>): Map<UInt, DatabaseEntity> {
+    return FfiConverterMapUIntTypeDatabaseEntity.lift(
+    rustCallWithError(PmanException) { _status ->
+    _UniFFILib.INSTANCE.uniffi_pman_lib_fn_func_get_entities_by_tags(FfiConverterULong.lower(`databaseId`),FfiConverterSequenceUInt.lower(`tagIds`),_status)
+})
+}
+
+@Throws(PmanException::class)
+
+fun `getExpiring`(`databaseId`: ULong, `days`: UInt): List<ExpiringEntity> {
+    return FfiConverterSequenceTypeExpiringEntity.lift(
+    rustCallWithError(PmanException) { _status ->
+    _UniFFILib.INSTANCE.uniffi_pman_lib_fn_func_get_expiring(FfiConverterULong.lower(`databaseId`),FfiConverterUInt.lower(`days`),_status)
+})
 }
+
 @Throws(PmanException::class)
 
-fun `addEntity`(`databaseId`: ULong, `name`: String, `groupId`: UInt, `userId`: UInt, `password`: String, `url`: String?, `properties`: Map<String, String>): UInt {
-    return FfiConverterUInt.lift(
+fun `getGroups`(`databaseId`: ULong): List<DatabaseGroup> {
+    return FfiConverterSequenceTypeDatabaseGroup.lift(
     rustCallWithError(PmanException) { _status ->
-    _UniFFILib.INSTANCE.uniffi_pman_lib_fn_func_add_entity(FfiConverterULong.lower(`databaseId`),FfiConverterString.lower(`name`),FfiConverterUInt.lower(`groupId`),FfiConverterUInt.lower(`userId`),FfiConverterString.lower(`password`),FfiConverterOptionalString.lower(`url`),FfiConverterMapStringString.lower(`properties`),_status)
+    _UniFFILib.INSTANCE.uniffi_pman_lib_fn_func_get_groups(FfiConverterULong.lower(`databaseId`),_status)
 })
 }
 
 @Throws(PmanException::class)
 
-fun `addGroup`(`databaseId`: ULong, `name`: String): UInt {
+fun `getHistoryLength`(`databaseId`: ULong): UInt {
     return FfiConverterUInt.lift(
     rustCallWithError(PmanException) { _status ->
-    _UniFFILib.INSTANCE.uniffi_pman_lib_fn_func_add_group(FfiConverterULong.lower(`databaseId`),FfiConverterString.lower(`name`),_status)
+    _UniFFILib.INSTANCE.uniffi_pman_lib_fn_func_get_history_length(FfiConverterULong.lower(`databaseId`),_status)
 })
 }
 
 @Throws(PmanException::class)
 
-fun `addUser`(`databaseId`: ULong, `name`: String): UInt {
-    return FfiConverterUInt.lift(
+fun `getKeySlots`(`databaseId`: ULong): Map<UInt, KeySlotType> {
+    return FfiConverterMapUIntTypeKeySlotType.lift(
     rustCallWithError(PmanException) { _status ->
-    _UniFFILib.INSTANCE.uniffi_pman_lib_fn_func_add_user(FfiConverterULong.lower(`databaseId`),FfiConverterString.lower(`name`),_status)
+    _UniFFILib.INSTANCE.uniffi_pman_lib_fn_func_get_key_slots(FfiConverterULong.lower(`databaseId`),_status)
 })
 }
 
 @Throws(PmanException::class)
 
-fun `close`(`databaseId`: ULong) =
-    
+fun `getMembers`(`databaseId`: ULong): Map<UInt, DatabaseMember> {
+    return FfiConverterMapUIntTypeDatabaseMember.lift(
     rustCallWithError(PmanException) { _status ->
-    _UniFFILib.INSTANCE.uniffi_pman_lib_fn_func_close(FfiConverterULong.lower(`databaseId`),_status)
+    _UniFFILib.INSTANCE.uniffi_pman_lib_fn_func_get_members(FfiConverterULong.lower(`databaseId`),_status)
+})
 }
 
+@Throws(PmanException::class)
+
+fun `getTags`(`databaseId`: ULong): Map<UInt, String> {
+    return FfiConverterMapUIntString.lift(
+    rustCallWithError(PmanException) { _status ->
+    _UniFFILib.INSTANCE.uniffi_pman_lib_fn_func_get_tags(FfiConverterULong.lower(`databaseId`),_status)
+})
+}
 
 @Throws(PmanException::class)
 
-fun `create`(`databaseType`: PasswordDatabaseType, `passwordHash`: ByteArray, `password2Hash`: ByteArray?, `keyFileContents`: ByteArray?, `fileName`: String): ULong {
-    return FfiConverterULong.lift(
+fun `getTemplates`(`databaseId`: ULong): List<EntityTemplate> {
+    return FfiConverterSequenceTypeEntityTemplate.lift(
     rustCallWithError(PmanException) { _status ->
-    _UniFFILib.INSTANCE.uniffi_pman_lib_fn_func_create(FfiConverterTypePasswordDatabaseType.lower(`databaseType`),FfiConverterByteArray.lower(`passwordHash`),FfiConverterOptionalByteArray.lower(`password2Hash`),FfiConverterOptionalByteArray.lower(`keyFileContents`),FfiConverterString.lower(`fileName`),_status)
+    _UniFFILib.INSTANCE.uniffi_pman_lib_fn_func_get_templates(FfiConverterULong.lower(`databaseId`),_status)
 })
 }
 
 @Throws(PmanException::class)
 
-fun `getEntities`(`databaseId`: ULong, `groupId`: UInt): Map<UInt, DatabaseEntity> {
-    return FfiConverterMapUIntTypeDatabaseEntity.lift(
+fun `getTrash`(`databaseId`: ULong): List<TrashedEntity> {
+    return FfiConverterSequenceTypeTrashedEntity.lift(
     rustCallWithError(PmanException) { _status ->
-    _UniFFILib.INSTANCE.uniffi_pman_lib_fn_func_get_entities(FfiConverterULong.lower(`databaseId`),FfiConverterUInt.lower(`groupId`),_status)
+    _UniFFILib.INSTANCE.uniffi_pman_lib_fn_func_get_trash(FfiConverterULong.lower(`databaseId`),_status)
 })
 }
 
 @Throws(PmanException::class)
 
-fun `getGroups`(`databaseId`: ULong): List<DatabaseGroup> {
-    return FfiConverterSequenceTypeDatabaseGroup.lift(
+fun `getTrashRetention`(`databaseId`: ULong): UInt {
+    return FfiConverterUInt.lift(
     rustCallWithError(PmanException) { _status ->
-    _UniFFILib.INSTANCE.uniffi_pman_lib_fn_func_get_groups(FfiConverterULong.lower(`databaseId`),_status)
+    _UniFFILib.INSTANCE.uniffi_pman_lib_fn_func_get_trash_retention(FfiConverterULong.lower(`databaseId`),_status)
 })
 }
 
 @Throws(PmanException::class)
 
-fun `getUsers`(`databaseId`: ULong): Map<UInt, String> {
-    return FfiConverterMapUIntString.lift(
+fun `getUsers`(`databaseId`: ULong): Map<UInt, DatabaseUser> {
+    return FfiConverterMapUIntTypeDatabaseUser.lift(
     rustCallWithError(PmanException) { _status ->
     _UniFFILib.INSTANCE.uniffi_pman_lib_fn_func_get_users(FfiConverterULong.lower(`databaseId`),_status)
 })
@@ -1859,6 +4143,15 @@ fun `getUsers`(`databaseId`: ULong): Map<UInt, String> {
 
 @Throws(PmanException::class)
 
+fun `isAuditPending`(`databaseId`: ULong): Boolean {
+    return FfiConverterBoolean.lift(
+    rustCallWithError(PmanException) { _status ->
+    _UniFFILib.INSTANCE.uniffi_pman_lib_fn_func_is_audit_pending(FfiConverterULong.lower(`databaseId`),_status)
+})
+}
+
+@Throws(PmanException::class)
+
 fun `isReadOnly`(`databaseId`: ULong): Boolean {
     return FfiConverterBoolean.lift(
     rustCallWithError(PmanException) { _status ->
@@ -1874,6 +4167,24 @@ fun `libInit`() =
 }
 
 
+@Throws(PmanException::class)
+
+fun `listEntities`(`databaseId`: ULong, `groupId`: UInt?, `sortKey`: EntitySortKey, `descending`: Boolean, `offset`: UInt, `limit`: UInt): EntityPage {
+    return FfiConverterTypeEntityPage.lift(
+    rustCallWithError(PmanException) { _status ->
+    _UniFFILib.INSTANCE.uniffi_pman_lib_fn_func_list_entities(FfiConverterULong.lower(`databaseId`),FfiConverterOptionalUInt.lower(`groupId`),FfiConverterTypeEntitySortKey.lower(`sortKey`),FfiConverterBoolean.lower(`descending`),FfiConverterUInt.lower(`offset`),FfiConverterUInt.lower(`limit`),_status)
+})
+}
+
+@Throws(PmanException::class)
+
+fun `mergeGroups`(`databaseId`: ULong, `id`: UInt, `targetId`: UInt, `rewriteHistory`: Boolean) =
+    
+    rustCallWithError(PmanException) { _status ->
+    _UniFFILib.INSTANCE.uniffi_pman_lib_fn_func_merge_groups(FfiConverterULong.lower(`databaseId`),FfiConverterUInt.lower(`id`),FfiConverterUInt.lower(`targetId`),FfiConverterBoolean.lower(`rewriteHistory`),_status)
+}
+
+
 @Throws(PmanException::class)
 
 fun `modifyEntity`(`databaseId`: ULong, `entityId`: UInt, `newGroupId`: UInt?, `newUserId`: UInt?, `newPassword`: String?, `newUrl`: String?, `changeUrl`: Boolean, `newProperties`: Map<String, String>, `modifiedProperties`: Map<UInt, String?>) =
@@ -1883,6 +4194,24 @@ fun `modifyEntity`(`databaseId`: ULong, `entityId`: UInt, `newGroupId`: UInt?, `
 }
 
 
+@Throws(PmanException::class)
+
+fun `moveGroup`(`databaseId`: ULong, `id`: UInt, `newParentId`: UInt?) =
+    
+    rustCallWithError(PmanException) { _status ->
+    _UniFFILib.INSTANCE.uniffi_pman_lib_fn_func_move_group(FfiConverterULong.lower(`databaseId`),FfiConverterUInt.lower(`id`),FfiConverterOptionalUInt.lower(`newParentId`),_status)
+}
+
+
+@Throws(PmanException::class)
+
+fun `nextHotpCode`(`databaseId`: ULong, `entityId`: UInt): OtpCode {
+    return FfiConverterTypeOtpCode.lift(
+    rustCallWithError(PmanException) { _status ->
+    _UniFFILib.INSTANCE.uniffi_pman_lib_fn_func_next_hotp_code(FfiConverterULong.lower(`databaseId`),FfiConverterUInt.lower(`entityId`),_status)
+})
+}
+
 @Throws(PmanException::class)
 
 fun `open`(`databaseId`: ULong) =
@@ -1901,6 +4230,15 @@ fun `preOpen`(`databaseId`: ULong, `passwordHash`: ByteArray, `password2Hash`: B
 }
 
 
+@Throws(PmanException::class)
+
+fun `preOpenWithKeySlot`(`databaseId`: ULong, `slotType`: KeySlotType, `secret`: ByteArray, `keyFileContents`: ByteArray?) =
+    
+    rustCallWithError(PmanException) { _status ->
+    _UniFFILib.INSTANCE.uniffi_pman_lib_fn_func_pre_open_with_key_slot(FfiConverterULong.lower(`databaseId`),FfiConverterTypeKeySlotType.lower(`slotType`),FfiConverterByteArray.lower(`secret`),FfiConverterOptionalByteArray.lower(`keyFileContents`),_status)
+}
+
+
 @Throws(PmanException::class)
 
 fun `prepare`(`data`: ByteArray, `fileName`: String): ULong {
@@ -1910,6 +4248,15 @@ fun `prepare`(`data`: ByteArray, `fileName`: String): ULong {
 })
 }
 
+@Throws(PmanException::class)
+
+fun `purgeEntityHistory`(`databaseId`: ULong, `entityId`: UInt) =
+    
+    rustCallWithError(PmanException) { _status ->
+    _UniFFILib.INSTANCE.uniffi_pman_lib_fn_func_purge_entity_history(FfiConverterULong.lower(`databaseId`),FfiConverterUInt.lower(`entityId`),_status)
+}
+
+
 
 fun `remove`(`databaseId`: ULong) =
     
@@ -1918,6 +4265,24 @@ fun `remove`(`databaseId`: ULong) =
 }
 
 
+@Throws(PmanException::class)
+
+fun `removeAttachment`(`databaseId`: ULong, `entityId`: UInt, `attachmentId`: UInt) =
+    
+    rustCallWithError(PmanException) { _status ->
+    _UniFFILib.INSTANCE.uniffi_pman_lib_fn_func_remove_attachment(FfiConverterULong.lower(`databaseId`),FfiConverterUInt.lower(`entityId`),FfiConverterUInt.lower(`attachmentId`),_status)
+}
+
+
+@Throws(PmanException::class)
+
+fun `removeDataFileBackups`(`databaseId`: ULong) =
+    
+    rustCallWithError(PmanException) { _status ->
+    _UniFFILib.INSTANCE.uniffi_pman_lib_fn_func_remove_data_file_backups(FfiConverterULong.lower(`databaseId`),_status)
+}
+
+
 @Throws(PmanException::class)
 
 fun `removeEntity`(`databaseId`: ULong, `id`: UInt) =
@@ -1936,6 +4301,33 @@ fun `removeGroup`(`databaseId`: ULong, `id`: UInt) =
 }
 
 
+@Throws(PmanException::class)
+
+fun `removeKeySlot`(`databaseId`: ULong, `id`: UInt) =
+    
+    rustCallWithError(PmanException) { _status ->
+    _UniFFILib.INSTANCE.uniffi_pman_lib_fn_func_remove_key_slot(FfiConverterULong.lower(`databaseId`),FfiConverterUInt.lower(`id`),_status)
+}
+
+
+@Throws(PmanException::class)
+
+fun `removeTag`(`databaseId`: ULong, `entityId`: UInt, `tagId`: UInt) =
+    
+    rustCallWithError(PmanException) { _status ->
+    _UniFFILib.INSTANCE.uniffi_pman_lib_fn_func_remove_tag(FfiConverterULong.lower(`databaseId`),FfiConverterUInt.lower(`entityId`),FfiConverterUInt.lower(`tagId`),_status)
+}
+
+
+@Throws(PmanException::class)
+
+fun `removeTemplate`(`databaseId`: ULong, `templateId`: UInt) =
+    
+    rustCallWithError(PmanException) { _status ->
+    _UniFFILib.INSTANCE.uniffi_pman_lib_fn_func_remove_template(FfiConverterULong.lower(`databaseId`),FfiConverterUInt.lower(`templateId`),_status)
+}
+
+
 @Throws(PmanException::class)
 
 fun `removeUser`(`databaseId`: ULong, `id`: UInt) =
@@ -1963,6 +4355,51 @@ fun `renameGroup`(`databaseId`: ULong, `id`: UInt, `newName`: String) =
 }
 
 
+@Throws(PmanException::class)
+
+fun `renameUser`(`databaseId`: ULong, `id`: UInt, `newName`: String) =
+    
+    rustCallWithError(PmanException) { _status ->
+    _UniFFILib.INSTANCE.uniffi_pman_lib_fn_func_rename_user(FfiConverterULong.lower(`databaseId`),FfiConverterUInt.lower(`id`),FfiConverterString.lower(`newName`),_status)
+}
+
+
+@Throws(PmanException::class)
+
+fun `replaceUser`(`databaseId`: ULong, `id`: UInt, `replacementId`: UInt, `rewriteHistory`: Boolean) =
+    
+    rustCallWithError(PmanException) { _status ->
+    _UniFFILib.INSTANCE.uniffi_pman_lib_fn_func_replace_user(FfiConverterULong.lower(`databaseId`),FfiConverterUInt.lower(`id`),FfiConverterUInt.lower(`replacementId`),FfiConverterBoolean.lower(`rewriteHistory`),_status)
+}
+
+
+@Throws(PmanException::class)
+
+fun `restoreEntity`(`databaseId`: ULong, `entityId`: UInt) =
+    
+    rustCallWithError(PmanException) { _status ->
+    _UniFFILib.INSTANCE.uniffi_pman_lib_fn_func_restore_entity(FfiConverterULong.lower(`databaseId`),FfiConverterUInt.lower(`entityId`),_status)
+}
+
+
+@Throws(PmanException::class)
+
+fun `restoreEntityVersion`(`databaseId`: ULong, `entityId`: UInt, `version`: UInt) =
+    
+    rustCallWithError(PmanException) { _status ->
+    _UniFFILib.INSTANCE.uniffi_pman_lib_fn_func_restore_entity_version(FfiConverterULong.lower(`databaseId`),FfiConverterUInt.lower(`entityId`),FfiConverterUInt.lower(`version`),_status)
+}
+
+
+@Throws(PmanException::class)
+
+fun `revokeMember`(`databaseId`: ULong, `memberId`: UInt): List<KeySlotType> {
+    return FfiConverterSequenceTypeKeySlotType.lift(
+    rustCallWithError(PmanException) { _status ->
+    _UniFFILib.INSTANCE.uniffi_pman_lib_fn_func_revoke_member(FfiConverterULong.lower(`databaseId`),FfiConverterUInt.lower(`memberId`),_status)
+})
+}
+
 @Throws(PmanException::class)
 
 fun `save`(`databaseId`: ULong): ByteArray? {
@@ -1983,6 +4420,15 @@ fun `search`(`databaseId`: ULong, `searchString`: String): Map<UInt, Map<UInt, D
 
 @Throws(PmanException::class)
 
+fun `searchEntities`(`databaseId`: ULong, `searchString`: String, `sortKey`: EntitySortKey, `descending`: Boolean, `offset`: UInt, `limit`: UInt): EntityPage {
+    return FfiConverterTypeEntityPage.lift(
+    rustCallWithError(PmanException) { _status ->
+    _UniFFILib.INSTANCE.uniffi_pman_lib_fn_func_search_entities(FfiConverterULong.lower(`databaseId`),FfiConverterString.lower(`searchString`),FfiConverterTypeEntitySortKey.lower(`sortKey`),FfiConverterBoolean.lower(`descending`),FfiConverterUInt.lower(`offset`),FfiConverterUInt.lower(`limit`),_status)
+})
+}
+
+@Throws(PmanException::class)
+
 fun `setArgon2`(`databaseId`: ULong, `hashId`: ULong, `iterations`: ULong, `parallelism`: ULong, `memory`: ULong) =
     
     rustCallWithError(PmanException) { _status ->
@@ -1990,6 +4436,69 @@ fun `setArgon2`(`databaseId`: ULong, `hashId`: ULong, `iterations`: ULong, `para
 }
 
 
+@Throws(PmanException::class)
+
+fun `setAuditSettings`(`databaseId`: ULong, `settings`: AuditSettings) =
+    
+    rustCallWithError(PmanException) { _status ->
+    _UniFFILib.INSTANCE.uniffi_pman_lib_fn_func_set_audit_settings(FfiConverterULong.lower(`databaseId`),FfiConverterTypeAuditSettings.lower(`settings`),_status)
+}
+
+
+@Throws(PmanException::class)
+
+fun `setDeviceLabel`(`databaseId`: ULong, `label`: String) =
+    
+    rustCallWithError(PmanException) { _status ->
+    _UniFFILib.INSTANCE.uniffi_pman_lib_fn_func_set_device_label(FfiConverterULong.lower(`databaseId`),FfiConverterString.lower(`label`),_status)
+}
+
+
+@Throws(PmanException::class)
+
+fun `setEncryption`(`databaseId`: ULong, `layerId`: ULong, `engines`: List<CryptoEngine>) =
+    
+    rustCallWithError(PmanException) { _status ->
+    _UniFFILib.INSTANCE.uniffi_pman_lib_fn_func_set_encryption(FfiConverterULong.lower(`databaseId`),FfiConverterULong.lower(`layerId`),FfiConverterSequenceTypeCryptoEngine.lower(`engines`),_status)
+}
+
+
+@Throws(PmanException::class)
+
+fun `setEntityExpiry`(`databaseId`: ULong, `entityId`: UInt, `expiresAt`: ULong?, `maxAgeDays`: UInt?) =
+    
+    rustCallWithError(PmanException) { _status ->
+    _UniFFILib.INSTANCE.uniffi_pman_lib_fn_func_set_entity_expiry(FfiConverterULong.lower(`databaseId`),FfiConverterUInt.lower(`entityId`),FfiConverterOptionalULong.lower(`expiresAt`),FfiConverterOptionalUInt.lower(`maxAgeDays`),_status)
+}
+
+
+@Throws(PmanException::class)
+
+fun `setEntityHistoryLength`(`databaseId`: ULong, `entityId`: UInt, `length`: UInt?) =
+    
+    rustCallWithError(PmanException) { _status ->
+    _UniFFILib.INSTANCE.uniffi_pman_lib_fn_func_set_entity_history_length(FfiConverterULong.lower(`databaseId`),FfiConverterUInt.lower(`entityId`),FfiConverterOptionalUInt.lower(`length`),_status)
+}
+
+
+@Throws(PmanException::class)
+
+fun `setEntityOtp`(`databaseId`: ULong, `entityId`: UInt, `otp`: String?) =
+    
+    rustCallWithError(PmanException) { _status ->
+    _UniFFILib.INSTANCE.uniffi_pman_lib_fn_func_set_entity_otp(FfiConverterULong.lower(`databaseId`),FfiConverterUInt.lower(`entityId`),FfiConverterOptionalString.lower(`otp`),_status)
+}
+
+
+@Throws(PmanException::class)
+
+fun `setEntityUrls`(`databaseId`: ULong, `entityId`: UInt, `urls`: List<EntityUrl>) =
+    
+    rustCallWithError(PmanException) { _status ->
+    _UniFFILib.INSTANCE.uniffi_pman_lib_fn_func_set_entity_urls(FfiConverterULong.lower(`databaseId`),FfiConverterUInt.lower(`entityId`),FfiConverterSequenceTypeEntityUrl.lower(`urls`),_status)
+}
+
+
 @Throws(PmanException::class)
 
 fun `setFile1LocationQs3`(`databaseId`: ULong, `fileName`: String, `s3Key`: ByteArray) =
@@ -2008,4 +4517,58 @@ fun `setFile2LocationQs3`(`databaseId`: ULong, `fileName`: String, `s3Key`: Byte
 }
 
 
+@Throws(PmanException::class)
+
+fun `setGroupMaxAge`(`databaseId`: ULong, `groupId`: UInt, `maxAgeDays`: UInt?) =
+    
+    rustCallWithError(PmanException) { _status ->
+    _UniFFILib.INSTANCE.uniffi_pman_lib_fn_func_set_group_max_age(FfiConverterULong.lower(`databaseId`),FfiConverterUInt.lower(`groupId`),FfiConverterOptionalUInt.lower(`maxAgeDays`),_status)
+}
+
+
+@Throws(PmanException::class)
+
+fun `setHistoryLength`(`databaseId`: ULong, `length`: UInt) =
+    
+    rustCallWithError(PmanException) { _status ->
+    _UniFFILib.INSTANCE.uniffi_pman_lib_fn_func_set_history_length(FfiConverterULong.lower(`databaseId`),FfiConverterUInt.lower(`length`),_status)
+}
+
+
+@Throws(PmanException::class)
+
+fun `setPasswords`(`databaseId`: ULong, `passwordHash`: ByteArray, `password2Hash`: ByteArray) =
+    
+    rustCallWithError(PmanException) { _status ->
+    _UniFFILib.INSTANCE.uniffi_pman_lib_fn_func_set_passwords(FfiConverterULong.lower(`databaseId`),FfiConverterByteArray.lower(`passwordHash`),FfiConverterByteArray.lower(`password2Hash`),_status)
+}
+
+
+@Throws(PmanException::class)
+
+fun `setPropertyLayout`(`databaseId`: ULong, `entityId`: UInt, `layout`: List<PropertyLayout>) =
+    
+    rustCallWithError(PmanException) { _status ->
+    _UniFFILib.INSTANCE.uniffi_pman_lib_fn_func_set_property_layout(FfiConverterULong.lower(`databaseId`),FfiConverterUInt.lower(`entityId`),FfiConverterSequenceTypePropertyLayout.lower(`layout`),_status)
+}
+
+
+@Throws(PmanException::class)
+
+fun `setTrashRetention`(`databaseId`: ULong, `days`: UInt) =
+    
+    rustCallWithError(PmanException) { _status ->
+    _UniFFILib.INSTANCE.uniffi_pman_lib_fn_func_set_trash_retention(FfiConverterULong.lower(`databaseId`),FfiConverterUInt.lower(`days`),_status)
+}
+
+
+@Throws(PmanException::class)
+
+fun `updateUser`(`databaseId`: ULong, `id`: UInt, `user`: DatabaseUser) =
+    
+    rustCallWithError(PmanException) { _status ->
+    _UniFFILib.INSTANCE.uniffi_pman_lib_fn_func_update_user(FfiConverterULong.lower(`databaseId`),FfiConverterUInt.lower(`id`),FfiConverterTypeDatabaseUser.lower(`user`),_status)
+}
+
+
 
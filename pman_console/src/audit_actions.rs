@@ -0,0 +1,47 @@
+use std::io::{Error, ErrorKind};
+use std::time::SystemTime;
+use pman_lib::{get_audit_log, set_audit_settings};
+use pman_lib::structs_interfaces::AuditSettings;
+
+// days = 0 shows the whole audit log
+pub fn show_audit_log(database: u64, days: isize) -> Result<bool, Error> {
+    let since = if days > 0 {
+        let timestamp = SystemTime::now().duration_since(SystemTime::UNIX_EPOCH).unwrap().as_secs();
+        Some(timestamp.saturating_sub(days as u64 * 86400))
+    } else { None };
+    for event in get_audit_log(database, since, None)
+        .map_err(|e| Error::new(ErrorKind::Other, e.to_string()))? {
+        println!("{} {} {:?} {:?} {} {}{}", format_timestamp(event.timestamp),
+                 if event.device_label.is_empty() { "-" } else { &event.device_label },
+                 event.event_type, event.object_type, event.object_id, event.object_name,
+                 if event.details.is_empty() { "".to_string() } else { format!(" ({})", event.details) });
+    }
+    Ok(false)
+}
+
+pub fn set_audit(database: u64, max_records: isize, log_reveals: bool) -> Result<bool, Error> {
+    if max_records < 0 {
+        return Err(Error::new(ErrorKind::InvalidInput, "audit log size expected"));
+    }
+    set_audit_settings(database, AuditSettings{max_records: max_records as u32, log_reveals})
+        .map_err(|e| Error::new(ErrorKind::Other, e.to_string()))?;
+    Ok(true)
+}
+
+// UTC date and time in format YYYY-MM-DD HH:MM:SS
+fn format_timestamp(timestamp: u64) -> String {
+    let days = (timestamp / 86400) as i64;
+    let seconds = timestamp % 86400;
+    // civil date from days since 1970-01-01
+    let z = days + 719468;
+    let era = z.div_euclid(146097);
+    let day_of_era = z - era * 146097;
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+    format!("{:04}-{:02}-{:02} {:02}:{:02}:{:02}", year, month, day,
+            seconds / 3600, seconds % 3600 / 60, seconds % 60)
+}
@@ -1,7 +1,10 @@
 use std::io::{Error, ErrorKind};
-use pman_lib::set_argon2;
+use pman_lib::{close, export_to_new_database, get_groups, save, set_argon2};
+use pman_lib::structs_interfaces::CryptoEngine;
 use crate::Parameters;
-use crate::utils::load_file;
+use crate::groups_users_actions::find_group_by_path;
+use crate::passwords::build_new_password_hashes;
+use crate::utils::{create_file, load_file, parse_string_array};
 
 pub fn set_file1_location(database: u64, parameters: &Parameters) -> Result<bool, Error> {
     match parameters.file1_parameter.get_value().as_str() {
@@ -35,6 +38,38 @@ pub fn set_file2_location(database: u64, parameters: &Parameters) -> Result<bool
     }
 }
 
+// exports groups to a new database with its own passwords and file locations, source database is not changed
+pub fn share_export(database: u64, parameters: &Parameters) -> Result<bool, Error> {
+    let out_file = parameters.out_file_parameter.get_value();
+    if out_file.is_empty() {
+        return Err(Error::new(ErrorKind::InvalidInput, "output file name expected"));
+    }
+    let groups = get_groups(database)
+        .map_err(|e| Error::new(ErrorKind::Other, e.to_string()))?;
+    let mut group_ids = Vec::new();
+    for name in parse_string_array(parameters.group_names_parameter.get_value(), "group names expected", None)? {
+        group_ids.push(find_group_by_path(&groups, &name)?);
+    }
+    let (password_hash, password2_hash) = build_new_password_hashes(parameters)?;
+    let key_file_contents = load_file(parameters.database_key_file_parameter.get_value())?;
+    let exported = export_to_new_database(database, group_ids, password_hash, password2_hash,
+                                          key_file_contents, out_file.clone())
+        .map_err(|e| Error::new(ErrorKind::Other, e.to_string()))?;
+    let result = save_exported_database(exported, &out_file, parameters);
+    close(exported)
+        .map_err(|e| Error::new(ErrorKind::Other, e.to_string()))?;
+    result.map(|_|false)
+}
+
+fn save_exported_database(database: u64, file_name: &String, parameters: &Parameters) -> Result<(), Error> {
+    set_file1_location(database, parameters)?;
+    set_file2_location(database, parameters)?;
+    if let Some(data) = save(database).map_err(|e| Error::new(ErrorKind::Other, e.to_string()))? {
+        create_file(file_name, data)?;
+    }
+    Ok(())
+}
+
 pub fn set_hash2(database: u64, parameters: &Parameters) -> Result<bool, Error> {
     set_hash(database, 1, parameters.hash2_parameter.get_value(),
              parameters.iterations2_parameter.get_value(),
@@ -61,3 +96,43 @@ fn set_hash(database: u64, hash_id: u64, hash_type: String, iterations: isize, m
     }
 }
 
+
+pub fn set_encryption1(database: u64, parameters: &Parameters) -> Result<bool, Error> {
+    set_encryption(database, 0, parameters.encryption_parameter.get_value())?;
+    Ok(true)
+}
+
+pub fn set_encryption2(database: u64, parameters: &Parameters) -> Result<bool, Error> {
+    set_encryption(database, 1, parameters.encryption2_parameter.get_value())?;
+    Ok(true)
+}
+
+// algorithms separated by '+' are applied as a cascade in the given order
+fn set_encryption(database: u64, layer_id: u64, encryption: String) -> Result<(), Error> {
+    let mut engines = Vec::new();
+    for algorithm in encryption.split('+') {
+        match algorithm {
+            "chacha20" => engines.push(CryptoEngine::Chacha20),
+            "aes-ctr" => engines.push(CryptoEngine::AesCtr),
+            _ => return Err(Error::new(ErrorKind::InvalidInput, "unknown encryption algorithm"))
+        }
+    }
+    pman_lib::set_encryption(database, layer_id, engines)
+        .map_err(|e| Error::new(ErrorKind::Other, e.to_string()))
+}
+
+pub fn upgrade_database(database: u64) -> Result<bool, Error> {
+    let version = pman_lib::get_database_version(database)
+        .map_err(|e| Error::new(ErrorKind::Other, e.to_string()))?;
+    if version.stored_version == version.current_version {
+        println!("database version {} is up to date", format_version(version.current_version));
+        return Ok(false);
+    }
+    println!("upgrading database from version {} to version {}",
+             format_version(version.stored_version), format_version(version.current_version));
+    Ok(true)
+}
+
+fn format_version(version: u16) -> String {
+    format!("{}.{}", version >> 8, version & 0xFF)
+}
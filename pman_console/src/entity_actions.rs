@@ -1,19 +1,26 @@
 use std::collections::{HashMap, HashSet};
-use std::io::{Error, ErrorKind};
+use std::io::{Error, ErrorKind, Read, stdin};
 use std::sync::Arc;
-use pman_lib::{add_entity, DatabaseEntity, get_entities, get_groups, get_users, modify_entity, remove_entity, search};
+use std::time::SystemTime;
+use std::path::Path;
+use pman_lib::{add_attachment, add_entity, add_note, add_tag, DatabaseEntity, empty_trash, find_by_url, get_attachment, get_entities, get_entities_by_tags, get_expiring, get_groups, get_tags, get_trash, get_users, list_entities, modify_entity, next_hotp_code, purge_entity_history, remove_attachment, remove_entity, remove_tag, restore_entity, restore_entity_version, search, set_entity_expiry, set_entity_history_length, set_entity_otp, set_entity_urls, set_history_length, set_property_layout, set_trash_retention};
 use pman_lib::pman::database_entity::ENTITY_VERSION_LATEST;
-use pman_lib::structs_interfaces::DatabaseGroup;
+use pman_lib::structs_interfaces::{DatabaseGroup, DatabaseUser, EntityKind, EntitySortKey, EntityUrl, PropertyLayout, UrlMatchMode};
 use crate::{get_password, Parameters};
-use crate::utils::{parse_string_array, generate_password, load_file};
+use crate::groups_users_actions::{build_group_paths, find_group_by_path};
+use crate::template_actions::{find_template_by_name, get_template_names, parse_field_type};
+use crate::utils::{parse_string_array, generate_password, load_file, create_file};
 
 pub fn show_entities(database: u64, parameters: &Parameters) -> Result<bool, Error> {
     let entity_names = get_entity_names(parameters)?;
     let users = get_users(database)
         .map_err(|e| Error::new(ErrorKind::Other, e.to_string()))?;
+    let tags = get_tags(database)
+        .map_err(|e| Error::new(ErrorKind::Other, e.to_string()))?;
+    let templates = get_template_names(database)?;
     let (entities, groups) = get_entities_from_names(database, entity_names)?;
     for (_, entity) in entities {
-        show_entity(&groups, &users, entity)?;
+        show_entity(&groups, &users, &tags, &templates, entity)?;
     }
     Ok(false)
 }
@@ -33,8 +40,8 @@ pub fn show_entity_properties(database: u64, parameters: &Parameters) -> Result<
     for i in 0..entities.len() {
         let pnames = entities[i].1.get_property_names(ENTITY_VERSION_LATEST)
             .map_err(|e| Error::new(ErrorKind::Other, e.to_string()))?;
-        if let Some(id) = pnames.get(&property_names[i]) {
-            let value = entities[i].1.get_property_value(ENTITY_VERSION_LATEST, *id)
+        if let Some(property) = pnames.iter().find(|p|p.name == property_names[i]) {
+            let value = entities[i].1.get_property_value(ENTITY_VERSION_LATEST, property.id)
                 .map_err(|e| Error::new(ErrorKind::Other, e.to_string()))?;
             print!("{}", value);
         } else {
@@ -45,14 +52,15 @@ pub fn show_entity_properties(database: u64, parameters: &Parameters) -> Result<
 }
 
 pub fn search_entities(database: u64, parameters: &Parameters) -> Result<bool, Error> {
-    let entity_names = get_entity_names(parameters)?;
-    let groups: HashMap<u32, String> = get_groups(database)
-        .map_err(|e| Error::new(ErrorKind::Other, e.to_string()))?
-        .into_iter()
-        .map(|g|(g.id, g.name.clone()))
-        .collect();
-    for name in entity_names {
-        let result = search(database, name)
+    let query = parameters.query_parameter.get_value();
+    // entity names are quoted to be searched as partial names
+    let queries = if query.is_empty() {
+        get_entity_names(parameters)?.into_iter().map(|name|format!("\"{}\"", name)).collect()
+    } else { vec![query] };
+    let groups = build_group_paths(&get_groups(database)
+        .map_err(|e| Error::new(ErrorKind::Other, e.to_string()))?);
+    for query in queries {
+        let result = search(database, query)
             .map_err(|e| Error::new(ErrorKind::Other, e.to_string()))?;
         for (group_id, entities) in result {
             println!("{}:", groups.get(&group_id).unwrap());
@@ -105,9 +113,9 @@ fn build_property_maps(entity: Arc<DatabaseEntity>, parameters: HashMap<String,
     let mut new_properties = HashMap::new();
     let mut modified_properties = HashMap::new();
     for (name, value) in parameters {
-        if let Some(id) = property_names.get(&name) {
+        if let Some(property) = property_names.iter().find(|p|p.name == name) {
             let v = if value == "None" { None } else { Some(value) };
-            modified_properties.insert(*id, v);
+            modified_properties.insert(property.id, v);
         } else {
             new_properties.insert(name, value);
         }
@@ -115,6 +123,469 @@ fn build_property_maps(entity: Arc<DatabaseEntity>, parameters: HashMap<String,
     Ok((new_properties, modified_properties))
 }
 
+pub fn set_entities_otp(database: u64, parameters: &Parameters) -> Result<bool, Error> {
+    let entity_names = get_entity_names(parameters)?;
+    let otps: HashMap<String, String> = entity_names.clone().into_iter()
+        .zip(parse_string_array(parameters.entity_otp_parameter.get_value(),
+                                "entity otp values expected", Some(entity_names.len()))?)
+        .collect();
+    let (entities, _) = get_entities_from_names(database, entity_names)?;
+    for (entity_id, entity) in entities {
+        let name = entity.get_name()
+            .map_err(|e| Error::new(ErrorKind::Other, e.to_string()))?;
+        let value = otps.get(&name).unwrap();
+        let otp = if value == "None" { None } else if value == "Ask" {
+            Some(get_password(format!("otp for entity {}: ", name).as_str(), "".to_string())?)
+        } else { Some(value.clone()) };
+        set_entity_otp(database, entity_id, otp)
+            .map_err(|e| Error::new(ErrorKind::Other, e.to_string()))?;
+    }
+    Ok(true)
+}
+
+// entity urls format: mode:url;mode:url, None removes all urls
+pub fn set_entities_urls(database: u64, parameters: &Parameters) -> Result<bool, Error> {
+    let entity_names = get_entity_names(parameters)?;
+    let urls: HashMap<String, String> = entity_names.clone().into_iter()
+        .zip(parse_string_array(parameters.entity_urls_parameter.get_value(),
+                                "entity urls expected", Some(entity_names.len()))?)
+        .collect();
+    let (entities, _) = get_entities_from_names(database, entity_names)?;
+    for (entity_id, entity) in entities {
+        let name = entity.get_name()
+            .map_err(|e| Error::new(ErrorKind::Other, e.to_string()))?;
+        let entity_urls = parse_url_list(urls.get(&name).unwrap())?;
+        set_entity_urls(database, entity_id, entity_urls)
+            .map_err(|e| Error::new(ErrorKind::Other, e.to_string()))?;
+    }
+    Ok(true)
+}
+
+fn parse_url_list(value: &String) -> Result<Vec<EntityUrl>, Error> {
+    let mut result = Vec::new();
+    if value == "None" {
+        return Ok(result);
+    }
+    for part in value.split(';') {
+        let (mode, url) = part.split_once(':')
+            .ok_or(Error::new(ErrorKind::InvalidInput, "url should be in format mode:url"))?;
+        let match_mode = match mode {
+            "exact" => UrlMatchMode::Exact,
+            "host" => UrlMatchMode::Host,
+            "domain" => UrlMatchMode::BaseDomain,
+            "prefix" => UrlMatchMode::Prefix,
+            "regex" => UrlMatchMode::Regex,
+            _ => return Err(Error::new(ErrorKind::InvalidInput, "unknown url match mode"))
+        };
+        result.push(EntityUrl{url: url.to_string(), match_mode});
+    }
+    Ok(result)
+}
+
+pub fn show_entities_by_url(database: u64, parameters: &Parameters) -> Result<bool, Error> {
+    let group_paths = build_group_paths(&get_groups(database)
+        .map_err(|e| Error::new(ErrorKind::Other, e.to_string()))?);
+    for url in parse_string_array(parameters.entity_urls_parameter.get_value(), "urls expected", None)? {
+        println!("{}:", url);
+        for m in find_by_url(database, url)
+            .map_err(|e| Error::new(ErrorKind::Other, e.to_string()))? {
+            let entity = get_entities(database, m.group_id)
+                .map_err(|e| Error::new(ErrorKind::Other, e.to_string()))?
+                .remove(&m.entity_id).unwrap();
+            println!("  {}/{} {} ({:?})", group_paths.get(&m.group_id).unwrap(),
+                     entity.get_name().map_err(|e| Error::new(ErrorKind::Other, e.to_string()))?,
+                     m.url, m.match_mode);
+        }
+    }
+    Ok(false)
+}
+
+pub fn show_entities_page(database: u64, parameters: &Parameters) -> Result<bool, Error> {
+    let groups = get_groups(database)
+        .map_err(|e| Error::new(ErrorKind::Other, e.to_string()))?;
+    let group_id = match parameters.group_names_parameter.get_value() {
+        name if name.is_empty() => None,
+        name => Some(find_group_by_path(&groups, &name)?)
+    };
+    let (sort_key, descending) = parse_sort_key(parameters.sort_parameter.get_value())?;
+    let offset = parameters.offset_parameter.get_value() as u32;
+    let limit = match parameters.limit_parameter.get_value() {
+        0 => u32::MAX,
+        v => v as u32
+    };
+    let page = list_entities(database, group_id, sort_key, descending, offset, limit)
+        .map_err(|e| Error::new(ErrorKind::Other, e.to_string()))?;
+    let group_paths = build_group_paths(&groups);
+    for item in &page.items {
+        let entity_group_id = item.entity.get_group_id(ENTITY_VERSION_LATEST)
+            .map_err(|e| Error::new(ErrorKind::Other, e.to_string()))?;
+        println!("{}/{}", group_paths.get(&entity_group_id).unwrap(),
+                 item.entity.get_name().map_err(|e| Error::new(ErrorKind::Other, e.to_string()))?);
+    }
+    println!("{} of {} entities", page.items.len(), page.total);
+    Ok(false)
+}
+
+fn parse_sort_key(value: String) -> Result<(EntitySortKey, bool), Error> {
+    let (name, descending) = match value.strip_prefix('-') {
+        Some(name) => (name, true),
+        None => (value.as_str(), false)
+    };
+    let key = match name {
+        "name" => EntitySortKey::Name,
+        "created" => EntitySortKey::CreatedAt,
+        "modified" => EntitySortKey::ModifiedAt,
+        "group" => EntitySortKey::Group,
+        _ => return Err(Error::new(ErrorKind::InvalidInput, "invalid sort key"))
+    };
+    Ok((key, descending))
+}
+
+pub fn show_entities_otp(database: u64, parameters: &Parameters) -> Result<bool, Error> {
+    let entity_names = get_entity_names(parameters)?;
+    let (entities, _) = get_entities_from_names(database, entity_names)?;
+    let timestamp = SystemTime::now().duration_since(SystemTime::UNIX_EPOCH).unwrap().as_secs();
+    let mut save = false;
+    for (entity_id, entity) in entities {
+        let name = entity.get_name()
+            .map_err(|e| Error::new(ErrorKind::Other, e.to_string()))?;
+        match entity.get_otp_code(ENTITY_VERSION_LATEST, timestamp)
+            .map_err(|e| Error::new(ErrorKind::Other, e.to_string()))? {
+            None => println!("{}: no otp", name),
+            Some(code) => {
+                if let Some(valid_for) = code.valid_for {
+                    println!("{}: {} valid for {}s", name, code.code, valid_for);
+                } else {
+                    // hotp counter has to be advanced and saved
+                    let code = next_hotp_code(database, entity_id)
+                        .map_err(|e| Error::new(ErrorKind::Other, e.to_string()))?;
+                    println!("{}: {}", name, code.code);
+                    save = true;
+                }
+            }
+        }
+    }
+    Ok(save)
+}
+
+pub fn attach_files(database: u64, parameters: &Parameters) -> Result<bool, Error> {
+    let entity_names = get_entity_names(parameters)?;
+    let files: HashMap<String, String> = entity_names.clone().into_iter()
+        .zip(parse_string_array(parameters.attach_parameter.get_value(),
+                                "attachment file names expected", Some(entity_names.len()))?)
+        .collect();
+    let (entities, _) = get_entities_from_names(database, entity_names)?;
+    for (entity_id, entity) in entities {
+        let name = entity.get_name()
+            .map_err(|e| Error::new(ErrorKind::Other, e.to_string()))?;
+        let file_name = files.get(&name).unwrap();
+        let attachment_name = Path::new(file_name).file_name()
+            .and_then(|n|n.to_str())
+            .ok_or(Error::new(ErrorKind::InvalidInput, "invalid attachment file name"))?
+            .to_string();
+        let data = load_file(file_name.clone())?;
+        add_attachment(database, entity_id, attachment_name, data)
+            .map_err(|e| Error::new(ErrorKind::Other, e.to_string()))?;
+    }
+    Ok(true)
+}
+
+pub fn show_attachments(database: u64, parameters: &Parameters) -> Result<bool, Error> {
+    let entity_names = get_entity_names(parameters)?;
+    let (entities, _) = get_entities_from_names(database, entity_names)?;
+    for (_, entity) in entities {
+        println!("{}:", entity.get_name()
+            .map_err(|e| Error::new(ErrorKind::Other, e.to_string()))?);
+        for (name, _) in entity.get_attachment_names(ENTITY_VERSION_LATEST)
+            .map_err(|e| Error::new(ErrorKind::Other, e.to_string()))? {
+            println!("  {}", name);
+        }
+    }
+    Ok(false)
+}
+
+pub fn extract_attachment(database: u64, parameters: &Parameters) -> Result<bool, Error> {
+    let out_file = parameters.out_file_parameter.get_value();
+    if out_file.is_empty() {
+        return Err(Error::new(ErrorKind::InvalidInput, "output file name expected"));
+    }
+    let (entities, attachment_names) = get_entities_with_attachment_names(database, parameters)?;
+    if entities.len() != 1 {
+        return Err(Error::new(ErrorKind::InvalidInput, "single entity name expected"));
+    }
+    let (entity_id, entity) = &entities[0];
+    let attachment_id = get_attachment_id(entity, &attachment_names[0])?;
+    let data = get_attachment(database, *entity_id, ENTITY_VERSION_LATEST, attachment_id)
+        .map_err(|e| Error::new(ErrorKind::Other, e.to_string()))?;
+    create_file(&out_file, data)?;
+    Ok(false)
+}
+
+pub fn remove_attachments(database: u64, parameters: &Parameters) -> Result<bool, Error> {
+    let (entities, attachment_names) = get_entities_with_attachment_names(database, parameters)?;
+    for i in 0..entities.len() {
+        let attachment_id = get_attachment_id(&entities[i].1, &attachment_names[i])?;
+        remove_attachment(database, entities[i].0, attachment_id)
+            .map_err(|e| Error::new(ErrorKind::Other, e.to_string()))?;
+    }
+    Ok(true)
+}
+
+fn get_entities_with_attachment_names(database: u64, parameters: &Parameters)
+    -> Result<(Vec<(u32, Arc<DatabaseEntity>)>, Vec<String>), Error> {
+    let mut names = HashMap::new();
+    for name in get_entity_names(parameters)? {
+        let name_attachment_name: Vec<&str> = name.split('@').collect();
+        if name_attachment_name.len() != 2 {
+            return Err(Error::new(ErrorKind::InvalidInput, "entity name should be in format name@attachment_name"))
+        }
+        names.insert(name_attachment_name[0].to_string(), name_attachment_name[1].to_string());
+    }
+    let (entities, _) = get_entities_from_names(database, names.keys().cloned().collect())?;
+    let mut attachment_names = Vec::new();
+    for (_, entity) in &entities {
+        let name = entity.get_name()
+            .map_err(|e| Error::new(ErrorKind::Other, e.to_string()))?;
+        attachment_names.push(names.get(&name).unwrap().clone());
+    }
+    Ok((entities, attachment_names))
+}
+
+fn get_attachment_id(entity: &Arc<DatabaseEntity>, attachment_name: &String) -> Result<u32, Error> {
+    let names = entity.get_attachment_names(ENTITY_VERSION_LATEST)
+        .map_err(|e| Error::new(ErrorKind::Other, e.to_string()))?;
+    names.get(attachment_name).cloned()
+        .ok_or(Error::new(ErrorKind::NotFound, "unknown attachment name"))
+}
+
+pub fn add_entities_tags(database: u64, parameters: &Parameters) -> Result<bool, Error> {
+    let entity_names = get_entity_names(parameters)?;
+    let tags = get_entity_tags(parameters)?;
+    let (entities, _) = get_entities_from_names(database, entity_names)?;
+    for (entity_id, _) in entities {
+        for tag in &tags {
+            add_tag(database, entity_id, tag.clone())
+                .map_err(|e| Error::new(ErrorKind::Other, e.to_string()))?;
+        }
+    }
+    Ok(true)
+}
+
+pub fn remove_entities_tags(database: u64, parameters: &Parameters) -> Result<bool, Error> {
+    let entity_names = get_entity_names(parameters)?;
+    let tag_ids = get_tag_ids(database, parameters)?;
+    let (entities, _) = get_entities_from_names(database, entity_names)?;
+    for (entity_id, entity) in entities {
+        let entity_tag_ids = entity.get_tag_ids(ENTITY_VERSION_LATEST)
+            .map_err(|e| Error::new(ErrorKind::Other, e.to_string()))?;
+        for tag_id in &tag_ids {
+            if entity_tag_ids.contains(tag_id) {
+                remove_tag(database, entity_id, *tag_id)
+                    .map_err(|e| Error::new(ErrorKind::Other, e.to_string()))?;
+            }
+        }
+    }
+    Ok(true)
+}
+
+pub fn show_entities_by_tags(database: u64, parameters: &Parameters) -> Result<bool, Error> {
+    let tag_ids = get_tag_ids(database, parameters)?;
+    let groups = build_group_paths(&get_groups(database)
+        .map_err(|e| Error::new(ErrorKind::Other, e.to_string()))?);
+    for (_, entity) in get_entities_by_tags(database, tag_ids)
+        .map_err(|e| Error::new(ErrorKind::Other, e.to_string()))? {
+        let group_id = entity.get_group_id(ENTITY_VERSION_LATEST)
+            .map_err(|e| Error::new(ErrorKind::Other, e.to_string()))?;
+        println!("{}: {}", groups.get(&group_id).unwrap(),
+                 entity.get_name().map_err(|e| Error::new(ErrorKind::Other, e.to_string()))?);
+    }
+    Ok(false)
+}
+
+// expires-in & max-age values are in days, 0 removes the value, negative value keeps it unchanged
+pub fn set_entities_expiry(database: u64, parameters: &Parameters) -> Result<bool, Error> {
+    let expires_in = parameters.expires_in_parameter.get_value();
+    let max_age = parameters.max_age_parameter.get_value();
+    if expires_in < 0 && max_age < 0 {
+        return Err(Error::new(ErrorKind::InvalidInput, "expires-in or max-age expected"));
+    }
+    let entity_names = get_entity_names(parameters)?;
+    let (entities, _) = get_entities_from_names(database, entity_names)?;
+    let timestamp = SystemTime::now().duration_since(SystemTime::UNIX_EPOCH).unwrap().as_secs();
+    for (entity_id, entity) in entities {
+        let expires_at = match expires_in {
+            0 => None,
+            v if v < 0 => entity.get_expires_at(ENTITY_VERSION_LATEST)
+                .map_err(|e| Error::new(ErrorKind::Other, e.to_string()))?,
+            v => Some(timestamp + v as u64 * 86400)
+        };
+        let max_age_days = match max_age {
+            0 => None,
+            v if v < 0 => entity.get_max_age_days(ENTITY_VERSION_LATEST)
+                .map_err(|e| Error::new(ErrorKind::Other, e.to_string()))?,
+            v => Some(v as u32)
+        };
+        set_entity_expiry(database, entity_id, expires_at, max_age_days)
+            .map_err(|e| Error::new(ErrorKind::Other, e.to_string()))?;
+    }
+    Ok(true)
+}
+
+// layout format: name:type[:secret];name:type[:secret]..., all entity properties in the new order
+pub fn set_entities_property_layout(database: u64, parameters: &Parameters) -> Result<bool, Error> {
+    let entity_names = get_entity_names(parameters)?;
+    let layouts = parse_string_array(parameters.property_layout_parameter.get_value(),
+                                     "property layouts expected", Some(entity_names.len()))?;
+    let (entities, _) = get_entities_from_names(database, entity_names)?;
+    for ((entity_id, entity), layout) in entities.into_iter().zip(layouts) {
+        let properties = entity.get_property_names(ENTITY_VERSION_LATEST)
+            .map_err(|e| Error::new(ErrorKind::Other, e.to_string()))?;
+        let mut new_layout = Vec::new();
+        for part in layout.split(';') {
+            let parts: Vec<&str> = part.split(':').collect();
+            if parts.len() < 2 || parts.len() > 3 || (parts.len() == 3 && parts[2] != "secret") {
+                return Err(Error::new(ErrorKind::InvalidInput, "property layout should be in format name:type[:secret]"));
+            }
+            let property = properties.iter().find(|p|p.name == parts[0])
+                .ok_or(Error::new(ErrorKind::NotFound, format!("unknown property name {}", parts[0])))?;
+            new_layout.push(PropertyLayout{id: property.id, field_type: parse_field_type(parts[1])?, secret: parts.len() == 3});
+        }
+        set_property_layout(database, entity_id, new_layout)
+            .map_err(|e| Error::new(ErrorKind::Other, e.to_string()))?;
+    }
+    Ok(true)
+}
+
+pub fn show_expiring_entities(database: u64, days: isize) -> Result<bool, Error> {
+    let groups = get_groups(database)
+        .map_err(|e| Error::new(ErrorKind::Other, e.to_string()))?;
+    let paths = build_group_paths(&groups);
+    let mut entities = HashMap::new();
+    for group in &groups {
+        entities.extend(get_entities(database, group.id)
+            .map_err(|e| Error::new(ErrorKind::Other, e.to_string()))?);
+    }
+    let timestamp = SystemTime::now().duration_since(SystemTime::UNIX_EPOCH).unwrap().as_secs();
+    for expiring in get_expiring(database, days as u32)
+        .map_err(|e| Error::new(ErrorKind::Other, e.to_string()))? {
+        let entity = entities.get(&expiring.entity_id).unwrap();
+        let group_id = entity.get_group_id(ENTITY_VERSION_LATEST)
+            .map_err(|e| Error::new(ErrorKind::Other, e.to_string()))?;
+        let status = if expiring.expires_at <= timestamp {
+            format!("expired {} days ago", (timestamp - expiring.expires_at) / 86400)
+        } else {
+            format!("expires in {} days", (expiring.expires_at - timestamp) / 86400)
+        };
+        println!("{}: {} {}", paths.get(&group_id).unwrap(),
+                 entity.get_name().map_err(|e| Error::new(ErrorKind::Other, e.to_string()))?, status);
+    }
+    Ok(false)
+}
+
+pub fn restore_entities_version(database: u64, parameters: &Parameters) -> Result<bool, Error> {
+    let version = parameters.entity_version_parameter.get_value();
+    if version <= 0 {
+        return Err(Error::new(ErrorKind::InvalidInput, "entity version expected"));
+    }
+    let (entities, _) = get_entities_from_names(database, get_entity_names(parameters)?)?;
+    for (entity_id, _) in entities {
+        restore_entity_version(database, entity_id, version as u32)
+            .map_err(|e| Error::new(ErrorKind::Other, e.to_string()))?;
+    }
+    Ok(true)
+}
+
+pub fn set_database_history_length(database: u64, length: isize) -> Result<bool, Error> {
+    if length <= 0 {
+        return Err(Error::new(ErrorKind::InvalidInput, "history length expected"));
+    }
+    set_history_length(database, length as u32)
+        .map_err(|e| Error::new(ErrorKind::Other, e.to_string()))?;
+    Ok(true)
+}
+
+pub fn set_entities_history_length(database: u64, parameters: &Parameters) -> Result<bool, Error> {
+    let length = match parameters.history_length_parameter.get_value() {
+        0 => None,
+        v if v < 0 => return Err(Error::new(ErrorKind::InvalidInput, "history length expected")),
+        v => Some(v as u32)
+    };
+    let (entities, _) = get_entities_from_names(database, get_entity_names(parameters)?)?;
+    for (entity_id, _) in entities {
+        set_entity_history_length(database, entity_id, length)
+            .map_err(|e| Error::new(ErrorKind::Other, e.to_string()))?;
+    }
+    Ok(true)
+}
+
+pub fn purge_entities_history(database: u64, parameters: &Parameters) -> Result<bool, Error> {
+    let (entities, _) = get_entities_from_names(database, get_entity_names(parameters)?)?;
+    for (entity_id, _) in entities {
+        purge_entity_history(database, entity_id)
+            .map_err(|e| Error::new(ErrorKind::Other, e.to_string()))?;
+    }
+    Ok(true)
+}
+
+pub fn show_trash(database: u64) -> Result<bool, Error> {
+    let groups = get_groups(database)
+        .map_err(|e| Error::new(ErrorKind::Other, e.to_string()))?;
+    let paths = build_group_paths(&groups);
+    let timestamp = SystemTime::now().duration_since(SystemTime::UNIX_EPOCH).unwrap().as_secs();
+    for item in get_trash(database)
+        .map_err(|e| Error::new(ErrorKind::Other, e.to_string()))? {
+        println!("{}: {} deleted {} days ago", paths.get(&item.group_id).unwrap(), item.name,
+                 timestamp.saturating_sub(item.deleted_at) / 86400);
+    }
+    Ok(false)
+}
+
+// most recently deleted entity is restored when trash contains several entities with the same name
+pub fn restore_entities(database: u64, parameters: &Parameters) -> Result<bool, Error> {
+    let trash = get_trash(database)
+        .map_err(|e| Error::new(ErrorKind::Other, e.to_string()))?;
+    for name in get_entity_names(parameters)? {
+        let item = trash.iter().rev().find(|t|t.name == name)
+            .ok_or(Error::new(ErrorKind::NotFound, format!("entity {} not found in trash", name)))?;
+        restore_entity(database, item.entity_id)
+            .map_err(|e| Error::new(ErrorKind::Other, e.to_string()))?;
+    }
+    Ok(true)
+}
+
+pub fn clear_trash(database: u64) -> Result<bool, Error> {
+    empty_trash(database)
+        .map_err(|e| Error::new(ErrorKind::Other, e.to_string()))?;
+    Ok(true)
+}
+
+pub fn set_trash_retention_days(database: u64, days: isize) -> Result<bool, Error> {
+    if days < 0 {
+        return Err(Error::new(ErrorKind::InvalidInput, "days expected"));
+    }
+    set_trash_retention(database, days as u32)
+        .map_err(|e| Error::new(ErrorKind::Other, e.to_string()))?;
+    Ok(true)
+}
+
+fn get_entity_tags(parameters: &Parameters) -> Result<Vec<String>, Error> {
+    parse_string_array(parameters.entity_tags_parameter.get_value(),
+                       "entity tags expected", None)
+}
+
+fn get_tag_ids(database: u64, parameters: &Parameters) -> Result<Vec<u32>, Error> {
+    let tags: HashMap<String, u32> = get_tags(database)
+        .map_err(|e| Error::new(ErrorKind::Other, e.to_string()))?
+        .into_iter()
+        .map(|(k, v)|(v, k))
+        .collect();
+    let mut result = Vec::new();
+    for tag in get_entity_tags(parameters)? {
+        result.push(*tags.get(&tag).ok_or(Error::new(ErrorKind::NotFound, "tag not found"))?);
+    }
+    Ok(result)
+}
+
 pub fn remove_entities(database: u64, parameters: &Parameters) -> Result<bool, Error> {
     let entity_names = get_entity_names(parameters)?;
     let (entities, _) = get_entities_from_names(database, entity_names)?;
@@ -204,15 +675,22 @@ pub fn add_entities(database: u64, parameters: &Parameters) -> Result<bool, Erro
     let params = if !parameters.entity_properties_parameter.get_value().is_empty() {
         Some(get_entity_parameters(parameters, l)?)
     } else {None};
-    let groups: HashMap<String, u32> = get_groups(database)
-        .map_err(|e| Error::new(ErrorKind::Other, e.to_string()))?
+    let tags = if !parameters.entity_tags_parameter.get_value().is_empty() {
+        get_entity_tags(parameters)?
+    } else {Vec::new()};
+    let template_name = parameters.entity_template_parameter.get_value();
+    let template_id = if !template_name.is_empty() {
+        Some(find_template_by_name(&get_template_names(database)?, &template_name)?)
+    } else {None};
+    let groups: HashMap<String, u32> = build_group_paths(&get_groups(database)
+        .map_err(|e| Error::new(ErrorKind::Other, e.to_string()))?)
         .into_iter()
-        .map(|g|(g.name.clone(), g.id))
+        .map(|(id, path)|(path, id))
         .collect();
     let users: HashMap<String, u32> = get_users(database)
         .map_err(|e| Error::new(ErrorKind::Other, e.to_string()))?
         .into_iter()
-        .map(|(k, v)|(v, k))
+        .map(|(k, v)|(v.name, k))
         .collect();
     let mut entity_group_ids = Vec::new();
     let mut entity_user_ids = Vec::new();
@@ -227,37 +705,122 @@ pub fn add_entities(database: u64, parameters: &Parameters) -> Result<bool, Erro
         let properties = if let Some(p) = &params {
             p[i].clone()
         } else { HashMap::new() };
-        add_entity(database, entity_names[i].clone(), entity_group_ids[i],
-                   entity_user_ids[i], password, entity_urls[i].clone(), properties)
+        let entity_id = add_entity(database, entity_names[i].clone(), entity_group_ids[i],
+                                   entity_user_ids[i], password, entity_urls[i].clone(), properties, template_id)
+            .map_err(|e| Error::new(ErrorKind::Other, e.to_string()))?;
+        for tag in &tags {
+            add_tag(database, entity_id, tag.clone())
+                .map_err(|e| Error::new(ErrorKind::Other, e.to_string()))?;
+        }
+    }
+    Ok(true)
+}
+
+pub fn add_notes(database: u64, parameters: &Parameters) -> Result<bool, Error> {
+    let entity_names = get_entity_names(parameters)?;
+    let l = Some(entity_names.len());
+    let entity_groups =
+        parse_string_array(parameters.entity_groups_parameter.get_value(), "entity groups expected", l)?;
+    let notes = get_notes(parameters, l)?;
+    let tags = if !parameters.entity_tags_parameter.get_value().is_empty() {
+        get_entity_tags(parameters)?
+    } else {Vec::new()};
+    let groups = get_groups(database)
+        .map_err(|e| Error::new(ErrorKind::Other, e.to_string()))?;
+    let mut entity_group_ids = Vec::new();
+    for group in &entity_groups {
+        entity_group_ids.push(find_group_by_path(&groups, group)?);
+    }
+    for i in 0..l.unwrap() {
+        let entity_id = add_note(database, entity_names[i].clone(), entity_group_ids[i], notes[i].clone())
+            .map_err(|e| Error::new(ErrorKind::Other, e.to_string()))?;
+        for tag in &tags {
+            add_tag(database, entity_id, tag.clone())
+                .map_err(|e| Error::new(ErrorKind::Other, e.to_string()))?;
+        }
+    }
+    Ok(true)
+}
+
+pub fn edit_notes(database: u64, parameters: &Parameters) -> Result<bool, Error> {
+    let entity_names = get_entity_names(parameters)?;
+    let (entities, _) = get_entities_from_names(database, entity_names)?;
+    let notes = get_notes(parameters, Some(entities.len()))?;
+    for (i, (entity_id, entity)) in entities.into_iter().enumerate() {
+        if entity.get_kind() != EntityKind::Note {
+            return Err(Error::new(ErrorKind::InvalidInput, "entity is not a note"));
+        }
+        modify_entity(database, entity_id, None, None, Some(notes[i].clone()), None, false,
+                      HashMap::new(), HashMap::new())
             .map_err(|e| Error::new(ErrorKind::Other, e.to_string()))?;
     }
     Ok(true)
 }
 
-fn show_entity(groups: &Vec<Arc<DatabaseGroup>>, users: &HashMap<u32, String>,
-               entity: Arc<DatabaseEntity>) -> Result<(), Error> {
+// note files - one file per entity, - reads note from stdin
+fn get_notes(parameters: &Parameters, l: Option<usize>) -> Result<Vec<String>, Error> {
+    let mut result = Vec::new();
+    for file_name in parse_string_array(parameters.note_files_parameter.get_value(), "note files expected", l)? {
+        let data = if file_name == "-" {
+            let mut d = Vec::new();
+            stdin().read_to_end(&mut d)?;
+            d
+        } else { load_file(file_name)? };
+        result.push(String::from_utf8(data)
+            .map_err(|_e| Error::new(ErrorKind::InvalidData, "note should be valid utf-8 text"))?);
+    }
+    Ok(result)
+}
+
+fn show_entity(groups: &Vec<Arc<DatabaseGroup>>, users: &HashMap<u32, DatabaseUser>, tags: &HashMap<u32, String>,
+               templates: &HashMap<u32, String>, entity: Arc<DatabaseEntity>) -> Result<(), Error> {
     println!("Name: {}", entity.get_name()
         .map_err(|e| Error::new(ErrorKind::Other, e.to_string()))?);
     let group_id = entity.get_group_id(ENTITY_VERSION_LATEST)
         .map_err(|e| Error::new(ErrorKind::Other, e.to_string()))?;
-    let group = groups.iter()
-        .find(|g|g.id == group_id).unwrap().name.clone();
-    println!("Group: {}", group);
-    let user_id = entity.get_user_id(ENTITY_VERSION_LATEST)
-        .map_err(|e| Error::new(ErrorKind::Other, e.to_string()))?;
-    println!("User: {}", users.get(&user_id).unwrap().clone());
-    let url = entity.get_url(ENTITY_VERSION_LATEST)
+    println!("Group: {}", build_group_paths(groups).get(&group_id).unwrap());
+    if entity.get_kind() == EntityKind::Note {
+        println!("Note:\n{}", entity.get_password(ENTITY_VERSION_LATEST)
+            .map_err(|e| Error::new(ErrorKind::Other, e.to_string()))?);
+    } else {
+        let user_id = entity.get_user_id(ENTITY_VERSION_LATEST)
+            .map_err(|e| Error::new(ErrorKind::Other, e.to_string()))?;
+        println!("User: {}", users.get(&user_id).unwrap().name);
+        let urls = entity.get_urls(ENTITY_VERSION_LATEST)
+            .map_err(|e| Error::new(ErrorKind::Other, e.to_string()))?;
+        if urls.is_empty() {
+            println!("Url: None");
+        }
+        for url in urls {
+            println!("Url: {} ({:?})", url.url, url.match_mode);
+        }
+        println!("Password: {}", entity.get_password(ENTITY_VERSION_LATEST)
+            .map_err(|e| Error::new(ErrorKind::Other, e.to_string()))?);
+    }
+    let tag_names: Vec<String> = entity.get_tag_ids(ENTITY_VERSION_LATEST)
         .map_err(|e| Error::new(ErrorKind::Other, e.to_string()))?
-        .unwrap_or("None".to_string());
-    println!("Url: {}", url);
-    println!("Password: {}", entity.get_password(ENTITY_VERSION_LATEST)
-        .map_err(|e| Error::new(ErrorKind::Other, e.to_string()))?);
+        .into_iter()
+        .map(|id|tags.get(&id).cloned().unwrap_or(id.to_string()))
+        .collect();
+    println!("Tags: {}", tag_names.join(","));
+    if let Some(template_id) = entity.get_template_id(ENTITY_VERSION_LATEST)
+        .map_err(|e| Error::new(ErrorKind::Other, e.to_string()))? {
+        println!("Template: {}", templates.get(&template_id).cloned().unwrap_or(template_id.to_string()));
+    }
+    if let Some(expires_at) = entity.get_expires_at(ENTITY_VERSION_LATEST)
+        .map_err(|e| Error::new(ErrorKind::Other, e.to_string()))? {
+        println!("Expires at: {}", expires_at);
+    }
+    if let Some(days) = entity.get_max_age_days(ENTITY_VERSION_LATEST)
+        .map_err(|e| Error::new(ErrorKind::Other, e.to_string()))? {
+        println!("Password max age: {} days", days);
+    }
     println!("Properties:");
-    for (name, id) in entity.get_property_names(ENTITY_VERSION_LATEST)
+    for property in entity.get_property_names(ENTITY_VERSION_LATEST)
         .map_err(|e| Error::new(ErrorKind::Other, e.to_string()))? {
-        let value = entity.get_property_value(ENTITY_VERSION_LATEST, id)
+        let value = entity.get_property_value(ENTITY_VERSION_LATEST, property.id)
             .map_err(|e| Error::new(ErrorKind::Other, e.to_string()))?;
-        println!("{}:{}", name, value);
+        println!("{}:{}", property.name, value);
     }
     println!("-------------------------------------");
     Ok(())
@@ -268,13 +831,14 @@ pub fn select_entities(database: u64, group_names: String) -> Result<bool, Error
         .map_err(|e| Error::new(ErrorKind::Other, e.to_string()))?;
     let users = get_users(database)
         .map_err(|e| Error::new(ErrorKind::Other, e.to_string()))?;
+    let tags = get_tags(database)
+        .map_err(|e| Error::new(ErrorKind::Other, e.to_string()))?;
+    let templates = get_template_names(database)?;
     for name in parse_string_array(group_names, "group names expected", None)? {
-        let group = groups.iter()
-            .find(|g|g.name == name)
-            .ok_or(Error::new(ErrorKind::NotFound, "group not found"))?;
-        for (_, entity) in get_entities(database, group.id)
+        let group_id = find_group_by_path(&groups, &name)?;
+        for (_, entity) in get_entities(database, group_id)
             .map_err(|e| Error::new(ErrorKind::Other, e.to_string()))? {
-            show_entity(&groups, &users, entity)?;
+            show_entity(&groups, &users, &tags, &templates, entity)?;
         }
     }
     Ok(false)
@@ -1,31 +1,205 @@
+use std::collections::HashMap;
 use std::io::{Error, ErrorKind};
-use pman_lib::{add_group, add_user, get_groups, get_users};
+use std::sync::Arc;
+use pman_lib::{add_child_group, add_group, add_user, get_groups, get_users, merge_groups, move_group, rename_user, replace_user, set_group_max_age, update_user};
+use pman_lib::structs_interfaces::{DatabaseGroup, DatabaseUser};
 use crate::utils::parse_string_array;
 
 pub fn select_users(database: u64) -> Result<bool, Error> {
-    for (_, name) in get_users(database)
+    for (_, user) in get_users(database)
         .map_err(|e| Error::new(ErrorKind::Other, e.to_string()))? {
-        println!("{}", name);
+        let mut parts = vec![user.name];
+        if !user.display_name.is_empty() {
+            parts.push(format!("\"{}\"", user.display_name));
+        }
+        if !user.email.is_empty() {
+            parts.push(format!("<{}>", user.email));
+        }
+        let mut fields: Vec<String> = user.fields.into_iter()
+            .map(|(name, value)|format!("{}:{}", name, value))
+            .collect();
+        fields.sort();
+        parts.extend(fields);
+        println!("{}", parts.join(" "));
     }
     Ok(false)
 }
 
+// user info format: email:value;display_name:value;field_name:value, None clears user info
+pub fn update_users(database: u64, user_names: String, user_info: String) -> Result<bool, Error> {
+    let names = parse_string_array(user_names, "user names expected", None)?;
+    let infos = parse_string_array(user_info, "user info expected", Some(names.len()))?;
+    let users = get_users(database)
+        .map_err(|e| Error::new(ErrorKind::Other, e.to_string()))?;
+    for (name, info) in names.into_iter().zip(infos) {
+        let mut user = DatabaseUser{name: name.clone(), email: String::new(), display_name: String::new(),
+                                    fields: HashMap::new()};
+        if info != "None" {
+            for part in info.split(';') {
+                let (key, value) = part.split_once(':')
+                    .ok_or(Error::new(ErrorKind::InvalidInput, "user info should be in format name:value"))?;
+                match key {
+                    "email" => user.email = value.to_string(),
+                    "display_name" => user.display_name = value.to_string(),
+                    _ => { user.fields.insert(key.to_string(), value.to_string()); }
+                }
+            }
+        }
+        update_user(database, find_user_by_name(&users, &name)?, user)
+            .map_err(|e| Error::new(ErrorKind::Other, e.to_string()))?;
+    }
+    Ok(true)
+}
+
 pub fn select_groups(database: u64) -> Result<bool, Error> {
-    for group in get_groups(database)
-        .map_err(|e| Error::new(ErrorKind::Other, e.to_string()))? {
-        println!("{} {}", group.name, group.entities_count);
+    let groups = get_groups(database)
+        .map_err(|e| Error::new(ErrorKind::Other, e.to_string()))?;
+    let paths = build_group_paths(&groups);
+    let mut sorted: Vec<(&String, &Arc<DatabaseGroup>)> = groups.iter()
+        .map(|g|(paths.get(&g.id).unwrap(), g))
+        .collect();
+    sorted.sort_by_key(|(path, _)|*path);
+    for (path, group) in sorted {
+        match group.max_age_days {
+            Some(days) => println!("{} {} {} max age {} days", path, group.entities_count,
+                                   group.total_entities_count, days),
+            None => println!("{} {} {}", path, group.entities_count, group.total_entities_count)
+        }
     }
     Ok(false)
 }
 
+// missing parent groups are created
 pub fn add_groups(database: u64, group_names: String) -> Result<bool, Error> {
+    let groups = get_groups(database)
+        .map_err(|e| Error::new(ErrorKind::Other, e.to_string()))?;
+    let mut paths: HashMap<String, u32> = build_group_paths(&groups).into_iter()
+        .map(|(id, path)|(path, id))
+        .collect();
+    for name in parse_string_array(group_names, "group names expected", None)? {
+        let mut path = String::new();
+        let mut parent_id = None;
+        for part in name.split('/') {
+            path = if path.is_empty() { part.to_string() } else { format!("{}/{}", path, part) };
+            let id = match paths.get(&path) {
+                Some(id) => *id,
+                None => {
+                    let id = match parent_id {
+                        None => add_group(database, part.to_string()),
+                        Some(pid) => add_child_group(database, pid, part.to_string())
+                    }.map_err(|e| Error::new(ErrorKind::Other, e.to_string()))?;
+                    paths.insert(path.clone(), id);
+                    id
+                }
+            };
+            parent_id = Some(id);
+        }
+    }
+    Ok(true)
+}
+
+// parent group "/" moves groups to the root level
+pub fn move_groups(database: u64, group_names: String, parent_group: String) -> Result<bool, Error> {
+    let groups = get_groups(database)
+        .map_err(|e| Error::new(ErrorKind::Other, e.to_string()))?;
+    let parent_id = if parent_group == "/" { None } else {
+        Some(find_group_by_path(&groups, &parent_group)?)
+    };
+    for name in parse_string_array(group_names, "group names expected", None)? {
+        move_group(database, find_group_by_path(&groups, &name)?, parent_id)
+            .map_err(|e| Error::new(ErrorKind::Other, e.to_string()))?;
+    }
+    Ok(true)
+}
+
+pub fn rename_users(database: u64, user_names: String, new_names: String) -> Result<bool, Error> {
+    let names = parse_string_array(user_names, "user names expected", None)?;
+    let new_names = parse_string_array(new_names, "new names expected", Some(names.len()))?;
+    let users = get_users(database)
+        .map_err(|e| Error::new(ErrorKind::Other, e.to_string()))?;
+    for (name, new_name) in names.into_iter().zip(new_names) {
+        rename_user(database, find_user_by_name(&users, &name)?, new_name)
+            .map_err(|e| Error::new(ErrorKind::Other, e.to_string()))?;
+    }
+    Ok(true)
+}
+
+pub fn replace_users(database: u64, user_names: String, replacement_user: String,
+                     rewrite_history: bool) -> Result<bool, Error> {
+    let users = get_users(database)
+        .map_err(|e| Error::new(ErrorKind::Other, e.to_string()))?;
+    let replacement_id = find_user_by_name(&users, &replacement_user)?;
+    for name in parse_string_array(user_names, "user names expected", None)? {
+        replace_user(database, find_user_by_name(&users, &name)?, replacement_id, rewrite_history)
+            .map_err(|e| Error::new(ErrorKind::Other, e.to_string()))?;
+    }
+    Ok(true)
+}
+
+pub fn merge_groups_into(database: u64, group_names: String, target_group: String,
+                         rewrite_history: bool) -> Result<bool, Error> {
+    let groups = get_groups(database)
+        .map_err(|e| Error::new(ErrorKind::Other, e.to_string()))?;
+    let target_id = find_group_by_path(&groups, &target_group)?;
     for name in parse_string_array(group_names, "group names expected", None)? {
-        add_group(database, name)
+        merge_groups(database, find_group_by_path(&groups, &name)?, target_id, rewrite_history)
             .map_err(|e| Error::new(ErrorKind::Other, e.to_string()))?;
     }
     Ok(true)
 }
 
+fn find_user_by_name(users: &HashMap<u32, DatabaseUser>, name: &String) -> Result<u32, Error> {
+    users.iter()
+        .find(|(_id, user)|user.name == *name)
+        .map(|(id, _n)|*id)
+        .ok_or(Error::new(ErrorKind::NotFound, format!("user {} not found", name)))
+}
+
+// max_age in days, 0 removes the policy
+pub fn set_groups_max_age(database: u64, group_names: String, max_age: isize) -> Result<bool, Error> {
+    if max_age < 0 {
+        return Err(Error::new(ErrorKind::InvalidInput, "max-age expected"));
+    }
+    let groups = get_groups(database)
+        .map_err(|e| Error::new(ErrorKind::Other, e.to_string()))?;
+    let max_age_days = if max_age == 0 { None } else { Some(max_age as u32) };
+    for name in parse_string_array(group_names, "group names expected", None)? {
+        set_group_max_age(database, find_group_by_path(&groups, &name)?, max_age_days)
+            .map_err(|e| Error::new(ErrorKind::Other, e.to_string()))?;
+    }
+    Ok(true)
+}
+
+// map group id -> group path like Work/Cloud/AWS
+pub fn build_group_paths(groups: &Vec<Arc<DatabaseGroup>>) -> HashMap<u32, String> {
+    let by_id: HashMap<u32, &Arc<DatabaseGroup>> = groups.iter().map(|g|(g.id, g)).collect();
+    let mut result = HashMap::new();
+    for group in groups {
+        let mut path = group.name.clone();
+        let mut parent_id = group.parent_id;
+        let mut depth = 0;
+        while let Some(id) = parent_id {
+            depth += 1;
+            match by_id.get(&id) {
+                Some(parent) if depth <= groups.len() => {
+                    path = format!("{}/{}", parent.name, path);
+                    parent_id = parent.parent_id;
+                }
+                _ => break
+            }
+        }
+        result.insert(group.id, path);
+    }
+    result
+}
+
+pub fn find_group_by_path(groups: &Vec<Arc<DatabaseGroup>>, path: &String) -> Result<u32, Error> {
+    build_group_paths(groups).into_iter()
+        .find(|(_id, p)|*p == *path)
+        .map(|(id, _p)|id)
+        .ok_or(Error::new(ErrorKind::NotFound, "group not found"))
+}
+
 pub fn add_users(database: u64, user_names: String) -> Result<bool, Error> {
     for name in parse_string_array(user_names, "user names expected", None)? {
         add_user(database, name)
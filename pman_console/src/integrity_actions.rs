@@ -0,0 +1,20 @@
+use std::io::{Error, ErrorKind};
+use pman_lib::check_integrity;
+
+pub fn check_database_integrity(database: u64, repair: bool) -> Result<bool, Error> {
+    let issues = check_integrity(database, repair)
+        .map_err(|e| Error::new(ErrorKind::Other, e.to_string()))?;
+    if issues.is_empty() {
+        println!("No integrity issues found");
+        return Ok(false);
+    }
+    for issue in &issues {
+        println!("{:?} {}: {}", issue.issue_type, issue.record_id, issue.description);
+    }
+    if repair {
+        println!("{} issues found, database repaired", issues.len());
+    } else {
+        println!("{} issues found, use --repair to fix them", issues.len());
+    }
+    Ok(repair)
+}
@@ -0,0 +1,102 @@
+use std::io::{Error, ErrorKind};
+use pman_lib::{add_key_file_slot, add_member, add_recovery_key, generate_member_key_pair, get_key_slots, get_members,
+               remove_key_slot, revoke_member, set_passwords};
+use pman_lib::structs_interfaces::KeySlotType;
+use crate::Parameters;
+use crate::passwords::build_new_password_hashes;
+use crate::utils::{bytes_to_hex, create_file, load_file, parse_string_array};
+
+pub fn select_key_slots(database: u64) -> Result<bool, Error> {
+    let mut slots: Vec<(u32, KeySlotType)> = get_key_slots(database)
+        .map_err(|e| Error::new(ErrorKind::Other, e.to_string()))?
+        .into_iter()
+        .collect();
+    slots.sort_by_key(|(id, _)|*id);
+    for (id, slot_type) in slots {
+        println!("{} {:?}", id, slot_type);
+    }
+    Ok(false)
+}
+
+pub fn create_recovery_key(database: u64) -> Result<bool, Error> {
+    let key = add_recovery_key(database)
+        .map_err(|e| Error::new(ErrorKind::Other, e.to_string()))?;
+    println!("Recovery key: {}", bytes_to_hex(&key));
+    Ok(true)
+}
+
+pub fn create_key_file_slot(database: u64, key_file_name: String) -> Result<bool, Error> {
+    if key_file_name.is_empty() {
+        return Err(Error::new(ErrorKind::InvalidInput, "slot key file name expected"));
+    }
+    let id = add_key_file_slot(database, load_file(key_file_name)?)
+        .map_err(|e| Error::new(ErrorKind::Other, e.to_string()))?;
+    println!("Key slot id: {}", id);
+    Ok(true)
+}
+
+pub fn remove_key_slots(database: u64, key_slot_ids: String) -> Result<bool, Error> {
+    for id in parse_string_array(key_slot_ids, "key slot ids expected", None)? {
+        let slot_id = id.parse::<u32>()
+            .map_err(|_e| Error::new(ErrorKind::InvalidInput, "invalid key slot id"))?;
+        remove_key_slot(database, slot_id)
+            .map_err(|e| Error::new(ErrorKind::Other, e.to_string()))?;
+    }
+    Ok(true)
+}
+
+pub fn change_passwords(database: u64, parameters: &Parameters) -> Result<bool, Error> {
+    let (password_hash, password2_hash) = build_new_password_hashes(parameters)?;
+    set_passwords(database, password_hash, password2_hash)
+        .map_err(|e| Error::new(ErrorKind::Other, e.to_string()))?;
+    Ok(true)
+}
+
+pub fn create_member_key_files(file_name: String) -> Result<(), Error> {
+    if file_name.is_empty() {
+        return Err(Error::new(ErrorKind::InvalidInput, "member key file name expected"));
+    }
+    let key_pair = generate_member_key_pair();
+    create_file(&file_name, key_pair.private_key)?;
+    create_file(&(file_name + ".pub"), key_pair.public_key)
+}
+
+pub fn select_members(database: u64) -> Result<bool, Error> {
+    let mut members: Vec<(u32, String, Vec<u8>)> = get_members(database)
+        .map_err(|e| Error::new(ErrorKind::Other, e.to_string()))?
+        .into_iter()
+        .map(|(id, m)|(id, m.name, m.public_key))
+        .collect();
+    members.sort_by_key(|(id, _, _)|*id);
+    for (id, name, public_key) in members {
+        println!("{} {} {}", id, name, bytes_to_hex(&public_key));
+    }
+    Ok(false)
+}
+
+pub fn add_members(database: u64, member_names: String, public_key_files: String) -> Result<bool, Error> {
+    let names = parse_string_array(member_names, "member names expected", None)?;
+    let key_files = parse_string_array(public_key_files, "member public key files expected",
+                                       Some(names.len()))?;
+    for (name, key_file) in names.into_iter().zip(key_files) {
+        let id = add_member(database, name, load_file(key_file)?)
+            .map_err(|e| Error::new(ErrorKind::Other, e.to_string()))?;
+        println!("Member id: {}", id);
+    }
+    Ok(true)
+}
+
+pub fn revoke_members(database: u64, member_names: String) -> Result<bool, Error> {
+    let members = get_members(database)
+        .map_err(|e| Error::new(ErrorKind::Other, e.to_string()))?;
+    for name in parse_string_array(member_names, "member names expected", None)? {
+        let id = members.iter().find(|(_id, m)|m.name == name).map(|(id, _m)|*id)
+            .ok_or(Error::new(ErrorKind::NotFound, format!("member {} not found", name)))?;
+        let removed = revoke_member(database, id)
+            .map_err(|e| Error::new(ErrorKind::Other, e.to_string()))?;
+        for slot_type in removed {
+            println!("Warning: {:?} key slot was removed, add it again", slot_type);
+        }
+    }
+    Ok(true)
+}
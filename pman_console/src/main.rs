@@ -3,6 +3,11 @@ mod groups_users_actions;
 mod passwords;
 mod db_properties;
 mod utils;
+mod key_slot_actions;
+mod ssh_agent;
+mod integrity_actions;
+mod template_actions;
+mod audit_actions;
 
 use std::collections::HashMap;
 use std::io::{Error, ErrorKind, Read};
@@ -11,15 +16,23 @@ use std::fs;
 use std::fs::File;
 use std::time::Instant;
 use arguments_parser::{Arguments, IntParameter, BoolParameter, Switch, StringParameter, EnumParameter};
-use pman_lib::{build_argon2_hash, create, get_database_type, lib_init, open, pre_open, prepare, save};
+use pman_lib::{build_argon2_hash, create, disable_core_dumps, get_database_type, get_database_version, is_audit_pending, lib_init, open, pre_open, pre_open_with_key_slot, prepare, remove_data_file_backups, save, set_device_label};
 use pman_lib::pman::data_file::build_qs3_location_data;
 use pman_lib::pman::network::{NetworkFileHandler, QS3Handler};
+use pman_lib::structs_interfaces::{KeySlotType, PasswordDatabaseType};
 use rand::Rng;
-use crate::db_properties::{set_hash1, set_hash2, set_file1_location, set_file2_location};
-use crate::entity_actions::{add_entities, modify_entities, remove_entities, search_entities, select_entities, show_entities, show_entity_properties};
-use crate::groups_users_actions::{add_groups, add_users, select_groups, select_users};
+use crate::integrity_actions::check_database_integrity;
+use crate::audit_actions::{set_audit, show_audit_log};
+use crate::db_properties::{set_hash1, set_hash2, set_file1_location, set_file2_location, set_encryption1,
+                           set_encryption2, share_export, upgrade_database};
+use crate::entity_actions::{add_entities, add_entities_tags, add_notes, attach_files, clear_trash, edit_notes, extract_attachment, modify_entities, remove_attachments, remove_entities, purge_entities_history, remove_entities_tags, restore_entities, restore_entities_version, search_entities, select_entities, show_entities_page, set_database_history_length, set_entities_expiry, set_entities_history_length, set_entities_otp, set_entities_property_layout, set_entities_urls, set_trash_retention_days, show_attachments, show_entities, show_entities_by_tags, show_entities_by_url, show_expiring_entities, show_entities_otp, show_entity_properties, show_trash};
+use crate::groups_users_actions::{add_groups, add_users, merge_groups_into, move_groups, rename_users, replace_users, select_groups, select_users, set_groups_max_age, update_users};
+use crate::key_slot_actions::{add_members, change_passwords, create_key_file_slot, create_member_key_files, create_recovery_key,
+                              remove_key_slots, revoke_members, select_key_slots, select_members};
+use crate::template_actions::{create_template, remove_templates, select_templates};
 use crate::passwords::{build_password_hashes, build_password_hashes_from_key_file, create_key_file, generate_password_command};
-use crate::utils::{create_file, get_password, load_file};
+use crate::ssh_agent::run_ssh_agent;
+use crate::utils::{create_backup, create_file, get_password, hex_to_bytes, load_file, replace_file_with_backup};
 
 const TIME_DEFAULT: isize = 1000;
 const PARALLELISM_DEFAULT: isize = 6;
@@ -60,6 +73,45 @@ struct Parameters {
     entity_properties_parameter: StringParameter,
     key_file_parameter: StringParameter,
     database_key_file_parameter: StringParameter,
+    recovery_key_parameter: StringParameter,
+    unlock_key_file_parameter: StringParameter,
+    slot_key_file_parameter: StringParameter,
+    member_key_parameter: StringParameter,
+    member_names_parameter: StringParameter,
+    member_public_keys_parameter: StringParameter,
+    key_slot_ids_parameter: StringParameter,
+    new_password_parameter: StringParameter,
+    new_password2_parameter: StringParameter,
+    entity_otp_parameter: StringParameter,
+    ssh_agent_socket_parameter: StringParameter,
+    ssh_key_property_parameter: StringParameter,
+    ssh_agent_confirm_parameter: BoolParameter,
+    repair_parameter: BoolParameter,
+    attach_parameter: StringParameter,
+    out_file_parameter: StringParameter,
+    entity_tags_parameter: StringParameter,
+    parent_group_parameter: StringParameter,
+    max_age_parameter: IntParameter,
+    expires_in_parameter: IntParameter,
+    days_parameter: IntParameter,
+    entity_version_parameter: IntParameter,
+    history_length_parameter: IntParameter,
+    note_files_parameter: StringParameter,
+    entity_template_parameter: StringParameter,
+    template_fields_parameter: StringParameter,
+    property_layout_parameter: StringParameter,
+    device_label_parameter: StringParameter,
+    audit_size_parameter: IntParameter,
+    audit_reveals_parameter: BoolParameter,
+    new_names_parameter: StringParameter,
+    replacement_user_parameter: StringParameter,
+    target_group_parameter: StringParameter,
+    rewrite_history_parameter: BoolParameter,
+    user_info_parameter: StringParameter,
+    sort_parameter: StringParameter,
+    offset_parameter: IntParameter,
+    limit_parameter: IntParameter,
+    query_parameter: StringParameter,
 }
 
 struct DatabaseAction {
@@ -69,6 +121,7 @@ struct DatabaseAction {
 }
 
 fn main() -> Result<(), Error> {
+    disable_core_dumps().map_err(|e| Error::new(ErrorKind::Other, e.to_string()))?;
     let file1_parameter = StringParameter::new("qs3");
     let file2_parameter = StringParameter::new("qs3");
     let password_parameter = StringParameter::new("");
@@ -78,13 +131,15 @@ fn main() -> Result<(), Error> {
     let hash_values = vec!["argon2".to_string()];
     let hash_parameter = EnumParameter::new(hash_values.clone(), "argon2");
     let hash2_parameter = EnumParameter::new(hash_values, "argon2");
-    let encryption_values = vec!["aes".to_string()];
-    let encryption_parameter = EnumParameter::new(encryption_values.clone(), "aes");
-    let encryption2_parameter = EnumParameter::new(encryption_values, "aes");
+    let encryption_values = vec!["chacha20".to_string(), "aes-ctr".to_string(),
+                                 "aes-ctr+chacha20".to_string(), "chacha20+aes-ctr".to_string()];
+    let encryption_parameter = EnumParameter::new(encryption_values.clone(), "chacha20");
+    let encryption2_parameter = EnumParameter::new(encryption_values, "chacha20");
     let verbose_parameter = BoolParameter::new();
     let create_parameter = BoolParameter::new();
     let argon2_test_parameter = BoolParameter::new();
     let key_create_parameter = BoolParameter::new();
+    let member_key_create_parameter = BoolParameter::new();
     let time_parameter = IntParameter::new(TIME_DEFAULT, |v|v>0);
     let parallelism_parameter = IntParameter::new(PARALLELISM_DEFAULT, |v|v>0&&v<256);
     let time2_parameter = IntParameter::new(TIME_DEFAULT, |v|v>0);
@@ -111,6 +166,45 @@ fn main() -> Result<(), Error> {
     let generate_password_parameter = StringParameter::new("");
     let key_file_parameter = StringParameter::new("");
     let database_key_file_parameter = StringParameter::new("");
+    let recovery_key_parameter = StringParameter::new("");
+    let unlock_key_file_parameter = StringParameter::new("");
+    let slot_key_file_parameter = StringParameter::new("");
+    let member_key_parameter = StringParameter::new("");
+    let member_names_parameter = StringParameter::new("");
+    let member_public_keys_parameter = StringParameter::new("");
+    let key_slot_ids_parameter = StringParameter::new("");
+    let new_password_parameter = StringParameter::new("");
+    let new_password2_parameter = StringParameter::new("");
+    let entity_otp_parameter = StringParameter::new("");
+    let ssh_agent_socket_parameter = StringParameter::new("");
+    let ssh_key_property_parameter = StringParameter::new("ssh_private_key");
+    let ssh_agent_confirm_parameter = BoolParameter::new();
+    let repair_parameter = BoolParameter::new();
+    let attach_parameter = StringParameter::new("");
+    let out_file_parameter = StringParameter::new("");
+    let entity_tags_parameter = StringParameter::new("");
+    let parent_group_parameter = StringParameter::new("");
+    let max_age_parameter = IntParameter::new(-1, |v|v>=0);
+    let expires_in_parameter = IntParameter::new(-1, |v|v>=0);
+    let days_parameter = IntParameter::new(0, |v|v>=0);
+    let entity_version_parameter = IntParameter::new(0, |v|v>0);
+    let history_length_parameter = IntParameter::new(-1, |v|v>=0);
+    let note_files_parameter = StringParameter::new("");
+    let entity_template_parameter = StringParameter::new("");
+    let template_fields_parameter = StringParameter::new("");
+    let property_layout_parameter = StringParameter::new("");
+    let device_label_parameter = StringParameter::new("");
+    let audit_size_parameter = IntParameter::new(-1, |v|v>=0);
+    let audit_reveals_parameter = BoolParameter::new();
+    let new_names_parameter = StringParameter::new("");
+    let replacement_user_parameter = StringParameter::new("");
+    let target_group_parameter = StringParameter::new("");
+    let rewrite_history_parameter = BoolParameter::new();
+    let user_info_parameter = StringParameter::new("");
+    let sort_parameter = StringParameter::new("name");
+    let offset_parameter = IntParameter::new(0, |v|v>=0);
+    let limit_parameter = IntParameter::new(0, |v|v>=0);
+    let query_parameter = StringParameter::new("");
     let parameters = Parameters{
         file1_parameter,
         file2_parameter,
@@ -145,7 +239,46 @@ fn main() -> Result<(), Error> {
         entity_urls_parameter,
         entity_properties_parameter,
         key_file_parameter,
-        database_key_file_parameter
+        database_key_file_parameter,
+        recovery_key_parameter,
+        unlock_key_file_parameter,
+        slot_key_file_parameter,
+        member_key_parameter,
+        member_names_parameter,
+        member_public_keys_parameter,
+        key_slot_ids_parameter,
+        new_password_parameter,
+        new_password2_parameter,
+        entity_otp_parameter,
+        ssh_agent_socket_parameter,
+        ssh_key_property_parameter,
+        ssh_agent_confirm_parameter,
+        repair_parameter,
+        attach_parameter,
+        out_file_parameter,
+        entity_tags_parameter,
+        parent_group_parameter,
+        max_age_parameter,
+        expires_in_parameter,
+        days_parameter,
+        entity_version_parameter,
+        history_length_parameter,
+        note_files_parameter,
+        entity_template_parameter,
+        template_fields_parameter,
+        property_layout_parameter,
+        device_label_parameter,
+        audit_size_parameter,
+        audit_reveals_parameter,
+        new_names_parameter,
+        replacement_user_parameter,
+        target_group_parameter,
+        rewrite_history_parameter,
+        user_info_parameter,
+        sort_parameter,
+        offset_parameter,
+        limit_parameter,
+        query_parameter
     };
     let switches = [
         Switch::new("action", None, Some("actions"),
@@ -162,6 +295,8 @@ fn main() -> Result<(), Error> {
         Switch::new("create mode", Some('c'), None, &parameters.create_parameter),
         Switch::new("argon2 test mode", None, Some("argon2-test"), &argon2_test_parameter),
         Switch::new("key file create", None, Some("key-create"), &key_create_parameter),
+        Switch::new("member key pair create, public key is saved to the member key file name with .pub suffix",
+                    None, Some("member-key-create"), &member_key_create_parameter),
         Switch::new("qs3 path for s3 test", None, Some("qs3-path"), &qs3_path_parameter),
         Switch::new("qs3 key file for s3 test", None, Some("qs3-key"), &qs3_key_parameter),
         Switch::new("qs3 path for file1", None, Some("qs3-path1"), &parameters.qs3_path_parameter1),
@@ -217,7 +352,85 @@ fn main() -> Result<(), Error> {
         Switch::new("key file name", None, Some("key-file"),
                     &parameters.key_file_parameter),
         Switch::new("database key file name", None, Some("db-key-file"),
-                    &parameters.database_key_file_parameter)
+                    &parameters.database_key_file_parameter),
+        Switch::new("recovery key to open database", None, Some("recovery-key"),
+                    &parameters.recovery_key_parameter),
+        Switch::new("key file name to open database", None, Some("unlock-key-file"),
+                    &parameters.unlock_key_file_parameter),
+        Switch::new("key file name for new key slot", None, Some("slot-key-file"),
+                    &parameters.slot_key_file_parameter),
+        Switch::new("member private key file name to open database", None, Some("member-key"),
+                    &parameters.member_key_parameter),
+        Switch::new("member names", None, Some("member-names"),
+                    &parameters.member_names_parameter),
+        Switch::new("member public key file names", None, Some("member-public-keys"),
+                    &parameters.member_public_keys_parameter),
+        Switch::new("key slot ids", None, Some("key-slot-ids"),
+                    &parameters.key_slot_ids_parameter),
+        Switch::new("new first password", None, Some("new-pw"),
+                    &parameters.new_password_parameter),
+        Switch::new("new second password", None, Some("new-pw2"),
+                    &parameters.new_password2_parameter),
+        Switch::new("entity otp values (otpauth uri or base32 secret)", None, Some("entity-otp"),
+                    &parameters.entity_otp_parameter),
+        Switch::new("ssh agent socket path", None, Some("ssh-agent-socket"),
+                    &parameters.ssh_agent_socket_parameter),
+        Switch::new("entity property name with ssh private key", None, Some("ssh-key-property"),
+                    &parameters.ssh_key_property_parameter),
+        Switch::new("ask for confirmation on each ssh key use", None, Some("ssh-agent-confirm"),
+                    &parameters.ssh_agent_confirm_parameter),
+        Switch::new("repair database in fsck action", None, Some("repair"),
+                    &parameters.repair_parameter),
+        Switch::new("files to attach to entities", None, Some("attach"),
+                    &parameters.attach_parameter),
+        Switch::new("output file name", None, Some("out"),
+                    &parameters.out_file_parameter),
+        Switch::new("entity tags", None, Some("entity-tags"),
+                    &parameters.entity_tags_parameter),
+        Switch::new("parent group path, / for root level", None, Some("parent-group"),
+                    &parameters.parent_group_parameter),
+        Switch::new("password max age in days, 0 removes it", None, Some("max-age"),
+                    &parameters.max_age_parameter),
+        Switch::new("password expiry in days from now, 0 removes it", None, Some("expires-in"),
+                    &parameters.expires_in_parameter),
+        Switch::new("number of days for expiring passwords report", None, Some("days"),
+                    &parameters.days_parameter),
+        Switch::new("entity history version, 1 is the previous version", None, Some("entity-version"),
+                    &parameters.entity_version_parameter),
+        Switch::new("number of kept entity versions, 0 resets entity history length to database default", None,
+                    Some("history-length"), &parameters.history_length_parameter),
+        Switch::new("note file names, - for stdin", None, Some("note-files"),
+                    &parameters.note_files_parameter),
+        Switch::new("entity template name", None, Some("entity-template"),
+                    &parameters.entity_template_parameter),
+        Switch::new("template fields in format name:type[:secret][:required]", None, Some("template-fields"),
+                    &parameters.template_fields_parameter),
+        Switch::new("entity property layouts in format name:type[:secret];...", None, Some("property-layout"),
+                    &parameters.property_layout_parameter),
+        Switch::new("device label for audit log records", None, Some("device"),
+                    &parameters.device_label_parameter),
+        Switch::new("max number of audit log records, 0 disables audit log", None, Some("audit-size"),
+                    &parameters.audit_size_parameter),
+        Switch::new("log password and secret property reveals", None, Some("audit-reveals"),
+                    &parameters.audit_reveals_parameter),
+        Switch::new("new names", None, Some("new-names"),
+                    &parameters.new_names_parameter),
+        Switch::new("user that replaces removed users", None, Some("replacement-user"),
+                    &parameters.replacement_user_parameter),
+        Switch::new("target group path", None, Some("target-group"),
+                    &parameters.target_group_parameter),
+        Switch::new("rewrite entities history instead of adding new versions", None, Some("rewrite-history"),
+                    &parameters.rewrite_history_parameter),
+        Switch::new("user info in format email:value;display_name:value;field_name:value", None, Some("user-info"),
+                    &parameters.user_info_parameter),
+        Switch::new("entities sort key: name, created, modified or group, - prefix for descending order", None, Some("sort"),
+                    &parameters.sort_parameter),
+        Switch::new("number of entities to skip", None, Some("offset"),
+                    &parameters.offset_parameter),
+        Switch::new("max number of entities to show, 0 for no limit", None, Some("limit"),
+                    &parameters.limit_parameter),
+        Switch::new("search query, for example: amazon url:aws user:john prop:pin=1234 created>=2024-01-01 OR NOT tag:work", None, Some("query"),
+                    &parameters.query_parameter)
     ];
     let mut arguments = Arguments::new("pman_console", &switches, None);
     if let Err(e) = arguments.build(args().skip(1).collect()) {
@@ -238,6 +451,8 @@ fn main() -> Result<(), Error> {
                     parameters.memory_parameter.get_value(), salt)
     } else if key_create_parameter.get_value() {
         create_key_file(parameters)
+    } else if member_key_create_parameter.get_value() {
+        create_member_key_files(parameters.member_key_parameter.get_value())
     } else if qs3_test(qs3_path_parameter.get_value(),
                        qs3_key_parameter.get_value(), parameters.database_key_file_parameter.get_value())? ||
         generate_password_command(generate_password_parameter.get_value())? {
@@ -254,9 +469,16 @@ fn execute_database_operations(parameters: Parameters) -> Result<(), Error> {
         return Ok(());
     }
     let database_type = get_database_type(&file_name)?;
+    let is_pman_database = database_type == PasswordDatabaseType::Pman;
     let verbose = parameters.verbose_parameter.get_value();
-    let (password_hash, password2_hash) =
-        if parameters.key_file_parameter.get_value().is_empty() {
+    let key_slot = get_unlock_key_slot(&parameters)?;
+    if key_slot.is_some() && parameters.create_parameter.get_value() {
+        println!("recovery key, key file or member key cannot be used in create mode");
+        return Ok(());
+    }
+    let (password_hash, password2_hash) = if key_slot.is_some() {
+        (Vec::new(), None)
+    } else if parameters.key_file_parameter.get_value().is_empty() {
         build_password_hashes(&database_type, &parameters)?
     } else {
         build_password_hashes_from_key_file(parameters.key_file_parameter.get_value())?
@@ -267,20 +489,35 @@ fn execute_database_operations(parameters: Parameters) -> Result<(), Error> {
     };
     lib_init();
     let database = if parameters.create_parameter.get_value() {
-        create(database_type, password_hash, password2_hash, key_file_contents, file_name.clone())
-            .map_err(|e| Error::new(ErrorKind::Other, e.to_string()))?
+        let id = create(database_type, password_hash, password2_hash, key_file_contents, file_name.clone())
+            .map_err(|e| Error::new(ErrorKind::Other, e.to_string()))?;
+        if is_pman_database {
+            set_encryption1(id, &parameters)?;
+            set_encryption2(id, &parameters)?;
+        }
+        id
     } else {
         let mut f = File::open(file_name.clone())?;
         let mut data = Vec::new();
         f.read_to_end(&mut data)?;
         let id = prepare(data, file_name.clone())
             .map_err(|e| Error::new(ErrorKind::Other, e.to_string()))?;
-        pre_open(id, password_hash, password2_hash, key_file_contents)
-            .map_err(|e| Error::new(ErrorKind::Other, e.to_string()))?;
+        if let Some((slot_type, secret)) = key_slot {
+            pre_open_with_key_slot(id, slot_type, secret, key_file_contents)
+                .map_err(|e| Error::new(ErrorKind::Other, e.to_string()))?;
+        } else {
+            pre_open(id, password_hash, password2_hash, key_file_contents)
+                .map_err(|e| Error::new(ErrorKind::Other, e.to_string()))?;
+        }
         open(id)
             .map_err(|e| Error::new(ErrorKind::Other, e.to_string()))?;
         id
     };
+    let device_label = parameters.device_label_parameter.get_value();
+    if is_pman_database && !device_label.is_empty() {
+        set_device_label(database, device_label)
+            .map_err(|e| Error::new(ErrorKind::Other, e.to_string()))?;
+    }
     let mut save_database = false;
     let database_actions = build_database_actions();
     for action in parameters.actions_parameter.get_value().split(',') {
@@ -293,16 +530,55 @@ fn execute_database_operations(parameters: Parameters) -> Result<(), Error> {
             return Ok(());
         }
     }
+    // read-only actions may add reveal records to the audit log
+    if is_pman_database && !save_database {
+        save_database = is_audit_pending(database)
+            .map_err(|e| Error::new(ErrorKind::Other, e.to_string()))?;
+    }
     if save_database {
+        let upgraded = if is_pman_database {
+            let version = get_database_version(database)
+                .map_err(|e| Error::new(ErrorKind::Other, e.to_string()))?;
+            version.stored_version != version.current_version
+        } else { false };
+        // all the files are backed up before upgraded data files are uploaded,
+        // backups are removed after the new main file is written
+        let backup_file_name = if upgraded { Some(create_backup(&file_name)?) } else { None };
         let contents = save(database)
-            .map_err(|e| Error::new(ErrorKind::Other, e.to_string()))?;
+            .map_err(|e| Error::new(ErrorKind::Other, match &backup_file_name {
+                Some(name) => format!("{}, old main file is kept in {}", e, name),
+                None => e.to_string()
+            }))?;
         if let Some(data) = contents {
-            create_file(&file_name, data)?;
+            if let Some(name) = backup_file_name {
+                replace_file_with_backup(&file_name, data, &name)?;
+                remove_data_file_backups(database)
+                    .map_err(|e| Error::new(ErrorKind::Other, e.to_string()))?;
+                fs::remove_file(name)?;
+            } else {
+                create_file(&file_name, data)?;
+            }
         }
     }
     Ok(())
 }
 
+fn get_unlock_key_slot(parameters: &Parameters) -> Result<Option<(KeySlotType, Vec<u8>)>, Error> {
+    let recovery_key = parameters.recovery_key_parameter.get_value();
+    if !recovery_key.is_empty() {
+        return Ok(Some((KeySlotType::RecoveryKey, hex_to_bytes(recovery_key)?)));
+    }
+    let key_file_name = parameters.unlock_key_file_parameter.get_value();
+    if !key_file_name.is_empty() {
+        return Ok(Some((KeySlotType::KeyFile, load_file(key_file_name)?)));
+    }
+    let member_key_file_name = parameters.member_key_parameter.get_value();
+    if !member_key_file_name.is_empty() {
+        return Ok(Some((KeySlotType::Member, load_file(member_key_file_name)?)));
+    }
+    Ok(None)
+}
+
 fn show_actions_help(actions: &HashMap<&str, DatabaseAction>) {
     println!("Database actions:");
     let mut sorted: Vec<(&str, &DatabaseAction)> = actions.into_iter().map(|(k, v)|(*k, v)).collect();
@@ -325,38 +601,72 @@ fn build_database_actions() -> HashMap<&'static str, DatabaseAction> {
             handler: |_database, _parameters|Ok(false)}),
         ("save", DatabaseAction{description: "save action", dependencies: Vec::new(),
             handler: |_database, _parameters|Ok(true)}),
-        ("add_groups", DatabaseAction{description: "add database group", dependencies: vec!["group_names"],
+        ("add_groups", DatabaseAction{description: "add database groups, group names are paths like Work/Cloud/AWS", dependencies: vec!["group_names"],
             handler: |database, parameters|add_groups(database, parameters.group_names_parameter.get_value())}),
+        ("move_groups", DatabaseAction{description: "move groups with their subgroups to the parent group",
+            dependencies: vec!["group_names", "parent_group"],
+            handler: |database, parameters|move_groups(database, parameters.group_names_parameter.get_value(),
+                                                       parameters.parent_group_parameter.get_value())}),
         ("get_groups", DatabaseAction{description: "get database groups", dependencies: Vec::new(),
             handler: |database, _parameters|select_groups(database)}),
         ("get_users", DatabaseAction{description: "get database users", dependencies: Vec::new(),
             handler: |database, _parameters|select_users(database)}),
         ("add_users", DatabaseAction{description: "add database users", dependencies: vec!["user_names"],
             handler: |database, parameters|add_users(database, parameters.user_names_parameter.get_value())}),
+        ("rename_users", DatabaseAction{description: "rename database users",
+            dependencies: vec!["user_names", "new_names"],
+            handler: |database, parameters|rename_users(database, parameters.user_names_parameter.get_value(),
+                                                        parameters.new_names_parameter.get_value())}),
+        ("update_users", DatabaseAction{description: "set users email, display name and custom fields",
+            dependencies: vec!["user_names", "user_info"],
+            handler: |database, parameters|update_users(database, parameters.user_names_parameter.get_value(),
+                                                        parameters.user_info_parameter.get_value())}),
+        ("replace_users", DatabaseAction{description: "remove users and assign their entities to the replacement user",
+            dependencies: vec!["user_names", "replacement_user", "rewrite_history"],
+            handler: |database, parameters|replace_users(database, parameters.user_names_parameter.get_value(),
+                                                         parameters.replacement_user_parameter.get_value(),
+                                                         parameters.rewrite_history_parameter.get_value())}),
+        ("merge_groups", DatabaseAction{description: "move entities and subgroups of groups to the target group and remove the groups",
+            dependencies: vec!["group_names", "target_group", "rewrite_history"],
+            handler: |database, parameters|merge_groups_into(database, parameters.group_names_parameter.get_value(),
+                                                             parameters.target_group_parameter.get_value(),
+                                                             parameters.rewrite_history_parameter.get_value())}),
         ("add_entities", DatabaseAction{description: "add database entities",
             dependencies: vec!["entity_names", "entity_groups", "entity_users", "entity_passwords", "entity_urls", "entity_properties"],
             handler: |database, parameters|add_entities(database, parameters)}),
         ("get_entities", DatabaseAction{description: "get database entities", dependencies: vec!["group_names"],
             handler: |database, parameters|select_entities(database, parameters.group_names_parameter.get_value())}),
+        ("list", DatabaseAction{description: "list entities page, optionally filtered by group name",
+            dependencies: vec!["group_names", "sort", "offset", "limit"],
+            handler: |database, parameters|show_entities_page(database, parameters)}),
         ("remove_entities", DatabaseAction{description: "remove database entities", dependencies: vec!["entity_names"],
             handler: |database, parameters|remove_entities(database, parameters)}),
         ("modify_entities", DatabaseAction{description: "modify database entities",
             dependencies: vec!["entity_names", "entity_passwords", "entity_urls", "entity_properties"],
             handler: |database, parameters|modify_entities(database, parameters)}),
+        ("share_export", DatabaseAction{description: "export groups with their entities and users to a new database",
+            dependencies: vec!["group_names", "out", "new_pw", "new_pw2", "qs3_path1", "qs3_key1", "qs3_path2", "qs3_key2"],
+            handler: |database, parameters|share_export(database, parameters)}),
         ("set_hash1", DatabaseAction{description: "set first password hashing algorithm",
             dependencies: vec!["iterations", "memory", "parallelism"],
             handler: |database, parameters|set_hash1(database, parameters)}),
         ("set_hash2", DatabaseAction{description: "set second password hashing algorithm",
             dependencies: vec!["iterations2", "memory2", "parallelism2"],
             handler: |database, parameters|set_hash2(database, parameters)}),
+        ("set_encryption1", DatabaseAction{description: "set names & passwords data encryption algorithm (cascade)",
+            dependencies: vec!["e"],
+            handler: |database, parameters|set_encryption1(database, parameters)}),
+        ("set_encryption2", DatabaseAction{description: "set passwords data encryption algorithm (cascade)",
+            dependencies: vec!["e2"],
+            handler: |database, parameters|set_encryption2(database, parameters)}),
         ("file1_location", DatabaseAction{description: "set file1 location",
             dependencies: vec!["qs3_path1", "qs3_key1"],
             handler: |database, parameters|set_file1_location(database, parameters)}),
         ("file2_location", DatabaseAction{description: "set file2 location",
             dependencies: vec!["qs3_path2", "qs3_key2"],
             handler: |database, parameters|set_file2_location(database, parameters)}),
-        ("search", DatabaseAction{description: "search by entity partial name",
-            dependencies: vec!["entity_names"],
+        ("search", DatabaseAction{description: "search by search query or entity partial names",
+            dependencies: vec!["query", "entity_names"],
             handler: |database, parameters|search_entities(database, parameters)}),
         ("show", DatabaseAction{description: "show database entities",
             dependencies: vec!["entity_names"],
@@ -364,6 +674,124 @@ fn build_database_actions() -> HashMap<&'static str, DatabaseAction> {
         ("show_properties", DatabaseAction{description: "show database properties values",
             dependencies: vec!["entity_names in format name@property_name"],
             handler: |database, parameters|show_entity_properties(database, parameters)}),
+        ("get_key_slots", DatabaseAction{description: "get database key slots", dependencies: Vec::new(),
+            handler: |database, _parameters|select_key_slots(database)}),
+        ("add_recovery_key", DatabaseAction{description: "add recovery key slot", dependencies: Vec::new(),
+            handler: |database, _parameters|create_recovery_key(database)}),
+        ("add_key_file_slot", DatabaseAction{description: "add key file slot", dependencies: vec!["slot_key_file"],
+            handler: |database, parameters|create_key_file_slot(database, parameters.slot_key_file_parameter.get_value())}),
+        ("add_members", DatabaseAction{description: "add database members that open the database with own private keys",
+            dependencies: vec!["member_names", "member_public_keys"],
+            handler: |database, parameters|add_members(database, parameters.member_names_parameter.get_value(),
+                                                       parameters.member_public_keys_parameter.get_value())}),
+        ("get_members", DatabaseAction{description: "get database members", dependencies: Vec::new(),
+            handler: |database, _parameters|select_members(database)}),
+        ("revoke_members", DatabaseAction{description: "revoke database members, master keys are rotated, removed recovery key, key file and passwords slots are reported",
+            dependencies: vec!["member_names"],
+            handler: |database, parameters|revoke_members(database, parameters.member_names_parameter.get_value())}),
+        ("remove_key_slots", DatabaseAction{description: "remove key slots", dependencies: vec!["key_slot_ids"],
+            handler: |database, parameters|remove_key_slots(database, parameters.key_slot_ids_parameter.get_value())}),
+        ("change_passwords", DatabaseAction{description: "change database passwords",
+            dependencies: vec!["new_pw", "new_pw2"],
+            handler: |database, parameters|change_passwords(database, parameters)}),
+        ("set_otp", DatabaseAction{description: "set entities otp",
+            dependencies: vec!["entity_names", "entity_otp"],
+            handler: |database, parameters|set_entities_otp(database, parameters)}),
+        ("set_urls", DatabaseAction{description: "set entities urls, urls format: mode:url;mode:url, modes: exact, host, domain, prefix, regex",
+            dependencies: vec!["entity_names", "entity_urls"],
+            handler: |database, parameters|set_entities_urls(database, parameters)}),
+        ("find_url", DatabaseAction{description: "show entities with urls matching the given urls, best matches first",
+            dependencies: vec!["entity_urls"],
+            handler: |database, parameters|show_entities_by_url(database, parameters)}),
+        ("otp", DatabaseAction{description: "show entities current otp codes, hotp counters are advanced",
+            dependencies: vec!["entity_names"],
+            handler: |database, parameters|show_entities_otp(database, parameters)}),
+        ("ssh_agent", DatabaseAction{description: "run ssh agent with keys from entities properties, optionally filtered by group paths",
+            dependencies: vec!["ssh_agent_socket", "ssh_key_property"],
+            handler: |database, parameters|run_ssh_agent(database, parameters)}),
+        ("fsck", DatabaseAction{description: "check database integrity, --repair removes orphaned records and rebuilds index lists",
+            dependencies: vec!["repair"],
+            handler: |database, parameters|check_database_integrity(database, parameters.repair_parameter.get_value())}),
+        ("add_tags", DatabaseAction{description: "add tags to entities",
+            dependencies: vec!["entity_names", "entity_tags"],
+            handler: |database, parameters|add_entities_tags(database, parameters)}),
+        ("remove_tags", DatabaseAction{description: "remove tags from entities",
+            dependencies: vec!["entity_names", "entity_tags"],
+            handler: |database, parameters|remove_entities_tags(database, parameters)}),
+        ("get_by_tags", DatabaseAction{description: "show entities that have all the given tags",
+            dependencies: vec!["entity_tags"],
+            handler: |database, parameters|show_entities_by_tags(database, parameters)}),
+        ("upgrade", DatabaseAction{description: "upgrade database to the latest format version, old files are kept in .bak files until the new ones are saved",
+            dependencies: Vec::new(),
+            handler: |database, _parameters|upgrade_database(database)}),
+        ("attach", DatabaseAction{description: "attach files to entities",
+            dependencies: vec!["entity_names", "attach"],
+            handler: |database, parameters|attach_files(database, parameters)}),
+        ("attachments", DatabaseAction{description: "show entities attachment names",
+            dependencies: vec!["entity_names"],
+            handler: |database, parameters|show_attachments(database, parameters)}),
+        ("extract_attachment", DatabaseAction{description: "save entity attachment to file, entity name should be in format name@attachment_name",
+            dependencies: vec!["entity_names", "out"],
+            handler: |database, parameters|extract_attachment(database, parameters)}),
+        ("remove_attachments", DatabaseAction{description: "remove entities attachments, entity names should be in format name@attachment_name",
+            dependencies: vec!["entity_names"],
+            handler: |database, parameters|remove_attachments(database, parameters)}),
+        ("set_expiry", DatabaseAction{description: "set entities password expiry date and max age",
+            dependencies: vec!["entity_names", "expires_in", "max_age"],
+            handler: |database, parameters|set_entities_expiry(database, parameters)}),
+        ("set_group_max_age", DatabaseAction{description: "set password max age policy for groups and their subgroups",
+            dependencies: vec!["group_names", "max_age"],
+            handler: |database, parameters|set_groups_max_age(database, parameters.group_names_parameter.get_value(),
+                                                              parameters.max_age_parameter.get_value())}),
+        ("set_property_layout", DatabaseAction{description: "set entity properties order, types and secret flags",
+            dependencies: vec!["entity_names", "property_layout"],
+            handler: |database, parameters|set_entities_property_layout(database, parameters)}),
+        ("report_expiring", DatabaseAction{description: "show entities with expired passwords or passwords that expire within given number of days",
+            dependencies: vec!["days"],
+            handler: |database, parameters|show_expiring_entities(database, parameters.days_parameter.get_value())}),
+        ("restore_version", DatabaseAction{description: "restore entities to the given history version",
+            dependencies: vec!["entity_names", "entity_version"],
+            handler: |database, parameters|restore_entities_version(database, parameters)}),
+        ("set_history_length", DatabaseAction{description: "set database history length, existing histories are pruned",
+            dependencies: vec!["history_length"],
+            handler: |database, parameters|set_database_history_length(database, parameters.history_length_parameter.get_value())}),
+        ("set_entity_history_length", DatabaseAction{description: "set history length for entities",
+            dependencies: vec!["entity_names", "history_length"],
+            handler: |database, parameters|set_entities_history_length(database, parameters)}),
+        ("purge_history", DatabaseAction{description: "remove all previous versions of entities",
+            dependencies: vec!["entity_names"],
+            handler: |database, parameters|purge_entities_history(database, parameters)}),
+        ("trash_list", DatabaseAction{description: "show removed entities", dependencies: Vec::new(),
+            handler: |database, _parameters|show_trash(database)}),
+        ("restore", DatabaseAction{description: "restore removed entities into their original groups",
+            dependencies: vec!["entity_names"],
+            handler: |database, parameters|restore_entities(database, parameters)}),
+        ("empty_trash", DatabaseAction{description: "destroy all removed entities", dependencies: Vec::new(),
+            handler: |database, _parameters|clear_trash(database)}),
+        ("set_trash_retention", DatabaseAction{description: "set number of days removed entities are kept in trash, 0 keeps them until empty_trash",
+            dependencies: vec!["days"],
+            handler: |database, parameters|set_trash_retention_days(database, parameters.days_parameter.get_value())}),
+        ("audit", DatabaseAction{description: "show audit log records for the given number of days, 0 shows all records",
+            dependencies: vec!["days"],
+            handler: |database, parameters|show_audit_log(database, parameters.days_parameter.get_value())}),
+        ("set_audit", DatabaseAction{description: "set audit log size and reveals logging",
+            dependencies: vec!["audit_size", "audit_reveals"],
+            handler: |database, parameters|set_audit(database, parameters.audit_size_parameter.get_value(),
+                                                     parameters.audit_reveals_parameter.get_value())}),
+        ("add_notes", DatabaseAction{description: "add secure notes",
+            dependencies: vec!["entity_names", "entity_groups", "note_files"],
+            handler: |database, parameters|add_notes(database, parameters)}),
+        ("edit_notes", DatabaseAction{description: "replace secure notes text",
+            dependencies: vec!["entity_names", "note_files"],
+            handler: |database, parameters|edit_notes(database, parameters)}),
+        ("get_templates", DatabaseAction{description: "show entity templates", dependencies: Vec::new(),
+            handler: |database, _parameters|select_templates(database)}),
+        ("add_template", DatabaseAction{description: "add entity template",
+            dependencies: vec!["entity_template", "template_fields"],
+            handler: |database, parameters|create_template(database, parameters.entity_template_parameter.get_value(),
+                                                           parameters.template_fields_parameter.get_value())}),
+        ("remove_templates", DatabaseAction{description: "remove entity templates", dependencies: vec!["entity_template"],
+            handler: |database, parameters|remove_templates(database, parameters.entity_template_parameter.get_value())}),
     ]);
     database_actions
 }
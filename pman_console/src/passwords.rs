@@ -74,6 +74,12 @@ pub fn build_password_hashes(database_type: &PasswordDatabaseType, parameters: &
     Ok((password_hash, password2_hash))
 }
 
+pub fn build_new_password_hashes(parameters: &Parameters) -> Result<(Vec<u8>, Vec<u8>), Error> {
+    let password = get_password("new password: ", parameters.new_password_parameter.get_value())?;
+    let password2 = get_password("new password2: ", parameters.new_password2_parameter.get_value())?;
+    Ok((create_hash(password), create_hash(password2)))
+}
+
 pub fn build_password_hashes_from_key_file(file_name: String) -> Result<(Vec<u8>, Option<Vec<u8>>), Error> {
     let data = load_file(file_name)?;
     build_password_hashes_from_data(data, "".to_string())
@@ -0,0 +1,353 @@
+use std::collections::HashSet;
+use std::io::{Error, ErrorKind, Read, Write};
+use std::sync::{Arc, Mutex};
+use pman_lib::{get_entities, get_groups};
+use pman_lib::pman::database_entity::ENTITY_VERSION_LATEST;
+use rsa::signature::{SignatureEncoding, Signer};
+use sha2::{Sha256, Sha512};
+use ssh_key::{Algorithm, HashAlg, Mpint, PrivateKey, Signature};
+use ssh_key::private::{KeypairData, RsaKeypair};
+use crate::groups_users_actions::build_group_paths;
+use crate::Parameters;
+use crate::utils::parse_string_array;
+
+/*
+
+ssh-agent protocol (draft-miller-ssh-agent) subset:
+
+message: uint32 length, byte type, contents
+SSH_AGENTC_REQUEST_IDENTITIES -> SSH_AGENT_IDENTITIES_ANSWER: uint32 nkeys, (string key blob, string comment) * nkeys
+SSH_AGENTC_SIGN_REQUEST: string key blob, string data, uint32 flags -> SSH_AGENT_SIGN_RESPONSE: string signature
+all other requests -> SSH_AGENT_FAILURE
+
+*/
+
+const SSH_AGENT_FAILURE: u8 = 5;
+const SSH_AGENTC_REQUEST_IDENTITIES: u8 = 11;
+const SSH_AGENT_IDENTITIES_ANSWER: u8 = 12;
+const SSH_AGENTC_SIGN_REQUEST: u8 = 13;
+const SSH_AGENT_SIGN_RESPONSE: u8 = 14;
+
+const SSH_AGENT_RSA_SHA2_256: u32 = 2;
+const SSH_AGENT_RSA_SHA2_512: u32 = 4;
+
+const MAX_MESSAGE_LENGTH: usize = 256 * 1024;
+
+// connections are served concurrently, confirmation prompts are asked one at a time
+static CONFIRM_LOCK: Mutex<()> = Mutex::new(());
+
+struct AgentKey {
+    name: String,
+    public_key_blob: Vec<u8>,
+    private_key: PrivateKey
+}
+
+// keys are kept in memory only, the socket is removed when the agent is stopped with SIGINT, SIGTERM or SIGHUP
+pub fn run_ssh_agent(database: u64, parameters: &Parameters) -> Result<bool, Error> {
+    let socket_path = parameters.ssh_agent_socket_parameter.get_value();
+    if socket_path.is_empty() {
+        return Err(Error::new(ErrorKind::InvalidInput, "ssh agent socket path expected"));
+    }
+    let keys = load_keys(database, parameters)?;
+    if keys.is_empty() {
+        return Err(Error::new(ErrorKind::NotFound, "no ssh keys found"));
+    }
+    for key in &keys {
+        println!("Loaded key {}", key.name);
+    }
+    serve(socket_path, keys, parameters.ssh_agent_confirm_parameter.get_value())?;
+    Ok(false)
+}
+
+fn load_keys(database: u64, parameters: &Parameters) -> Result<Vec<AgentKey>, Error> {
+    let property_name = parameters.ssh_key_property_parameter.get_value();
+    // group names are not unique in nested groups, so groups are selected by path
+    let selected_paths: HashSet<String> = if parameters.group_names_parameter.get_value().is_empty() {
+        HashSet::new()
+    } else {
+        parse_string_array(parameters.group_names_parameter.get_value(), "group paths expected", None)?
+            .into_iter()
+            .collect()
+    };
+    let groups = get_groups(database)
+        .map_err(|e| Error::new(ErrorKind::Other, e.to_string()))?;
+    let group_paths = build_group_paths(&groups);
+    let mut keys = Vec::new();
+    for group in groups {
+        if !selected_paths.is_empty() && !selected_paths.contains(group_paths.get(&group.id).unwrap()) {
+            continue;
+        }
+        for (_, entity) in get_entities(database, group.id)
+            .map_err(|e| Error::new(ErrorKind::Other, e.to_string()))? {
+            let property_names = entity.get_property_names(ENTITY_VERSION_LATEST)
+                .map_err(|e| Error::new(ErrorKind::Other, e.to_string()))?;
+            if let Some(property) = property_names.iter().find(|p|p.name == property_name) {
+                let name = entity.get_name()
+                    .map_err(|e| Error::new(ErrorKind::Other, e.to_string()))?;
+                let value = entity.get_property_value(ENTITY_VERSION_LATEST, property.id)
+                    .map_err(|e| Error::new(ErrorKind::Other, e.to_string()))?;
+                let private_key = PrivateKey::from_openssh(value)
+                    .map_err(|e| Error::new(ErrorKind::InvalidData,
+                                            format!("entity {}: {}", name, e.to_string())))?;
+                if private_key.is_encrypted() {
+                    return Err(Error::new(ErrorKind::Unsupported,
+                                          format!("entity {}: encrypted ssh keys are not supported", name)));
+                }
+                let public_key_blob = private_key.public_key().to_bytes()
+                    .map_err(|e| Error::new(ErrorKind::InvalidData, e.to_string()))?;
+                keys.push(AgentKey{name, public_key_blob, private_key});
+            }
+        }
+    }
+    Ok(keys)
+}
+
+#[cfg(unix)]
+static SOCKET_PATH: std::sync::OnceLock<std::ffi::CString> = std::sync::OnceLock::new();
+
+// accept is restarted after signals, so the handler removes the socket and exits,
+// only async-signal-safe functions can be called here
+#[cfg(unix)]
+extern "C" fn handle_stop_signal(signal: libc::c_int) {
+    if let Some(path) = SOCKET_PATH.get() {
+        unsafe { libc::unlink(path.as_ptr()) };
+    }
+    unsafe { libc::_exit(128 + signal) };
+}
+
+#[cfg(unix)]
+fn install_stop_signal_handlers(socket_path: &str) -> Result<(), Error> {
+    let path = std::ffi::CString::new(socket_path)
+        .map_err(|_e| Error::new(ErrorKind::InvalidInput, "invalid ssh agent socket path"))?;
+    let _ = SOCKET_PATH.set(path);
+    for signal in [libc::SIGINT, libc::SIGTERM, libc::SIGHUP] {
+        let mut action: libc::sigaction = unsafe { std::mem::zeroed() };
+        action.sa_sigaction = handle_stop_signal as *const () as libc::sighandler_t;
+        if unsafe { libc::sigaction(signal, &action, std::ptr::null_mut()) } != 0 {
+            return Err(Error::last_os_error());
+        }
+    }
+    Ok(())
+}
+
+#[cfg(unix)]
+fn serve(socket_path: String, keys: Vec<AgentKey>, confirm: bool) -> Result<(), Error> {
+    use std::os::unix::fs::FileTypeExt;
+    use std::os::unix::net::UnixListener;
+
+    match std::fs::symlink_metadata(&socket_path) {
+        Ok(metadata) => {
+            if !metadata.file_type().is_socket() {
+                return Err(Error::new(ErrorKind::AlreadyExists,
+                                      format!("{} exists and is not a socket", socket_path)));
+            }
+            std::fs::remove_file(&socket_path)?;
+        },
+        Err(e) => {
+            if e.kind() != ErrorKind::NotFound {
+                return Err(e);
+            }
+        }
+    }
+    // the socket is created with 0600 permissions
+    let old_mask = unsafe { libc::umask(0o177) };
+    let listener = UnixListener::bind(&socket_path);
+    unsafe { libc::umask(old_mask) };
+    let listener = listener?;
+    if let Err(e) = install_stop_signal_handlers(&socket_path) {
+        let _ = std::fs::remove_file(&socket_path);
+        return Err(e);
+    }
+    println!("SSH_AUTH_SOCK={}; export SSH_AUTH_SOCK;", socket_path);
+    // each connection is handled in its own thread, so a client keeping its connection open
+    // (for example ssh session with agent forwarding) does not block other clients
+    let keys = Arc::new(keys);
+    for stream in listener.incoming() {
+        match stream {
+            Ok(mut s) => {
+                let connection_keys = keys.clone();
+                std::thread::spawn(move || {
+                    if let Err(e) = handle_connection(&mut s, &connection_keys, confirm) {
+                        if e.kind() != ErrorKind::UnexpectedEof {
+                            println!("ssh agent connection error: {}", e);
+                        }
+                    }
+                });
+            },
+            Err(e) => {
+                let _ = std::fs::remove_file(&socket_path);
+                return Err(e);
+            }
+        }
+    }
+    std::fs::remove_file(&socket_path)
+}
+
+#[cfg(not(unix))]
+fn serve(_socket_path: String, _keys: Vec<AgentKey>, _confirm: bool) -> Result<(), Error> {
+    Err(Error::new(ErrorKind::Unsupported, "ssh agent requires unix sockets"))
+}
+
+fn handle_connection<T: Read + Write>(stream: &mut T, keys: &Vec<AgentKey>, confirm: bool) -> Result<(), Error> {
+    loop {
+        let mut buffer32 = [0u8; 4];
+        stream.read_exact(&mut buffer32)?;
+        let length = u32::from_be_bytes(buffer32) as usize;
+        if length == 0 || length > MAX_MESSAGE_LENGTH {
+            return Err(Error::new(ErrorKind::InvalidData, "invalid ssh agent message length"));
+        }
+        let mut message = vec![0u8; length];
+        stream.read_exact(&mut message)?;
+        let response = handle_message(&message, keys, confirm)
+            .unwrap_or_else(|_e| vec![SSH_AGENT_FAILURE]);
+        stream.write_all(&(response.len() as u32).to_be_bytes())?;
+        stream.write_all(&response)?;
+    }
+}
+
+fn handle_message(message: &Vec<u8>, keys: &Vec<AgentKey>, confirm: bool) -> Result<Vec<u8>, Error> {
+    match message[0] {
+        SSH_AGENTC_REQUEST_IDENTITIES => {
+            let mut response = vec![SSH_AGENT_IDENTITIES_ANSWER];
+            response.extend_from_slice(&(keys.len() as u32).to_be_bytes());
+            for key in keys {
+                write_string(&mut response, &key.public_key_blob);
+                write_string(&mut response, key.name.as_bytes());
+            }
+            Ok(response)
+        },
+        SSH_AGENTC_SIGN_REQUEST => {
+            let mut offset = 1;
+            let key_blob = read_string(message, &mut offset)?;
+            let data = read_string(message, &mut offset)?;
+            let flags = read_u32(message, &mut offset)?;
+            let key = keys.iter()
+                .find(|k| k.public_key_blob == key_blob)
+                .ok_or(Error::new(ErrorKind::NotFound, "key not found"))?;
+            if confirm && !confirm_key_usage(&key.name)? {
+                return Err(Error::new(ErrorKind::PermissionDenied, "key usage is not confirmed"));
+            }
+            let signature = sign(&key.private_key, &data, flags)?;
+            let signature_bytes: Vec<u8> = signature.try_into()
+                .map_err(|e: ssh_key::Error| Error::new(ErrorKind::Other, e.to_string()))?;
+            let mut response = vec![SSH_AGENT_SIGN_RESPONSE];
+            write_string(&mut response, &signature_bytes);
+            Ok(response)
+        },
+        _ => Err(Error::new(ErrorKind::Unsupported, "unsupported ssh agent request"))
+    }
+}
+
+fn sign(private_key: &PrivateKey, data: &Vec<u8>, flags: u32) -> Result<Signature, Error> {
+    if let KeypairData::Rsa(keypair) = private_key.key_data() {
+        return sign_rsa(keypair, data, flags);
+    }
+    private_key.try_sign(data)
+        .map_err(|e| Error::new(ErrorKind::Other, e.to_string()))
+}
+
+// ssh_key rsa signing always uses sha512 and fails to convert some keys, so rsa signatures are built here
+fn sign_rsa(keypair: &RsaKeypair, data: &Vec<u8>, flags: u32) -> Result<Signature, Error> {
+    let private_key = rsa::RsaPrivateKey::from_components(
+        build_rsa_biguint(&keypair.public.n)?,
+        build_rsa_biguint(&keypair.public.e)?,
+        build_rsa_biguint(&keypair.private.d)?,
+        vec![build_rsa_biguint(&keypair.private.p)?, build_rsa_biguint(&keypair.private.q)?]
+    ).map_err(|e| Error::new(ErrorKind::InvalidData, e.to_string()))?;
+    let (hash, signature) = if flags & SSH_AGENT_RSA_SHA2_512 != 0 {
+        (HashAlg::Sha512, rsa::pkcs1v15::SigningKey::<Sha512>::new(private_key).try_sign(data))
+    } else if flags & SSH_AGENT_RSA_SHA2_256 != 0 {
+        (HashAlg::Sha256, rsa::pkcs1v15::SigningKey::<Sha256>::new(private_key).try_sign(data))
+    } else {
+        return Err(Error::new(ErrorKind::Unsupported, "ssh-rsa signatures are not supported"));
+    };
+    let signature = signature.map_err(|e| Error::new(ErrorKind::Other, e.to_string()))?;
+    Signature::new(Algorithm::Rsa { hash: Some(hash) }, signature.to_vec())
+        .map_err(|e| Error::new(ErrorKind::Other, e.to_string()))
+}
+
+fn build_rsa_biguint(value: &Mpint) -> Result<rsa::BigUint, Error> {
+    let bytes = value.as_positive_bytes()
+        .ok_or(Error::new(ErrorKind::InvalidData, "invalid rsa key"))?;
+    Ok(rsa::BigUint::from_bytes_be(bytes))
+}
+
+fn confirm_key_usage(name: &String) -> Result<bool, Error> {
+    let _lock = CONFIRM_LOCK.lock().unwrap();
+    print!("Allow signing with key {}? [y/N] ", name);
+    std::io::stdout().flush()?;
+    let mut answer = String::new();
+    std::io::stdin().read_line(&mut answer)?;
+    Ok(answer.trim().eq_ignore_ascii_case("y"))
+}
+
+fn read_u32(message: &Vec<u8>, offset: &mut usize) -> Result<u32, Error> {
+    if message.len() < *offset + 4 {
+        return Err(Error::new(ErrorKind::InvalidData, "ssh agent message is too short"));
+    }
+    let mut buffer32 = [0u8; 4];
+    buffer32.copy_from_slice(&message[*offset..*offset+4]);
+    *offset += 4;
+    Ok(u32::from_be_bytes(buffer32))
+}
+
+fn read_string(message: &Vec<u8>, offset: &mut usize) -> Result<Vec<u8>, Error> {
+    let length = read_u32(message, offset)? as usize;
+    if message.len() < *offset + length {
+        return Err(Error::new(ErrorKind::InvalidData, "ssh agent message is too short"));
+    }
+    let result = message[*offset..*offset+length].to_vec();
+    *offset += length;
+    Ok(result)
+}
+
+fn write_string(output: &mut Vec<u8>, value: &[u8]) {
+    output.extend_from_slice(&(value.len() as u32).to_be_bytes());
+    output.extend_from_slice(value);
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Error;
+    use rand::rngs::OsRng;
+    use rsa::signature::Verifier;
+    use ssh_key::{Algorithm, PrivateKey, Signature};
+    use crate::ssh_agent::{AgentKey, handle_message, read_string, SSH_AGENT_FAILURE, SSH_AGENT_IDENTITIES_ANSWER,
+                           SSH_AGENT_SIGN_RESPONSE, SSH_AGENTC_REQUEST_IDENTITIES, SSH_AGENTC_SIGN_REQUEST,
+                           write_string};
+
+    #[test]
+    fn test_ssh_agent_messages() -> Result<(), Error> {
+        let private_key = PrivateKey::random(&mut OsRng, Algorithm::Ed25519).unwrap();
+        let public_key = private_key.public_key().clone();
+        let public_key_blob = public_key.to_bytes().unwrap();
+        let keys = vec![AgentKey{name: "test".to_string(), public_key_blob: public_key_blob.clone(), private_key}];
+
+        let response = handle_message(&vec![SSH_AGENTC_REQUEST_IDENTITIES], &keys, false)?;
+        assert_eq!(response[0], SSH_AGENT_IDENTITIES_ANSWER);
+        assert_eq!(response[1..5], 1u32.to_be_bytes());
+        let mut offset = 5;
+        assert_eq!(read_string(&response, &mut offset)?, public_key_blob);
+        assert_eq!(read_string(&response, &mut offset)?, b"test".to_vec());
+
+        let data = b"data to sign".to_vec();
+        let mut request = vec![SSH_AGENTC_SIGN_REQUEST];
+        write_string(&mut request, &public_key_blob);
+        write_string(&mut request, &data);
+        request.extend_from_slice(&0u32.to_be_bytes());
+        let response = handle_message(&request, &keys, false)?;
+        assert_eq!(response[0], SSH_AGENT_SIGN_RESPONSE);
+        let mut offset = 1;
+        let signature_bytes = read_string(&response, &mut offset)?;
+        let signature = Signature::try_from(signature_bytes.as_slice()).unwrap();
+        assert!(public_key.key_data().verify(&data, &signature).is_ok());
+
+        let mut request = vec![SSH_AGENTC_SIGN_REQUEST];
+        write_string(&mut request, &vec![1, 2, 3]);
+        write_string(&mut request, &data);
+        request.extend_from_slice(&0u32.to_be_bytes());
+        assert!(handle_message(&request, &keys, false).is_err());
+        assert!(handle_message(&vec![SSH_AGENT_FAILURE], &keys, false).is_err());
+        Ok(())
+    }
+}
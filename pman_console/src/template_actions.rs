@@ -0,0 +1,86 @@
+use std::collections::HashMap;
+use std::io::{Error, ErrorKind};
+use pman_lib::{add_template, get_templates, remove_template};
+use pman_lib::structs_interfaces::{FieldType, TemplateField};
+use crate::utils::parse_string_array;
+
+pub fn select_templates(database: u64) -> Result<bool, Error> {
+    for template in get_templates(database)
+        .map_err(|e| Error::new(ErrorKind::Other, e.to_string()))? {
+        println!("{}{}", template.name, if template.built_in { " (built-in)" } else { "" });
+        for field in template.fields {
+            println!("  {} {:?}{}{}", field.name, field.field_type,
+                     if field.secret { " secret" } else { "" },
+                     if field.required { " required" } else { "" });
+        }
+    }
+    Ok(false)
+}
+
+// fields format: name:type[:secret][:required], types: text,url,email,phone,date,multiline
+pub fn create_template(database: u64, name: String, fields: String) -> Result<bool, Error> {
+    if name.is_empty() {
+        return Err(Error::new(ErrorKind::InvalidInput, "template name expected"));
+    }
+    let mut template_fields = Vec::new();
+    for field in parse_string_array(fields, "template fields expected", None)? {
+        template_fields.push(parse_template_field(&field)?);
+    }
+    add_template(database, name, template_fields)
+        .map_err(|e| Error::new(ErrorKind::Other, e.to_string()))?;
+    Ok(true)
+}
+
+pub fn remove_templates(database: u64, names: String) -> Result<bool, Error> {
+    let templates = get_template_names(database)?;
+    for name in parse_string_array(names, "template names expected", None)? {
+        remove_template(database, find_template_by_name(&templates, &name)?)
+            .map_err(|e| Error::new(ErrorKind::Other, e.to_string()))?;
+    }
+    Ok(true)
+}
+
+// map template id -> template name
+pub fn get_template_names(database: u64) -> Result<HashMap<u32, String>, Error> {
+    Ok(get_templates(database)
+        .map_err(|e| Error::new(ErrorKind::Other, e.to_string()))?
+        .into_iter()
+        .map(|t|(t.id, t.name))
+        .collect())
+}
+
+pub fn find_template_by_name(templates: &HashMap<u32, String>, name: &String) -> Result<u32, Error> {
+    templates.iter()
+        .find(|(_id, n)|*n == name)
+        .map(|(id, _n)|*id)
+        .ok_or(Error::new(ErrorKind::NotFound, format!("template {} not found", name)))
+}
+
+fn parse_template_field(field: &String) -> Result<TemplateField, Error> {
+    let parts: Vec<&str> = field.split(':').collect();
+    if parts.len() < 2 {
+        return Err(Error::new(ErrorKind::InvalidInput, "template field should be in format name:type[:secret][:required]"));
+    }
+    let field_type = parse_field_type(parts[1])?;
+    let mut result = TemplateField{name: parts[0].to_string(), field_type, secret: false, required: false};
+    for flag in &parts[2..] {
+        match *flag {
+            "secret" => result.secret = true,
+            "required" => result.required = true,
+            _ => return Err(Error::new(ErrorKind::InvalidInput, format!("unknown field flag {}", flag)))
+        }
+    }
+    Ok(result)
+}
+
+pub fn parse_field_type(field_type: &str) -> Result<FieldType, Error> {
+    match field_type {
+        "text" => Ok(FieldType::Text),
+        "url" => Ok(FieldType::Url),
+        "email" => Ok(FieldType::Email),
+        "phone" => Ok(FieldType::Phone),
+        "date" => Ok(FieldType::Date),
+        "multiline" => Ok(FieldType::Multiline),
+        _ => Err(Error::new(ErrorKind::InvalidInput, format!("unknown field type {}", field_type)))
+    }
+}
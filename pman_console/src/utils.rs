@@ -1,3 +1,4 @@
+use std::fs;
 use std::fs::File;
 use std::io::{Error, ErrorKind, Read, Write};
 use passterm::prompt_password_tty;
@@ -58,6 +59,22 @@ pub fn create_file(file_name: &String, data: Vec<u8>) -> Result<(), Error> {
     f.write_all(data.as_slice())
 }
 
+pub fn create_backup(file_name: &String) -> Result<String, Error> {
+    let backup_file_name = format!("{}.bak", file_name);
+    fs::copy(file_name, &backup_file_name)?;
+    Ok(backup_file_name)
+}
+
+// backup file is kept, it should be removed by the caller when all the files are saved
+pub fn replace_file_with_backup(file_name: &String, data: Vec<u8>, backup_file_name: &String) -> Result<(), Error> {
+    create_file(file_name, data.clone())?;
+    if load_file(file_name.clone())? != data {
+        return Err(Error::new(ErrorKind::InvalidData,
+                              format!("file verification failed, old file is kept in {}", backup_file_name)));
+    }
+    Ok(())
+}
+
 pub fn get_password(prompt: &str, password: String) -> Result<String, Error> {
     if !password.is_empty() {
         return Ok(password);
@@ -65,3 +82,17 @@ pub fn get_password(prompt: &str, password: String) -> Result<String, Error> {
     prompt_password_tty(Some(prompt))
         .map_err(|e|Error::new(ErrorKind::Other, e.to_string()))
 }
+
+pub fn bytes_to_hex(data: &Vec<u8>) -> String {
+    data.iter().map(|b|format!("{:02x}", b)).collect()
+}
+
+pub fn hex_to_bytes(hex: String) -> Result<Vec<u8>, Error> {
+    if hex.len() % 2 != 0 {
+        return Err(Error::new(ErrorKind::InvalidInput, "invalid hex string length"));
+    }
+    (0..hex.len()).step_by(2)
+        .map(|i|u8::from_str_radix(&hex[i..i+2], 16)
+            .map_err(|_e|Error::new(ErrorKind::InvalidInput, "invalid hex string")))
+        .collect()
+}
@@ -6,23 +6,25 @@ use aes::cipher::{BlockDecrypt, BlockEncrypt, KeyInit};
 use aes::cipher::generic_array::GenericArray;
 use chacha20::ChaCha20;
 use chacha20::cipher::{KeyIvInit, StreamCipher};
+use ctr::Ctr128BE;
 use rand::RngCore;
 use rand::rngs::OsRng;
 use crate::error_builders::{build_corrupted_data_error, build_unsupported_error};
+use crate::secure_memory::{SecureBox, wipe};
 
 pub trait CryptoProcessor {
-    fn encode(&self, data: Vec<u8>) -> Result<Vec<u8>, Error>;
+    fn encode(&self, data: &[u8]) -> Result<Vec<u8>, Error>;
     fn decode(&self, data: &Vec<u8>) -> Result<Vec<u8>, Error>;
     fn encode_bytes(&self, data: &mut [u8]) -> Result<(), Error>;
     fn decode_bytes(&self, data: &mut [u8]) -> Result<(), Error>;
 }
 
 pub struct AesProcessor {
-    cipher: Aes256
+    cipher: SecureBox<Aes256>
 }
 
 impl CryptoProcessor for AesProcessor {
-    fn encode(&self, data: Vec<u8>) -> Result<Vec<u8>, Error> {
+    fn encode(&self, data: &[u8]) -> Result<Vec<u8>, Error> {
         let mut out_data = Vec::new();
         let mut l = data.len();
         let mut idx = 0;
@@ -43,6 +45,7 @@ impl CryptoProcessor for AesProcessor {
                 l -= size;
             }
             let mut block = GenericArray::from(in_data);
+            wipe(&mut in_data);
             self.cipher.encrypt_block(&mut block);
             out_data.extend_from_slice(block.as_slice())
         }
@@ -50,7 +53,12 @@ impl CryptoProcessor for AesProcessor {
     }
 
     fn decode(&self, data: &Vec<u8>) -> Result<Vec<u8>, Error> {
-        let mut out_data = Vec::new();
+        // empty data is a valid encoding of an empty value
+        if data.len() % 16 != 0 {
+            return Err(build_corrupted_data_error("decode0"))
+        }
+        // allocated once, so that no unwiped copies of the plain text are left behind by reallocation
+        let mut out_data = Vec::with_capacity(data.len());
         let mut out_length = 0;
         for i in (0..data.len()).step_by(16) {
             let end = i + 16;
@@ -68,14 +76,17 @@ impl CryptoProcessor for AesProcessor {
                 out_length -= size;
             } else {
                 if out_length == 0 {
+                    wipe(&mut out_data);
                     return Err(build_corrupted_data_error("decode1"))
                 }
                 let size = min(out_length, 9);
                 out_data.extend_from_slice(&sl[7..7+size]);
                 out_length -= size;
             }
+            wipe(block.as_mut_slice());
         }
         if out_length != 0 {
+            wipe(&mut out_data);
             return Err(build_corrupted_data_error("decode2"))
         }
         Ok(out_data)
@@ -93,7 +104,7 @@ impl CryptoProcessor for AesProcessor {
 impl AesProcessor {
     pub fn new(key: [u8;32]) -> Arc<dyn CryptoProcessor + Send + Sync> {
         let k = GenericArray::from(key);
-        Arc::new(AesProcessor{ cipher: Aes256::new(&k) })
+        Arc::new(AesProcessor{ cipher: SecureBox::new(Aes256::new(&k)) })
     }
 }
 
@@ -101,8 +112,8 @@ pub struct NoEncryptionProcessor {
 }
 
 impl CryptoProcessor for NoEncryptionProcessor {
-    fn encode(&self, data: Vec<u8>) -> Result<Vec<u8>, Error> {
-        Ok(data)
+    fn encode(&self, data: &[u8]) -> Result<Vec<u8>, Error> {
+        Ok(data.to_vec())
     }
 
     fn decode(&self, data: &Vec<u8>) -> Result<Vec<u8>, Error> {
@@ -125,12 +136,12 @@ impl NoEncryptionProcessor {
 }
 
 pub struct ChachaProcessor {
-    key: [u8; 32],
+    key: SecureBox<[u8; 32]>,
     iv: [u8; 12]
 }
 
 impl CryptoProcessor for ChachaProcessor {
-    fn encode(&self, _data: Vec<u8>) -> Result<Vec<u8>, Error> {
+    fn encode(&self, _data: &[u8]) -> Result<Vec<u8>, Error> {
         Err(build_unsupported_error())
     }
 
@@ -139,13 +150,13 @@ impl CryptoProcessor for ChachaProcessor {
     }
 
     fn encode_bytes(&self, data: &mut [u8]) -> Result<(), Error> {
-        let mut cipher = ChaCha20::new((&self.key).into(), (&self.iv).into());
+        let mut cipher = ChaCha20::new((&*self.key).into(), (&self.iv).into());
         cipher.apply_keystream(data);
         Ok(())
     }
 
     fn decode_bytes(&self, data: &mut [u8]) -> Result<(), Error> {
-        let mut cipher = ChaCha20::new((&self.key).into(), (&self.iv).into());
+        let mut cipher = ChaCha20::new((&*self.key).into(), (&self.iv).into());
         cipher.apply_keystream(data);
         Ok(())
     }
@@ -153,7 +164,75 @@ impl CryptoProcessor for ChachaProcessor {
 
 impl ChachaProcessor {
     pub fn new(key: [u8; 32], iv: [u8; 12]) -> Arc<dyn CryptoProcessor + Send + Sync> {
-        Arc::new(ChachaProcessor{key, iv})
+        Arc::new(ChachaProcessor{key: SecureBox::new(key), iv})
+    }
+}
+
+pub struct AesCtrProcessor {
+    key: SecureBox<[u8; 32]>,
+    iv: [u8; 16]
+}
+
+impl CryptoProcessor for AesCtrProcessor {
+    fn encode(&self, _data: &[u8]) -> Result<Vec<u8>, Error> {
+        Err(build_unsupported_error())
+    }
+
+    fn decode(&self, _data: &Vec<u8>) -> Result<Vec<u8>, Error> {
+        Err(build_unsupported_error())
+    }
+
+    fn encode_bytes(&self, data: &mut [u8]) -> Result<(), Error> {
+        let mut cipher = Ctr128BE::<Aes256>::new((&*self.key).into(), (&self.iv).into());
+        cipher.apply_keystream(data);
+        Ok(())
+    }
+
+    fn decode_bytes(&self, data: &mut [u8]) -> Result<(), Error> {
+        let mut cipher = Ctr128BE::<Aes256>::new((&*self.key).into(), (&self.iv).into());
+        cipher.apply_keystream(data);
+        Ok(())
+    }
+}
+
+impl AesCtrProcessor {
+    pub fn new(key: [u8; 32], iv: [u8; 16]) -> Arc<dyn CryptoProcessor + Send + Sync> {
+        Arc::new(AesCtrProcessor{key: SecureBox::new(key), iv})
+    }
+}
+
+// applies stream ciphers one after another, decoding is done in reverse order
+pub struct CascadeProcessor {
+    processors: Vec<Arc<dyn CryptoProcessor + Send + Sync>>
+}
+
+impl CryptoProcessor for CascadeProcessor {
+    fn encode(&self, _data: &[u8]) -> Result<Vec<u8>, Error> {
+        Err(build_unsupported_error())
+    }
+
+    fn decode(&self, _data: &Vec<u8>) -> Result<Vec<u8>, Error> {
+        Err(build_unsupported_error())
+    }
+
+    fn encode_bytes(&self, data: &mut [u8]) -> Result<(), Error> {
+        for processor in &self.processors {
+            processor.encode_bytes(data)?;
+        }
+        Ok(())
+    }
+
+    fn decode_bytes(&self, data: &mut [u8]) -> Result<(), Error> {
+        for processor in self.processors.iter().rev() {
+            processor.decode_bytes(data)?;
+        }
+        Ok(())
+    }
+}
+
+impl CascadeProcessor {
+    pub fn new(processors: Vec<Arc<dyn CryptoProcessor + Send + Sync>>) -> Arc<dyn CryptoProcessor + Send + Sync> {
+        Arc::new(CascadeProcessor{processors})
     }
 }
 
@@ -163,7 +242,7 @@ mod tests {
     use std::sync::Arc;
     use rand::RngCore;
     use rand::rngs::OsRng;
-    use crate::crypto::{AesProcessor, ChachaProcessor, CryptoProcessor};
+    use crate::crypto::{AesCtrProcessor, AesProcessor, CascadeProcessor, ChachaProcessor, CryptoProcessor};
 
     #[test]
     fn test_crypto_processors() -> Result<(), Error> {
@@ -171,22 +250,30 @@ mod tests {
         OsRng.fill_bytes(&mut key);
         let mut iv = [0u8;12];
         OsRng.fill_bytes(&mut iv);
+        let mut iv2 = [0u8;16];
+        OsRng.fill_bytes(&mut iv2);
         test_crypto_processor(AesProcessor::new(key.clone()))?;
-        test_crypto_processor2(ChachaProcessor::new(key, iv))
+        test_crypto_processor2(ChachaProcessor::new(key, iv))?;
+        test_crypto_processor2(AesCtrProcessor::new(key, iv2))?;
+        test_crypto_processor2(CascadeProcessor::new(vec![AesCtrProcessor::new(key, iv2),
+                                                          ChachaProcessor::new(key, iv)]))
     }
 
     fn test_crypto_processor(processor: Arc<dyn CryptoProcessor>) -> Result<(), Error> {
         let mut data = [0u8;64];
         OsRng.fill_bytes(&mut data);
-        let encoded = processor.encode(data.to_vec())?;
+        let encoded = processor.encode(&data)?;
         let decoded = processor.decode(&encoded)?;
         assert_eq!(decoded, data.to_vec());
 
         let mut data2 = [0u8;5];
         OsRng.fill_bytes(&mut data2);
-        let encoded2 = processor.encode(data2.to_vec())?;
+        let encoded2 = processor.encode(&data2)?;
         let decoded2 = processor.decode(&encoded2)?;
         assert_eq!(decoded2, data2.to_vec());
+
+        assert!(processor.decode(&encoded[..encoded.len() - 1].to_vec()).is_err());
+        assert!(processor.decode(&processor.encode(&[])?)?.is_empty());
         Ok(())
     }
 
@@ -2,7 +2,7 @@ use std::any::Any;
 use std::collections::HashMap;
 use std::io::{Error, ErrorKind};
 use crate::error_builders::build_read_only_db_error;
-use crate::structs_interfaces::{DatabaseGroup, PasswordDatabase, PasswordDatabaseEntity};
+use crate::structs_interfaces::{DatabaseGroup, DatabaseUser, EntitySortKey, EntityUrl, PasswordDatabase, PasswordDatabaseEntity, PropertyLayout, UrlMatch};
 
 pub struct KeePassDatabase {
 
@@ -31,7 +31,7 @@ impl PasswordDatabase for KeePassDatabase {
         todo!()
     }
 
-    fn get_users(&self) -> Result<HashMap<u32, String>, Error> {
+    fn get_users(&self) -> Result<HashMap<u32, DatabaseUser>, Error> {
         todo!()
     }
 
@@ -47,28 +47,67 @@ impl PasswordDatabase for KeePassDatabase {
         Err(build_read_only_db_error())
     }
 
+    fn rename_user(&self, _user_id: u32, _new_name: String) -> Result<(), Error> {
+        Err(build_read_only_db_error())
+    }
+
+    fn update_user(&self, _user_id: u32, _user: DatabaseUser) -> Result<(), Error> {
+        Err(build_read_only_db_error())
+    }
+
+    fn replace_user(&self, _user_id: u32, _replacement_id: u32, _rewrite_history: bool) -> Result<(), Error> {
+        Err(build_read_only_db_error())
+    }
+
     fn search(&self, _search_string: String) -> Result<HashMap<u32, HashMap<u32, Box<dyn PasswordDatabaseEntity + Send>>>, Error> {
         todo!()
     }
 
+    fn list_entities(&self, _group_id: Option<u32>, _sort_key: EntitySortKey, _descending: bool, _offset: u32, _limit: u32)
+        -> Result<(u32, Vec<(u32, Box<dyn PasswordDatabaseEntity + Send>)>), Error> {
+        Err(Error::new(ErrorKind::Unsupported, "not implemented"))
+    }
+
+    fn search_entities(&self, _search_string: String, _sort_key: EntitySortKey, _descending: bool, _offset: u32, _limit: u32)
+        -> Result<(u32, Vec<(u32, Box<dyn PasswordDatabaseEntity + Send>)>), Error> {
+        Err(Error::new(ErrorKind::Unsupported, "not implemented"))
+    }
+
     fn add_group(&self, _name: String) -> Result<u32, Error> {
         Err(build_read_only_db_error())
     }
 
+    fn add_child_group(&self, _parent_id: u32, _name: String) -> Result<u32, Error> {
+        Err(build_read_only_db_error())
+    }
+
     fn rename_group(&self, _group_id: u32, _new_name: String) -> Result<(), Error> {
         Err(build_read_only_db_error())
     }
 
+    fn move_group(&self, _group_id: u32, _new_parent_id: Option<u32>) -> Result<(), Error> {
+        Err(build_read_only_db_error())
+    }
+
     fn remove_group(&self, _group_id: u32) -> Result<(), Error> {
         Err(build_read_only_db_error())
     }
 
+    fn merge_groups(&self, _group_id: u32, _target_group_id: u32, _rewrite_history: bool) -> Result<(), Error> {
+        Err(build_read_only_db_error())
+    }
+
     fn remove_entity(&self, _entity_id: u32) -> Result<(), Error> {
         Err(build_read_only_db_error())
     }
 
     fn add_entity(&self, _group_id: u32, _name: String, _user_id: u32, _password: String,
-                  _url: Option<String>, _properties: HashMap<String, String>) -> Result<u32, Error> {
+                  _url: Option<String>, _properties: HashMap<String, String>,
+                  _template_id: Option<u32>) -> Result<u32, Error> {
+        Err(build_read_only_db_error())
+    }
+
+    fn add_note(&self, _group_id: u32, _name: String, _body: String) -> Result<u32, Error> {
         Err(build_read_only_db_error())
     }
 
@@ -83,6 +122,50 @@ impl PasswordDatabase for KeePassDatabase {
         Err(build_read_only_db_error())
     }
 
+    fn set_property_layout(&self, _entity_id: u32, _layout: Vec<PropertyLayout>) -> Result<(), Error> {
+        Err(build_read_only_db_error())
+    }
+
+    fn restore_entity_version(&self, _entity_id: u32, _version: u32) -> Result<(), Error> {
+        Err(build_read_only_db_error())
+    }
+
+    fn set_entity_urls(&self, _entity_id: u32, _urls: Vec<EntityUrl>) -> Result<(), Error> {
+        Err(build_read_only_db_error())
+    }
+
+    fn add_attachment(&self, _entity_id: u32, _name: String, _data: Vec<u8>) -> Result<u32, Error> {
+        Err(build_read_only_db_error())
+    }
+
+    fn get_attachment(&self, _entity_id: u32, _version: u32, _attachment_id: u32) -> Result<Vec<u8>, Error> {
+        Err(Error::new(ErrorKind::Unsupported, "not implemented"))
+    }
+
+    fn remove_attachment(&self, _entity_id: u32, _attachment_id: u32) -> Result<(), Error> {
+        Err(build_read_only_db_error())
+    }
+
+    fn get_tags(&self) -> Result<HashMap<u32, String>, Error> {
+        Err(Error::new(ErrorKind::Unsupported, "not implemented"))
+    }
+
+    fn add_tag(&self, _entity_id: u32, _tag: String) -> Result<u32, Error> {
+        Err(build_read_only_db_error())
+    }
+
+    fn remove_tag(&self, _entity_id: u32, _tag_id: u32) -> Result<(), Error> {
+        Err(build_read_only_db_error())
+    }
+
+    fn get_entities_by_tags(&self, _tag_ids: Vec<u32>) -> Result<HashMap<u32, Box<dyn PasswordDatabaseEntity + Send>>, Error> {
+        Err(Error::new(ErrorKind::Unsupported, "not implemented"))
+    }
+
+    fn find_by_url(&self, _url: String) -> Result<Vec<UrlMatch>, Error> {
+        Err(Error::new(ErrorKind::Unsupported, "not implemented"))
+    }
+
     fn save(&self) -> Result<Option<Vec<u8>>, Error> {
         Err(build_read_only_db_error())
     }
@@ -90,6 +173,10 @@ impl PasswordDatabase for KeePassDatabase {
     fn as_any(&self) -> &dyn Any {
         self
     }
+
+    fn as_mut_any(&mut self) -> &mut dyn Any {
+        self
+    }
 }
 
 impl KeePassDatabase {
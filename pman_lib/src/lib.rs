@@ -3,9 +3,11 @@ use std::io::{Error, ErrorKind};
 use std::sync::{Arc, Mutex};
 use thiserror::Error;
 use crate::keepass::keepass_database::KeePassDatabase;
+use crate::otp::{OtpAlgorithm, OtpCode, OtpParameters};
 use crate::pman::pman_database::PmanDatabase;
 use crate::pman::pman_database_file::{build_argon2_key, build_argon2_properties};
-use crate::structs_interfaces::{DatabaseGroup, PasswordDatabase, PasswordDatabaseEntity, PasswordDatabaseType};
+use crate::pman::key_slots::build_member_key_pair;
+use crate::structs_interfaces::{AuditEvent, AuditEventType, AuditObjectType, AuditSettings, DatabaseGroup, DatabaseMember, DatabaseUser, DatabaseVersion, EntityKind, EntitySortKey, EntityTemplate, ExpiringEntity, FieldType, IntegrityIssue, IntegrityIssueType, KeySlotType, MemberKeyPair, PasswordDatabase, PasswordDatabaseEntity, PasswordDatabaseType, PropertyInfo, PropertyLayout, TemplateField, TrashedEntity, EntityUrl, UrlMatch, UrlMatchMode};
 use crate::structs_interfaces::CryptoEngine;
 use crate::structs_interfaces::HashAlgorithm;
 
@@ -13,6 +15,10 @@ pub mod structs_interfaces;
 mod keepass;
 pub mod pman;
 pub mod crypto;
+pub mod otp;
+pub mod url_match;
+pub mod search_query;
+pub mod secure_memory;
 mod error_builders;
 
 uniffi::include_scaffolding!("pman_lib");
@@ -43,6 +49,10 @@ pub fn lib_init() {
     }
 }
 
+pub fn disable_core_dumps() -> Result<(), PmanError> {
+    secure_memory::disable_core_dumps().map_err(|e|PmanError::message(e.to_string()))
+}
+
 pub fn get_database_type(file_name: &String) -> Result<PasswordDatabaseType, Error> {
     let l = file_name.len();
     if l < 6 {
@@ -148,6 +158,21 @@ pub fn pre_open(database_id: u64, password_hash: Vec<u8>, password2_hash: Option
         .map_err(|e|PmanError::message(e.to_string()))
 }
 
+pub fn export_to_new_database(database_id: u64, group_ids: Vec<u32>, password_hash: Vec<u8>,
+                              password2_hash: Vec<u8>, key_file_contents: Vec<u8>, file_name: String)
+    -> Result<u64, PmanError> {
+    let db = get_pman_database(database_id)?;
+    let database = db.export_to_new_database(group_ids, password_hash, password2_hash, key_file_contents)
+        .map_err(|e| PmanError::message(e.to_string()))?;
+    let db_id = unsafe{NEXT_DB_ID};
+    unsafe{
+        DATABASES.as_mut().unwrap()
+            .insert(db_id, DatabaseFile{file_name, database: Box::new(database)});
+        NEXT_DB_ID += 1
+    };
+    Ok(db_id)
+}
+
 pub fn open(database_id: u64) -> Result<(), PmanError> {
     let db = get_database(database_id)?;
     db.database.open()
@@ -181,6 +206,200 @@ fn get_pman_database<'a>(database_id: u64) -> Result<&'a PmanDatabase, PmanError
     db.database.as_any().downcast_ref().ok_or(PmanError::message("wrong database type"))
 }
 
+fn get_mut_pman_database<'a>(database_id: u64) -> Result<&'a mut PmanDatabase, PmanError> {
+    let db = get_mut_database(database_id)?;
+    db.database.as_mut_any().downcast_mut().ok_or(PmanError::message("wrong database type"))
+}
+
+pub fn pre_open_with_key_slot(database_id: u64, slot_type: KeySlotType, secret: Vec<u8>,
+                              key_file_contents: Option<Vec<u8>>) -> Result<(), PmanError> {
+    let db = get_mut_pman_database(database_id)?;
+    db.pre_open_with_key_slot(slot_type, secret, key_file_contents)
+        .map_err(|e|PmanError::message(e.to_string()))
+}
+
+// engines are applied in the given order, more than one engine builds a cipher cascade
+pub fn set_encryption(database_id: u64, layer_id: u64, engines: Vec<CryptoEngine>) -> Result<(), PmanError> {
+    let db = get_pman_database(database_id)?;
+    db.set_encryption(layer_id as usize, engines).map_err(|e|PmanError::message(e.to_string()))
+}
+
+pub fn check_integrity(database_id: u64, repair: bool) -> Result<Vec<IntegrityIssue>, PmanError> {
+    let db = get_pman_database(database_id)?;
+    db.check_integrity(repair).map_err(|e|PmanError::message(e.to_string()))
+}
+
+pub fn get_database_version(database_id: u64) -> Result<DatabaseVersion, PmanError> {
+    let db = get_pman_database(database_id)?;
+    db.get_version().map_err(|e|PmanError::message(e.to_string()))
+}
+
+pub fn remove_data_file_backups(database_id: u64) -> Result<(), PmanError> {
+    let db = get_pman_database(database_id)?;
+    db.remove_data_file_backups().map_err(|e|PmanError::message(e.to_string()))
+}
+
+pub fn get_key_slots(database_id: u64) -> Result<HashMap<u32, KeySlotType>, PmanError> {
+    let db = get_pman_database(database_id)?;
+    db.get_key_slots().map_err(|e|PmanError::message(e.to_string()))
+}
+
+pub fn add_recovery_key(database_id: u64) -> Result<Vec<u8>, PmanError> {
+    let db = get_pman_database(database_id)?;
+    db.add_recovery_key().map_err(|e|PmanError::message(e.to_string()))
+}
+
+pub fn add_key_file_slot(database_id: u64, key_file: Vec<u8>) -> Result<u32, PmanError> {
+    let db = get_pman_database(database_id)?;
+    db.add_key_file_slot(key_file).map_err(|e|PmanError::message(e.to_string()))
+}
+
+pub fn remove_key_slot(database_id: u64, id: u32) -> Result<(), PmanError> {
+    let db = get_pman_database(database_id)?;
+    db.remove_key_slot(id).map_err(|e|PmanError::message(e.to_string()))
+}
+
+pub fn generate_member_key_pair() -> MemberKeyPair {
+    build_member_key_pair()
+}
+
+pub fn get_members(database_id: u64) -> Result<HashMap<u32, DatabaseMember>, PmanError> {
+    let db = get_pman_database(database_id)?;
+    db.get_members().map_err(|e|PmanError::message(e.to_string()))
+}
+
+pub fn add_member(database_id: u64, name: String, public_key: Vec<u8>) -> Result<u32, PmanError> {
+    let db = get_pman_database(database_id)?;
+    db.add_member(name, public_key).map_err(|e|PmanError::message(e.to_string()))
+}
+
+pub fn revoke_member(database_id: u64, member_id: u32) -> Result<Vec<KeySlotType>, PmanError> {
+    let db = get_pman_database(database_id)?;
+    db.revoke_member(member_id).map_err(|e|PmanError::message(e.to_string()))
+}
+
+pub fn set_passwords(database_id: u64, password_hash: Vec<u8>, password2_hash: Vec<u8>) -> Result<(), PmanError> {
+    let db = get_pman_database(database_id)?;
+    db.set_passwords(password_hash, password2_hash).map_err(|e|PmanError::message(e.to_string()))
+}
+
+pub fn set_entity_otp(database_id: u64, entity_id: u32, otp: Option<String>) -> Result<(), PmanError> {
+    let db = get_pman_database(database_id)?;
+    db.set_entity_otp(entity_id, otp).map_err(|e|PmanError::message(e.to_string()))
+}
+
+pub fn next_hotp_code(database_id: u64, entity_id: u32) -> Result<OtpCode, PmanError> {
+    let db = get_pman_database(database_id)?;
+    db.next_hotp_code(entity_id).map_err(|e|PmanError::message(e.to_string()))
+}
+
+pub fn set_entity_expiry(database_id: u64, entity_id: u32, expires_at: Option<u64>,
+                         max_age_days: Option<u32>) -> Result<(), PmanError> {
+    let db = get_pman_database(database_id)?;
+    db.set_entity_expiry(entity_id, expires_at, max_age_days).map_err(|e|PmanError::message(e.to_string()))
+}
+
+pub fn set_group_max_age(database_id: u64, group_id: u32, max_age_days: Option<u32>) -> Result<(), PmanError> {
+    let db = get_pman_database(database_id)?;
+    db.set_group_max_age(group_id, max_age_days).map_err(|e|PmanError::message(e.to_string()))
+}
+
+pub fn get_templates(database_id: u64) -> Result<Vec<EntityTemplate>, PmanError> {
+    let db = get_pman_database(database_id)?;
+    db.get_templates().map_err(|e|PmanError::message(e.to_string()))
+}
+
+pub fn add_template(database_id: u64, name: String, fields: Vec<TemplateField>) -> Result<u32, PmanError> {
+    let db = get_pman_database(database_id)?;
+    db.add_template(name, fields).map_err(|e|PmanError::message(e.to_string()))
+}
+
+pub fn remove_template(database_id: u64, template_id: u32) -> Result<(), PmanError> {
+    let db = get_pman_database(database_id)?;
+    db.remove_template(template_id).map_err(|e|PmanError::message(e.to_string()))
+}
+
+pub fn get_expiring(database_id: u64, days: u32) -> Result<Vec<ExpiringEntity>, PmanError> {
+    let db = get_pman_database(database_id)?;
+    db.get_expiring(days).map_err(|e|PmanError::message(e.to_string()))
+}
+
+pub fn get_history_length(database_id: u64) -> Result<u32, PmanError> {
+    let db = get_pman_database(database_id)?;
+    db.get_history_length().map_err(|e|PmanError::message(e.to_string()))
+}
+
+pub fn set_history_length(database_id: u64, length: u32) -> Result<(), PmanError> {
+    let db = get_pman_database(database_id)?;
+    db.set_history_length(length).map_err(|e|PmanError::message(e.to_string()))
+}
+
+pub fn set_entity_history_length(database_id: u64, entity_id: u32, length: Option<u32>) -> Result<(), PmanError> {
+    let db = get_pman_database(database_id)?;
+    db.set_entity_history_length(entity_id, length).map_err(|e|PmanError::message(e.to_string()))
+}
+
+pub fn purge_entity_history(database_id: u64, entity_id: u32) -> Result<(), PmanError> {
+    let db = get_pman_database(database_id)?;
+    db.purge_entity_history(entity_id).map_err(|e|PmanError::message(e.to_string()))
+}
+
+pub fn get_trash(database_id: u64) -> Result<Vec<TrashedEntity>, PmanError> {
+    let db = get_pman_database(database_id)?;
+    db.get_trash().map_err(|e|PmanError::message(e.to_string()))
+}
+
+pub fn restore_entity(database_id: u64, entity_id: u32) -> Result<(), PmanError> {
+    let db = get_pman_database(database_id)?;
+    db.restore_entity(entity_id).map_err(|e|PmanError::message(e.to_string()))
+}
+
+pub fn empty_trash(database_id: u64) -> Result<(), PmanError> {
+    let db = get_pman_database(database_id)?;
+    db.empty_trash().map_err(|e|PmanError::message(e.to_string()))
+}
+
+pub fn get_trash_retention(database_id: u64) -> Result<u32, PmanError> {
+    let db = get_pman_database(database_id)?;
+    db.get_trash_retention().map_err(|e|PmanError::message(e.to_string()))
+}
+
+pub fn set_trash_retention(database_id: u64, days: u32) -> Result<(), PmanError> {
+    let db = get_pman_database(database_id)?;
+    db.set_trash_retention(days).map_err(|e|PmanError::message(e.to_string()))
+}
+
+pub fn get_audit_log(database_id: u64, since: Option<u64>, object_id: Option<u32>) -> Result<Vec<AuditEvent>, PmanError> {
+    let db = get_pman_database(database_id)?;
+    db.get_audit_log(since, object_id).map_err(|e|PmanError::message(e.to_string()))
+}
+
+pub fn get_audit_settings(database_id: u64) -> Result<AuditSettings, PmanError> {
+    let db = get_pman_database(database_id)?;
+    db.get_audit_settings().map_err(|e|PmanError::message(e.to_string()))
+}
+
+pub fn set_audit_settings(database_id: u64, settings: AuditSettings) -> Result<(), PmanError> {
+    let db = get_pman_database(database_id)?;
+    db.set_audit_settings(settings).map_err(|e|PmanError::message(e.to_string()))
+}
+
+pub fn set_device_label(database_id: u64, label: String) -> Result<(), PmanError> {
+    let db = get_pman_database(database_id)?;
+    db.set_device_label(label).map_err(|e|PmanError::message(e.to_string()))
+}
+
+pub fn is_audit_pending(database_id: u64) -> Result<bool, PmanError> {
+    let db = get_pman_database(database_id)?;
+    Ok(db.is_audit_pending())
+}
+
+pub fn build_totp_uri(secret: String, algorithm: OtpAlgorithm, digits: u32, period: u64) -> Result<String, PmanError> {
+    OtpParameters::new_totp(secret, algorithm, digits, period)
+        .map(|p|p.to_uri())
+        .map_err(|e|PmanError::message(e.to_string()))
+}
+
 pub fn set_file1_location_qs3(database_id: u64, file_name: String, qs3_key: Vec<u8>) -> Result<(), PmanError> {
     let db = get_pman_database(database_id)?;
     db.set_file1_location_qs3(file_name, qs3_key)
@@ -211,6 +430,16 @@ pub fn add_group(database_id: u64, name: String) -> Result<u32, PmanError> {
 }
 
 
+pub fn add_child_group(database_id: u64, parent_id: u32, name: String) -> Result<u32, PmanError> {
+    let db = get_database(database_id)?;
+    db.database.add_child_group(parent_id, name).map_err(|e|PmanError::message(e.to_string()))
+}
+
+pub fn move_group(database_id: u64, id: u32, new_parent_id: Option<u32>) -> Result<(), PmanError> {
+    let db = get_database(database_id)?;
+    db.database.move_group(id, new_parent_id).map_err(|e|PmanError::message(e.to_string()))
+}
+
 pub fn rename_group(database_id: u64, id: u32, new_name: String) -> Result<(), PmanError> {
     let db = get_database(database_id)?;
     db.database.rename_group(id, new_name).map_err(|e|PmanError::message(e.to_string()))
@@ -221,7 +450,12 @@ pub fn remove_group(database_id: u64, id: u32) -> Result<(), PmanError> {
     db.database.remove_group(id).map_err(|e|PmanError::message(e.to_string()))
 }
 
-pub fn get_users(database_id: u64) -> Result<HashMap<u32, String>, PmanError> {
+pub fn merge_groups(database_id: u64, id: u32, target_id: u32, rewrite_history: bool) -> Result<(), PmanError> {
+    let db = get_database(database_id)?;
+    db.database.merge_groups(id, target_id, rewrite_history).map_err(|e|PmanError::message(e.to_string()))
+}
+
+pub fn get_users(database_id: u64) -> Result<HashMap<u32, DatabaseUser>, PmanError> {
     let db = get_database(database_id)?;
     db.database.get_users().map_err(|e|PmanError::message(e.to_string()))
 }
@@ -236,6 +470,21 @@ pub fn remove_user(database_id: u64, id: u32) -> Result<(), PmanError> {
     db.database.remove_user(id).map_err(|e|PmanError::message(e.to_string()))
 }
 
+pub fn rename_user(database_id: u64, id: u32, new_name: String) -> Result<(), PmanError> {
+    let db = get_database(database_id)?;
+    db.database.rename_user(id, new_name).map_err(|e|PmanError::message(e.to_string()))
+}
+
+pub fn update_user(database_id: u64, id: u32, user: DatabaseUser) -> Result<(), PmanError> {
+    let db = get_database(database_id)?;
+    db.database.update_user(id, user).map_err(|e|PmanError::message(e.to_string()))
+}
+
+pub fn replace_user(database_id: u64, id: u32, replacement_id: u32, rewrite_history: bool) -> Result<(), PmanError> {
+    let db = get_database(database_id)?;
+    db.database.replace_user(id, replacement_id, rewrite_history).map_err(|e|PmanError::message(e.to_string()))
+}
+
 pub fn get_entities(database_id: u64, group_id: u32) -> Result<HashMap<u32, Arc<DatabaseEntity>>, PmanError> {
     let db = get_database(database_id)?;
     db.database.get_entities(group_id)
@@ -245,12 +494,18 @@ pub fn get_entities(database_id: u64, group_id: u32) -> Result<HashMap<u32, Arc<
 }
 
 pub fn add_entity(database_id: u64, name: String, group_id: u32, user_id: u32, password: String,
-                  url: Option<String>, properties: HashMap<String, String>) -> Result<u32, PmanError> {
+                  url: Option<String>, properties: HashMap<String, String>,
+                  template_id: Option<u32>) -> Result<u32, PmanError> {
     let db = get_database(database_id)?;
-    db.database.add_entity(group_id, name, user_id, password, url, properties)
+    db.database.add_entity(group_id, name, user_id, password, url, properties, template_id)
         .map_err(|e|PmanError::message(e.to_string()))
 }
 
+pub fn add_note(database_id: u64, name: String, group_id: u32, body: String) -> Result<u32, PmanError> {
+    let db = get_database(database_id)?;
+    db.database.add_note(group_id, name, body).map_err(|e|PmanError::message(e.to_string()))
+}
+
 pub fn rename_entity(database_id: u64, entity_id: u32, new_name: String) -> Result<(), PmanError> {
     let db = get_database(database_id)?;
     db.database.rename_entity(entity_id, new_name).map_err(|e|PmanError::message(e.to_string()))
@@ -266,11 +521,68 @@ pub fn modify_entity(database_id: u64, entity_id: u32, new_group_id: Option<u32>
         .map_err(|e|PmanError::message(e.to_string()))
 }
 
+pub fn set_property_layout(database_id: u64, entity_id: u32, layout: Vec<PropertyLayout>) -> Result<(), PmanError> {
+    let db = get_database(database_id)?;
+    db.database.set_property_layout(entity_id, layout).map_err(|e|PmanError::message(e.to_string()))
+}
+
+pub fn restore_entity_version(database_id: u64, entity_id: u32, version: u32) -> Result<(), PmanError> {
+    let db = get_database(database_id)?;
+    db.database.restore_entity_version(entity_id, version).map_err(|e|PmanError::message(e.to_string()))
+}
+
+pub fn set_entity_urls(database_id: u64, entity_id: u32, urls: Vec<EntityUrl>) -> Result<(), PmanError> {
+    let db = get_database(database_id)?;
+    db.database.set_entity_urls(entity_id, urls).map_err(|e|PmanError::message(e.to_string()))
+}
+
+pub fn find_by_url(database_id: u64, url: String) -> Result<Vec<UrlMatch>, PmanError> {
+    let db = get_database(database_id)?;
+    db.database.find_by_url(url).map_err(|e|PmanError::message(e.to_string()))
+}
+
 pub fn remove_entity(database_id: u64, id: u32) -> Result<(), PmanError> {
     let db = get_database(database_id)?;
     db.database.remove_entity(id).map_err(|e|PmanError::message(e.to_string()))
 }
 
+pub fn add_attachment(database_id: u64, entity_id: u32, name: String, data: Vec<u8>) -> Result<u32, PmanError> {
+    let db = get_database(database_id)?;
+    db.database.add_attachment(entity_id, name, data).map_err(|e|PmanError::message(e.to_string()))
+}
+
+pub fn get_attachment(database_id: u64, entity_id: u32, version: u32, attachment_id: u32) -> Result<Vec<u8>, PmanError> {
+    let db = get_database(database_id)?;
+    db.database.get_attachment(entity_id, version, attachment_id).map_err(|e|PmanError::message(e.to_string()))
+}
+
+pub fn remove_attachment(database_id: u64, entity_id: u32, attachment_id: u32) -> Result<(), PmanError> {
+    let db = get_database(database_id)?;
+    db.database.remove_attachment(entity_id, attachment_id).map_err(|e|PmanError::message(e.to_string()))
+}
+
+pub fn get_tags(database_id: u64) -> Result<HashMap<u32, String>, PmanError> {
+    let db = get_database(database_id)?;
+    db.database.get_tags().map_err(|e|PmanError::message(e.to_string()))
+}
+
+pub fn add_tag(database_id: u64, entity_id: u32, tag: String) -> Result<u32, PmanError> {
+    let db = get_database(database_id)?;
+    db.database.add_tag(entity_id, tag).map_err(|e|PmanError::message(e.to_string()))
+}
+
+pub fn remove_tag(database_id: u64, entity_id: u32, tag_id: u32) -> Result<(), PmanError> {
+    let db = get_database(database_id)?;
+    db.database.remove_tag(entity_id, tag_id).map_err(|e|PmanError::message(e.to_string()))
+}
+
+pub fn get_entities_by_tags(database_id: u64, tag_ids: Vec<u32>) -> Result<HashMap<u32, Arc<DatabaseEntity>>, PmanError> {
+    let db = get_database(database_id)?;
+    db.database.get_entities_by_tags(tag_ids)
+        .map(build_entity_map)
+        .map_err(|e|PmanError::message(e.to_string()))
+}
+
 pub fn search(database_id: u64, search_string: String)
     -> Result<HashMap<u32, HashMap<u32, Arc<DatabaseEntity>>>, PmanError> {
     let db = get_database(database_id)?;
@@ -280,6 +592,41 @@ pub fn search(database_id: u64, search_string: String)
         .map_err(|e|PmanError::message(e.to_string()))
 }
 
+pub struct EntityListItem {
+    pub id: u32,
+    pub entity: Arc<DatabaseEntity>
+}
+
+pub struct EntityPage {
+    pub total: u32,
+    pub items: Vec<EntityListItem>
+}
+
+pub fn list_entities(database_id: u64, group_id: Option<u32>, sort_key: EntitySortKey, descending: bool,
+                     offset: u32, limit: u32) -> Result<EntityPage, PmanError> {
+    let db = get_database(database_id)?;
+    db.database.list_entities(group_id, sort_key, descending, offset, limit)
+        .map(build_entity_page)
+        .map_err(|e|PmanError::message(e.to_string()))
+}
+
+pub fn search_entities(database_id: u64, search_string: String, sort_key: EntitySortKey, descending: bool,
+                       offset: u32, limit: u32) -> Result<EntityPage, PmanError> {
+    let db = get_database(database_id)?;
+    db.database.search_entities(search_string, sort_key, descending, offset, limit)
+        .map(build_entity_page)
+        .map_err(|e|PmanError::message(e.to_string()))
+}
+
+fn build_entity_page((total, items): (u32, Vec<(u32, Box<dyn PasswordDatabaseEntity + Send>)>)) -> EntityPage {
+    EntityPage{
+        total,
+        items: items.into_iter()
+            .map(|(id, entity)|EntityListItem{id, entity: Arc::new(DatabaseEntity::new(entity))})
+            .collect()
+    }
+}
+
 fn build_entity_map(map: HashMap<u32, Box<dyn PasswordDatabaseEntity + Send>>) -> HashMap<u32, Arc<DatabaseEntity>> {
     map.into_iter()
         .map(|(k, v)|(k, Arc::new(DatabaseEntity::new(v)))).collect()
@@ -314,10 +661,42 @@ impl DatabaseEntity {
         self.entity.lock().unwrap().get_url(version).map_err(|e|PmanError::message(e.to_string()))
     }
 
-    pub fn get_property_names(&self, version: u32) -> Result<HashMap<String, u32>, PmanError> {
+    pub fn get_urls(&self, version: u32) -> Result<Vec<EntityUrl>, PmanError> {
+        self.entity.lock().unwrap().get_urls(version).map_err(|e|PmanError::message(e.to_string()))
+    }
+
+    pub fn get_property_names(&self, version: u32) -> Result<Vec<PropertyInfo>, PmanError> {
         self.entity.lock().unwrap().get_property_names(version).map_err(|e|PmanError::message(e.to_string()))
     }
 
+    pub fn get_attachment_names(&self, version: u32) -> Result<HashMap<String, u32>, PmanError> {
+        self.entity.lock().unwrap().get_attachment_names(version).map_err(|e|PmanError::message(e.to_string()))
+    }
+
+    pub fn get_tag_ids(&self, version: u32) -> Result<Vec<u32>, PmanError> {
+        self.entity.lock().unwrap().get_tag_ids(version).map_err(|e|PmanError::message(e.to_string()))
+    }
+
+    pub fn get_expires_at(&self, version: u32) -> Result<Option<u64>, PmanError> {
+        self.entity.lock().unwrap().get_expires_at(version).map_err(|e|PmanError::message(e.to_string()))
+    }
+
+    pub fn get_max_age_days(&self, version: u32) -> Result<Option<u32>, PmanError> {
+        self.entity.lock().unwrap().get_max_age_days(version).map_err(|e|PmanError::message(e.to_string()))
+    }
+
+    pub fn get_kind(&self) -> EntityKind {
+        self.entity.lock().unwrap().get_kind()
+    }
+
+    pub fn get_template_id(&self, version: u32) -> Result<Option<u32>, PmanError> {
+        self.entity.lock().unwrap().get_template_id(version).map_err(|e|PmanError::message(e.to_string()))
+    }
+
+    pub fn get_history_length(&self) -> Option<u32> {
+        self.entity.lock().unwrap().get_history_length()
+    }
+
     pub fn get_property_value(&self, version: u32, index: u32) -> Result<String, PmanError> {
         self.entity.lock().unwrap().get_property_value(version, index).map_err(|e|PmanError::message(e.to_string()))
     }
@@ -326,6 +705,10 @@ impl DatabaseEntity {
         self.entity.lock().unwrap().get_created_at(version).map_err(|e|PmanError::message(e.to_string()))
     }
 
+    pub fn get_otp_code(&self, version: u32, timestamp: u64) -> Result<Option<OtpCode>, PmanError> {
+        self.entity.lock().unwrap().get_otp_code(version, timestamp).map_err(|e|PmanError::message(e.to_string()))
+    }
+
     pub fn get_max_version(&self) -> u32 {
         self.entity.lock().unwrap().get_max_version()
     }
@@ -0,0 +1,363 @@
+use std::io::{Error, ErrorKind};
+use hmac::{Hmac, Mac};
+use sha1::Sha1;
+use sha2::{Sha256, Sha512};
+
+/*
+
+OTP parameters are stored in the passwords file as otpauth URI:
+otpauth://TYPE/LABEL?secret=SECRET&issuer=ISSUER&algorithm=ALGORITHM&digits=DIGITS&period=PERIOD&counter=COUNTER
+
+TYPE - totp or hotp
+SECRET - base32 encoded secret
+ISSUER - optional provider name, kept as is
+ALGORITHM - SHA1 (default), SHA256, SHA512
+DIGITS - 6 (default) .. 10
+PERIOD - totp period in seconds, 30 by default
+COUNTER - hotp counter, the next counter value to be used, incremented each time hotp code is generated
+
+*/
+
+const BASE32_ALPHABET: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
+
+const DEFAULT_DIGITS: u32 = 6;
+const DEFAULT_PERIOD: u64 = 30;
+
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum OtpAlgorithm {
+    Sha1,
+    Sha256,
+    Sha512
+}
+
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum OtpType {
+    Totp,
+    Hotp
+}
+
+#[derive(PartialEq, Debug)]
+pub struct OtpCode {
+    pub code: String,
+    // seconds until totp code expires, None for hotp
+    pub valid_for: Option<u64>
+}
+
+#[derive(PartialEq, Debug)]
+pub struct OtpParameters {
+    otp_type: OtpType,
+    label: String,
+    secret: Vec<u8>,
+    issuer: Option<String>,
+    algorithm: OtpAlgorithm,
+    digits: u32,
+    period: u64,
+    counter: u64
+}
+
+impl OtpParameters {
+    pub fn new_totp(secret: String, algorithm: OtpAlgorithm, digits: u32, period: u64) -> Result<OtpParameters, Error> {
+        let parameters = OtpParameters{
+            otp_type: OtpType::Totp,
+            label: String::new(),
+            secret: base32_decode(&secret)?,
+            issuer: None,
+            algorithm,
+            digits,
+            period,
+            counter: 0
+        };
+        parameters.validate()?;
+        Ok(parameters)
+    }
+
+    // same as parse, for values read from the passwords file
+    pub fn parse_bytes(value: &[u8]) -> Result<OtpParameters, Error> {
+        let v = std::str::from_utf8(value)
+            .map_err(|e|Error::new(ErrorKind::InvalidData, e.to_string()))?;
+        OtpParameters::parse(v)
+    }
+
+    // value - otpauth URI or base32 encoded totp secret
+    pub fn parse(value: &str) -> Result<OtpParameters, Error> {
+        let v = value.trim();
+        if !v.starts_with("otpauth://") {
+            return OtpParameters::new_totp(v.to_string(), OtpAlgorithm::Sha1, DEFAULT_DIGITS,
+                                           DEFAULT_PERIOD);
+        }
+        let rest = &v[10..];
+        let (path, query) = rest.split_once('?')
+            .ok_or(build_invalid_otp_uri_error())?;
+        let (type_name, label) = path.split_once('/')
+            .ok_or(build_invalid_otp_uri_error())?;
+        let otp_type = match type_name.to_lowercase().as_str() {
+            "totp" => OtpType::Totp,
+            "hotp" => OtpType::Hotp,
+            _ => return Err(Error::new(ErrorKind::InvalidInput, "unsupported otp type"))
+        };
+        let mut parameters = OtpParameters{
+            otp_type,
+            label: percent_decode(label)?,
+            secret: Vec::new(),
+            issuer: None,
+            algorithm: OtpAlgorithm::Sha1,
+            digits: DEFAULT_DIGITS,
+            period: DEFAULT_PERIOD,
+            counter: 0
+        };
+        for part in query.split('&') {
+            let (name, value) = part.split_once('=')
+                .ok_or(build_invalid_otp_uri_error())?;
+            let v = percent_decode(value)?;
+            match name {
+                "secret" => parameters.secret = base32_decode(&v)?,
+                "issuer" => parameters.issuer = Some(v),
+                "algorithm" => parameters.algorithm = parse_algorithm(&v)?,
+                "digits" => parameters.digits = u32::try_from(parse_number(&v)?)
+                    .map_err(|_e| Error::new(ErrorKind::InvalidInput, "invalid otp digits"))?,
+                "period" => parameters.period = parse_number(&v)?,
+                "counter" => parameters.counter = parse_number(&v)?,
+                _ => {}
+            }
+        }
+        parameters.validate()?;
+        Ok(parameters)
+    }
+
+    fn validate(&self) -> Result<(), Error> {
+        if self.secret.is_empty() {
+            return Err(Error::new(ErrorKind::InvalidInput, "empty otp secret"));
+        }
+        if self.digits < 6 || self.digits > 10 {
+            return Err(Error::new(ErrorKind::InvalidInput, "invalid otp digits"));
+        }
+        if self.period == 0 {
+            return Err(Error::new(ErrorKind::InvalidInput, "invalid otp period"));
+        }
+        Ok(())
+    }
+
+    pub fn to_uri(&self) -> String {
+        let type_name = match self.otp_type {
+            OtpType::Totp => "totp",
+            OtpType::Hotp => "hotp"
+        };
+        let algorithm = match self.algorithm {
+            OtpAlgorithm::Sha1 => "SHA1",
+            OtpAlgorithm::Sha256 => "SHA256",
+            OtpAlgorithm::Sha512 => "SHA512"
+        };
+        let mut result = format!("otpauth://{}/{}?secret={}&algorithm={}&digits={}", type_name,
+                                 percent_encode(&self.label), base32_encode(&self.secret), algorithm,
+                                 self.digits);
+        if let Some(issuer) = &self.issuer {
+            result += format!("&issuer={}", percent_encode(issuer)).as_str();
+        }
+        match self.otp_type {
+            OtpType::Totp => result += format!("&period={}", self.period).as_str(),
+            OtpType::Hotp => result += format!("&counter={}", self.counter).as_str()
+        }
+        result
+    }
+
+    // returns hotp code for the current counter and advances the counter,
+    // the caller has to store the updated parameters
+    pub fn next_hotp_code(&mut self) -> Result<OtpCode, Error> {
+        if self.otp_type != OtpType::Hotp {
+            return Err(Error::new(ErrorKind::InvalidInput, "not a hotp otp"));
+        }
+        let code = self.generate(0)?;
+        self.counter = self.counter.checked_add(1)
+            .ok_or(Error::new(ErrorKind::InvalidInput, "hotp counter overflow"))?;
+        Ok(code)
+    }
+
+    // timestamp - unix time in seconds, ignored for hotp,
+    // hotp counter is not changed, next_hotp_code should be used to get a code to be sent
+    pub fn generate(&self, timestamp: u64) -> Result<OtpCode, Error> {
+        let (counter, valid_for) = match self.otp_type {
+            OtpType::Totp => (timestamp / self.period, Some(self.period - timestamp % self.period)),
+            OtpType::Hotp => (self.counter, None)
+        };
+        let hash = self.build_hmac(&counter.to_be_bytes())?;
+        let offset = (hash[hash.len() - 1] & 0x0F) as usize;
+        let mut buffer32 = [0u8; 4];
+        buffer32.copy_from_slice(&hash[offset..offset+4]);
+        let value = (u32::from_be_bytes(buffer32) & 0x7FFFFFFF) as u64;
+        let code = value % 10u64.pow(self.digits);
+        Ok(OtpCode{code: format!("{:0width$}", code, width = self.digits as usize), valid_for})
+    }
+
+    fn build_hmac(&self, data: &[u8]) -> Result<Vec<u8>, Error> {
+        match self.algorithm {
+            OtpAlgorithm::Sha1 => {
+                let mut mac = Hmac::<Sha1>::new_from_slice(&self.secret)
+                    .map_err(|e| Error::new(ErrorKind::InvalidInput, e.to_string()))?;
+                mac.update(data);
+                Ok(mac.finalize().into_bytes().to_vec())
+            },
+            OtpAlgorithm::Sha256 => {
+                let mut mac = Hmac::<Sha256>::new_from_slice(&self.secret)
+                    .map_err(|e| Error::new(ErrorKind::InvalidInput, e.to_string()))?;
+                mac.update(data);
+                Ok(mac.finalize().into_bytes().to_vec())
+            },
+            OtpAlgorithm::Sha512 => {
+                let mut mac = Hmac::<Sha512>::new_from_slice(&self.secret)
+                    .map_err(|e| Error::new(ErrorKind::InvalidInput, e.to_string()))?;
+                mac.update(data);
+                Ok(mac.finalize().into_bytes().to_vec())
+            }
+        }
+    }
+}
+
+fn build_invalid_otp_uri_error() -> Error {
+    Error::new(ErrorKind::InvalidInput, "invalid otpauth uri")
+}
+
+fn parse_algorithm(value: &String) -> Result<OtpAlgorithm, Error> {
+    match value.to_uppercase().as_str() {
+        "SHA1" => Ok(OtpAlgorithm::Sha1),
+        "SHA256" => Ok(OtpAlgorithm::Sha256),
+        "SHA512" => Ok(OtpAlgorithm::Sha512),
+        _ => Err(Error::new(ErrorKind::InvalidInput, "unsupported otp algorithm"))
+    }
+}
+
+fn parse_number(value: &String) -> Result<u64, Error> {
+    value.parse::<u64>().map_err(|_e| build_invalid_otp_uri_error())
+}
+
+fn base32_decode(value: &String) -> Result<Vec<u8>, Error> {
+    let mut result = Vec::new();
+    let mut buffer = 0u32;
+    let mut bits = 0;
+    for c in value.bytes() {
+        if c == b'=' || c == b' ' || c == b'-' {
+            continue;
+        }
+        let v = BASE32_ALPHABET.iter().position(|a| *a == c.to_ascii_uppercase())
+            .ok_or(Error::new(ErrorKind::InvalidInput, "invalid base32 string"))?;
+        buffer = (buffer << 5) | v as u32;
+        bits += 5;
+        if bits >= 8 {
+            bits -= 8;
+            result.push((buffer >> bits) as u8);
+            buffer &= (1 << bits) - 1;
+        }
+    }
+    Ok(result)
+}
+
+fn base32_encode(value: &Vec<u8>) -> String {
+    let mut result = String::new();
+    let mut buffer = 0u32;
+    let mut bits = 0;
+    for b in value {
+        buffer = (buffer << 8) | *b as u32;
+        bits += 8;
+        while bits >= 5 {
+            bits -= 5;
+            result.push(BASE32_ALPHABET[((buffer >> bits) & 0x1F) as usize] as char);
+        }
+        buffer &= (1 << bits) - 1;
+    }
+    if bits > 0 {
+        result.push(BASE32_ALPHABET[((buffer << (5 - bits)) & 0x1F) as usize] as char);
+    }
+    result
+}
+
+fn percent_decode(value: &str) -> Result<String, Error> {
+    let bytes = value.as_bytes();
+    let mut result = Vec::new();
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' {
+            if i + 3 > bytes.len() {
+                return Err(build_invalid_otp_uri_error());
+            }
+            let hex = std::str::from_utf8(&bytes[i+1..i+3])
+                .map_err(|_e| build_invalid_otp_uri_error())?;
+            result.push(u8::from_str_radix(hex, 16).map_err(|_e| build_invalid_otp_uri_error())?);
+            i += 3;
+        } else {
+            result.push(bytes[i]);
+            i += 1;
+        }
+    }
+    String::from_utf8(result).map_err(|_e| build_invalid_otp_uri_error())
+}
+
+fn percent_encode(value: &String) -> String {
+    let mut result = String::new();
+    for b in value.bytes() {
+        if b.is_ascii_alphanumeric() || b == b'-' || b == b'.' || b == b'_' || b == b'~' || b == b':' || b == b'@' {
+            result.push(b as char);
+        } else {
+            result += format!("%{:02X}", b).as_str();
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Error;
+    use crate::otp::{base32_decode, base32_encode, OtpAlgorithm, OtpParameters};
+
+    // RFC 6238 test vectors
+    #[test]
+    fn test_totp() -> Result<(), Error> {
+        let secret1 = base32_encode(&b"12345678901234567890".to_vec());
+        let secret256 = base32_encode(&b"12345678901234567890123456789012".to_vec());
+        let secret512 = base32_encode(&b"1234567890123456789012345678901234567890123456789012345678901234".to_vec());
+        let p1 = OtpParameters::new_totp(secret1, OtpAlgorithm::Sha1, 8, 30)?;
+        let p256 = OtpParameters::new_totp(secret256, OtpAlgorithm::Sha256, 8, 30)?;
+        let p512 = OtpParameters::new_totp(secret512, OtpAlgorithm::Sha512, 8, 30)?;
+        assert_eq!(p1.generate(59)?.code, "94287082");
+        assert_eq!(p1.generate(59)?.valid_for, Some(1));
+        assert_eq!(p256.generate(59)?.code, "46119246");
+        assert_eq!(p512.generate(59)?.code, "90693936");
+        assert_eq!(p1.generate(1111111109)?.code, "07081804");
+        assert_eq!(p256.generate(2000000000)?.code, "90698825");
+        assert_eq!(p512.generate(20000000000)?.code, "47863826");
+        Ok(())
+    }
+
+    // RFC 4226 test vectors
+    #[test]
+    fn test_hotp() -> Result<(), Error> {
+        let secret = base32_encode(&b"12345678901234567890".to_vec());
+        let p = OtpParameters::parse(&format!("otpauth://hotp/Test?secret={}&counter=5", secret))?;
+        let code = p.generate(0)?;
+        assert_eq!(code.code, "254676");
+        assert_eq!(code.valid_for, None);
+        let mut p2 = OtpParameters::parse(&p.to_uri())?;
+        assert_eq!(p2.next_hotp_code()?.code, "254676");
+        assert_eq!(p2.next_hotp_code()?.code, "287922");
+        assert_eq!(p2.counter, 7);
+        assert!(OtpParameters::new_totp(secret, OtpAlgorithm::Sha1, 6, 30)?.next_hotp_code().is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse() -> Result<(), Error> {
+        let p = OtpParameters::parse(&"JBSWY3DPEHPK3PXP".to_string())?;
+        assert_eq!(p.secret, base32_decode(&"JBSWY3DPEHPK3PXP".to_string())?);
+        let uri = "otpauth://totp/Example%3Aalice%40google.com?secret=JBSWY3DPEHPK3PXP&issuer=Example&algorithm=SHA256&digits=8&period=60".to_string();
+        let p2 = OtpParameters::parse(&uri)?;
+        assert_eq!(p2.label, "Example:alice@google.com");
+        assert_eq!(p2.algorithm, OtpAlgorithm::Sha256);
+        assert_eq!(p2.digits, 8);
+        assert_eq!(p2.period, 60);
+        assert_eq!(p2.issuer, Some("Example".to_string()));
+        assert!(p2.to_uri().contains("&issuer=Example"));
+        assert_eq!(OtpParameters::parse(&p2.to_uri())?, p2);
+        assert!(OtpParameters::parse(&"otpauth://totp/a?secret=1".to_string()).is_err());
+        // 4294967302 = 2^32 + 6
+        assert!(OtpParameters::parse(&"otpauth://totp/a?secret=JBSWY3DPEHPK3PXP&digits=4294967302".to_string()).is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,269 @@
+/*
+
+audit log
+|-AUDIT_LOG_ID -> list of audit record ids, oldest record first (names file)
+|-record id -> audit record
+|     timestamp (u64), event type (u8), object type (u8), object id (u32)
+|     object name, details, device label: length (u16), string
+|-AUDIT_SETTINGS_ID -> max records count, reveals logging flag (names file)
+
+audit log is disabled by default (max records count = 0), oldest records are removed
+when the log is full, device label is not stored in the database and should be set after every open.
+audit settings changes are logged, existing records are kept when the log is shrunk or disabled
+
+*/
+
+use std::io::{Error, ErrorKind};
+use std::sync::MutexGuard;
+use crate::error_builders::build_corrupted_data_error;
+use crate::pman::database_entity::get_current_timestamp;
+use crate::pman::id_value_map::id_value_map::ByteValue;
+use crate::pman::pman_database::{AUDIT_LOG_ID, AUDIT_SETTINGS_ID};
+use crate::pman::pman_database_file::PmanDatabaseFile;
+use crate::structs_interfaces::{AuditEvent, AuditEventType, AuditObjectType, AuditSettings};
+
+const EVENT_TYPE_ADD: u8 = 1;
+const EVENT_TYPE_MODIFY: u8 = 2;
+const EVENT_TYPE_RENAME: u8 = 3;
+const EVENT_TYPE_REMOVE: u8 = 4;
+const EVENT_TYPE_RESTORE: u8 = 5;
+const EVENT_TYPE_REVEAL: u8 = 6;
+
+const OBJECT_TYPE_GROUP: u8 = 1;
+const OBJECT_TYPE_USER: u8 = 2;
+const OBJECT_TYPE_ENTITY: u8 = 3;
+const OBJECT_TYPE_DATABASE: u8 = 4;
+
+pub const MAX_AUDIT_RECORDS: u32 = 100000;
+
+impl ByteValue for AuditEvent {
+    fn from_bytes(source: Vec<u8>) -> Result<Box<AuditEvent>, Error> {
+        if source.len() < 14 {
+            return Err(build_corrupted_data_error("AuditEvent.from_bytes1"));
+        }
+        let mut buffer64 = [0u8; 8];
+        buffer64.copy_from_slice(&source[0..8]);
+        let timestamp = u64::from_le_bytes(buffer64);
+        let event_type = event_type_from_u8(source[8])?;
+        let object_type = object_type_from_u8(source[9])?;
+        let mut buffer32 = [0u8; 4];
+        buffer32.copy_from_slice(&source[10..14]);
+        let object_id = u32::from_le_bytes(buffer32);
+        let (object_name, offset) = string_from_bytes(&source, 14)?;
+        let (details, offset) = string_from_bytes(&source, offset)?;
+        let (device_label, offset) = string_from_bytes(&source, offset)?;
+        if offset != source.len() {
+            return Err(build_corrupted_data_error("AuditEvent.from_bytes2"));
+        }
+        Ok(Box::new(AuditEvent{timestamp, event_type, object_type, object_id, object_name, details, device_label}))
+    }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut result = Vec::new();
+        result.extend_from_slice(&self.timestamp.to_le_bytes());
+        result.push(event_type_to_u8(self.event_type));
+        result.push(object_type_to_u8(self.object_type));
+        result.extend_from_slice(&self.object_id.to_le_bytes());
+        string_to_bytes(&self.object_name, &mut result);
+        string_to_bytes(&self.details, &mut result);
+        string_to_bytes(&self.device_label, &mut result);
+        result
+    }
+}
+
+pub fn get_audit_settings(file: &MutexGuard<PmanDatabaseFile>) -> Result<AuditSettings, Error> {
+    match file.get_from_names::<Vec<u32>>(AUDIT_SETTINGS_ID) {
+        Ok(v) => {
+            if v.len() != 2 {
+                return Err(build_corrupted_data_error("get_audit_settings"));
+            }
+            Ok(AuditSettings{max_records: v[0], log_reveals: v[1] != 0})
+        }
+        Err(e) if e.kind() == ErrorKind::NotFound => Ok(AuditSettings{max_records: 0, log_reveals: false}),
+        Err(e) => Err(e)
+    }
+}
+
+// the change is logged even when it disables the log, existing records are not removed here,
+// when the log is shrunk oldest records are removed by the next added records
+pub fn set_audit_settings(file: &mut MutexGuard<PmanDatabaseFile>, settings: AuditSettings) -> Result<(), Error> {
+    if settings.max_records > MAX_AUDIT_RECORDS {
+        return Err(Error::new(ErrorKind::InvalidInput, "audit log size is too big"));
+    }
+    let old_settings = get_audit_settings(file)?;
+    if old_settings == settings {
+        return Ok(());
+    }
+    file.set_in_names_unindexed(AUDIT_SETTINGS_ID, vec![settings.max_records, settings.log_reveals as u32])?;
+    if old_settings.max_records == 0 && settings.max_records == 0 {
+        return Ok(());
+    }
+    let details = format!("audit settings: max records {} -> {}, log reveals {} -> {}",
+                          old_settings.max_records, settings.max_records,
+                          old_settings.log_reveals, settings.log_reveals);
+    // no records are removed by this one
+    append_audit_record(file, AuditEventType::Modify, AuditObjectType::Database, 0, String::new(),
+                        details, usize::MAX)
+}
+
+pub fn is_audit_enabled(file: &MutexGuard<PmanDatabaseFile>, event_type: AuditEventType) -> Result<bool, Error> {
+    let settings = get_audit_settings(file)?;
+    Ok(settings.max_records > 0 && (event_type != AuditEventType::Reveal || settings.log_reveals))
+}
+
+pub fn add_audit_record(file: &mut MutexGuard<PmanDatabaseFile>, event_type: AuditEventType,
+                        object_type: AuditObjectType, object_id: u32, object_name: String,
+                        details: String) -> Result<(), Error> {
+    if !is_audit_enabled(file, event_type)? {
+        return Ok(());
+    }
+    let max_records = get_audit_settings(file)?.max_records as usize;
+    append_audit_record(file, event_type, object_type, object_id, object_name, details, max_records)
+}
+
+fn append_audit_record(file: &mut MutexGuard<PmanDatabaseFile>, event_type: AuditEventType,
+                       object_type: AuditObjectType, object_id: u32, object_name: String,
+                       details: String, max_records: usize) -> Result<(), Error> {
+    let event = AuditEvent{
+        timestamp: get_current_timestamp(),
+        event_type,
+        object_type,
+        object_id,
+        object_name,
+        details,
+        device_label: file.get_device_label()
+    };
+    let mut ids = get_audit_log_ids(file)?;
+    ids.push(file.add_to_names_unindexed(event)?);
+    file.set_audit_pending();
+    save_audit_log_ids(file, ids, max_records)
+}
+
+// records sorted from oldest to newest
+pub fn get_audit_log(file: &MutexGuard<PmanDatabaseFile>) -> Result<Vec<AuditEvent>, Error> {
+    let mut result = Vec::new();
+    for id in get_audit_log_ids(file)? {
+        result.push(file.get_from_names(id)?);
+    }
+    Ok(result)
+}
+
+fn get_audit_log_ids(file: &MutexGuard<PmanDatabaseFile>) -> Result<Vec<u32>, Error> {
+    match file.get_from_names(AUDIT_LOG_ID) {
+        Ok(ids) => Ok(ids),
+        Err(e) if e.kind() == ErrorKind::NotFound => Ok(Vec::new()),
+        Err(e) => Err(e)
+    }
+}
+
+fn save_audit_log_ids(file: &mut MutexGuard<PmanDatabaseFile>, mut ids: Vec<u32>,
+                      max_records: usize) -> Result<(), Error> {
+    if ids.len() > max_records {
+        let count = ids.len() - max_records;
+        for id in ids.drain(..count) {
+            file.remove_from_names_unindexed(&id)?;
+        }
+    }
+    if !ids.is_empty() {
+        return file.set_in_names_unindexed(AUDIT_LOG_ID, ids);
+    }
+    match file.remove_from_names_unindexed(&AUDIT_LOG_ID) {
+        Err(e) if e.kind() == ErrorKind::NotFound => Ok(()),
+        r => r
+    }
+}
+
+fn event_type_to_u8(event_type: AuditEventType) -> u8 {
+    match event_type {
+        AuditEventType::Add => EVENT_TYPE_ADD,
+        AuditEventType::Modify => EVENT_TYPE_MODIFY,
+        AuditEventType::Rename => EVENT_TYPE_RENAME,
+        AuditEventType::Remove => EVENT_TYPE_REMOVE,
+        AuditEventType::Restore => EVENT_TYPE_RESTORE,
+        AuditEventType::Reveal => EVENT_TYPE_REVEAL
+    }
+}
+
+fn event_type_from_u8(event_type: u8) -> Result<AuditEventType, Error> {
+    match event_type {
+        EVENT_TYPE_ADD => Ok(AuditEventType::Add),
+        EVENT_TYPE_MODIFY => Ok(AuditEventType::Modify),
+        EVENT_TYPE_RENAME => Ok(AuditEventType::Rename),
+        EVENT_TYPE_REMOVE => Ok(AuditEventType::Remove),
+        EVENT_TYPE_RESTORE => Ok(AuditEventType::Restore),
+        EVENT_TYPE_REVEAL => Ok(AuditEventType::Reveal),
+        _ => Err(build_corrupted_data_error("event_type_from_u8"))
+    }
+}
+
+fn object_type_to_u8(object_type: AuditObjectType) -> u8 {
+    match object_type {
+        AuditObjectType::Group => OBJECT_TYPE_GROUP,
+        AuditObjectType::User => OBJECT_TYPE_USER,
+        AuditObjectType::Entity => OBJECT_TYPE_ENTITY,
+        AuditObjectType::Database => OBJECT_TYPE_DATABASE
+    }
+}
+
+fn object_type_from_u8(object_type: u8) -> Result<AuditObjectType, Error> {
+    match object_type {
+        OBJECT_TYPE_GROUP => Ok(AuditObjectType::Group),
+        OBJECT_TYPE_USER => Ok(AuditObjectType::User),
+        OBJECT_TYPE_ENTITY => Ok(AuditObjectType::Entity),
+        OBJECT_TYPE_DATABASE => Ok(AuditObjectType::Database),
+        _ => Err(build_corrupted_data_error("object_type_from_u8"))
+    }
+}
+
+// longer strings are truncated to u16::MAX bytes
+fn string_to_bytes(value: &String, output: &mut Vec<u8>) {
+    let mut length = value.len().min(u16::MAX as usize);
+    while !value.is_char_boundary(length) {
+        length -= 1;
+    }
+    output.extend_from_slice(&(length as u16).to_le_bytes());
+    output.extend_from_slice(&value.as_bytes()[..length]);
+}
+
+fn string_from_bytes(source: &Vec<u8>, offset: usize) -> Result<(String, usize), Error> {
+    if source.len() < offset + 2 {
+        return Err(build_corrupted_data_error("string_from_bytes1"));
+    }
+    let length = u16::from_le_bytes([source[offset], source[offset + 1]]) as usize;
+    let start = offset + 2;
+    if source.len() < start + length {
+        return Err(build_corrupted_data_error("string_from_bytes2"));
+    }
+    let value = String::from_utf8(source[start..start+length].to_vec())
+        .map_err(|_e|build_corrupted_data_error("string_from_bytes3"))?;
+    Ok((value, start + length))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Error;
+    use crate::pman::id_value_map::id_value_map::ByteValue;
+    use crate::structs_interfaces::{AuditEvent, AuditEventType, AuditObjectType};
+
+    #[test]
+    fn test_audit_event() -> Result<(), Error> {
+        let event = AuditEvent{
+            timestamp: 1700000000,
+            event_type: AuditEventType::Rename,
+            object_type: AuditObjectType::Group,
+            object_id: 123,
+            object_name: "Work".to_string(),
+            details: "Home".to_string(),
+            device_label: "laptop".to_string()
+        };
+        let event2 = AuditEvent::from_bytes(event.to_bytes())?;
+        assert_eq!(*event2, event);
+        let mut bytes = event.to_bytes();
+        bytes.push(0);
+        assert!(AuditEvent::from_bytes(bytes).is_err());
+        let mut bytes = event.to_bytes();
+        bytes[8] = 0;
+        assert!(AuditEvent::from_bytes(bytes).is_err());
+        Ok(())
+    }
+}
@@ -4,12 +4,12 @@ use std::sync::Arc;
 use hmac::digest::KeyInit;
 use hmac::{Hmac, Mac};
 use sha2::{Sha256, Digest};
-use crate::crypto::{AesProcessor, ChachaProcessor, CryptoProcessor};
+use crate::crypto::{AesCtrProcessor, AesProcessor, CascadeProcessor, ChachaProcessor, CryptoProcessor};
 use crate::error_builders::{build_corrupted_data_error, build_unsupported_algorithm_error};
 use crate::pman::id_value_map::id_value_map::{ByteValue, IdValueMap};
 use crate::pman::ids::{ENCRYPTION_ALGORITHM1_PROPERTIES_ID, ENCRYPTION_ALGORITHM2_PROPERTIES_ID,
                        FILE_LOCATION_ID, HASH_ALGORITHM_PROPERTIES_ID};
-use crate::pman::pman_database_file::{build_aes_processor, build_chacha_processor, build_chacha_salt, build_encryption_keys, default_aes_properties, default_argon2_properties, default_chacha_properties, ENCRYPTION_ALGORITHM_AES, ENCRYPTION_ALGORITHM_CHACHA20, FILE_LOCATION_QS3, get_encryption_algorithms, modify_header_algorithm_properties, set_argon2_in_header, set_file_location_qs3};
+use crate::pman::pman_database_file::{build_aes_ctr_salt, build_aes_processor, build_chacha_processor, build_chacha_salt, build_encryption_keys, default_aes_properties, default_argon2_properties, default_chacha_properties, ENCRYPTION_ALGORITHM_AES, ENCRYPTION_ALGORITHM_AES_CTR, ENCRYPTION_ALGORITHM_CASCADE, ENCRYPTION_ALGORITHM_CHACHA20, FILE_LOCATION_QS3, get_cascade_algorithms, get_encryption_algorithms, modify_header_algorithm_properties, set_argon2_in_header, set_encryption_in_header, set_file_location_qs3};
 
 pub struct DataFile {
     is_updated: bool,
@@ -33,7 +33,7 @@ impl DataFile {
         })
     }
 
-    pub fn pre_load(data: &mut Vec<u8>, offset: usize, l: usize, encryption_key1: [u8; 32], alg11: u8,
+    pub fn pre_load(data: &mut Vec<u8>, offset: usize, l: usize, encryption_key1: [u8; 32], alg11: &Vec<u8>,
                     processor12: Arc<dyn CryptoProcessor + Send + Sync>, ) -> Result<DataFile, Error> {
         let data_length = validate_data_hmac(&encryption_key1, data, l)?;
 
@@ -47,7 +47,7 @@ impl DataFile {
         Ok(DataFile { is_updated: false, names_data, passwords_data: None, data_length, passwords_data_offset })
     }
 
-    pub fn load(&mut self, data: &mut Vec<u8>, encryption_key2: [u8; 32], alg21: u8,
+    pub fn load(&mut self, data: &mut Vec<u8>, encryption_key2: [u8; 32], alg21: &Vec<u8>,
                 processor22: Arc<dyn CryptoProcessor + Send + Sync>) -> Result<(), Error> {
         if self.passwords_data.is_some() {
             return Err(Error::new(ErrorKind::AlreadyExists, "passwords data already loaded"));
@@ -80,9 +80,9 @@ impl DataFile {
         Ok(())
     }
 
-    pub fn save(&mut self, data: &mut Vec<u8>, encryption_key1: [u8; 32], alg11: u8,
+    pub fn save(&mut self, data: &mut Vec<u8>, encryption_key1: [u8; 32], alg11: &Vec<u8>,
                 processor12: Option<Arc<dyn CryptoProcessor + Send + Sync>>,
-                encryption_key2: [u8; 32], alg21: u8,
+                encryption_key2: [u8; 32], alg21: &Vec<u8>,
                 processor22: Option<Arc<dyn CryptoProcessor + Send + Sync>>) -> Result<(), Error> {
         self.check_passwords_data()?;
         let processor11
@@ -152,6 +152,13 @@ impl DataFile {
         self.passwords_data.as_mut().unwrap().set(id, value)
     }
 
+    #[cfg(test)]
+    pub fn set_raw_in_passwords(&mut self, id: u32, value: Vec<u8>) -> Result<(), Error> {
+        self.check_passwords_data()?;
+        self.passwords_data.as_mut().unwrap().set_raw(id, value);
+        Ok(())
+    }
+
     pub fn remove_from_names(&mut self, id: &u32) {
         self.is_updated = true;
         self.names_data.remove(id);
@@ -173,6 +180,24 @@ impl DataFile {
         Ok(self.passwords_data.as_ref().unwrap().get_records_count())
     }
 
+    pub fn get_names_ids(&self) -> Vec<u32> {
+        self.names_data.get_ids()
+    }
+
+    pub fn get_passwords_ids(&self) -> Result<Vec<u32>, Error> {
+        self.check_passwords_data()?;
+        Ok(self.passwords_data.as_ref().unwrap().get_ids())
+    }
+
+    pub fn check_names_record(&self, id: u32) -> Result<(), Error> {
+        self.names_data.check(id)
+    }
+
+    pub fn check_passwords_record(&self, id: u32) -> Result<(), Error> {
+        self.check_passwords_data()?;
+        self.passwords_data.as_ref().unwrap().check(id)
+    }
+
     pub fn build_names_passwords_file_info(processor2: Arc<dyn CryptoProcessor + Send + Sync>) -> Result<IdValueMap, Error> {
         let mut h = IdValueMap::new(processor2)?;
         h.add_with_id(HASH_ALGORITHM_PROPERTIES_ID, default_argon2_properties()).unwrap();
@@ -200,6 +225,10 @@ impl DataFile {
         modify_header_algorithm_properties(&mut self.names_data)
     }
 
+    pub fn set_encryption_in_header(&mut self, properties: Vec<u8>) -> Result<(), Error> {
+        set_encryption_in_header(&mut self.names_data, properties)
+    }
+
     pub fn set_argon2_in_header(&mut self, iterations: u8, parallelism: u8, memory: u16) -> Result<(), Error> {
         set_argon2_in_header(&mut self.names_data, iterations, parallelism, memory)
     }
@@ -225,8 +254,24 @@ fn get_location_data(header: &IdValueMap) -> Result<Vec<u8>, Error> {
     header.get(FILE_LOCATION_ID)
 }
 
-fn build_encryption_processor(algorithm: u8, encryption_key: [u8; 32], data: &mut Vec<u8>)
-    -> Result<Arc<dyn CryptoProcessor>, Error> {
+fn build_encryption_processor(algorithm_properties: &Vec<u8>, encryption_key: [u8; 32], data: &mut Vec<u8>)
+    -> Result<Arc<dyn CryptoProcessor + Send + Sync>, Error> {
+    if algorithm_properties.len() == 0 {
+        return Err(build_corrupted_data_error("build_encryption_processor"));
+    }
+    if algorithm_properties[0] != ENCRYPTION_ALGORITHM_CASCADE {
+        return build_layer_processor(algorithm_properties[0], encryption_key, data);
+    }
+    let mut processors = Vec::new();
+    for (idx, algorithm) in get_cascade_algorithms(algorithm_properties)?.into_iter().enumerate() {
+        let key = build_cascade_key(&encryption_key, idx)?;
+        processors.push(build_layer_processor(algorithm, key, data)?);
+    }
+    Ok(CascadeProcessor::new(processors))
+}
+
+fn build_layer_processor(algorithm: u8, encryption_key: [u8; 32], data: &mut Vec<u8>)
+    -> Result<Arc<dyn CryptoProcessor + Send + Sync>, Error> {
     let mut algorithm_parameters = vec![algorithm];
     match algorithm {
         ENCRYPTION_ALGORITHM_AES => {
@@ -240,10 +285,25 @@ fn build_encryption_processor(algorithm: u8, encryption_key: [u8; 32], data: &mu
             let processor = build_chacha_processor(algorithm_parameters, encryption_key)?;
             Ok(processor)
         },
+        ENCRYPTION_ALGORITHM_AES_CTR => {
+            let salt = build_aes_ctr_salt();
+            data.extend_from_slice(&salt);
+            Ok(AesCtrProcessor::new(encryption_key, salt))
+        },
         _ => Err(build_unsupported_algorithm_error())
     }
 }
 
+// every cipher in the cascade gets its own key derived from the layer key
+fn build_cascade_key(encryption_key: &[u8; 32], idx: usize) -> Result<[u8; 32], Error> {
+    let mut mac: HmacSha256 = KeyInit::new_from_slice(encryption_key)
+        .map_err(|e|Error::new(ErrorKind::InvalidData, e.to_string()))?;
+    mac.update(b"cascade");
+    mac.update(&[idx as u8]);
+    let mut key = [0u8; 32];
+    key.copy_from_slice(&mac.finalize().into_bytes());
+    Ok(key)
+}
 
 // validate data using sha256
 pub fn add_data_hash_and_hmac(data: &mut Vec<u8>, encryption_key: [u8; 32]) -> Result<(), Error> {
@@ -263,24 +323,55 @@ pub fn add_data_hash_and_hmac(data: &mut Vec<u8>, encryption_key: [u8; 32]) -> R
     Ok(())
 }
 
-fn encrypt_data(processor: Arc<dyn CryptoProcessor>, data: &mut Vec<u8>, offset: usize, length: usize) -> Result<(), Error> {
+fn encrypt_data(processor: Arc<dyn CryptoProcessor + Send + Sync>, data: &mut Vec<u8>, offset: usize, length: usize) -> Result<(), Error> {
     processor.encode_bytes(&mut data[offset..length])
 }
 
-pub fn decrypt_data(processor: Arc<dyn CryptoProcessor>, data: &mut Vec<u8>, offset: usize, length: usize) -> Result<(), Error> {
+pub fn decrypt_data(processor: Arc<dyn CryptoProcessor + Send + Sync>, data: &mut Vec<u8>, offset: usize, length: usize) -> Result<(), Error> {
     processor.decode_bytes(&mut data[offset..length])
 }
 
-fn load_encryption_processor(alg1: u8, encryption_key: [u8; 32], data: &Vec<u8>, offset: usize)
-    -> Result<(Arc<dyn CryptoProcessor>, usize), Error> {
+fn load_encryption_processor(algorithm_properties: &Vec<u8>, encryption_key: [u8; 32], data: &Vec<u8>, offset: usize)
+    -> Result<(Arc<dyn CryptoProcessor + Send + Sync>, usize), Error> {
+    if algorithm_properties.len() == 0 {
+        return Err(build_corrupted_data_error("load_encryption_processor"));
+    }
+    if algorithm_properties[0] != ENCRYPTION_ALGORITHM_CASCADE {
+        return load_layer_processor(algorithm_properties[0], encryption_key, data, offset);
+    }
+    let mut processors = Vec::new();
+    let mut next_offset = offset;
+    for (idx, algorithm) in get_cascade_algorithms(algorithm_properties)?.into_iter().enumerate() {
+        let key = build_cascade_key(&encryption_key, idx)?;
+        let (processor, end) = load_layer_processor(algorithm, key, data, next_offset)?;
+        processors.push(processor);
+        next_offset = end;
+    }
+    Ok((CascadeProcessor::new(processors), next_offset))
+}
+
+fn load_layer_processor(alg1: u8, encryption_key: [u8; 32], data: &Vec<u8>, offset: usize)
+    -> Result<(Arc<dyn CryptoProcessor + Send + Sync>, usize), Error> {
     match alg1 {
         ENCRYPTION_ALGORITHM_AES=> Ok((AesProcessor::new(encryption_key), offset)),
         ENCRYPTION_ALGORITHM_CHACHA20 => {
             let mut iv = [0u8; 12];
             let end = offset + 12;
+            if end > data.len() {
+                return Err(build_corrupted_data_error("load_layer_processor"));
+            }
             iv.copy_from_slice(&data[offset..end]);
             Ok((ChachaProcessor::new(encryption_key, iv), end))
         },
+        ENCRYPTION_ALGORITHM_AES_CTR => {
+            let mut iv = [0u8; 16];
+            let end = offset + 16;
+            if end > data.len() {
+                return Err(build_corrupted_data_error("load_layer_processor"));
+            }
+            iv.copy_from_slice(&data[offset..end]);
+            Ok((AesCtrProcessor::new(encryption_key, iv), end))
+        },
         _ => Err(build_unsupported_algorithm_error())
     }
 }
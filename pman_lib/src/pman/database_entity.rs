@@ -3,21 +3,97 @@ use std::io::{Error, ErrorKind};
 use std::sync::{Arc, Mutex, MutexGuard};
 use std::time::SystemTime;
 use crate::error_builders::{build_corrupted_data_error, build_not_found_error};
+use crate::otp::{OtpCode, OtpParameters};
+use crate::pman::audit_log::{add_audit_record, is_audit_enabled};
 use crate::pman::id_value_map::id_value_map::ByteValue;
 use crate::pman::pman_database_file::PmanDatabaseFile;
-use crate::structs_interfaces::PasswordDatabaseEntity;
+use crate::secure_memory::SecureBytes;
+use crate::pman::templates::{field_type_from_u8, field_type_to_u8};
+use crate::structs_interfaces::{AuditEventType, AuditObjectType, EntityKind, EntityUrl, FieldType, PasswordDatabaseEntity, PropertyInfo,
+                                UrlMatchMode};
 
 pub const ENTITY_VERSION_LATEST: u32 = 0;
 
-#[derive(PartialEq, Eq, Debug)]
+// extra fields are stored after the entity history as (field type, value id) lists, one list per history item
+const EXTRA_FIELD_OTP: u8 = 1;
+// attachment is stored as name field followed by data field
+const EXTRA_FIELD_ATTACHMENT_NAME: u8 = 2;
+const EXTRA_FIELD_ATTACHMENT_DATA: u8 = 3;
+const EXTRA_FIELD_TAG: u8 = 4;
+// password expiry timestamp in seconds
+const EXTRA_FIELD_EXPIRES_AT: u8 = 5;
+const EXTRA_FIELD_MAX_AGE_DAYS: u8 = 6;
+// entity kind, absent for password entities
+const EXTRA_FIELD_KIND: u8 = 7;
+const EXTRA_FIELD_TEMPLATE: u8 = 8;
+
+const ENTITY_KIND_NOTE: u32 = 1;
+
+// notes have no user, user id field is set to NO_USER_ID
+pub const NO_USER_ID: u32 = 0;
+
+// extra fields count is stored as u8: otp + 2 * MAX_ATTACHMENTS + MAX_TAGS + expiry fields + kind + template <= 255
+pub const MAX_ATTACHMENTS: usize = 100;
+pub const MAX_TAGS: usize = 50;
+
+// history keeps the latest versions and the original version
+pub const MIN_HISTORY_LENGTH: usize = 2;
+pub const MAX_HISTORY_LENGTH: usize = 255;
+
+// property info is stored after extra fields as one byte per property, one list per history item:
+// field type | PROPERTY_FLAG_VISIBLE, entities without property info block have secret text properties
+const PROPERTY_FLAG_VISIBLE: u8 = 0x80;
+
+// url info is stored after history length as one list per history item:
+// entity url match mode (u8), additional urls count (u8), (url id, match mode) for each additional url
+pub const MAX_URLS: usize = 20;
+// match mode of urls of entities without url info block
+pub const DEFAULT_URL_MATCH_MODE: UrlMatchMode = UrlMatchMode::BaseDomain;
+
+const URL_MATCH_EXACT: u8 = 1;
+const URL_MATCH_HOST: u8 = 2;
+const URL_MATCH_BASE_DOMAIN: u8 = 3;
+const URL_MATCH_PREFIX: u8 = 4;
+const URL_MATCH_REGEX: u8 = 5;
+
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct EntityProperty {
+    pub name_id: u32,
+    // value id in passwords file for secret properties, in names file for visible ones
+    pub value_id: u32,
+    pub field_type: FieldType,
+    pub secret: bool
+}
+
+impl EntityProperty {
+    pub fn new(name_id: u32, value_id: u32) -> EntityProperty {
+        EntityProperty{name_id, value_id, field_type: FieldType::Text, secret: true}
+    }
+}
+
+#[derive(PartialEq, Eq, Debug, Clone)]
 pub struct PmanDatabaseEntityFields {
     password_id: u32,
     group_id: u32,
     user_id: u32,
     url_id: Option<u32>,
     created_at: u64,
-    // map property name id (in names file) -> property value id (in passwords file)
-    properties: HashMap<u32, u32>,
+    // properties in display order
+    properties: Vec<EntityProperty>,
+    // otpauth uri id (in passwords file)
+    otp_id: Option<u32>,
+    // map attachment name id (in names file) -> attachment data id (in passwords file)
+    attachments: HashMap<u32, u32>,
+    // tag ids (tag names are stored in tags list)
+    tags: HashSet<u32>,
+    expires_at: Option<u32>,
+    max_age_days: Option<u32>,
+    // note body is stored in password field
+    note: bool,
+    template_id: Option<u32>,
+    url_match_mode: UrlMatchMode,
+    // additional urls: url id (in names file), match mode
+    extra_urls: Vec<(u32, UrlMatchMode)>
 }
 
 impl PmanDatabaseEntityFields {
@@ -28,9 +104,9 @@ impl PmanDatabaseEntityFields {
         output.extend_from_slice(&self.url_id.unwrap_or(0).to_le_bytes());
         output.extend_from_slice(&self.created_at.to_le_bytes());
         output.push(self.properties.len() as u8);
-        for (k, v) in &self.properties {
-            output.extend_from_slice(&k.to_le_bytes());
-            output.extend_from_slice(&v.to_le_bytes());
+        for property in &self.properties {
+            output.extend_from_slice(&property.name_id.to_le_bytes());
+            output.extend_from_slice(&property.value_id.to_le_bytes());
         }
     }
 
@@ -60,7 +136,7 @@ impl PmanDatabaseEntityFields {
         if source.len() < offset + length * 8 {
             return Err(build_corrupted_data_error("PmanDatabaseEntityFields.from_bytes2"));
         }
-        let mut properties = HashMap::new();
+        let mut properties: Vec<EntityProperty> = Vec::new();
         while length > 0 {
             buffer32.copy_from_slice(&source[offset..offset+4]);
             offset += 4;
@@ -68,9 +144,10 @@ impl PmanDatabaseEntityFields {
             buffer32.copy_from_slice(&source[offset..offset+4]);
             offset += 4;
             let value = u32::from_le_bytes(buffer32);
-            if properties.insert(key, value).is_some() {
+            if properties.iter().any(|p|p.name_id == key) {
                 return Err(build_corrupted_data_error("PmanDatabaseEntityFields.from_bytes3"));
             }
+            properties.push(EntityProperty::new(key, value));
             length -= 1;
         }
         let fields = PmanDatabaseEntityFields{
@@ -79,23 +156,177 @@ impl PmanDatabaseEntityFields {
             user_id,
             url_id: if url_id == 0 { None } else { Some(url_id) },
             created_at,
-            properties
+            properties,
+            otp_id: None,
+            attachments: HashMap::new(),
+            tags: HashSet::new(),
+            expires_at: None,
+            max_age_days: None,
+            note: false,
+            template_id: None,
+            url_match_mode: DEFAULT_URL_MATCH_MODE,
+            extra_urls: Vec::new()
         };
         Ok((fields, offset))
     }
 
+    fn extra_fields_to_bytes(&self, output: &mut Vec<u8>) {
+        let mut extra_fields = Vec::new();
+        if let Some(otp_id) = self.otp_id {
+            extra_fields.push((EXTRA_FIELD_OTP, otp_id));
+        }
+        for (k, v) in &self.attachments {
+            extra_fields.push((EXTRA_FIELD_ATTACHMENT_NAME, *k));
+            extra_fields.push((EXTRA_FIELD_ATTACHMENT_DATA, *v));
+        }
+        for tag_id in &self.tags {
+            extra_fields.push((EXTRA_FIELD_TAG, *tag_id));
+        }
+        if let Some(expires_at) = self.expires_at {
+            extra_fields.push((EXTRA_FIELD_EXPIRES_AT, expires_at));
+        }
+        if let Some(max_age_days) = self.max_age_days {
+            extra_fields.push((EXTRA_FIELD_MAX_AGE_DAYS, max_age_days));
+        }
+        if self.note {
+            extra_fields.push((EXTRA_FIELD_KIND, ENTITY_KIND_NOTE));
+        }
+        if let Some(template_id) = self.template_id {
+            extra_fields.push((EXTRA_FIELD_TEMPLATE, template_id));
+        }
+        output.push(extra_fields.len() as u8);
+        for (field_type, value) in extra_fields {
+            output.push(field_type);
+            output.extend_from_slice(&value.to_le_bytes());
+        }
+    }
+
+    fn extra_fields_from_bytes(&mut self, source: &Vec<u8>, mut offset: usize) -> Result<usize, Error> {
+        if source.len() <= offset {
+            return Err(build_corrupted_data_error("PmanDatabaseEntityFields.extra_fields_from_bytes1"));
+        }
+        let mut length = source[offset] as usize;
+        offset += 1;
+        if source.len() < offset + length * 5 {
+            return Err(build_corrupted_data_error("PmanDatabaseEntityFields.extra_fields_from_bytes2"));
+        }
+        let mut buffer32 = [0u8; 4];
+        let mut attachment_name_id = None;
+        while length > 0 {
+            let field_type = source[offset];
+            buffer32.copy_from_slice(&source[offset+1..offset+5]);
+            offset += 5;
+            let value = u32::from_le_bytes(buffer32);
+            match (field_type, attachment_name_id) {
+                (EXTRA_FIELD_OTP, None) => self.otp_id = Some(value),
+                (EXTRA_FIELD_ATTACHMENT_NAME, None) => attachment_name_id = Some(value),
+                (EXTRA_FIELD_ATTACHMENT_DATA, Some(name_id)) => {
+                    if self.attachments.insert(name_id, value).is_some() {
+                        return Err(build_corrupted_data_error("PmanDatabaseEntityFields.extra_fields_from_bytes3"));
+                    }
+                    attachment_name_id = None;
+                }
+                (EXTRA_FIELD_EXPIRES_AT, None) => self.expires_at = Some(value),
+                (EXTRA_FIELD_MAX_AGE_DAYS, None) => self.max_age_days = Some(value),
+                (EXTRA_FIELD_KIND, None) if value == ENTITY_KIND_NOTE => self.note = true,
+                (EXTRA_FIELD_TEMPLATE, None) => self.template_id = Some(value),
+                (EXTRA_FIELD_TAG, None) => {
+                    if !self.tags.insert(value) {
+                        return Err(build_corrupted_data_error("PmanDatabaseEntityFields.extra_fields_from_bytes6"));
+                    }
+                }
+                _ => return Err(build_corrupted_data_error("PmanDatabaseEntityFields.extra_fields_from_bytes4"))
+            }
+            length -= 1;
+        }
+        if attachment_name_id.is_some() {
+            return Err(build_corrupted_data_error("PmanDatabaseEntityFields.extra_fields_from_bytes5"));
+        }
+        Ok(offset)
+    }
+
+    fn property_info_to_bytes(&self, output: &mut Vec<u8>) {
+        output.push(self.properties.len() as u8);
+        for property in &self.properties {
+            let flags = if property.secret { 0 } else { PROPERTY_FLAG_VISIBLE };
+            output.push(field_type_to_u8(property.field_type) | flags);
+        }
+    }
+
+    fn property_info_from_bytes(&mut self, source: &Vec<u8>, mut offset: usize) -> Result<usize, Error> {
+        if source.len() <= offset || source[offset] as usize != self.properties.len() {
+            return Err(build_corrupted_data_error("PmanDatabaseEntityFields.property_info_from_bytes1"));
+        }
+        offset += 1;
+        if source.len() < offset + self.properties.len() {
+            return Err(build_corrupted_data_error("PmanDatabaseEntityFields.property_info_from_bytes2"));
+        }
+        for property in &mut self.properties {
+            let info = source[offset];
+            offset += 1;
+            property.field_type = field_type_from_u8(info & !PROPERTY_FLAG_VISIBLE)?;
+            property.secret = info & PROPERTY_FLAG_VISIBLE == 0;
+        }
+        Ok(offset)
+    }
+
+    fn url_info_to_bytes(&self, output: &mut Vec<u8>) {
+        output.push(url_match_mode_to_u8(self.url_match_mode));
+        output.push(self.extra_urls.len() as u8);
+        for (url_id, match_mode) in &self.extra_urls {
+            output.extend_from_slice(&url_id.to_le_bytes());
+            output.push(url_match_mode_to_u8(*match_mode));
+        }
+    }
+
+    fn url_info_from_bytes(&mut self, source: &Vec<u8>, mut offset: usize) -> Result<usize, Error> {
+        if source.len() < offset + 2 {
+            return Err(build_corrupted_data_error("PmanDatabaseEntityFields.url_info_from_bytes1"));
+        }
+        self.url_match_mode = url_match_mode_from_u8(source[offset])?;
+        let mut length = source[offset + 1] as usize;
+        offset += 2;
+        if source.len() < offset + length * 5 {
+            return Err(build_corrupted_data_error("PmanDatabaseEntityFields.url_info_from_bytes2"));
+        }
+        let mut buffer32 = [0u8; 4];
+        while length > 0 {
+            buffer32.copy_from_slice(&source[offset..offset+4]);
+            let match_mode = url_match_mode_from_u8(source[offset + 4])?;
+            offset += 5;
+            self.extra_urls.push((u32::from_le_bytes(buffer32), match_mode));
+            length -= 1;
+        }
+        Ok(offset)
+    }
+
     fn collect_names_ids(&self, result: &mut HashSet<u32>) {
         if let Some(url_id) = self.url_id {
             result.insert(url_id);
         }
-        for (k, _v) in &self.properties {
+        for (url_id, _match_mode) in &self.extra_urls {
+            result.insert(*url_id);
+        }
+        for property in &self.properties {
+            result.insert(property.name_id);
+            if !property.secret {
+                result.insert(property.value_id);
+            }
+        }
+        for (k, _v) in &self.attachments {
             result.insert(*k);
         }
     }
 
     fn collect_passwords_ids(&self, result: &mut HashSet<u32>) {
         result.insert(self.password_id);
-        for (_k, v) in &self.properties {
+        for property in self.properties.iter().filter(|p|p.secret) {
+            result.insert(property.value_id);
+        }
+        if let Some(otp_id) = self.otp_id {
+            result.insert(otp_id);
+        }
+        for (_k, v) in &self.attachments {
             result.insert(*v);
         }
     }
@@ -103,8 +334,14 @@ impl PmanDatabaseEntityFields {
 
 pub struct PmanDatabaseEntity {
     database_file: Option<Arc<Mutex<PmanDatabaseFile>>>,
+    // entity record id for audit records, not stored
+    entity_id: u32,
     name_id: u32,
-    history: Vec<PmanDatabaseEntityFields>
+    history: Vec<PmanDatabaseEntityFields>,
+    // overrides database history length, stored after property info blocks, 0 - database default
+    history_length: Option<usize>,
+    // creation time of the purged original version, stored after url info blocks, 0 - not purged
+    purged_created_at: Option<u64>
 }
 
 impl ByteValue for PmanDatabaseEntity {
@@ -124,10 +361,50 @@ impl ByteValue for PmanDatabaseEntity {
             offset = new_offset;
             length -= 1;
         }
+        // entities without extra fields block were written by older versions
+        if offset != source.len() {
+            for item in &mut history {
+                offset = item.extra_fields_from_bytes(&source, offset)?;
+            }
+        }
+        // entities without property info block were written by older versions
+        if offset != source.len() {
+            for item in &mut history {
+                offset = item.property_info_from_bytes(&source, offset)?;
+            }
+        }
+        let mut history_length = None;
+        if offset != source.len() {
+            if source[offset] != 0 {
+                history_length = Some(source[offset] as usize);
+            }
+            offset += 1;
+        }
+        // entities without url info block were written by older versions
+        if offset != source.len() {
+            for item in &mut history {
+                offset = item.url_info_from_bytes(&source, offset)?;
+            }
+        }
+        // entities without creation time block were written by older versions
+        let mut purged_created_at = None;
+        if offset != source.len() {
+            if source.len() < offset + 8 {
+                return Err(build_corrupted_data_error("PmanDatabaseEntity.from_bytes"));
+            }
+            let mut buffer64 = [0u8; 8];
+            buffer64.copy_from_slice(&source[offset..offset+8]);
+            offset += 8;
+            let created_at = u64::from_le_bytes(buffer64);
+            if created_at != 0 {
+                purged_created_at = Some(created_at);
+            }
+        }
         if offset != source.len() {
             Err(build_corrupted_data_error("PmanDatabaseEntity.from_bytes"))
         } else {
-            Ok(Box::new(PmanDatabaseEntity { name_id, database_file: None, history }))
+            Ok(Box::new(PmanDatabaseEntity { name_id, database_file: None, entity_id: 0, history, history_length,
+                                             purged_created_at }))
         }
     }
 
@@ -138,6 +415,17 @@ impl ByteValue for PmanDatabaseEntity {
         for item in &self.history {
             item.to_bytes(&mut result);
         }
+        for item in &self.history {
+            item.extra_fields_to_bytes(&mut result);
+        }
+        for item in &self.history {
+            item.property_info_to_bytes(&mut result);
+        }
+        result.push(self.history_length.unwrap_or(0) as u8);
+        for item in &self.history {
+            item.url_info_to_bytes(&mut result);
+        }
+        result.extend_from_slice(&self.purged_created_at.unwrap_or(0).to_le_bytes());
         result
     }
 }
@@ -164,7 +452,10 @@ impl PasswordDatabaseEntity for PmanDatabaseEntity {
     fn get_password(&self, version: u32) -> Result<String, Error> {
         self.check_version(version)?;
         let id = self.history.get(version as usize).unwrap().password_id;
-        self.database_file.as_ref().unwrap().lock().unwrap().get_from_passwords(id)
+        let mut file = self.database_file.as_ref().unwrap().lock().unwrap();
+        let password = file.get_from_passwords(id)?;
+        self.audit_reveal(&mut file, version, if self.is_note() { "note" } else { "password" }.to_string())?;
+        Ok(password)
     }
 
     fn get_url(&self, version: u32) -> Result<Option<String>, Error> {
@@ -177,21 +468,46 @@ impl PasswordDatabaseEntity for PmanDatabaseEntity {
         Ok(None)
     }
 
-    fn get_property_names(&self, version: u32) -> Result<HashMap<String, u32>, Error> {
+    fn get_urls(&self, version: u32) -> Result<Vec<EntityUrl>, Error> {
         self.check_version(version)?;
-        let mut result = HashMap::new();
-        for (k, _v) in &self.history.get(version as usize).unwrap().properties {
-            let kk = *k;
-            let name = self.database_file.as_ref().unwrap().lock().unwrap().get_from_names(kk)?;
-            result.insert(name, kk);
+        let fields = self.history.get(version as usize).unwrap();
+        let file = self.database_file.as_ref().unwrap().lock().unwrap();
+        let mut result = Vec::new();
+        if let Some(id) = fields.url_id {
+            result.push(EntityUrl{url: file.get_from_names(id)?, match_mode: fields.url_match_mode});
+        }
+        for (url_id, match_mode) in &fields.extra_urls {
+            result.push(EntityUrl{url: file.get_from_names(*url_id)?, match_mode: *match_mode});
+        }
+        Ok(result)
+    }
+
+    fn get_property_names(&self, version: u32) -> Result<Vec<PropertyInfo>, Error> {
+        self.check_version(version)?;
+        let mut result = Vec::new();
+        for property in &self.history.get(version as usize).unwrap().properties {
+            let name = self.database_file.as_ref().unwrap().lock().unwrap().get_from_names(property.name_id)?;
+            result.push(PropertyInfo{
+                id: property.name_id,
+                name,
+                field_type: property.field_type,
+                secret: property.secret
+            });
         }
         Ok(result)
     }
 
     fn get_property_value(&self, version: u32, index: u32) -> Result<String, Error> {
         self.check_version(version)?;
-        if let Some(v) = self.history.get(version as usize).unwrap().properties.get(&index) {
-            return self.database_file.as_ref().unwrap().lock().unwrap().get_from_passwords(*v);
+        if let Some(property) = self.history.get(version as usize).unwrap().properties.iter()
+            .find(|p|p.name_id == index) {
+            let mut file = self.database_file.as_ref().unwrap().lock().unwrap();
+            let value = read_property_value(&mut file, property)?;
+            if property.secret {
+                let name = file.get_from_names(property.name_id)?;
+                self.audit_reveal(&mut file, version, name)?;
+            }
+            return Ok(value);
         }
         Err(build_not_found_error())
     }
@@ -200,56 +516,303 @@ impl PasswordDatabaseEntity for PmanDatabaseEntity {
         self.check_version(version)?;
         Ok(self.history.get(version as usize).unwrap().created_at)
     }
+
+    fn get_attachment_names(&self, version: u32) -> Result<HashMap<String, u32>, Error> {
+        self.check_version(version)?;
+        let mut result = HashMap::new();
+        for (k, _v) in &self.history.get(version as usize).unwrap().attachments {
+            let name = self.database_file.as_ref().unwrap().lock().unwrap().get_from_names(*k)?;
+            result.insert(name, *k);
+        }
+        Ok(result)
+    }
+
+    fn get_tag_ids(&self, version: u32) -> Result<Vec<u32>, Error> {
+        self.check_version(version)?;
+        let mut result: Vec<u32> = self.history.get(version as usize).unwrap().tags.iter()
+            .map(|id|*id).collect();
+        result.sort();
+        Ok(result)
+    }
+
+    fn get_expires_at(&self, version: u32) -> Result<Option<u64>, Error> {
+        self.check_version(version)?;
+        Ok(self.history.get(version as usize).unwrap().expires_at.map(|v|v as u64))
+    }
+
+    fn get_max_age_days(&self, version: u32) -> Result<Option<u32>, Error> {
+        self.check_version(version)?;
+        Ok(self.history.get(version as usize).unwrap().max_age_days)
+    }
+
+    fn get_kind(&self) -> EntityKind {
+        if self.is_note() { EntityKind::Note } else { EntityKind::Password }
+    }
+
+    fn get_template_id(&self, version: u32) -> Result<Option<u32>, Error> {
+        self.check_version(version)?;
+        Ok(self.history.get(version as usize).unwrap().template_id)
+    }
+
+    fn get_history_length(&self) -> Option<u32> {
+        self.history_length.map(|l|l as u32)
+    }
+
+    fn get_otp_code(&self, version: u32, timestamp: u64) -> Result<Option<OtpCode>, Error> {
+        self.check_version(version)?;
+        if let Some(id) = self.history.get(version as usize).unwrap().otp_id {
+            let uri: SecureBytes = self.database_file.as_ref().unwrap().lock().unwrap().get_from_passwords(id)?;
+            return Ok(Some(OtpParameters::parse_bytes(&uri)?.generate(timestamp)?));
+        }
+        Ok(None)
+    }
 }
 
 impl PmanDatabaseEntity {
     pub fn new(database_file: Arc<Mutex<PmanDatabaseFile>>, name_id: u32, password_id: u32, group_id: u32,
-               user_id: u32, url_id: Option<u32>, properties: HashMap<u32, u32>) -> PmanDatabaseEntity {
-        PmanDatabaseEntity{name_id, database_file: Some(database_file), history: vec![PmanDatabaseEntityFields{
+               user_id: u32, url_id: Option<u32>, properties: Vec<EntityProperty>) -> PmanDatabaseEntity {
+        PmanDatabaseEntity{name_id, database_file: Some(database_file), entity_id: 0, history: vec![PmanDatabaseEntityFields{
             password_id,
             group_id,
             user_id,
             url_id,
             created_at: get_current_timestamp(),
             properties,
-        }]}
+            otp_id: None,
+            attachments: HashMap::new(),
+            tags: HashSet::new(),
+            expires_at: None,
+            max_age_days: None,
+            note: false,
+            template_id: None,
+            url_match_mode: DEFAULT_URL_MATCH_MODE,
+            extra_urls: Vec::new()
+        }], history_length: None, purged_created_at: None}
+    }
+
+    pub fn new_from_template(database_file: Arc<Mutex<PmanDatabaseFile>>, name_id: u32, password_id: u32,
+                             group_id: u32, user_id: u32, url_id: Option<u32>, properties: Vec<EntityProperty>,
+                             template_id: u32) -> PmanDatabaseEntity {
+        let mut entity = PmanDatabaseEntity::new(database_file, name_id, password_id, group_id,
+                                                 user_id, url_id, properties);
+        entity.history[0].template_id = Some(template_id);
+        entity
+    }
+
+    // note body is stored in passwords file and versioned like a password
+    pub fn new_note(database_file: Arc<Mutex<PmanDatabaseFile>>, name_id: u32, body_id: u32,
+                    group_id: u32) -> PmanDatabaseEntity {
+        let mut entity = PmanDatabaseEntity::new(database_file, name_id, body_id, group_id,
+                                                 NO_USER_ID, None, Vec::new());
+        entity.history[0].note = true;
+        entity
     }
 
     pub fn update(&mut self, file: &mut MutexGuard<PmanDatabaseFile>, password_id: u32, group_id: u32, user_id: u32,
-                  url_id: Option<u32>, properties: HashMap<u32, u32>) -> Result<(), Error> {
-        self.history.insert(0, PmanDatabaseEntityFields{
-            password_id,
-            group_id,
-            user_id,
-            url_id,
-            created_at: get_current_timestamp(),
-            properties,
-        });
-        let max_length = file.get_history_length()?;
-        if self.history.len() > max_length {
-            let deleted = self.history.remove(max_length - 1);
-            let mut deleted_names_ids = HashSet::new();
-            deleted.collect_names_ids(&mut deleted_names_ids);
-            let mut deleted_passwords_ids = HashSet::new();
-            deleted.collect_passwords_ids(&mut deleted_passwords_ids);
-            let active_names_ids = self.collect_names_ids();
-            let active_passwords_ids = self.collect_passwords_ids();
-            let to_be_deleled_names_ids =
-                get_unused_ids(active_names_ids, deleted_names_ids);
-            let to_be_deleled_passwords_ids =
-                get_unused_ids(active_passwords_ids, deleted_passwords_ids);
-            for id in to_be_deleled_names_ids {
-                file.remove_from_names(&id)?;
+                  url_id: Option<u32>, properties: Vec<EntityProperty>, otp_id: Option<u32>) -> Result<(), Error> {
+        let mut fields = self.history.get(0).unwrap().clone();
+        fields.password_id = password_id;
+        fields.group_id = group_id;
+        fields.user_id = user_id;
+        fields.url_id = url_id;
+        fields.created_at = get_current_timestamp();
+        fields.properties = properties;
+        fields.otp_id = otp_id;
+        self.add_history_item(file, fields)
+    }
+
+    // attachments - map attachment name id -> attachment data id,
+    // adds a new version that counts against the history length like a password change
+    pub fn update_attachments(&mut self, file: &mut MutexGuard<PmanDatabaseFile>,
+                              attachments: HashMap<u32, u32>) -> Result<(), Error> {
+        if attachments.len() > MAX_ATTACHMENTS {
+            return Err(Error::new(ErrorKind::InvalidInput, "too many attachments"));
+        }
+        let mut fields = self.history.get(0).unwrap().clone();
+        fields.created_at = get_current_timestamp();
+        fields.attachments = attachments;
+        self.add_history_item(file, fields)
+    }
+
+    pub fn update_tags(&mut self, file: &mut MutexGuard<PmanDatabaseFile>,
+                       tags: HashSet<u32>) -> Result<(), Error> {
+        if tags.len() > MAX_TAGS {
+            return Err(Error::new(ErrorKind::InvalidInput, "too many tags"));
+        }
+        self.update_latest(file, |fields|fields.tags = tags)
+    }
+
+    // expires_at - timestamp in seconds, expiry is changed in the latest version, so it does not look
+    // like a password change
+    pub fn update_expiry(&mut self, file: &mut MutexGuard<PmanDatabaseFile>, expires_at: Option<u64>,
+                         max_age_days: Option<u32>) -> Result<(), Error> {
+        let expires_at = match expires_at {
+            Some(v) => Some(u32::try_from(v)
+                .map_err(|_e|Error::new(ErrorKind::InvalidInput, "invalid expiry timestamp"))?),
+            None => None
+        };
+        self.update_latest(file, |fields|{
+            fields.expires_at = expires_at;
+            fields.max_age_days = max_age_days;
+        })
+    }
+
+    // rewrite_history = false adds new version when the latest version references user_id,
+    // rewrite_history = true replaces user_id in all versions without adding a new one,
+    // returns true when the entity was changed
+    pub fn replace_user_id(&mut self, file: &mut MutexGuard<PmanDatabaseFile>, user_id: u32, new_user_id: u32,
+                           rewrite_history: bool) -> Result<bool, Error> {
+        self.replace_id(file, rewrite_history, |fields|{
+            if fields.user_id != user_id {
+                return false;
+            }
+            fields.user_id = new_user_id;
+            true
+        })
+    }
+
+    // works the same way as replace_user_id
+    pub fn replace_group_id(&mut self, file: &mut MutexGuard<PmanDatabaseFile>, group_id: u32, new_group_id: u32,
+                            rewrite_history: bool) -> Result<bool, Error> {
+        self.replace_id(file, rewrite_history, |fields|{
+            if fields.group_id != group_id {
+                return false;
             }
-            for id in to_be_deleled_passwords_ids {
-                file.remove_from_passwords(&id)?;
+            fields.group_id = new_group_id;
+            true
+        })
+    }
+
+    fn replace_id(&mut self, file: &mut MutexGuard<PmanDatabaseFile>, rewrite_history: bool,
+                  replace: impl Fn(&mut PmanDatabaseEntityFields) -> bool) -> Result<bool, Error> {
+        if rewrite_history {
+            let mut changed = false;
+            for fields in &mut self.history {
+                changed |= replace(fields);
             }
+            return Ok(changed);
+        }
+        let mut fields = self.history.get(0).unwrap().clone();
+        if !replace(&mut fields) {
+            return Ok(false);
+        }
+        fields.created_at = get_current_timestamp();
+        self.add_history_item(file, fields)?;
+        Ok(true)
+    }
+
+    // extra_urls - (url id, match mode) pairs of additional urls, urls are changed in the latest version
+    pub fn update_urls(&mut self, file: &mut MutexGuard<PmanDatabaseFile>, url_id: Option<u32>,
+                       url_match_mode: UrlMatchMode, extra_urls: Vec<(u32, UrlMatchMode)>) -> Result<(), Error> {
+        if extra_urls.len() + 1 > MAX_URLS {
+            return Err(Error::new(ErrorKind::InvalidInput, "too many urls"));
+        }
+        self.update_latest(file, |fields|{
+            fields.url_id = url_id;
+            fields.url_match_mode = url_match_mode;
+            fields.extra_urls = extra_urls;
+        })
+    }
+
+    // new head item reuses record ids of the given version
+    pub fn restore_version(&mut self, file: &mut MutexGuard<PmanDatabaseFile>, version: u32) -> Result<(), Error> {
+        self.check_version(version)?;
+        if version == ENTITY_VERSION_LATEST {
+            return Err(Error::new(ErrorKind::InvalidInput, "latest version cannot be restored"));
+        }
+        let mut fields = self.history.get(version as usize).unwrap().clone();
+        fields.created_at = get_current_timestamp();
+        self.add_history_item(file, fields)
+    }
+
+    // length - None resets history length to the database default
+    pub fn set_history_length(&mut self, file: &mut MutexGuard<PmanDatabaseFile>,
+                              length: Option<usize>) -> Result<(), Error> {
+        if let Some(l) = length {
+            check_history_length(l)?;
+        }
+        self.history_length = length;
+        self.prune_history(file)
+    }
+
+    // removes history items over the history length, original version is kept
+    pub fn prune_history(&mut self, file: &mut MutexGuard<PmanDatabaseFile>) -> Result<(), Error> {
+        let max_length = match self.history_length {
+            Some(l) => l,
+            None => file.get_history_length()?
+        };
+        let mut deleted = Vec::new();
+        while self.history.len() > max_length {
+            deleted.push(self.history.remove(max_length - 1));
+        }
+        self.remove_unused_records(file, deleted)
+    }
+
+    // removes all versions except the latest one, entity creation time is kept in purged_created_at
+    pub fn purge_history(&mut self, file: &mut MutexGuard<PmanDatabaseFile>) -> Result<(), Error> {
+        if self.history.len() == 1 {
+            return Ok(());
+        }
+        self.purged_created_at = Some(self.get_entity_created_at());
+        let deleted = self.history.split_off(1);
+        self.remove_unused_records(file, deleted)
+    }
+
+    // metadata changes are applied to the latest version without adding a new one,
+    // so they don't push password versions out of the history
+    fn update_latest(&mut self, file: &mut MutexGuard<PmanDatabaseFile>,
+                     update: impl FnOnce(&mut PmanDatabaseEntityFields)) -> Result<(), Error> {
+        let previous = self.history[0].clone();
+        update(&mut self.history[0]);
+        self.remove_unused_records(file, vec![previous])
+    }
+
+    fn add_history_item(&mut self, file: &mut MutexGuard<PmanDatabaseFile>,
+                        fields: PmanDatabaseEntityFields) -> Result<(), Error> {
+        self.history.insert(0, fields);
+        self.prune_history(file)
+    }
+
+    // removes records of deleted history items that are not used by remaining items
+    fn remove_unused_records(&self, file: &mut MutexGuard<PmanDatabaseFile>,
+                             deleted: Vec<PmanDatabaseEntityFields>) -> Result<(), Error> {
+        if deleted.is_empty() {
+            return Ok(());
+        }
+        let mut deleted_names_ids = HashSet::new();
+        let mut deleted_passwords_ids = HashSet::new();
+        for item in &deleted {
+            item.collect_names_ids(&mut deleted_names_ids);
+            item.collect_passwords_ids(&mut deleted_passwords_ids);
+        }
+        let active_names_ids = self.collect_names_ids();
+        let active_passwords_ids = self.collect_passwords_ids();
+        let to_be_deleled_names_ids =
+            get_unused_ids(active_names_ids, deleted_names_ids);
+        let to_be_deleled_passwords_ids =
+            get_unused_ids(active_passwords_ids, deleted_passwords_ids);
+        for id in to_be_deleled_names_ids {
+            file.remove_from_names(&id)?;
+        }
+        for id in to_be_deleled_passwords_ids {
+            file.remove_from_passwords(&id)?;
         }
         Ok(())
     }
     
-    pub fn set_database_file(&mut self, database_file: Arc<Mutex<PmanDatabaseFile>>) {
+    pub fn set_database_file(&mut self, database_file: Arc<Mutex<PmanDatabaseFile>>, entity_id: u32) {
         self.database_file = Some(database_file);
+        self.entity_id = entity_id;
+    }
+
+    // logs secret reveal when reveals logging is enabled, details - revealed field name
+    fn audit_reveal(&self, file: &mut MutexGuard<PmanDatabaseFile>, version: u32, details: String) -> Result<(), Error> {
+        if !is_audit_enabled(file, AuditEventType::Reveal)? {
+            return Ok(());
+        }
+        let name = file.get_from_names(self.name_id)?;
+        let details = if version == ENTITY_VERSION_LATEST { details } else { format!("{} (version {})", details, version) };
+        add_audit_record(file, AuditEventType::Reveal, AuditObjectType::Entity, self.entity_id, name, details)
     }
 
     pub fn collect_names_ids(&self) -> Vec<u32> {
@@ -269,6 +832,22 @@ impl PmanDatabaseEntity {
         result.into_iter().collect()
     }
 
+    pub fn collect_group_ids(&self) -> HashSet<u32> {
+        self.history.iter().map(|e|e.group_id).collect()
+    }
+
+    pub fn collect_user_ids(&self) -> HashSet<u32> {
+        self.history.iter().filter(|e|!e.note).map(|e|e.user_id).collect()
+    }
+
+    pub fn collect_template_ids(&self) -> HashSet<u32> {
+        self.history.iter().filter_map(|e|e.template_id).collect()
+    }
+
+    pub fn collect_tag_ids(&self) -> HashSet<u32> {
+        self.history.iter().flat_map(|e|e.tags.iter().map(|id|*id)).collect()
+    }
+
     pub fn contains_group_id(&self, group_id: u32) -> bool {
         self.history.iter().find(|e|e.group_id == group_id).is_some()
     }
@@ -288,17 +867,74 @@ impl PmanDatabaseEntity {
         self.name_id
     }
 
+    pub fn is_note(&self) -> bool {
+        self.history.get(0).unwrap().note
+    }
+
     pub fn get_password_id(&self) -> u32 {
         self.history.get(0).unwrap().password_id
     }
 
-    pub fn get_properties(&self) -> HashMap<u32, u32> {
+    pub fn get_properties(&self) -> Vec<EntityProperty> {
         self.history.get(0).unwrap().properties.clone()
     }
 
     pub fn get_url_id(&self) -> Option<u32> {
         self.history.get(0).unwrap().url_id
     }
+
+    pub fn get_latest_template_id(&self) -> Option<u32> {
+        self.history.get(0).unwrap().template_id
+    }
+
+    // entity url id followed by additional url ids
+    pub fn get_url_ids(&self) -> Vec<u32> {
+        let fields = self.history.get(0).unwrap();
+        fields.url_id.into_iter()
+            .chain(fields.extra_urls.iter().map(|(id, _match_mode)|*id))
+            .collect()
+    }
+
+    pub fn get_otp_id(&self) -> Option<u32> {
+        self.history.get(0).unwrap().otp_id
+    }
+
+    pub fn get_attachments(&self) -> HashMap<u32, u32> {
+        self.history.get(0).unwrap().attachments.clone()
+    }
+
+    pub fn get_tags(&self) -> HashSet<u32> {
+        self.history.get(0).unwrap().tags.clone()
+    }
+
+    // creation time of the original version, kept when history is purged
+    pub fn get_entity_created_at(&self) -> u64 {
+        self.purged_created_at.unwrap_or(self.history.last().unwrap().created_at)
+    }
+
+    // returns created_at of the oldest history item with the current password
+    pub fn get_password_changed_at(&self) -> u64 {
+        let password_id = self.get_password_id();
+        self.history.iter()
+            .take_while(|e|e.password_id == password_id)
+            .last()
+            .unwrap()
+            .created_at
+    }
+
+    pub fn get_attachment_id(&self, version: u32, attachment_id: u32) -> Result<u32, Error> {
+        self.check_version(version)?;
+        self.history.get(version as usize).unwrap().attachments.get(&attachment_id)
+            .map(|id|*id)
+            .ok_or(Error::new(ErrorKind::NotFound, "attachment not found"))
+    }
+}
+
+pub fn check_history_length(length: usize) -> Result<(), Error> {
+    if length < MIN_HISTORY_LENGTH || length > MAX_HISTORY_LENGTH {
+        return Err(Error::new(ErrorKind::InvalidInput, "invalid history length"));
+    }
+    Ok(())
 }
 
 fn get_unused_ids(active_ids: Vec<u32>, deleted_ids: HashSet<u32>) -> Vec<u32> {
@@ -306,20 +942,69 @@ fn get_unused_ids(active_ids: Vec<u32>, deleted_ids: HashSet<u32>) -> Vec<u32> {
     deleted_ids.into_iter().filter(|i|!active.contains(i)).collect()
 }
 
-fn get_current_timestamp() -> u64 {
+pub fn read_property_value(file: &mut MutexGuard<PmanDatabaseFile>, property: &EntityProperty) -> Result<String, Error> {
+    if property.secret {
+        file.get_from_passwords(property.value_id)
+    } else {
+        file.get_from_names(property.value_id)
+    }
+}
+
+fn url_match_mode_to_u8(match_mode: UrlMatchMode) -> u8 {
+    match match_mode {
+        UrlMatchMode::Exact => URL_MATCH_EXACT,
+        UrlMatchMode::Host => URL_MATCH_HOST,
+        UrlMatchMode::BaseDomain => URL_MATCH_BASE_DOMAIN,
+        UrlMatchMode::Prefix => URL_MATCH_PREFIX,
+        UrlMatchMode::Regex => URL_MATCH_REGEX
+    }
+}
+
+fn url_match_mode_from_u8(match_mode: u8) -> Result<UrlMatchMode, Error> {
+    match match_mode {
+        URL_MATCH_EXACT => Ok(UrlMatchMode::Exact),
+        URL_MATCH_HOST => Ok(UrlMatchMode::Host),
+        URL_MATCH_BASE_DOMAIN => Ok(UrlMatchMode::BaseDomain),
+        URL_MATCH_PREFIX => Ok(UrlMatchMode::Prefix),
+        URL_MATCH_REGEX => Ok(UrlMatchMode::Regex),
+        _ => Err(build_corrupted_data_error("url_match_mode_from_u8"))
+    }
+}
+
+#[cfg(not(test))]
+pub fn get_current_timestamp() -> u64 {
+    SystemTime::now().duration_since(SystemTime::UNIX_EPOCH).unwrap().as_secs()
+}
+
+// tests move the clock forward with advance_test_clock instead of sleeping,
+// the offset is per thread so parallel tests don't affect each other
+#[cfg(test)]
+thread_local! {
+    static TEST_CLOCK_OFFSET: std::cell::Cell<u64> = const { std::cell::Cell::new(0) };
+}
+
+#[cfg(test)]
+pub fn get_current_timestamp() -> u64 {
     SystemTime::now().duration_since(SystemTime::UNIX_EPOCH).unwrap().as_secs()
+        + TEST_CLOCK_OFFSET.with(|offset|offset.get())
+}
+
+#[cfg(test)]
+pub fn advance_test_clock(seconds: u64) {
+    TEST_CLOCK_OFFSET.with(|offset|offset.set(offset.get() + seconds));
 }
 
 #[cfg(test)]
 mod tests {
-    use std::collections::HashMap;
+    use std::collections::{HashMap, HashSet};
     use std::io::Error;
     use std::sync::{Arc, Mutex};
     use rand::RngCore;
     use rand::rngs::OsRng;
-    use crate::pman::database_entity::PmanDatabaseEntity;
+    use crate::pman::database_entity::{EntityProperty, PmanDatabaseEntity};
     use crate::pman::id_value_map::id_value_map::ByteValue;
     use crate::pman::pman_database_file::PmanDatabaseFile;
+    use crate::structs_interfaces::{FieldType, UrlMatchMode};
 
     #[test]
     fn test_database_entity() -> Result<(), Error> {
@@ -331,13 +1016,23 @@ mod tests {
         let hash2_vec = Vec::from(hash2);
         let db =
             Arc::new(Mutex::new(PmanDatabaseFile::new(hash1_vec.clone(), hash2_vec.clone())?));
-        let mut entity1 = PmanDatabaseEntity::new(db.clone(), 1, 2, 3, 4, None, HashMap::new());
+        let mut entity1 = PmanDatabaseEntity::new(db.clone(), 1, 2, 3, 4, None, Vec::new());
+        let visible = EntityProperty{name_id: 112, value_id: 113, field_type: FieldType::Email, secret: false};
         entity1.update(&mut db.lock().unwrap(),66, 77, 88, Some(99),
-                       HashMap::from([(110, 111), (112, 113)]))?;
+                       vec![EntityProperty::new(110, 111), visible], Some(114))?;
+        entity1.update_attachments(&mut db.lock().unwrap(), HashMap::from([(115, 116), (117, 118)]))?;
+        entity1.update_tags(&mut db.lock().unwrap(), HashSet::from([119, 120]))?;
+        entity1.update_expiry(&mut db.lock().unwrap(), Some(1700000000), Some(90))?;
+        entity1.update_urls(&mut db.lock().unwrap(), Some(99), UrlMatchMode::Host,
+                            vec![(121, UrlMatchMode::Regex), (122, UrlMatchMode::Exact)])?;
+        entity1.set_history_length(&mut db.lock().unwrap(), Some(10))?;
+        entity1.purged_created_at = Some(1600000000);
         let entity2 = PmanDatabaseEntity::new(db, 5, 6, 7, 8, Some(9),
-                                              HashMap::from([(10, 11), (12, 13)]));
+                                              vec![EntityProperty::new(12, 13), EntityProperty::new(10, 11)]);
         let e1 = PmanDatabaseEntity::from_bytes(entity1.to_bytes())?;
         assert_eq!(entity1.history, e1.history);
+        assert_eq!(e1.history_length, Some(10));
+        assert_eq!(e1.purged_created_at, Some(1600000000));
         let e2 = PmanDatabaseEntity::from_bytes(entity2.to_bytes())?;
         assert_eq!(entity2.history, e2.history);
         Ok(())
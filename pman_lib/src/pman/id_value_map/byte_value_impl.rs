@@ -1,6 +1,7 @@
 use std::io::{Error, ErrorKind};
 use crate::error_builders::build_corrupted_data_error;
 use crate::pman::id_value_map::id_value_map::ByteValue;
+use crate::secure_memory::SecureBytes;
 
 impl ByteValue for Vec<u8> {
     fn from_bytes(source: Vec<u8>) -> Result<Box<Vec<u8>>, Error> {
@@ -12,6 +13,16 @@ impl ByteValue for Vec<u8> {
     }
 }
 
+impl ByteValue for SecureBytes {
+    fn from_bytes(source: Vec<u8>) -> Result<Box<SecureBytes>, Error> {
+        Ok(Box::new(SecureBytes::new(source)))
+    }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        self.to_vec()
+    }
+}
+
 impl ByteValue for String {
     fn from_bytes(source: Vec<u8>) -> Result<Box<String>, Error> {
         String::from_utf8(source)
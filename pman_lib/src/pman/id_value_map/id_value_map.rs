@@ -4,6 +4,7 @@ use std::sync::Arc;
 use uniffi::deps::bytes::BufMut;
 use crate::crypto::CryptoProcessor;
 use crate::error_builders::{build_corrupted_data_error, build_not_found_error};
+use crate::secure_memory::{SecureBytes, wipe};
 
 pub trait ByteValue {
     fn from_bytes(source: Vec<u8>) -> Result<Box<Self>, Error>;
@@ -66,7 +67,7 @@ impl IdValueMap {
     }
 
     pub fn add<T: ByteValue>(&mut self, value: T) -> Result<u32, Error> {
-        let v = self.processor.encode(value.to_bytes())?;
+        let v = self.processor.encode(&SecureBytes::new(value.to_bytes()))?;
         let id = self.next_id;
         self.map.insert(id, v);
         self.next_id += 1;
@@ -77,7 +78,7 @@ impl IdValueMap {
         if self.map.contains_key(&id) {
             return Err(Error::new(ErrorKind::InvalidInput, "record already exists"));
         }
-        let v = self.processor.encode(value.to_bytes())?;
+        let v = self.processor.encode(&SecureBytes::new(value.to_bytes()))?;
         self.map.insert(id, v);
         if id >= self.next_id {
             self.next_id = id + 1;
@@ -86,8 +87,11 @@ impl IdValueMap {
     }
 
     pub fn set<T: ByteValue>(&mut self, id: u32, value: T) -> Result<(), Error> {
-        let v = self.processor.encode(value.to_bytes())?;
+        let v = self.processor.encode(&SecureBytes::new(value.to_bytes()))?;
         self.map.insert(id, v);
+        if id >= self.next_id {
+            self.next_id = id + 1;
+        }
         Ok(())
     }
 
@@ -144,8 +148,8 @@ impl IdValueMap {
         for (key, value) in &self.map {
             let k = *key;
             output.put_u32_le(k);
-            let decoded = self.processor.decode(value)?;
-            let encoded = encode_processor.encode(decoded)?;
+            let decoded = SecureBytes::new(self.processor.decode(value)?);
+            let encoded = encode_processor.encode(&decoded)?;
             output.put_u32_le(encoded.len() as u32);
             output.put_slice(&encoded);
             new_map.insert(k, encoded);
@@ -158,6 +162,26 @@ impl IdValueMap {
     pub fn get_records_count(&self)  -> usize {
         self.map.len()
     }
+
+    pub fn get_ids(&self) -> Vec<u32> {
+        self.map.keys().map(|k|*k).collect()
+    }
+
+    // stores the value as is, used to simulate corrupted records
+    #[cfg(test)]
+    pub fn set_raw(&mut self, id: u32, value: Vec<u8>) {
+        self.map.insert(id, value);
+    }
+
+    // checks that the record can be decrypted
+    pub fn check(&self, id: u32) -> Result<(), Error> {
+        if let Some(v) = self.map.get(&id) {
+            wipe(&mut self.processor.decode(v)?);
+            Ok(())
+        } else {
+            Err(build_not_found_error())
+        }
+    }
 }
 
 #[cfg(test)]
@@ -4,4 +4,6 @@ pub const ENCRYPTION_ALGORITHM1_PROPERTIES_ID: u32 = 3;
 pub const ENCRYPTION_ALGORITHM2_PROPERTIES_ID: u32 = 4;
 pub const FILE_LOCATION_ID: u32 = 5;
 
-pub const HISTORY_LENGTH_ID: u32 = 6;
\ No newline at end of file
+pub const HISTORY_LENGTH_ID: u32 = 6;
+pub const KEY_SLOTS_ID: u32 = 7;
+pub const MEMBERS_ID: u32 = 8;
\ No newline at end of file
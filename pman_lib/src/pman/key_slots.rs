@@ -0,0 +1,550 @@
+/*
+
+key slots (database version 2.0+, stored in the database header)
+|-KEY_SLOTS_ID -> list of key slot ids
+|-key slot id -> key slot
+|     slot type (passwords pair, recovery key, key file, member)
+|     key derivation algorithm properties (supported: argon2, x25519 for member slots)
+|     hmacsha256 for master keys (using key encryption key)
+|     master keys (encrypted with key encryption key, aes)
+
+master keys are random and are used instead of password hash based keys:
+    map1_encryption_key1, map1_encryption_key2, map2_encryption_key1, map2_encryption_key2
+
+key encryption key = kdf(slot secret), where slot secret is
+    passwords pair -> sha256(password1_hash+password2_hash)
+    recovery key   -> recovery key bytes
+    key file       -> sha256(key file contents)
+
+member key slots (stored in the database header)
+|-MEMBERS_ID -> list of member ids
+|-member id -> member
+|     key slot id (u32)
+|     member name
+member key slot kdf properties: KDF_X25519, ephemeral public key, member public key
+member key encryption key = sha256(x25519(ephemeral private key, member public key) + kdf properties without type),
+member unlocks the slot with x25519(member private key, ephemeral public key)
+master keys cannot be rewrapped for recovery key and key file slots, so they are removed when keys are rotated
+
+*/
+
+use std::collections::HashMap;
+use std::fmt;
+use std::io::{Error, ErrorKind};
+use curve25519_dalek::montgomery::MontgomeryPoint;
+use hmac::digest::KeyInit;
+use hmac::{Hmac, Mac};
+use rand::RngCore;
+use rand::rngs::OsRng;
+use sha2::{Sha256, Digest};
+use crate::crypto::AesProcessor;
+use crate::error_builders::{build_corrupted_data_error, build_not_found_error};
+use crate::pman::id_value_map::id_value_map::{ByteValue, IdValueMap};
+use crate::pman::ids::{HASH_ALGORITHM_PROPERTIES_ID, KEY_SLOTS_ID, MEMBERS_ID};
+use crate::pman::pman_database_file::{build_argon2_key, build_argon2_salt, set_argon2_salt,
+                                      HASH_ALGORITHM_ARGON2};
+use crate::secure_memory::{wipe, SecureBox, SecureBytes};
+use crate::structs_interfaces::{DatabaseMember, KeySlotType, MemberKeyPair};
+
+pub const KEY_SLOT_PASSWORDS: u8 = 1;
+pub const KEY_SLOT_RECOVERY_KEY: u8 = 2;
+pub const KEY_SLOT_KEY_FILE: u8 = 3;
+pub const KEY_SLOT_MEMBER: u8 = 4;
+
+pub const RECOVERY_KEY_LENGTH: usize = 32;
+pub const X25519_KEY_LENGTH: usize = 32;
+
+const KDF_X25519: u8 = 100;
+const X25519_KDF_PROPERTIES_LENGTH: usize = 1 + X25519_KEY_LENGTH * 2;
+
+const MASTER_KEYS_LENGTH: usize = 128;
+
+type HmacSha256 = Hmac<Sha256>;
+
+// master keys live in locked memory (SecureBox) and are only borrowed from there,
+// the struct is neither Copy nor Clone and is wiped on drop
+#[derive(PartialEq, Eq)]
+pub struct MasterKeys {
+    pub map1_encryption_key1: [u8; 32],
+    pub map1_encryption_key2: [u8; 32],
+    pub map2_encryption_key1: [u8; 32],
+    pub map2_encryption_key2: [u8; 32]
+}
+
+impl fmt::Debug for MasterKeys {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("MasterKeys { .. }")
+    }
+}
+
+impl Drop for MasterKeys {
+    fn drop(&mut self) {
+        wipe(&mut self.map1_encryption_key1);
+        wipe(&mut self.map1_encryption_key2);
+        wipe(&mut self.map2_encryption_key1);
+        wipe(&mut self.map2_encryption_key2);
+    }
+}
+
+impl MasterKeys {
+    pub fn new() -> SecureBox<MasterKeys> {
+        let mut keys = MasterKeys::empty();
+        OsRng.fill_bytes(&mut keys.map1_encryption_key1);
+        OsRng.fill_bytes(&mut keys.map1_encryption_key2);
+        OsRng.fill_bytes(&mut keys.map2_encryption_key1);
+        OsRng.fill_bytes(&mut keys.map2_encryption_key2);
+        keys
+    }
+
+    // all keys are zero, fields are filled in place
+    pub fn empty() -> SecureBox<MasterKeys> {
+        SecureBox::new(MasterKeys{
+            map1_encryption_key1: [0u8; 32],
+            map1_encryption_key2: [0u8; 32],
+            map2_encryption_key1: [0u8; 32],
+            map2_encryption_key2: [0u8; 32]
+        })
+    }
+
+    pub fn copy_from(&mut self, source: &MasterKeys) {
+        self.map1_encryption_key1.copy_from_slice(&source.map1_encryption_key1);
+        self.map1_encryption_key2.copy_from_slice(&source.map1_encryption_key2);
+        self.map2_encryption_key1.copy_from_slice(&source.map2_encryption_key1);
+        self.map2_encryption_key2.copy_from_slice(&source.map2_encryption_key2);
+    }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut result = Vec::new();
+        result.extend_from_slice(&self.map1_encryption_key1);
+        result.extend_from_slice(&self.map1_encryption_key2);
+        result.extend_from_slice(&self.map2_encryption_key1);
+        result.extend_from_slice(&self.map2_encryption_key2);
+        result
+    }
+
+    fn from_bytes(source: &Vec<u8>) -> Result<SecureBox<MasterKeys>, Error> {
+        if source.len() != MASTER_KEYS_LENGTH {
+            return Err(build_corrupted_data_error("MasterKeys.from_bytes"));
+        }
+        let mut keys = MasterKeys::empty();
+        keys.map1_encryption_key1.copy_from_slice(&source[0..32]);
+        keys.map1_encryption_key2.copy_from_slice(&source[32..64]);
+        keys.map2_encryption_key1.copy_from_slice(&source[64..96]);
+        keys.map2_encryption_key2.copy_from_slice(&source[96..128]);
+        Ok(keys)
+    }
+}
+
+pub struct KeySlot {
+    slot_type: u8,
+    kdf_properties: Vec<u8>,
+    mac: Vec<u8>,
+    wrapped_keys: Vec<u8>
+}
+
+impl ByteValue for KeySlot {
+    fn from_bytes(source: Vec<u8>) -> Result<Box<KeySlot>, Error> {
+        if source.len() < 2 {
+            return Err(build_corrupted_data_error("KeySlot.from_bytes1"));
+        }
+        let slot_type = source[0];
+        let kdf_length = source[1] as usize;
+        let mut offset = 2;
+        if source.len() < offset + kdf_length + 32 {
+            return Err(build_corrupted_data_error("KeySlot.from_bytes2"));
+        }
+        let kdf_properties = source[offset..offset+kdf_length].to_vec();
+        offset += kdf_length;
+        let mac = source[offset..offset+32].to_vec();
+        offset += 32;
+        let wrapped_keys = source[offset..].to_vec();
+        if wrapped_keys.is_empty() || wrapped_keys.len() % 16 != 0 {
+            return Err(build_corrupted_data_error("KeySlot.from_bytes3"));
+        }
+        Ok(Box::new(KeySlot{ slot_type, kdf_properties, mac, wrapped_keys }))
+    }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut result = vec![self.slot_type, self.kdf_properties.len() as u8];
+        result.extend_from_slice(&self.kdf_properties);
+        result.extend_from_slice(&self.mac);
+        result.extend_from_slice(&self.wrapped_keys);
+        result
+    }
+}
+
+impl KeySlot {
+    pub fn new(slot_type: u8, kdf_properties: Vec<u8>, secret: &Vec<u8>, keys: &MasterKeys)
+        -> Result<KeySlot, Error> {
+        let key_encryption_key = build_key_encryption_key(&kdf_properties, secret)?;
+        KeySlot::wrap(slot_type, kdf_properties, key_encryption_key, keys)
+    }
+
+    pub fn new_member(public_key: &Vec<u8>, keys: &MasterKeys) -> Result<KeySlot, Error> {
+        let member_key = parse_x25519_key(public_key)?;
+        let mut ephemeral_key = [0u8; X25519_KEY_LENGTH];
+        OsRng.fill_bytes(&mut ephemeral_key);
+        let ephemeral_public_key = MontgomeryPoint::mul_base_clamped(ephemeral_key);
+        let shared_secret = MontgomeryPoint(member_key).mul_clamped(ephemeral_key);
+        ephemeral_key.fill(0);
+        let mut kdf_properties = vec![KDF_X25519];
+        kdf_properties.extend_from_slice(ephemeral_public_key.as_bytes());
+        kdf_properties.extend_from_slice(&member_key);
+        let key_encryption_key = SecureBox::new(build_x25519_key_encryption_key(&shared_secret, &kdf_properties)?);
+        KeySlot::wrap(KEY_SLOT_MEMBER, kdf_properties, key_encryption_key, keys)
+    }
+
+    fn wrap(slot_type: u8, kdf_properties: Vec<u8>, key_encryption_key: SecureBox<[u8; 32]>, keys: &MasterKeys)
+        -> Result<KeySlot, Error> {
+        let data = SecureBytes::new(keys.to_bytes());
+        let mac = build_mac(&key_encryption_key, &data)?;
+        let wrapped_keys = AesProcessor::new(*key_encryption_key).encode(&data)?;
+        Ok(KeySlot{ slot_type, kdf_properties, mac, wrapped_keys })
+    }
+
+    // returns None when secret does not match this slot
+    pub fn unlock(&self, secret: &Vec<u8>) -> Result<Option<SecureBox<MasterKeys>>, Error> {
+        let key_encryption_key = build_key_encryption_key(&self.kdf_properties, secret)?;
+        let data = match AesProcessor::new(*key_encryption_key).decode(&self.wrapped_keys) {
+            Ok(d) => SecureBytes::new(d),
+            Err(_) => return Ok(None)
+        };
+        let mut mac: HmacSha256 = KeyInit::new_from_slice(&*key_encryption_key)
+            .map_err(|e|Error::new(ErrorKind::InvalidData, e.to_string()))?;
+        mac.update(&data);
+        if mac.verify_slice(&self.mac).is_err() {
+            return Ok(None);
+        }
+        MasterKeys::from_bytes(&data).map(|k|Some(k))
+    }
+
+    pub fn get_slot_type(&self) -> u8 {
+        self.slot_type
+    }
+
+    fn get_member_public_key(&self) -> Result<Vec<u8>, Error> {
+        if self.slot_type != KEY_SLOT_MEMBER || self.kdf_properties.len() != X25519_KDF_PROPERTIES_LENGTH {
+            return Err(build_corrupted_data_error("KeySlot.get_member_public_key"));
+        }
+        Ok(self.kdf_properties[1+X25519_KEY_LENGTH..].to_vec())
+    }
+}
+
+pub struct Member {
+    slot_id: u32,
+    name: String
+}
+
+impl ByteValue for Member {
+    fn from_bytes(source: Vec<u8>) -> Result<Box<Member>, Error> {
+        if source.len() < 5 {
+            return Err(build_corrupted_data_error("Member.from_bytes1"));
+        }
+        let slot_id = u32::from_le_bytes(source[0..4].try_into().unwrap());
+        let name = String::from_utf8(source[4..].to_vec())
+            .map_err(|_e|build_corrupted_data_error("Member.from_bytes2"))?;
+        Ok(Box::new(Member{ slot_id, name }))
+    }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut result = self.slot_id.to_le_bytes().to_vec();
+        result.extend_from_slice(self.name.as_bytes());
+        result
+    }
+}
+
+pub fn key_slot_type_to_u8(slot_type: KeySlotType) -> u8 {
+    match slot_type {
+        KeySlotType::Passwords => KEY_SLOT_PASSWORDS,
+        KeySlotType::RecoveryKey => KEY_SLOT_RECOVERY_KEY,
+        KeySlotType::KeyFile => KEY_SLOT_KEY_FILE,
+        KeySlotType::Member => KEY_SLOT_MEMBER
+    }
+}
+
+pub fn key_slot_type_from_u8(slot_type: u8) -> Result<KeySlotType, Error> {
+    match slot_type {
+        KEY_SLOT_PASSWORDS => Ok(KeySlotType::Passwords),
+        KEY_SLOT_RECOVERY_KEY => Ok(KeySlotType::RecoveryKey),
+        KEY_SLOT_KEY_FILE => Ok(KeySlotType::KeyFile),
+        KEY_SLOT_MEMBER => Ok(KeySlotType::Member),
+        _ => Err(build_corrupted_data_error("key_slot_type_from_u8"))
+    }
+}
+
+fn build_key_encryption_key(kdf_properties: &Vec<u8>, secret: &Vec<u8>) -> Result<SecureBox<[u8; 32]>, Error> {
+    if kdf_properties.len() == 0 {
+        return Err(build_corrupted_data_error("build_key_encryption_key"));
+    }
+    let key = match kdf_properties[0] {
+        HASH_ALGORITHM_ARGON2 => build_argon2_key(kdf_properties.clone(), secret),
+        KDF_X25519 => build_member_key_encryption_key(kdf_properties, secret),
+        _ => Err(Error::new(ErrorKind::Unsupported, "unsupported hash algorithm"))
+    }?;
+    Ok(SecureBox::new(key))
+}
+
+// secret - member private key
+fn build_member_key_encryption_key(kdf_properties: &Vec<u8>, secret: &Vec<u8>) -> Result<[u8; 32], Error> {
+    if kdf_properties.len() != X25519_KDF_PROPERTIES_LENGTH {
+        return Err(build_corrupted_data_error("build_member_key_encryption_key"));
+    }
+    let private_key = parse_x25519_key(secret)?;
+    let mut ephemeral_public_key = [0u8; X25519_KEY_LENGTH];
+    ephemeral_public_key.copy_from_slice(&kdf_properties[1..1+X25519_KEY_LENGTH]);
+    let shared_secret = MontgomeryPoint(ephemeral_public_key).mul_clamped(private_key);
+    build_x25519_key_encryption_key(&shared_secret, kdf_properties)
+}
+
+fn build_x25519_key_encryption_key(shared_secret: &MontgomeryPoint, kdf_properties: &Vec<u8>) -> Result<[u8; 32], Error> {
+    // low order public keys give all zero shared secret
+    if shared_secret.as_bytes().iter().all(|b|*b == 0) {
+        return Err(Error::new(ErrorKind::InvalidInput, "invalid x25519 public key"));
+    }
+    let mut hasher = Sha256::new();
+    hasher.update(shared_secret.as_bytes());
+    hasher.update(&kdf_properties[1..]);
+    Ok(hasher.finalize().into())
+}
+
+fn parse_x25519_key(key: &Vec<u8>) -> Result<[u8; X25519_KEY_LENGTH], Error> {
+    key.as_slice().try_into()
+        .map_err(|_e|Error::new(ErrorKind::InvalidInput, "x25519 key must be 32 bytes long"))
+}
+
+fn build_mac(key: &[u8; 32], data: &Vec<u8>) -> Result<Vec<u8>, Error> {
+    let mut mac: HmacSha256 = KeyInit::new_from_slice(key)
+        .map_err(|e|Error::new(ErrorKind::InvalidData, e.to_string()))?;
+    mac.update(data);
+    Ok(mac.finalize().into_bytes().to_vec())
+}
+
+pub fn build_passwords_secret(password_hash: &Vec<u8>, password2_hash: &Vec<u8>) -> Vec<u8> {
+    let mut hasher = Sha256::new();
+    hasher.update(password_hash);
+    hasher.update(password2_hash);
+    Vec::from(hasher.finalize().as_slice())
+}
+
+pub fn build_key_file_secret(key_file_contents: &Vec<u8>) -> Vec<u8> {
+    let mut hasher = Sha256::new();
+    hasher.update(key_file_contents);
+    Vec::from(hasher.finalize().as_slice())
+}
+
+pub fn build_member_key_pair() -> MemberKeyPair {
+    let mut private_key = [0u8; X25519_KEY_LENGTH];
+    OsRng.fill_bytes(&mut private_key);
+    let public_key = MontgomeryPoint::mul_base_clamped(private_key);
+    MemberKeyPair{ private_key: private_key.to_vec(), public_key: public_key.as_bytes().to_vec() }
+}
+
+pub fn build_recovery_key() -> Vec<u8> {
+    let mut key = [0u8; RECOVERY_KEY_LENGTH];
+    OsRng.fill_bytes(&mut key);
+    key.to_vec()
+}
+
+fn get_key_slot_ids(header: &IdValueMap) -> Result<Vec<u32>, Error> {
+    match header.get(KEY_SLOTS_ID) {
+        Ok(v) => Ok(v),
+        Err(e) => {
+            if e.kind() == ErrorKind::NotFound {
+                Ok(Vec::new())
+            } else {
+                Err(e)
+            }
+        }
+    }
+}
+
+pub fn get_key_slots(header: &IdValueMap) -> Result<HashMap<u32, u8>, Error> {
+    let slots: HashMap<u32, KeySlot> = header.get_indirect(KEY_SLOTS_ID)?;
+    Ok(slots.into_iter().map(|(id, slot)|(id, slot.get_slot_type())).collect())
+}
+
+pub fn add_key_slot(header: &mut IdValueMap, slot_type: u8, secret: &Vec<u8>, keys: &MasterKeys)
+    -> Result<u32, Error> {
+    let mut kdf_properties: Vec<u8> = header.get(HASH_ALGORITHM_PROPERTIES_ID)?;
+    set_argon2_salt(&mut kdf_properties, build_argon2_salt())?;
+    let slot = KeySlot::new(slot_type, kdf_properties, secret, keys)?;
+    add_slot(header, slot)
+}
+
+fn add_slot(header: &mut IdValueMap, slot: KeySlot) -> Result<u32, Error> {
+    let mut ids = get_key_slot_ids(header)?;
+    let id = header.add(slot)?;
+    ids.push(id);
+    header.set(KEY_SLOTS_ID, ids)?;
+    Ok(id)
+}
+
+pub fn remove_key_slot(header: &mut IdValueMap, id: u32) -> Result<(), Error> {
+    let mut ids = get_key_slot_ids(header)?;
+    let index = ids.iter().position(|i|*i == id).ok_or(build_not_found_error())?;
+    if ids.len() == 1 {
+        return Err(Error::new(ErrorKind::InvalidInput, "at least one key slot is required"));
+    }
+    ids.remove(index);
+    header.remove(&id);
+    header.set(KEY_SLOTS_ID, ids)
+}
+
+// replaces all slots of given type with new slot
+pub fn replace_key_slots(header: &mut IdValueMap, slot_type: u8, secret: &Vec<u8>, keys: &MasterKeys)
+    -> Result<u32, Error> {
+    let slots: HashMap<u32, KeySlot> = header.get_indirect(KEY_SLOTS_ID)?;
+    let mut ids = get_key_slot_ids(header)?;
+    for (id, slot) in slots {
+        if slot.get_slot_type() == slot_type {
+            header.remove(&id);
+            ids.retain(|i|*i != id);
+        }
+    }
+    header.set(KEY_SLOTS_ID, ids)?;
+    add_key_slot(header, slot_type, secret, keys)
+}
+
+fn get_member_ids(header: &IdValueMap) -> Result<Vec<u32>, Error> {
+    match header.get(MEMBERS_ID) {
+        Ok(v) => Ok(v),
+        Err(e) if e.kind() == ErrorKind::NotFound => Ok(Vec::new()),
+        Err(e) => Err(e)
+    }
+}
+
+pub fn get_members(header: &IdValueMap) -> Result<HashMap<u32, DatabaseMember>, Error> {
+    let members: HashMap<u32, Member> = match header.get_indirect(MEMBERS_ID) {
+        Ok(m) => m,
+        Err(e) if e.kind() == ErrorKind::NotFound => return Ok(HashMap::new()),
+        Err(e) => return Err(e)
+    };
+    let mut result = HashMap::new();
+    for (id, member) in members {
+        let slot: KeySlot = header.get(member.slot_id)?;
+        result.insert(id, DatabaseMember{ name: member.name, public_key: slot.get_member_public_key()? });
+    }
+    Ok(result)
+}
+
+pub fn add_member(header: &mut IdValueMap, name: String, public_key: &Vec<u8>, keys: &MasterKeys)
+    -> Result<u32, Error> {
+    if name.is_empty() {
+        return Err(Error::new(ErrorKind::InvalidInput, "empty member name"));
+    }
+    for (_id, member) in get_members(header)? {
+        if member.name == name {
+            return Err(Error::new(ErrorKind::AlreadyExists, "member with given name already exists"));
+        }
+        if member.public_key == *public_key {
+            return Err(Error::new(ErrorKind::AlreadyExists, "member with given public key already exists"));
+        }
+    }
+    let slot_id = add_slot(header, KeySlot::new_member(public_key, keys)?)?;
+    let mut ids = get_member_ids(header)?;
+    let id = header.add(Member{ slot_id, name })?;
+    ids.push(id);
+    header.set(MEMBERS_ID, ids)?;
+    Ok(id)
+}
+
+// member key slot is removed even when it is the last one, passwords key slot can be created on save
+pub fn remove_member(header: &mut IdValueMap, id: u32) -> Result<(), Error> {
+    let mut ids = get_member_ids(header)?;
+    let index = ids.iter().position(|i|*i == id).ok_or(build_not_found_error())?;
+    let member: Member = header.get(id)?;
+    ids.remove(index);
+    header.remove(&id);
+    header.set(MEMBERS_ID, ids)?;
+    let mut slot_ids = get_key_slot_ids(header)?;
+    slot_ids.retain(|i|*i != member.slot_id);
+    header.remove(&member.slot_id);
+    header.set(KEY_SLOTS_ID, slot_ids)
+}
+
+pub fn is_member_key_slot(header: &IdValueMap, id: u32) -> Result<bool, Error> {
+    let members: HashMap<u32, Member> = match header.get_indirect(MEMBERS_ID) {
+        Ok(m) => m,
+        Err(e) if e.kind() == ErrorKind::NotFound => return Ok(false),
+        Err(e) => return Err(e)
+    };
+    Ok(members.values().any(|m|m.slot_id == id))
+}
+
+// master keys are wrapped again to every member, all other slots are removed,
+// returns types of removed slots
+pub fn rotate_key_slots(header: &mut IdValueMap, keys: &MasterKeys) -> Result<Vec<u8>, Error> {
+    let members: HashMap<u32, Member> = match header.get_indirect(MEMBERS_ID) {
+        Ok(m) => m,
+        Err(e) if e.kind() == ErrorKind::NotFound => HashMap::new(),
+        Err(e) => return Err(e)
+    };
+    let mut slot_ids = Vec::new();
+    for (_id, member) in members {
+        let slot: KeySlot = header.get(member.slot_id)?;
+        header.set(member.slot_id, KeySlot::new_member(&slot.get_member_public_key()?, keys)?)?;
+        slot_ids.push(member.slot_id);
+    }
+    let mut removed = Vec::new();
+    for id in get_key_slot_ids(header)? {
+        if !slot_ids.contains(&id) {
+            let slot: KeySlot = header.get(id)?;
+            removed.push(slot.get_slot_type());
+            header.remove(&id);
+        }
+    }
+    slot_ids.sort();
+    header.set(KEY_SLOTS_ID, slot_ids)?;
+    removed.sort();
+    removed.dedup();
+    Ok(removed)
+}
+
+pub fn unlock_key_slots(header: &IdValueMap, slot_type: u8, secret: &Vec<u8>) -> Result<SecureBox<MasterKeys>, Error> {
+    let slots: HashMap<u32, KeySlot> = header.get_indirect(KEY_SLOTS_ID)?;
+    for (_id, slot) in slots {
+        if slot.get_slot_type() == slot_type {
+            if let Some(keys) = slot.unlock(secret)? {
+                return Ok(keys);
+            }
+        }
+    }
+    Err(Error::new(ErrorKind::InvalidData, "no key slot matches given credentials"))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Error;
+    use crate::crypto::NoEncryptionProcessor;
+    use crate::pman::id_value_map::id_value_map::{ByteValue, IdValueMap};
+    use crate::pman::ids::HASH_ALGORITHM_PROPERTIES_ID;
+    use crate::pman::key_slots::{add_key_slot, build_recovery_key, get_key_slots, KEY_SLOT_KEY_FILE,
+                                 KEY_SLOT_RECOVERY_KEY, KeySlot, MasterKeys, remove_key_slot, unlock_key_slots};
+    use crate::pman::pman_database_file::{build_argon2_properties, build_argon2_salt};
+
+    #[test]
+    fn test_key_slots() -> Result<(), Error> {
+        let mut header = IdValueMap::new(NoEncryptionProcessor::new())?;
+        header.add_with_id(HASH_ALGORITHM_PROPERTIES_ID,
+                           build_argon2_properties(1, 6, 1, build_argon2_salt()))?;
+        let keys = MasterKeys::new();
+        let recovery_key = build_recovery_key();
+        let key_file = vec![1u8, 2, 3];
+        let id1 = add_key_slot(&mut header, KEY_SLOT_RECOVERY_KEY, &recovery_key, &keys)?;
+        let id2 = add_key_slot(&mut header, KEY_SLOT_KEY_FILE, &key_file, &keys)?;
+        assert_eq!(get_key_slots(&header)?.len(), 2);
+        assert!(*unlock_key_slots(&header, KEY_SLOT_RECOVERY_KEY, &recovery_key)? == *keys);
+        assert!(*unlock_key_slots(&header, KEY_SLOT_KEY_FILE, &key_file)? == *keys);
+        assert!(unlock_key_slots(&header, KEY_SLOT_RECOVERY_KEY, &key_file).is_err());
+
+        let slot: KeySlot = header.get(id1)?;
+        let slot2 = KeySlot::from_bytes(slot.to_bytes())?;
+        assert!(slot2.unlock(&recovery_key)?.is_some_and(|k|*k == *keys));
+        assert_eq!(format!("{:?}", *keys), "MasterKeys { .. }");
+
+        remove_key_slot(&mut header, id1)?;
+        assert!(unlock_key_slots(&header, KEY_SLOT_RECOVERY_KEY, &recovery_key).is_err());
+        assert!(remove_key_slot(&mut header, id2).is_err());
+        Ok(())
+    }
+}
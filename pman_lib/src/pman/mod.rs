@@ -5,3 +5,7 @@ pub mod data_file;
 mod ids;
 pub mod id_value_map;
 pub mod network;
+pub mod key_slots;
+pub mod templates;
+pub mod audit_log;
+pub mod users;
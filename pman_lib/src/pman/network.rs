@@ -1,12 +1,14 @@
 use std::io::{Error, ErrorKind};
 use s3cli_lib::KeyInfo;
 use crate::error_builders::build_corrupted_data_error;
+use crate::pman::data_file::build_qs3_file_location;
 use crate::pman::pman_database_file::FILE_LOCATION_QS3;
 use s3cli_lib::qs3::QKeyInfo;
 
 pub trait NetworkFileHandler {
     fn download(&self) -> Result<Vec<u8>, Error>;
     fn upload(&self, data: Vec<u8>) -> Result<(), Error>;
+    fn delete(&self) -> Result<(), Error>;
 }
 
 pub struct QS3Handler {
@@ -27,6 +29,13 @@ impl NetworkFileHandler for QS3Handler {
         let _ = request_info.make_request(Some(data))?;
         Ok(())
     }
+
+    fn delete(&self) -> Result<(), Error> {
+        let request_info =
+            self.key_info.build_request_info("DELETE", chrono::Utc::now(), &Vec::new(), &self.path)?;
+        let _ = request_info.make_request(None)?;
+        Ok(())
+    }
 }
 
 impl QS3Handler {
@@ -72,4 +81,44 @@ pub fn download_file(rsa_key: &String, location_data: Vec<u8>) -> Result<Vec<u8>
 pub fn upload_file(rsa_key: &String, data: Vec<u8>, location_data: Vec<u8>) -> Result<(), Error> {
     let handler = build_file_handler(location_data, rsa_key)?;
     handler.upload(data)
-}
\ No newline at end of file
+}
+// backup file is stored next to the original one with .bak suffix
+fn build_backup_location(location_data: &Vec<u8>) -> Result<Vec<u8>, Error> {
+    if location_data.is_empty() || location_data[0] != FILE_LOCATION_QS3 {
+        return Err(build_corrupted_data_error("build_backup_location1"));
+    }
+    let (path, key_data) = decode_location_data(location_data[1..].to_vec())?;
+    let backup_path = format!("{}.bak", path);
+    if backup_path.len() > 255 {
+        return Err(Error::new(ErrorKind::InvalidInput, "file path is too long for a backup file"));
+    }
+    Ok(build_qs3_file_location(backup_path, key_data))
+}
+
+pub fn backup_file(rsa_key: &String, location_data: Vec<u8>) -> Result<(), Error> {
+    let backup_location = build_backup_location(&location_data)?;
+    let data = download_file(rsa_key, location_data)?;
+    upload_file(rsa_key, data, backup_location)
+}
+
+pub fn remove_backup_file(rsa_key: &String, location_data: Vec<u8>) -> Result<(), Error> {
+    let handler = build_file_handler(build_backup_location(&location_data)?, rsa_key)?;
+    handler.delete()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Error;
+    use crate::pman::data_file::build_qs3_file_location;
+    use crate::pman::network::build_backup_location;
+
+    #[test]
+    fn test_build_backup_location() -> Result<(), Error> {
+        let location = build_qs3_file_location("bucket/db.pdbf.1".to_string(), vec![1, 2, 3]);
+        assert_eq!(build_backup_location(&location)?,
+                   build_qs3_file_location("bucket/db.pdbf.1.bak".to_string(), vec![1, 2, 3]));
+        assert!(build_backup_location(&vec![0]).is_err());
+        assert!(build_backup_location(&build_qs3_file_location("a".repeat(252), Vec::new())).is_err());
+        Ok(())
+    }
+}
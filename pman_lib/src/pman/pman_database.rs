@@ -1,22 +1,65 @@
 use std::any::Any;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::io::{Error, ErrorKind};
 use std::sync::{Arc, Mutex, MutexGuard};
-use crate::error_builders::build_not_found_error;
-use crate::pman::database_entity::{ENTITY_VERSION_LATEST, PmanDatabaseEntity};
+use crate::error_builders::{build_corrupted_data_error, build_not_found_error};
+use crate::pman::audit_log::{add_audit_record, get_audit_log, get_audit_settings, is_audit_enabled, set_audit_settings};
+use crate::pman::database_entity::{DEFAULT_URL_MATCH_MODE, ENTITY_VERSION_LATEST, EntityProperty, get_current_timestamp,
+                                   MAX_ATTACHMENTS, MAX_TAGS, MAX_URLS, PmanDatabaseEntity, read_property_value};
 use crate::pman::id_value_map::id_value_map::ByteValue;
-use crate::pman::network::{download_file, upload_file};
-use crate::pman::pman_database_file::PmanDatabaseFile;
-use crate::structs_interfaces::{DatabaseGroup, PasswordDatabase, PasswordDatabaseEntity};
+use crate::pman::key_slots::{build_key_file_secret, KEY_SLOT_KEY_FILE, KEY_SLOT_MEMBER, key_slot_type_from_u8, key_slot_type_to_u8};
+use crate::otp::{OtpCode, OtpParameters};
+use crate::pman::network::{backup_file, download_file, remove_backup_file, upload_file};
+use crate::pman::pman_database_file::{crypto_engine_to_algorithm, PmanDatabaseFile};
+use crate::pman::users::validate_user;
+use crate::pman::templates::{get_built_in_templates, Template, validate_field_value, validate_properties};
+use crate::structs_interfaces::{AuditEvent, AuditEventType, AuditObjectType, AuditSettings, CryptoEngine, DatabaseGroup, DatabaseMember, DatabaseUser, DatabaseVersion, EntitySortKey, EntityTemplate, EntityUrl, ExpiringEntity, FieldType, IntegrityIssue, IntegrityIssueType, KeySlotType,
+                                PasswordDatabase, PasswordDatabaseEntity, PropertyLayout, TemplateField, TrashedEntity, UrlMatch};
+use crate::secure_memory::SecureBytes;
+use crate::search_query::{DateField, parse_search_query, SearchTarget, TextField};
+use crate::url_match::{match_url, validate_url_pattern};
 
 const GROUPS_ID: u32 = 1;
 const USERS_ID: u32 = 2;
 
 const ENTITIES_ID: u32 = 3;
+const TAGS_ID: u32 = 4;
+// group id, parent group id pairs, root groups are not stored
+const GROUP_PARENTS_ID: u32 = 5;
+// group id, password max age in days pairs
+const GROUP_POLICIES_ID: u32 = 6;
+// custom entity templates list
+const TEMPLATES_ID: u32 = 7;
+// entity id, deletion timestamp pairs, trashed entities are not in entities list
+const TRASH_ID: u32 = 8;
+// trash retention in days, single item list
+const TRASH_RETENTION_ID: u32 = 9;
+
+pub const DEFAULT_TRASH_RETENTION_DAYS: u32 = 30;
+// audit record ids list and audit settings, see audit_log.rs
+pub const AUDIT_LOG_ID: u32 = 10;
+pub const AUDIT_SETTINGS_ID: u32 = 11;
+// removed users and groups that are still referenced by entity history versions
+const RETIRED_USERS_ID: u32 = 12;
+const RETIRED_GROUPS_ID: u32 = 13;
+
+// lower ids are reserved for index lists
+const FIRST_RECORD_ID: u32 = 100;
 
 pub struct PmanDatabase {
     rsa_key: String,
-    file: Arc<Mutex<PmanDatabaseFile>>
+    file: Arc<Mutex<PmanDatabaseFile>>,
+    // names records version the index was built for and the index itself
+    entity_index: Mutex<Option<(u64, Arc<Vec<EntityIndexItem>>)>>
+}
+
+// entity fields used for sorting and filtering without decrypting entities
+struct EntityIndexItem {
+    entity_id: u32,
+    group_id: u32,
+    lowercase_name: String,
+    created_at: u64,
+    modified_at: u64
 }
 
 impl PasswordDatabase for PmanDatabase {
@@ -50,8 +93,16 @@ impl PasswordDatabase for PmanDatabase {
         file.open(data1, data2)
     }
 
+    // upgrade re-encrypts data files with new master keys, so the old ones are backed up first,
+    // the new files are verified by file.save() before they are uploaded
     fn save(&self) -> Result<Option<Vec<u8>>, Error> {
         let mut file = self.file.lock().unwrap();
+        let (stored_version, version) = file.get_version()?;
+        if stored_version != version {
+            let (location1, location2) = file.get_location_data()?;
+            backup_file(&self.rsa_key, location1)?;
+            backup_file(&self.rsa_key, location2)?;
+        }
         let (data1, data2) = file.save()?;
         if let Some((d2, d3)) = data2 {
             let (location1, location2) = file.get_location_data()?;
@@ -72,20 +123,32 @@ impl PasswordDatabase for PmanDatabase {
                 }
             }
         };
+        let parents = get_id_map(&self.file.lock().unwrap(), GROUP_PARENTS_ID)?;
+        let policies = get_id_map(&self.file.lock().unwrap(), GROUP_POLICIES_ID)?;
         let entities = self.get_all_entities()?;
         let mut counts = HashMap::new();
         for (_id, entity) in entities {
             let e = counts.entry(entity.get_group_id(ENTITY_VERSION_LATEST)?).or_insert(0u32);
             *e += 1;
         }
+        let mut total_counts = HashMap::new();
+        for (group_id, count) in &counts {
+            for id in get_group_ancestors(&parents, *group_id)? {
+                let e = total_counts.entry(id).or_insert(0u32);
+                *e += count;
+            }
+        }
         Ok(groups.into_iter().map(|(id, g)|DatabaseGroup{
             name: g,
             id,
+            parent_id: parents.get(&id).map(|p|*p),
             entities_count: *counts.get(&id).unwrap_or(&0),
+            total_entities_count: *total_counts.get(&id).unwrap_or(&0),
+            max_age_days: policies.get(&id).map(|d|*d),
         }).collect())
     }
 
-    fn get_users(&self) -> Result<HashMap<u32, String>, Error> {
+    fn get_users(&self) -> Result<HashMap<u32, DatabaseUser>, Error> {
         self.file.lock().unwrap().get_indirect_from_names(USERS_ID)
     }
 
@@ -101,7 +164,11 @@ impl PasswordDatabase for PmanDatabase {
     }
 
     fn add_user(&self, name: String) -> Result<u32, Error> {
-        self.add_to_list(USERS_ID, name, string_validator)
+        let user = DatabaseUser::new(name);
+        validate_user(&user)?;
+        let user_id = self.add_to_list(USERS_ID, user, user_validator)?;
+        self.audit(AuditEventType::Add, AuditObjectType::User, user_id, "")?;
+        Ok(user_id)
     }
 
     fn remove_user(&self, user_id: u32) -> Result<(), Error> {
@@ -110,87 +177,291 @@ impl PasswordDatabase for PmanDatabase {
         if entities.iter().find(|(_id, e)|e.contains_user_id(user_id)).is_some() {
             return Err(Error::new(ErrorKind::InvalidInput, "user name is in use"));
         }
-        self.remove_from_list(USERS_ID, user_id)
+        self.destroy_trashed_entities(|e|e.contains_user_id(user_id))?;
+        let name = self.get_audit_object_name(AuditObjectType::User, user_id)?;
+        self.remove_from_list(USERS_ID, user_id)?;
+        self.audit_with_name(AuditEventType::Remove, AuditObjectType::User, user_id, name, "")
+    }
+
+    fn rename_user(&self, user_id: u32, new_name: String) -> Result<(), Error> {
+        self.check_user_exists(user_id)?;
+        let mut user: DatabaseUser = self.file.lock().unwrap().get_from_names(user_id)?;
+        user.name = new_name;
+        self.update_user(user_id, user)
+    }
+
+    fn update_user(&self, user_id: u32, user: DatabaseUser) -> Result<(), Error> {
+        validate_user(&user)?;
+        self.check_user_exists(user_id)?;
+        let mut file = self.file.lock().unwrap();
+        let indexes: Vec<u32> = file.get_from_names::<Vec<u32>>(USERS_ID)?.into_iter()
+            .filter(|id|*id != user_id)
+            .collect();
+        user_validator(&indexes, &mut file, &user)?;
+        let old_user: DatabaseUser = file.get_from_names(user_id)?;
+        let changes: Vec<&str> = [("email", old_user.email != user.email),
+            ("display name", old_user.display_name != user.display_name), ("fields", old_user.fields != user.fields)]
+            .into_iter()
+            .filter(|(_name, changed)|*changed)
+            .map(|(name, _changed)|name)
+            .collect();
+        let renamed = old_user.name != user.name;
+        file.set_in_names(user_id, user)?;
+        drop(file);
+        if renamed {
+            self.audit(AuditEventType::Rename, AuditObjectType::User, user_id, &old_user.name)?;
+        }
+        if !changes.is_empty() {
+            self.audit(AuditEventType::Modify, AuditObjectType::User, user_id, &changes.join(","))?;
+        }
+        Ok(())
+    }
+
+    fn replace_user(&self, user_id: u32, replacement_id: u32, rewrite_history: bool) -> Result<(), Error> {
+        if user_id == replacement_id {
+            return Err(Error::new(ErrorKind::InvalidInput, "user cannot be replaced with itself"));
+        }
+        self.check_user_exists(user_id)?;
+        self.check_user_exists(replacement_id)?;
+        let (changed, in_use) =
+            self.replace_in_entities(|file, entity|entity.replace_user_id(file, user_id, replacement_id, rewrite_history),
+                                     |entity|entity.contains_user_id(user_id))?;
+        let name = self.get_audit_object_name(AuditObjectType::User, user_id)?;
+        self.remove_or_retire(USERS_ID, RETIRED_USERS_ID, user_id, in_use)?;
+        for entity_id in changed {
+            self.audit(AuditEventType::Modify, AuditObjectType::Entity, entity_id, "user")?;
+        }
+        let replacement_name = self.get_audit_object_name(AuditObjectType::User, replacement_id)?;
+        self.audit_with_name(AuditEventType::Remove, AuditObjectType::User, user_id, name,
+                             &format!("replaced with {}", replacement_name))
     }
 
     fn search(&self, search_string: String) -> Result<HashMap<u32, HashMap<u32, Box<dyn PasswordDatabaseEntity + Send>>>, Error> {
-        let entities = self.get_all_entities()?;
         let mut result: HashMap<u32, HashMap<u32, Box<dyn PasswordDatabaseEntity + Send>>> = HashMap::new();
-        for (entity_id, entity) in entities {
-            if entity.get_name()?.to_lowercase().contains(&search_string.to_lowercase()) {
-                let group_id = entity.get_group_id(ENTITY_VERSION_LATEST)?;
-                let map = result.entry(group_id).or_insert(HashMap::new());
-                map.insert(entity_id, Box::new(entity));
-            }
+        for (entity_id, entity) in self.find_entities(&search_string)? {
+            let group_id = entity.get_group_id(ENTITY_VERSION_LATEST)?;
+            let map = result.entry(group_id).or_insert(HashMap::new());
+            map.insert(entity_id, Box::new(entity));
         }
         Ok(result)
     }
 
+    fn list_entities(&self, group_id: Option<u32>, sort_key: EntitySortKey, descending: bool, offset: u32, limit: u32)
+        -> Result<(u32, Vec<(u32, Box<dyn PasswordDatabaseEntity + Send>)>), Error> {
+        if let Some(id) = group_id {
+            self.check_group_exists(id)?;
+        }
+        self.get_entities_page(|item|group_id.map(|id|item.group_id == id).unwrap_or(true),
+                               sort_key, descending, offset, limit)
+    }
+
+    fn search_entities(&self, search_string: String, sort_key: EntitySortKey, descending: bool, offset: u32, limit: u32)
+        -> Result<(u32, Vec<(u32, Box<dyn PasswordDatabaseEntity + Send>)>), Error> {
+        let ids: HashSet<u32> = self.find_entities(&search_string)?.into_keys().collect();
+        self.get_entities_page(|item|ids.contains(&item.entity_id), sort_key, descending, offset, limit)
+    }
+
     fn add_group(&self, name: String) -> Result<u32, Error> {
-        self.add_to_list(GROUPS_ID, name, string_validator)
+        let mut file = self.file.lock().unwrap();
+        let parents = get_id_map(&file, GROUP_PARENTS_ID)?;
+        check_group_name(&mut file, &parents, None, &name)?;
+        drop(file);
+        let group_id = self.add_to_list(GROUPS_ID, name, no_validator)?;
+        self.audit(AuditEventType::Add, AuditObjectType::Group, group_id, "")?;
+        Ok(group_id)
+    }
+
+    fn add_child_group(&self, parent_id: u32, name: String) -> Result<u32, Error> {
+        self.check_group_exists(parent_id)?;
+        let mut file = self.file.lock().unwrap();
+        let mut parents = get_id_map(&file, GROUP_PARENTS_ID)?;
+        check_group_name(&mut file, &parents, Some(parent_id), &name)?;
+        drop(file);
+        let group_id = self.add_to_list(GROUPS_ID, name, no_validator)?;
+        parents.insert(group_id, parent_id);
+        set_id_map(&mut self.file.lock().unwrap(), GROUP_PARENTS_ID, parents)?;
+        self.audit(AuditEventType::Add, AuditObjectType::Group, group_id, "")?;
+        Ok(group_id)
     }
 
     fn rename_group(&self, group_id: u32, new_name: String) -> Result<(), Error> {
         self.check_group_exists(group_id)?;
         let mut file = self.file.lock().unwrap();
-        let indexes: Vec<u32> = file.get_from_names(GROUPS_ID)?;
-        string_validator(&indexes, &mut file, &new_name)?;
-        file.set_in_names(group_id, new_name)
+        let parents = get_id_map(&file, GROUP_PARENTS_ID)?;
+        check_group_name(&mut file, &parents, parents.get(&group_id).map(|p|*p), &new_name)?;
+        let old_name: String = file.get_from_names(group_id)?;
+        file.set_in_names(group_id, new_name)?;
+        drop(file);
+        self.audit(AuditEventType::Rename, AuditObjectType::Group, group_id, &old_name)
+    }
+
+    fn move_group(&self, group_id: u32, new_parent_id: Option<u32>) -> Result<(), Error> {
+        self.check_group_exists(group_id)?;
+        if let Some(parent_id) = new_parent_id {
+            self.check_group_exists(parent_id)?;
+        }
+        let mut file = self.file.lock().unwrap();
+        let mut parents = get_id_map(&file, GROUP_PARENTS_ID)?;
+        if let Some(parent_id) = new_parent_id {
+            if get_group_ancestors(&parents, parent_id)?.contains(&group_id) {
+                return Err(Error::new(ErrorKind::InvalidInput, "group cannot be moved into its own subtree"));
+            }
+        }
+        if parents.get(&group_id).map(|p|*p) == new_parent_id {
+            return Ok(());
+        }
+        let name: String = file.get_from_names(group_id)?;
+        check_group_name(&mut file, &parents, new_parent_id, &name)?;
+        match new_parent_id {
+            Some(parent_id) => parents.insert(group_id, parent_id),
+            None => parents.remove(&group_id)
+        };
+        set_id_map(&mut file, GROUP_PARENTS_ID, parents)?;
+        drop(file);
+        self.audit(AuditEventType::Modify, AuditObjectType::Group, group_id, "parent group")
     }
 
     fn remove_group(&self, group_id: u32) -> Result<(), Error> {
         self.check_group_exists(group_id)?;
+        let mut parents = get_id_map(&self.file.lock().unwrap(), GROUP_PARENTS_ID)?;
+        let subtree = get_group_subtree(&parents, group_id);
         let entities = self.get_all_entities()?;
-        if entities.iter().find(|(_k, v)|v.contains_group_id(group_id)).is_some() {
+        if entities.iter().find(|(_k, v)|subtree.iter().any(|id|v.contains_group_id(*id))).is_some() {
             return Err(Error::new(ErrorKind::InvalidInput, "group is not empty"));
         }
-        self.remove_from_list(GROUPS_ID, group_id)
+        self.destroy_trashed_entities(|e|subtree.iter().any(|id|e.contains_group_id(*id)))?;
+        let mut policies = get_id_map(&self.file.lock().unwrap(), GROUP_POLICIES_ID)?;
+        for id in &subtree {
+            let name = self.get_audit_object_name(AuditObjectType::Group, *id)?;
+            self.remove_from_list(GROUPS_ID, *id)?;
+            self.audit_with_name(AuditEventType::Remove, AuditObjectType::Group, *id, name, "")?;
+            parents.remove(id);
+            policies.remove(id);
+        }
+        let mut file = self.file.lock().unwrap();
+        set_id_map(&mut file, GROUP_PARENTS_ID, parents)?;
+        set_id_map(&mut file, GROUP_POLICIES_ID, policies)
     }
 
-    fn remove_entity(&self, entity_id: u32) -> Result<(), Error> {
-        self.check_entity_exists(entity_id)?;
+    fn merge_groups(&self, group_id: u32, target_group_id: u32, rewrite_history: bool) -> Result<(), Error> {
+        if group_id == target_group_id {
+            return Err(Error::new(ErrorKind::InvalidInput, "group cannot be merged with itself"));
+        }
+        self.check_group_exists(group_id)?;
+        self.check_group_exists(target_group_id)?;
+        let target_names = self.get_entities(target_group_id)?.into_values()
+            .map(|e|e.get_name())
+            .collect::<Result<HashSet<String>, Error>>()?;
+        for (_id, entity) in self.get_entities(group_id)? {
+            if target_names.contains(&entity.get_name()?) {
+                return Err(Error::new(ErrorKind::AlreadyExists, "entity with given name already exists"));
+            }
+        }
         let mut file = self.file.lock().unwrap();
-        let entity: PmanDatabaseEntity = file.get_from_names(entity_id)?;
-        let names_ids = entity.collect_names_ids();
-        let passwords_ids = entity.collect_passwords_ids();
-        for id in names_ids {
-            file.remove_from_names(&id)?;
+        let mut parents = get_id_map(&file, GROUP_PARENTS_ID)?;
+        if get_group_ancestors(&parents, target_group_id)?.contains(&group_id) {
+            return Err(Error::new(ErrorKind::InvalidInput, "group cannot be merged into its own subtree"));
         }
-        for id in passwords_ids {
-            file.remove_from_passwords(&id)?;
+        let mut children: Vec<u32> = parents.iter()
+            .filter(|(_id, parent_id)|**parent_id == group_id)
+            .map(|(id, _parent_id)|*id)
+            .collect();
+        children.sort();
+        for child_id in &children {
+            let name: String = file.get_from_names(*child_id)?;
+            check_group_name(&mut file, &parents, Some(target_group_id), &name)?;
+        }
+        for child_id in &children {
+            parents.insert(*child_id, target_group_id);
+        }
+        parents.remove(&group_id);
+        set_id_map(&mut file, GROUP_PARENTS_ID, parents)?;
+        let mut policies = get_id_map(&file, GROUP_POLICIES_ID)?;
+        policies.remove(&group_id);
+        set_id_map(&mut file, GROUP_POLICIES_ID, policies)?;
+        drop(file);
+        let (changed, in_use) =
+            self.replace_in_entities(|file, entity|entity.replace_group_id(file, group_id, target_group_id, rewrite_history),
+                                     |entity|entity.contains_group_id(group_id))?;
+        let name = self.get_audit_object_name(AuditObjectType::Group, group_id)?;
+        self.remove_or_retire(GROUPS_ID, RETIRED_GROUPS_ID, group_id, in_use)?;
+        for child_id in children {
+            self.audit(AuditEventType::Modify, AuditObjectType::Group, child_id, "parent group")?;
+        }
+        for entity_id in changed {
+            self.audit(AuditEventType::Modify, AuditObjectType::Entity, entity_id, "group")?;
         }
-        file.remove_from_names(&entity_id)?;
+        let target_name = self.get_audit_object_name(AuditObjectType::Group, target_group_id)?;
+        self.audit_with_name(AuditEventType::Remove, AuditObjectType::Group, group_id, name,
+                             &format!("merged into {}", target_name))
+    }
+
+    // removed entity is moved to trash, expired trash items are purged
+    fn remove_entity(&self, entity_id: u32) -> Result<(), Error> {
+        self.check_entity_exists(entity_id)?;
+        self.unlink_from_list(ENTITIES_ID, entity_id)?;
+        let timestamp = get_current_timestamp();
+        let mut file = self.file.lock().unwrap();
+        let mut trash = get_id_map(&file, TRASH_ID)?;
+        trash.insert(entity_id, timestamp as u32);
+        set_id_map(&mut file, TRASH_ID, trash)?;
         drop(file);
-        self.remove_from_list(ENTITIES_ID, entity_id)
+        self.audit(AuditEventType::Remove, AuditObjectType::Entity, entity_id, "moved to trash")?;
+        self.purge_trash(timestamp)
     }
 
     fn add_entity(&self, group_id: u32, name: String, user_id: u32, password: String,
-                  url: Option<String>, properties: HashMap<String, String>) -> Result<u32, Error> {
+                  url: Option<String>, properties: HashMap<String, String>,
+                  template_id: Option<u32>) -> Result<u32, Error> {
         self.check_group_exists(group_id)?;
         self.check_user_exists(user_id)?;
         self.check_entity_name(group_id, name.clone())?;
         let mut file = self.file.lock().unwrap();
+        let template = match template_id {
+            Some(id) => Some(get_template(&file, id)?),
+            None => None
+        };
+        if let Some(t) = &template {
+            validate_properties(t, &properties)?;
+        }
         let name_id = file.add_to_names(name)?;
         let password_id = file.add_to_passwords(password)?;
         let url_id = if let Some(u) = url {
             Some(file.add_to_names(u)?)
         } else { None };
-        let mut property_ids = HashMap::new();
-        for (k, v) in properties {
-            let key_id = file.add_to_names(k)?;
-            let value_id = file.add_to_passwords(v)?;
-            property_ids.insert(key_id, value_id);
-        }
+        let mut property_ids = Vec::new();
+        add_new_properties(&mut file, template.as_ref(), properties, &mut property_ids)?;
+        drop(file);
+        let entity = match template_id {
+            Some(id) => PmanDatabaseEntity::new_from_template(self.file.clone(), name_id, password_id,
+                                                              group_id, user_id, url_id, property_ids, id),
+            None => PmanDatabaseEntity::new(self.file.clone(), name_id,
+                                            password_id, group_id, user_id, url_id, property_ids)
+        };
+        let entity_id = self.add_to_entity_list(entity)?;
+        self.audit(AuditEventType::Add, AuditObjectType::Entity, entity_id, "")?;
+        Ok(entity_id)
+    }
+
+    fn add_note(&self, group_id: u32, name: String, body: String) -> Result<u32, Error> {
+        self.check_group_exists(group_id)?;
+        self.check_entity_name(group_id, name.clone())?;
+        let mut file = self.file.lock().unwrap();
+        let name_id = file.add_to_names(name)?;
+        let body_id = file.add_to_passwords(body)?;
         drop(file);
-        let entity = PmanDatabaseEntity::new(self.file.clone(), name_id,
-                                             password_id, group_id, user_id, url_id, property_ids);
-        self.add_to_entity_list(entity)
+        let entity = PmanDatabaseEntity::new_note(self.file.clone(), name_id, body_id, group_id);
+        let entity_id = self.add_to_entity_list(entity)?;
+        self.audit(AuditEventType::Add, AuditObjectType::Entity, entity_id, "")?;
+        Ok(entity_id)
     }
 
     fn rename_entity(&self, entity_id: u32, new_name: String) -> Result<(), Error> {
         let entity = self.get_entity(entity_id)?;
         self.check_entity_name(entity.get_group_id(ENTITY_VERSION_LATEST)?, new_name.clone())?;
-        self.file.lock().unwrap().set_in_names(entity.get_name_id(), new_name)
+        let old_name = entity.get_name()?;
+        self.file.lock().unwrap().set_in_names(entity.get_name_id(), new_name)?;
+        self.audit(AuditEventType::Rename, AuditObjectType::Entity, entity_id, &old_name)
     }
 
     fn modify_entity(&self, entity_id: u32, new_group_id: Option<u32>,
@@ -198,6 +469,17 @@ impl PasswordDatabase for PmanDatabase {
                      change_url: bool, new_properties: HashMap<String, String>,
                      modified_properties: HashMap<u32, Option<String>>) -> Result<(), Error> {
         let mut entity = self.get_entity(entity_id)?;
+        let changes = [("group", new_group_id.is_some()), ("user", new_user_id.is_some()),
+                       ("password", new_password.is_some()), ("url", change_url),
+                       ("properties", !new_properties.is_empty() || !modified_properties.is_empty())]
+            .into_iter()
+            .filter(|(_name, changed)|*changed)
+            .map(|(name, _changed)|name)
+            .collect::<Vec<&str>>()
+            .join(", ");
+        if entity.is_note() && (new_user_id.is_some() || change_url) {
+            return Err(Error::new(ErrorKind::InvalidInput, "notes have no user and url"));
+        }
         let new_gid = if let Some(gid) = new_group_id {
             self.check_group_exists(gid)?;
             gid
@@ -207,6 +489,24 @@ impl PasswordDatabase for PmanDatabase {
             uid
         } else { entity.get_user_id(ENTITY_VERSION_LATEST)? };
         let mut file = self.file.lock().unwrap();
+        let template = match entity.get_latest_template_id() {
+            Some(id) => Some(get_template(&file, id)?),
+            None => None
+        };
+        if let Some(t) = &template {
+            let mut values = HashMap::new();
+            for property in &entity.get_properties() {
+                let value = match modified_properties.get(&property.name_id) {
+                    Some(Some(value)) => value.clone(),
+                    Some(None) => continue,
+                    None => read_property_value(&mut file, property)?
+                };
+                let name: String = file.get_from_names(property.name_id)?;
+                values.insert(name, value);
+            }
+            values.extend(new_properties.clone());
+            validate_properties(t, &values)?;
+        }
         let new_pid = if let Some(password) = new_password {
             file.add_to_passwords(password)?
         } else { entity.get_password_id() };
@@ -214,40 +514,262 @@ impl PasswordDatabase for PmanDatabase {
             build_new_url_id(&mut file, new_url, change_url, entity.get_url_id())?;
         let mut new_props= entity.get_properties();
         for (k, v) in modified_properties {
-            if !new_props.contains_key(&k) {
-                return Err(Error::new(ErrorKind::NotFound, "invalid property id"));
-            }
+            let index = new_props.iter().position(|p|p.name_id == k)
+                .ok_or(Error::new(ErrorKind::NotFound, "invalid property id"))?;
             if let Some(value) = v {
-                let id = file.add_to_passwords(value)?;
-                new_props.insert(k, id);
+                new_props[index].value_id = add_property_value(&mut file, new_props[index].secret, value)?;
+            } else {
+                new_props.remove(index);
+            }
+        }
+        add_new_properties(&mut file, template.as_ref(), new_properties, &mut new_props)?;
+        let otp_id = entity.get_otp_id();
+        entity.update(&mut file, new_pid, new_gid, new_uid, new_url_id, new_props, otp_id)?;
+        file.set_in_names(entity_id, entity)?;
+        drop(file);
+        self.audit(AuditEventType::Modify, AuditObjectType::Entity, entity_id, &changes)
+    }
+
+    fn set_property_layout(&self, entity_id: u32, layout: Vec<PropertyLayout>) -> Result<(), Error> {
+        let mut entity = self.get_entity(entity_id)?;
+        let properties = entity.get_properties();
+        if layout.len() != properties.len() {
+            return Err(Error::new(ErrorKind::InvalidInput, "property layout should contain all entity properties"));
+        }
+        let group_id = entity.get_group_id(ENTITY_VERSION_LATEST)?;
+        let user_id = entity.get_user_id(ENTITY_VERSION_LATEST)?;
+        let mut file = self.file.lock().unwrap();
+        let mut values = Vec::new();
+        for item in &layout {
+            let property = properties.iter().find(|p|p.name_id == item.id)
+                .ok_or(Error::new(ErrorKind::NotFound, "invalid property id"))?;
+            if values.iter().any(|(p, _v): &(&EntityProperty, String)|p.name_id == item.id) {
+                return Err(Error::new(ErrorKind::InvalidInput, "duplicate property id"));
+            }
+            let value = read_property_value(&mut file, property)?;
+            if !value.is_empty() && !validate_field_value(item.field_type, &value) {
+                let name: String = file.get_from_names(item.id)?;
+                return Err(Error::new(ErrorKind::InvalidInput, format!("invalid {} value", name)));
+            }
+            values.push((property, value));
+        }
+        let mut new_props = Vec::new();
+        for (item, (property, value)) in layout.into_iter().zip(values) {
+            // value moves between names and passwords files when secret flag changes
+            let value_id = if item.secret == property.secret {
+                property.value_id
             } else {
-                new_props.remove(&k);
+                add_property_value(&mut file, item.secret, value)?
+            };
+            new_props.push(EntityProperty{name_id: item.id, value_id, field_type: item.field_type, secret: item.secret});
+        }
+        entity.update(&mut file, entity.get_password_id(), group_id, user_id, entity.get_url_id(),
+                      new_props, entity.get_otp_id())?;
+        file.set_in_names(entity_id, entity)?;
+        drop(file);
+        self.audit(AuditEventType::Modify, AuditObjectType::Entity, entity_id, "property layout")
+    }
+
+    fn restore_entity_version(&self, entity_id: u32, version: u32) -> Result<(), Error> {
+        let mut entity = self.get_entity(entity_id)?;
+        let group_id = entity.get_group_id(version)?;
+        if group_id != entity.get_group_id(ENTITY_VERSION_LATEST)? {
+            self.check_entity_name(group_id, entity.get_name()?)?;
+        }
+        let mut file = self.file.lock().unwrap();
+        entity.restore_version(&mut file, version)?;
+        file.set_in_names(entity_id, entity)?;
+        drop(file);
+        self.audit(AuditEventType::Modify, AuditObjectType::Entity, entity_id, &format!("restored version {}", version))
+    }
+
+    fn set_entity_urls(&self, entity_id: u32, urls: Vec<EntityUrl>) -> Result<(), Error> {
+        if urls.len() > MAX_URLS {
+            return Err(Error::new(ErrorKind::InvalidInput, "too many urls"));
+        }
+        for url in &urls {
+            validate_url_pattern(&url.url, url.match_mode)?;
+        }
+        let mut entity = self.get_entity(entity_id)?;
+        if entity.is_note() {
+            return Err(Error::new(ErrorKind::InvalidInput, "notes have no user and url"));
+        }
+        let mut file = self.file.lock().unwrap();
+        // unchanged urls keep their record ids
+        let mut current: HashMap<String, u32> = HashMap::new();
+        for id in entity.get_url_ids() {
+            current.insert(file.get_from_names(id)?, id);
+        }
+        let mut ids = Vec::new();
+        for url in urls {
+            let id = match current.get(&url.url) {
+                Some(id) => *id,
+                None => file.add_to_names(url.url)?
+            };
+            ids.push((id, url.match_mode));
+        }
+        let (url_id, url_match_mode) = if ids.is_empty() {
+            (None, DEFAULT_URL_MATCH_MODE)
+        } else {
+            let (id, match_mode) = ids.remove(0);
+            (Some(id), match_mode)
+        };
+        entity.update_urls(&mut file, url_id, url_match_mode, ids)?;
+        file.set_in_names(entity_id, entity)?;
+        drop(file);
+        self.audit(AuditEventType::Modify, AuditObjectType::Entity, entity_id, "urls")
+    }
+
+    fn add_attachment(&self, entity_id: u32, name: String, data: Vec<u8>) -> Result<u32, Error> {
+        let mut entity = self.get_entity(entity_id)?;
+        let mut attachments = entity.get_attachments();
+        if attachments.len() >= MAX_ATTACHMENTS {
+            return Err(Error::new(ErrorKind::InvalidInput, "too many attachments"));
+        }
+        let mut file = self.file.lock().unwrap();
+        check_name_is_unique(&mut file, &attachments, name.clone(), "duplicate attachment name")?;
+        let name_id = file.add_to_names(name)?;
+        let data_id = file.add_to_passwords(data)?;
+        attachments.insert(name_id, data_id);
+        entity.update_attachments(&mut file, attachments)?;
+        file.set_in_names(entity_id, entity)?;
+        drop(file);
+        self.audit(AuditEventType::Modify, AuditObjectType::Entity, entity_id, "attachment added")?;
+        Ok(name_id)
+    }
+
+    fn get_attachment(&self, entity_id: u32, version: u32, attachment_id: u32) -> Result<Vec<u8>, Error> {
+        let entity = self.get_entity(entity_id)?;
+        let data_id = entity.get_attachment_id(version, attachment_id)?;
+        self.file.lock().unwrap().get_from_passwords(data_id)
+    }
+
+    fn remove_attachment(&self, entity_id: u32, attachment_id: u32) -> Result<(), Error> {
+        let mut entity = self.get_entity(entity_id)?;
+        let mut attachments = entity.get_attachments();
+        if attachments.remove(&attachment_id).is_none() {
+            return Err(Error::new(ErrorKind::NotFound, "attachment not found"));
+        }
+        let mut file = self.file.lock().unwrap();
+        entity.update_attachments(&mut file, attachments)?;
+        file.set_in_names(entity_id, entity)?;
+        drop(file);
+        self.audit(AuditEventType::Modify, AuditObjectType::Entity, entity_id, "attachment removed")
+    }
+
+    fn get_tags(&self) -> Result<HashMap<u32, String>, Error> {
+        match self.file.lock().unwrap().get_indirect_from_names(TAGS_ID) {
+            Ok(tags) => Ok(tags),
+            Err(e) => {
+                if e.kind() == ErrorKind::NotFound {
+                    Ok(HashMap::new())
+                } else {
+                    Err(e)
+                }
+            }
+        }
+    }
+
+    fn add_tag(&self, entity_id: u32, tag: String) -> Result<u32, Error> {
+        let mut entity = self.get_entity(entity_id)?;
+        let mut entity_tags = entity.get_tags();
+        let tag_id = match self.get_tags()?.into_iter().find(|(_id, name)|*name == tag) {
+            Some((id, _name)) => {
+                if entity_tags.contains(&id) {
+                    return Ok(id);
+                }
+                id
+            },
+            None => {
+                if tag.is_empty() {
+                    return Err(Error::new(ErrorKind::InvalidInput, "empty tag name"));
+                }
+                if entity_tags.len() >= MAX_TAGS {
+                    return Err(Error::new(ErrorKind::InvalidInput, "too many tags"));
+                }
+                self.add_to_list(TAGS_ID, tag, string_validator)?
+            }
+        };
+        entity_tags.insert(tag_id);
+        let mut file = self.file.lock().unwrap();
+        entity.update_tags(&mut file, entity_tags)?;
+        file.set_in_names(entity_id, entity)?;
+        drop(file);
+        self.audit(AuditEventType::Modify, AuditObjectType::Entity, entity_id, "tags")?;
+        Ok(tag_id)
+    }
+
+    fn remove_tag(&self, entity_id: u32, tag_id: u32) -> Result<(), Error> {
+        let mut entity = self.get_entity(entity_id)?;
+        let mut entity_tags = entity.get_tags();
+        if !entity_tags.remove(&tag_id) {
+            return Err(Error::new(ErrorKind::NotFound, "tag not found"));
+        }
+        let mut file = self.file.lock().unwrap();
+        entity.update_tags(&mut file, entity_tags)?;
+        file.set_in_names(entity_id, entity)?;
+        drop(file);
+        self.audit(AuditEventType::Modify, AuditObjectType::Entity, entity_id, "tags")?;
+        let entities = self.get_all_entities_with_trash()?;
+        if entities.iter().find(|(_id, e)|e.collect_tag_ids().contains(&tag_id)).is_none() {
+            self.remove_from_list(TAGS_ID, tag_id)?;
+        }
+        Ok(())
+    }
+
+    fn get_entities_by_tags(&self, tag_ids: Vec<u32>) -> Result<HashMap<u32, Box<dyn PasswordDatabaseEntity + Send>>, Error> {
+        if tag_ids.is_empty() {
+            return Err(Error::new(ErrorKind::InvalidInput, "tag ids expected"));
+        }
+        let entities = self.get_all_entities()?;
+        let mut result: HashMap<u32, Box<dyn PasswordDatabaseEntity + Send>> = HashMap::new();
+        for (k, v) in entities {
+            let entity_tags = v.get_tags();
+            if tag_ids.iter().all(|id|entity_tags.contains(id)) {
+                result.insert(k, Box::new(v));
             }
         }
-        for (k, v) in new_properties {
-            check_property_name(&mut file,&new_props, k.clone())?;
-            let key_id = file.add_to_names(k)?;
-            let value_id = file.add_to_passwords(v)?;
-            new_props.insert(key_id, value_id);
+        Ok(result)
+    }
+
+    fn find_by_url(&self, url: String) -> Result<Vec<UrlMatch>, Error> {
+        let mut matches = Vec::new();
+        for (entity_id, entity) in self.get_all_entities()? {
+            let mut best: Option<(u32, EntityUrl)> = None;
+            for entity_url in entity.get_urls(ENTITY_VERSION_LATEST)? {
+                if let Some(score) = match_url(&entity_url.url, entity_url.match_mode, &url) {
+                    if best.as_ref().map(|(s, _u)|score > *s).unwrap_or(true) {
+                        best = Some((score, entity_url));
+                    }
+                }
+            }
+            if let Some((score, entity_url)) = best {
+                let group_id = entity.get_group_id(ENTITY_VERSION_LATEST)?;
+                matches.push((score, UrlMatch{entity_id, group_id, url: entity_url.url, match_mode: entity_url.match_mode}));
+            }
         }
-        entity.update(&mut file, new_pid, new_gid, new_uid, new_url_id, new_props)?;
-        file.set_in_names(entity_id, entity)
+        matches.sort_by(|(s1, m1), (s2, m2)|s2.cmp(s1).then(m1.entity_id.cmp(&m2.entity_id)));
+        Ok(matches.into_iter().map(|(_s, m)|m).collect())
     }
 
     fn as_any(&self) -> &dyn Any {
         self
     }
+
+    fn as_mut_any(&mut self) -> &mut dyn Any {
+        self
+    }
 }
 
 impl PmanDatabase {
     pub fn new_from_file(contents: Vec<u8>) -> Result<Box<dyn PasswordDatabase>, Error> {
         let file = Arc::new(Mutex::new(PmanDatabaseFile::prepare(contents)?));
-        Ok(Box::new(PmanDatabase { rsa_key: "".to_string(), file }))
+        Ok(Box::new(PmanDatabase { rsa_key: "".to_string(), file, entity_index: Mutex::new(None) }))
     }
 
     pub fn new_from_file2(contents: Vec<u8>) -> Result<PmanDatabase, Error> {
         let file = Arc::new(Mutex::new(PmanDatabaseFile::prepare(contents)?));
-        Ok(PmanDatabase { rsa_key: "".to_string(), file })
+        Ok(PmanDatabase { rsa_key: "".to_string(), file, entity_index: Mutex::new(None) })
     }
 
     pub fn new(password_hash: Vec<u8>, password2_hash: Vec<u8>,
@@ -256,7 +778,7 @@ impl PmanDatabase {
             Arc::new(Mutex::new(PmanDatabaseFile::new(password_hash, password2_hash)?));
         let rsa_key = String::from_utf8(key_file_contents)
             .map_err(|_e|Error::new(ErrorKind::InvalidData, "invalid rsa key file"))?;
-        Ok(Box::new(PmanDatabase { rsa_key, file }))
+        Ok(Box::new(PmanDatabase { rsa_key, file, entity_index: Mutex::new(None) }))
     }
 
     pub fn new2(password_hash: Vec<u8>, password2_hash: Vec<u8>, key_file_contents: Vec<u8>)
@@ -265,7 +787,7 @@ impl PmanDatabase {
             Arc::new(Mutex::new(PmanDatabaseFile::new(password_hash, password2_hash)?));
         let rsa_key = String::from_utf8(key_file_contents)
             .map_err(|_e|Error::new(ErrorKind::InvalidData, "invalid rsa key file"))?;
-        Ok(PmanDatabase { rsa_key, file })
+        Ok(PmanDatabase { rsa_key, file, entity_index: Mutex::new(None) })
     }
 
     fn open_from_data(&self, data1: Vec<u8>, data2: Vec<u8>) -> Result<(), Error> {
@@ -278,9 +800,100 @@ impl PmanDatabase {
 
     fn get_all_entities(&self) -> Result<HashMap<u32, PmanDatabaseEntity>, Error> {
         let mut entities: HashMap<u32, PmanDatabaseEntity> = self.file.lock().unwrap().get_indirect_from_names(ENTITIES_ID)?;
-        for (_key, value) in &mut entities {
-            value.set_database_file(self.file.clone());
+        for (key, value) in &mut entities {
+            value.set_database_file(self.file.clone(), *key);
+        }
+        Ok(entities)
+    }
+
+    // entities matching the search query, see search_query.rs for the syntax
+    fn find_entities(&self, search_string: &str) -> Result<HashMap<u32, PmanDatabaseEntity>, Error> {
+        let query = parse_search_query(search_string)?;
+        let entities = self.get_all_entities()?;
+        if entities.is_empty() {
+            return Ok(entities);
+        }
+        let users = self.get_users()?;
+        let tags = self.get_tags()?;
+        let group_paths = self.get_group_paths()?;
+        let mut result = HashMap::new();
+        for (entity_id, entity) in entities {
+            let target = EntitySearchTarget{ entity: &entity, users: &users, tags: &tags, group_paths: &group_paths };
+            if query.matches(&target)? {
+                result.insert(entity_id, entity);
+            }
+        }
+        Ok(result)
+    }
+
+    // index is rebuilt after any names records change, audit log writes don't invalidate it
+    fn get_entity_index(&self) -> Result<Arc<Vec<EntityIndexItem>>, Error> {
+        let version = self.file.lock().unwrap().get_names_version();
+        let mut index = self.entity_index.lock().unwrap();
+        if let Some((index_version, items)) = index.as_ref() {
+            if *index_version == version {
+                return Ok(items.clone());
+            }
+        }
+        let mut items = Vec::new();
+        for (entity_id, entity) in self.get_all_entities()? {
+            items.push(EntityIndexItem{
+                entity_id,
+                group_id: entity.get_group_id(ENTITY_VERSION_LATEST)?,
+                lowercase_name: entity.get_name()?.to_lowercase(),
+                created_at: entity.get_entity_created_at(),
+                modified_at: entity.get_created_at(ENTITY_VERSION_LATEST)?
+            });
         }
+        let items = Arc::new(items);
+        *index = Some((version, items.clone()));
+        Ok(items)
+    }
+
+    // only entities of the requested page are decrypted
+    fn get_entities_page(&self, filter: impl Fn(&EntityIndexItem) -> bool, sort_key: EntitySortKey, descending: bool,
+                         offset: u32, limit: u32) -> Result<(u32, Vec<(u32, Box<dyn PasswordDatabaseEntity + Send>)>), Error> {
+        let index = self.get_entity_index()?;
+        let mut items: Vec<&EntityIndexItem> = index.iter().filter(|item|filter(item)).collect();
+        let group_paths = if sort_key == EntitySortKey::Group {
+            self.get_group_paths()?
+        } else { HashMap::new() };
+        let by_name = |a: &EntityIndexItem, b: &EntityIndexItem|a.lowercase_name.cmp(&b.lowercase_name)
+            .then(a.entity_id.cmp(&b.entity_id));
+        items.sort_by(|a, b|{
+            let ordering = match sort_key {
+                EntitySortKey::Name => by_name(a, b),
+                EntitySortKey::CreatedAt => a.created_at.cmp(&b.created_at).then(by_name(a, b)),
+                EntitySortKey::ModifiedAt => a.modified_at.cmp(&b.modified_at).then(by_name(a, b)),
+                EntitySortKey::Group => group_paths.get(&a.group_id).cmp(&group_paths.get(&b.group_id))
+                    .then(a.group_id.cmp(&b.group_id))
+                    .then(by_name(a, b))
+            };
+            if descending { ordering.reverse() } else { ordering }
+        });
+        let total = items.len() as u32;
+        let mut result: Vec<(u32, Box<dyn PasswordDatabaseEntity + Send>)> = Vec::new();
+        for item in items.into_iter().skip(offset as usize).take(limit as usize) {
+            result.push((item.entity_id, Box::new(self.get_entity(item.entity_id)?)));
+        }
+        Ok((total, result))
+    }
+
+    fn get_trashed_entities(&self) -> Result<HashMap<u32, PmanDatabaseEntity>, Error> {
+        let file = self.file.lock().unwrap();
+        let mut result = HashMap::new();
+        for entity_id in get_id_map(&file, TRASH_ID)?.into_keys() {
+            let mut entity: PmanDatabaseEntity = file.get_from_names(entity_id)?;
+            entity.set_database_file(self.file.clone(), entity_id);
+            result.insert(entity_id, entity);
+        }
+        Ok(result)
+    }
+
+    // trashed entities keep their tags
+    fn get_all_entities_with_trash(&self) -> Result<HashMap<u32, PmanDatabaseEntity>, Error> {
+        let mut entities = self.get_all_entities()?;
+        entities.extend(self.get_trashed_entities()?);
         Ok(entities)
     }
 
@@ -304,87 +917,1220 @@ impl PmanDatabase {
         Ok(idx)
     }
 
-    fn add_to_entity_list(&self, value: PmanDatabaseEntity) -> Result<u32, Error> {
-        self.add_to_list(ENTITIES_ID, value, no_validator)
+    fn add_to_entity_list(&self, value: PmanDatabaseEntity) -> Result<u32, Error> {
+        self.add_to_list(ENTITIES_ID, value, no_validator)
+    }
+
+    fn check_exists(&self, list_id: u32, id: u32, error_message: &str) -> Result<(), Error> {
+        let items: Vec<u32> = self.file.lock().unwrap().get_from_names(list_id)?;
+        if items.into_iter().find(|i| *i == id).is_none() {
+            return Err(Error::new(ErrorKind::NotFound, error_message));
+        }
+        Ok(())
+    }
+
+    fn check_group_exists(&self, group_id: u32) -> Result<(), Error> {
+        self.check_exists(GROUPS_ID, group_id, "group not found")
+    }
+
+    fn check_user_exists(&self, user_id: u32) -> Result<(), Error> {
+        self.check_exists(USERS_ID, user_id, "user not found")
+    }
+
+    fn check_entity_exists(&self, entity_id: u32) -> Result<(), Error> {
+        self.check_exists(ENTITIES_ID, entity_id, "entity not found")
+    }
+
+    fn remove_from_list(&self, list_id: u32, id: u32) -> Result<(), Error> {
+        self.unlink_from_list(list_id, id)?;
+        self.file.lock().unwrap().remove_from_names(&id)
+    }
+
+    // removes id from the list, the record itself is kept
+    fn unlink_from_list(&self, list_id: u32, id: u32) -> Result<(), Error> {
+        let mut file = self.file.lock().unwrap();
+        let mut indexes: Vec<u32> = file.get_from_names(list_id)?;
+        let index = indexes.iter().position(|i|*i == id).ok_or(build_not_found_error())?;
+        indexes.remove(index);
+        if indexes.is_empty() {
+            file.remove_from_names(&list_id)
+        } else {
+            file.set_in_names(list_id, indexes)
+        }
+    }
+
+    // applies replace to entities and trashed entities, returns ids of changed entities
+    // and true when any entity is still in_use after replacement
+    fn replace_in_entities(&self, replace: impl Fn(&mut MutexGuard<PmanDatabaseFile>, &mut PmanDatabaseEntity) -> Result<bool, Error>,
+                           in_use: impl Fn(&PmanDatabaseEntity) -> bool) -> Result<(Vec<u32>, bool), Error> {
+        let mut entities: Vec<(u32, PmanDatabaseEntity)> = self.get_all_entities_with_trash()?.into_iter().collect();
+        entities.sort_by_key(|(id, _e)|*id);
+        let mut file = self.file.lock().unwrap();
+        let mut changed = Vec::new();
+        let mut used = false;
+        for (entity_id, mut entity) in entities {
+            let entity_changed = replace(&mut file, &mut entity)?;
+            used |= in_use(&entity);
+            if entity_changed {
+                file.set_in_names(entity_id, entity)?;
+                changed.push(entity_id);
+            }
+        }
+        Ok((changed, used))
+    }
+
+    // retired records are kept for entity history versions
+    fn remove_or_retire(&self, list_id: u32, retired_list_id: u32, id: u32, retire: bool) -> Result<(), Error> {
+        if retire {
+            self.unlink_from_list(list_id, id)?;
+            self.link_to_list(retired_list_id, id)
+        } else {
+            self.remove_from_list(list_id, id)
+        }
+    }
+
+    // adds id of the existing record to the list
+    fn link_to_list(&self, list_id: u32, id: u32) -> Result<(), Error> {
+        let mut file = self.file.lock().unwrap();
+        let mut indexes: Vec<u32> = match file.get_from_names(list_id) {
+            Ok(v) => v,
+            Err(e) if e.kind() == ErrorKind::NotFound => Vec::new(),
+            Err(e) => return Err(e)
+        };
+        indexes.push(id);
+        file.set_in_names(list_id, indexes)
+    }
+
+    fn get_names_records_count(&self) -> Result<usize, Error> {
+        self.file.lock().unwrap().get_names_records_count()
+    }
+
+    fn get_passwords_records_count(&self) -> Result<usize, Error> {
+        self.file.lock().unwrap().get_passwords_records_count()
+    }
+
+    fn check_entity_name(&self, group_id: u32, name: String) -> Result<(), Error> {
+        for (_key, value) in self.get_entities(group_id)? {
+            if value.get_name()? == name {
+                return Err(Error::new(ErrorKind::AlreadyExists, "entity with given name already exists"));
+            }
+        }
+        Ok(())
+    }
+
+    // object name is read from the database, so the object must exist
+    fn audit(&self, event_type: AuditEventType, object_type: AuditObjectType, object_id: u32,
+             details: &str) -> Result<(), Error> {
+        let mut file = self.file.lock().unwrap();
+        if !is_audit_enabled(&file, event_type)? {
+            return Ok(());
+        }
+        let name = get_audit_object_name(&file, object_type, object_id)?;
+        add_audit_record(&mut file, event_type, object_type, object_id, name, details.to_string())
+    }
+
+    // name of the removed object should be read before removal
+    fn audit_with_name(&self, event_type: AuditEventType, object_type: AuditObjectType, object_id: u32,
+                       name: String, details: &str) -> Result<(), Error> {
+        add_audit_record(&mut self.file.lock().unwrap(), event_type, object_type, object_id, name, details.to_string())
+    }
+
+    fn get_audit_object_name(&self, object_type: AuditObjectType, object_id: u32) -> Result<String, Error> {
+        get_audit_object_name(&self.file.lock().unwrap(), object_type, object_id)
+    }
+
+    fn get_entity(&self, entity_id: u32) -> Result<PmanDatabaseEntity, Error> {
+        self.check_entity_exists(entity_id)?;
+        let mut entity: PmanDatabaseEntity = self.file.lock().unwrap().get_from_names(entity_id)?;
+        entity.set_database_file(self.file.clone(), entity_id);
+        Ok(entity)
+    }
+
+    // layer_id 0 - names & passwords data layer, 1 - passwords data layer
+    pub fn set_encryption(&self, layer_id: usize, engines: Vec<CryptoEngine>) -> Result<(), Error> {
+        let algorithms = engines.into_iter()
+            .map(crypto_engine_to_algorithm)
+            .collect::<Result<Vec<u8>, Error>>()?;
+        self.file.lock().unwrap().set_encryption(layer_id, algorithms)
+    }
+
+    pub fn set_file1_location_qs3(&self, file_name: String, s3_key: Vec<u8>) -> Result<(), Error> {
+        self.file.lock().unwrap().set_file1_location_qs3(file_name, s3_key)
+    }
+
+    pub fn set_file2_location_qs3(&self, file_name: String, s3_key: Vec<u8>) -> Result<(), Error> {
+        self.file.lock().unwrap().set_file2_location_qs3(file_name, s3_key)
+    }
+
+    // secret - recovery key, key file contents or member private key
+    pub fn pre_open_with_key_slot(&mut self, slot_type: KeySlotType, secret: Vec<u8>,
+                                  key_file_contents: Option<Vec<u8>>) -> Result<(), Error> {
+        if key_file_contents.is_none() {
+            return Err(Error::new(ErrorKind::InvalidInput, "key file is required"))
+        }
+        let (t, s) = match slot_type {
+            KeySlotType::Passwords =>
+                return Err(Error::new(ErrorKind::InvalidInput, "passwords key slot requires pre_open")),
+            KeySlotType::RecoveryKey => (key_slot_type_to_u8(slot_type), secret),
+            KeySlotType::KeyFile => (KEY_SLOT_KEY_FILE, build_key_file_secret(&secret)),
+            KeySlotType::Member => (KEY_SLOT_MEMBER, secret)
+        };
+        self.rsa_key = String::from_utf8(key_file_contents.unwrap())
+            .map_err(|_e|Error::new(ErrorKind::InvalidData, "invalid rsa key file"))?;
+        self.file.lock().unwrap().pre_open_with_key_slot(t, s)
+    }
+
+    pub fn get_key_slots(&self) -> Result<HashMap<u32, KeySlotType>, Error> {
+        let slots = self.file.lock().unwrap().get_key_slots()?;
+        let mut result = HashMap::new();
+        for (id, slot_type) in slots {
+            result.insert(id, key_slot_type_from_u8(slot_type)?);
+        }
+        Ok(result)
+    }
+
+    pub fn add_recovery_key(&self) -> Result<Vec<u8>, Error> {
+        self.file.lock().unwrap().add_recovery_key()
+    }
+
+    pub fn add_key_file_slot(&self, key_file: Vec<u8>) -> Result<u32, Error> {
+        self.file.lock().unwrap().add_key_slot(KEY_SLOT_KEY_FILE, build_key_file_secret(&key_file))
+    }
+
+    pub fn remove_key_slot(&self, id: u32) -> Result<(), Error> {
+        self.file.lock().unwrap().remove_key_slot(id)
+    }
+
+    pub fn set_passwords(&self, password_hash: Vec<u8>, password2_hash: Vec<u8>) -> Result<(), Error> {
+        self.file.lock().unwrap().set_passwords(password_hash, password2_hash)
+    }
+
+    pub fn get_members(&self) -> Result<HashMap<u32, DatabaseMember>, Error> {
+        self.file.lock().unwrap().get_members()
+    }
+
+    // public_key - x25519 public key of the member
+    pub fn add_member(&self, name: String, public_key: Vec<u8>) -> Result<u32, Error> {
+        self.file.lock().unwrap().add_member(name, public_key)
+    }
+
+    // master keys are rotated, recovery key and key file slots are removed,
+    // returns types of removed key slots that should be added again
+    pub fn revoke_member(&self, member_id: u32) -> Result<Vec<KeySlotType>, Error> {
+        self.file.lock().unwrap().revoke_member(member_id)?
+            .into_iter()
+            .map(key_slot_type_from_u8)
+            .collect()
+    }
+
+    pub fn get_version(&self) -> Result<DatabaseVersion, Error> {
+        let (stored_version, current_version) = self.file.lock().unwrap().get_version()?;
+        Ok(DatabaseVersion{stored_version, current_version})
+    }
+
+    // should be called after the upgraded main file is saved
+    pub fn remove_data_file_backups(&self) -> Result<(), Error> {
+        let (location1, location2) = self.file.lock().unwrap().get_location_data()?;
+        remove_backup_file(&self.rsa_key, location1)?;
+        remove_backup_file(&self.rsa_key, location2)
+    }
+
+    // checks references between index lists, entities and names/passwords records,
+    // repair mode rebuilds index lists and removes orphaned and undecryptable records
+    pub fn check_integrity(&self, repair: bool) -> Result<Vec<IntegrityIssue>, Error> {
+        let mut file = self.file.lock().unwrap();
+        let mut checker = IntegrityChecker::new(&file)?;
+        let groups: HashMap<u32, String> = checker.check_list(&file, GROUPS_ID, "groups")?;
+        let users: HashMap<u32, DatabaseUser> = checker.check_list(&file, USERS_ID, "users")?;
+        let tags: HashMap<u32, String> = checker.check_list(&file, TAGS_ID, "tags")?;
+        let templates: HashMap<u32, Template> = checker.check_list(&file, TEMPLATES_ID, "templates")?;
+        let entities: HashMap<u32, PmanDatabaseEntity> = checker.check_list(&file, ENTITIES_ID, "entities")?;
+        let _audit_log: HashMap<u32, AuditEvent> = checker.check_list(&file, AUDIT_LOG_ID, "audit log")?;
+        let retired_groups: HashMap<u32, String> = checker.check_list(&file, RETIRED_GROUPS_ID, "retired groups")?;
+        let retired_users: HashMap<u32, DatabaseUser> = checker.check_list(&file, RETIRED_USERS_ID, "retired users")?;
+        let group_ids: HashSet<u32> = groups.into_keys().collect();
+        // entity history versions may reference retired users and groups
+        let entity_group_ids: HashSet<u32> = group_ids.iter().map(|id|*id).chain(retired_groups.into_keys()).collect();
+        let user_ids: HashSet<u32> = users.into_keys().chain(retired_users.into_keys()).collect();
+        let tag_ids: HashSet<u32> = tags.into_keys().collect();
+        let template_ids: HashSet<u32> = templates.into_keys()
+            .chain(get_built_in_templates().into_iter().map(|t|t.id))
+            .collect();
+        checker.check_group_parents(&file, &group_ids);
+        checker.check_group_policies(&file, &group_ids);
+        let trash = checker.check_trash(&file, &entities);
+        let mut trash_ids: Vec<u32> = trash.keys().map(|id|*id).collect();
+        trash_ids.sort();
+        for entity_id in trash_ids {
+            checker.check_entity(entity_id, trash.get(&entity_id).unwrap(), &entity_group_ids, &user_ids, &tag_ids, &template_ids);
+        }
+        let mut entity_ids: Vec<u32> = entities.keys().map(|id|*id).collect();
+        entity_ids.sort();
+        let mut names = HashMap::new();
+        for entity_id in entity_ids {
+            let entity = entities.get(&entity_id).unwrap();
+            checker.check_entity(entity_id, entity, &entity_group_ids, &user_ids, &tag_ids, &template_ids);
+            if !checker.is_valid_name_id(entity.get_name_id()) {
+                continue;
+            }
+            let name: String = file.get_from_names(entity.get_name_id())?;
+            let group_id = entity.get_group_id(ENTITY_VERSION_LATEST)?;
+            if let Some(id) = names.insert((group_id, name.clone()), entity_id) {
+                checker.add_issue(IntegrityIssueType::DuplicateName, entity_id,
+                                  format!("entities {} and {} have the same name {} in group {}",
+                                          id, entity_id, name, group_id));
+            }
+        }
+        checker.check_orphans();
+        if repair {
+            checker.repair(&mut file)?;
+        }
+        Ok(checker.issues)
+    }
+
+    // otp - otpauth uri or base32 encoded totp secret, None removes otp field
+    pub fn set_entity_otp(&self, entity_id: u32, otp: Option<String>) -> Result<(), Error> {
+        let mut entity = self.get_entity(entity_id)?;
+        let group_id = entity.get_group_id(ENTITY_VERSION_LATEST)?;
+        let user_id = entity.get_user_id(ENTITY_VERSION_LATEST)?;
+        let mut file = self.file.lock().unwrap();
+        let otp_id = if let Some(o) = otp {
+            Some(file.add_to_passwords(OtpParameters::parse(&o)?.to_uri())?)
+        } else { None };
+        entity.update(&mut file, entity.get_password_id(), group_id, user_id, entity.get_url_id(),
+                      entity.get_properties(), otp_id)?;
+        file.set_in_names(entity_id, entity)?;
+        drop(file);
+        self.audit(AuditEventType::Modify, AuditObjectType::Entity, entity_id, "otp")
+    }
+
+    // returns hotp code and stores the incremented counter in place, no entity version is added,
+    // history versions share the otp record, so restoring a version does not rewind the counter
+    pub fn next_hotp_code(&self, entity_id: u32) -> Result<OtpCode, Error> {
+        let entity = self.get_entity(entity_id)?;
+        let otp_id = entity.get_otp_id()
+            .ok_or(Error::new(ErrorKind::InvalidInput, "entity has no otp field"))?;
+        let mut file = self.file.lock().unwrap();
+        let uri: SecureBytes = file.get_from_passwords(otp_id)?;
+        let mut parameters = OtpParameters::parse_bytes(&uri)?;
+        let code = parameters.next_hotp_code()?;
+        file.set_in_passwords(otp_id, parameters.to_uri())?;
+        drop(file);
+        self.audit(AuditEventType::Modify, AuditObjectType::Entity, entity_id, "hotp counter")?;
+        Ok(code)
+    }
+
+    // expires_at - timestamp in seconds, max_age_days overrides group policy
+    pub fn set_entity_expiry(&self, entity_id: u32, expires_at: Option<u64>,
+                             max_age_days: Option<u32>) -> Result<(), Error> {
+        if max_age_days == Some(0) {
+            return Err(Error::new(ErrorKind::InvalidInput, "max age must be greater than zero"));
+        }
+        let mut entity = self.get_entity(entity_id)?;
+        if entity.is_note() {
+            return Err(Error::new(ErrorKind::InvalidInput, "notes have no password expiry"));
+        }
+        let mut file = self.file.lock().unwrap();
+        entity.update_expiry(&mut file, expires_at, max_age_days)?;
+        file.set_in_names(entity_id, entity)?;
+        drop(file);
+        self.audit(AuditEventType::Modify, AuditObjectType::Entity, entity_id, "expiry")
+    }
+
+    // max age policy applies to entities of the group and its subgroups, None removes the policy
+    pub fn set_group_max_age(&self, group_id: u32, max_age_days: Option<u32>) -> Result<(), Error> {
+        if max_age_days == Some(0) {
+            return Err(Error::new(ErrorKind::InvalidInput, "max age must be greater than zero"));
+        }
+        self.check_group_exists(group_id)?;
+        let mut file = self.file.lock().unwrap();
+        let mut policies = get_id_map(&file, GROUP_POLICIES_ID)?;
+        match max_age_days {
+            Some(days) => policies.insert(group_id, days),
+            None => policies.remove(&group_id)
+        };
+        set_id_map(&mut file, GROUP_POLICIES_ID, policies)
+    }
+
+    // built-in templates followed by custom templates
+    pub fn get_templates(&self) -> Result<Vec<EntityTemplate>, Error> {
+        let mut result = get_built_in_templates();
+        let templates: HashMap<u32, Template> = match self.file.lock().unwrap().get_indirect_from_names(TEMPLATES_ID) {
+            Ok(t) => t,
+            Err(e) => {
+                if e.kind() == ErrorKind::NotFound {
+                    HashMap::new()
+                } else {
+                    return Err(e);
+                }
+            }
+        };
+        let mut custom: Vec<EntityTemplate> = templates.into_iter()
+            .map(|(id, t)|t.to_entity_template(id))
+            .collect();
+        custom.sort_by_key(|t|t.id);
+        result.append(&mut custom);
+        Ok(result)
+    }
+
+    pub fn add_template(&self, name: String, fields: Vec<TemplateField>) -> Result<u32, Error> {
+        let template = Template::new(name, fields)?;
+        if self.get_templates()?.iter().any(|t|&t.name == template.get_name()) {
+            return Err(Error::new(ErrorKind::AlreadyExists, "template with given name already exists"));
+        }
+        self.add_to_list(TEMPLATES_ID, template, no_validator)
+    }
+
+    // template cannot be removed while entity history references it
+    pub fn remove_template(&self, template_id: u32) -> Result<(), Error> {
+        if get_built_in_templates().iter().any(|t|t.id == template_id) {
+            return Err(Error::new(ErrorKind::InvalidInput, "built-in template cannot be removed"));
+        }
+        self.check_exists(TEMPLATES_ID, template_id, "template not found")?;
+        let entities = self.get_all_entities()?;
+        if entities.iter().any(|(_id, e)|e.collect_template_ids().contains(&template_id)) {
+            return Err(Error::new(ErrorKind::InvalidInput, "template is in use"));
+        }
+        self.destroy_trashed_entities(|e|e.collect_template_ids().contains(&template_id))?;
+        self.remove_from_list(TEMPLATES_ID, template_id)
+    }
+
+    pub fn get_history_length(&self) -> Result<u32, Error> {
+        Ok(self.file.lock().unwrap().get_history_length()? as u32)
+    }
+
+    // histories of entities that use database default history length are pruned
+    pub fn set_history_length(&self, length: u32) -> Result<(), Error> {
+        let entities = self.get_all_entities_with_trash()?;
+        let mut file = self.file.lock().unwrap();
+        let old_length = file.get_history_length()?;
+        file.set_history_length(length as usize)?;
+        if old_length == length as usize {
+            return Ok(());
+        }
+        for (entity_id, mut entity) in entities {
+            let max_version = entity.get_max_version();
+            entity.prune_history(&mut file)?;
+            if entity.get_max_version() != max_version {
+                file.set_in_names(entity_id, entity)?;
+            }
+        }
+        drop(file);
+        self.audit(AuditEventType::Modify, AuditObjectType::Database, 0,
+                   &format!("history length: {} -> {}", old_length, length))
+    }
+
+    // length - None resets entity history length to the database default
+    pub fn set_entity_history_length(&self, entity_id: u32, length: Option<u32>) -> Result<(), Error> {
+        let mut entity = self.get_entity(entity_id)?;
+        let mut file = self.file.lock().unwrap();
+        entity.set_history_length(&mut file, length.map(|l|l as usize))?;
+        file.set_in_names(entity_id, entity)?;
+        drop(file);
+        self.audit(AuditEventType::Modify, AuditObjectType::Entity, entity_id, "history length")
+    }
+
+    // removes all previous versions of the entity, e.g. after password leak
+    pub fn purge_entity_history(&self, entity_id: u32) -> Result<(), Error> {
+        let mut entity = self.get_entity(entity_id)?;
+        let mut file = self.file.lock().unwrap();
+        entity.purge_history(&mut file)?;
+        file.set_in_names(entity_id, entity)?;
+        drop(file);
+        self.audit(AuditEventType::Modify, AuditObjectType::Entity, entity_id, "history purged")
+    }
+
+    // trashed entities sorted by deletion time
+    pub fn get_trash(&self) -> Result<Vec<TrashedEntity>, Error> {
+        let trash = get_id_map(&self.file.lock().unwrap(), TRASH_ID)?;
+        let mut result = Vec::new();
+        for (entity_id, entity) in self.get_trashed_entities()? {
+            result.push(TrashedEntity{
+                entity_id,
+                name: entity.get_name()?,
+                group_id: entity.get_group_id(ENTITY_VERSION_LATEST)?,
+                deleted_at: *trash.get(&entity_id).unwrap() as u64
+            });
+        }
+        result.sort_by_key(|e|(e.deleted_at, e.entity_id));
+        Ok(result)
+    }
+
+    // entity is restored into its original group
+    pub fn restore_entity(&self, entity_id: u32) -> Result<(), Error> {
+        let mut trash = get_id_map(&self.file.lock().unwrap(), TRASH_ID)?;
+        if trash.remove(&entity_id).is_none() {
+            return Err(Error::new(ErrorKind::NotFound, "entity not found in trash"));
+        }
+        let mut entity: PmanDatabaseEntity = self.file.lock().unwrap().get_from_names(entity_id)?;
+        entity.set_database_file(self.file.clone(), entity_id);
+        self.check_entity_name(entity.get_group_id(ENTITY_VERSION_LATEST)?, entity.get_name()?)?;
+        set_id_map(&mut self.file.lock().unwrap(), TRASH_ID, trash)?;
+        self.link_to_list(ENTITIES_ID, entity_id)?;
+        self.audit(AuditEventType::Restore, AuditObjectType::Entity, entity_id, "")
+    }
+
+    pub fn empty_trash(&self) -> Result<(), Error> {
+        self.destroy_trashed_entities(|_e|true)
+    }
+
+    pub fn get_trash_retention(&self) -> Result<u32, Error> {
+        get_trash_retention(&self.file.lock().unwrap())
+    }
+
+    // days = 0 disables automatic trash purge
+    pub fn set_trash_retention(&self, days: u32) -> Result<(), Error> {
+        let mut file = self.file.lock().unwrap();
+        let old_days = get_trash_retention(&file)?;
+        if old_days == days {
+            return Ok(());
+        }
+        file.set_in_names(TRASH_RETENTION_ID, vec![days])?;
+        drop(file);
+        // shorter retention purges trashed entities, so the change is logged
+        self.audit(AuditEventType::Modify, AuditObjectType::Database, 0,
+                   &format!("trash retention: {} -> {} days", old_days, days))
+    }
+
+    // destroys trash items deleted more than retention days before timestamp
+    fn purge_trash(&self, timestamp: u64) -> Result<(), Error> {
+        let mut file = self.file.lock().unwrap();
+        let retention = get_trash_retention(&file)? as u64;
+        if retention == 0 {
+            return Ok(());
+        }
+        let trash = get_id_map(&file, TRASH_ID)?;
+        let expired: Vec<u32> = trash.iter()
+            .filter(|(_id, deleted_at)|**deleted_at as u64 + retention * 86400 <= timestamp)
+            .map(|(id, _deleted_at)|*id)
+            .collect();
+        destroy_trash_items(&mut file, expired)
+    }
+
+    // trashed entities that reference removed groups, users or templates cannot be restored
+    fn destroy_trashed_entities(&self, filter: impl Fn(&PmanDatabaseEntity) -> bool) -> Result<(), Error> {
+        let ids: Vec<u32> = self.get_trashed_entities()?.into_iter()
+            .filter(|(_id, e)|filter(e))
+            .map(|(id, _e)|id)
+            .collect();
+        destroy_trash_items(&mut self.file.lock().unwrap(), ids)
+    }
+
+    // records sorted from oldest to newest, since - minimal timestamp, object_id - group, user or entity id
+    pub fn get_audit_log(&self, since: Option<u64>, object_id: Option<u32>) -> Result<Vec<AuditEvent>, Error> {
+        Ok(get_audit_log(&self.file.lock().unwrap())?.into_iter()
+            .filter(|e|since.map(|s|e.timestamp >= s).unwrap_or(true) &&
+                object_id.map(|id|e.object_id == id).unwrap_or(true))
+            .collect())
+    }
+
+    pub fn get_audit_settings(&self) -> Result<AuditSettings, Error> {
+        get_audit_settings(&self.file.lock().unwrap())
+    }
+
+    // max_records = 0 disables audit log, existing records are kept
+    pub fn set_audit_settings(&self, settings: AuditSettings) -> Result<(), Error> {
+        set_audit_settings(&mut self.file.lock().unwrap(), settings)
+    }
+
+    // true when audit records were added after the last save, e.g. by secret reveals
+    pub fn is_audit_pending(&self) -> bool {
+        self.file.lock().unwrap().is_audit_pending()
+    }
+
+    // label of the current device is added to audit records until the database is closed
+    pub fn set_device_label(&self, label: String) -> Result<(), Error> {
+        self.file.lock().unwrap().set_device_label(label)
+    }
+
+    // creates a database with selected groups, their entities with history and referenced users, tags and templates.
+    // record ids are kept, so entity records are copied as is. groups referenced only by entity history
+    // are stored as retired groups. source database is not changed
+    pub fn export_to_new_database(&self, group_ids: Vec<u32>, password_hash: Vec<u8>, password2_hash: Vec<u8>,
+                                  key_file_contents: Vec<u8>) -> Result<PmanDatabase, Error> {
+        if group_ids.is_empty() {
+            return Err(Error::new(ErrorKind::InvalidInput, "group ids expected"));
+        }
+        for group_id in &group_ids {
+            self.check_group_exists(*group_id)?;
+        }
+        let selected: HashSet<u32> = group_ids.into_iter().collect();
+        let mut entity_ids = Vec::new();
+        let mut names_ids = HashSet::new();
+        let mut passwords_ids = HashSet::new();
+        let mut history_group_ids = HashSet::new();
+        let mut user_ids = HashSet::new();
+        let mut tag_ids = HashSet::new();
+        let mut template_ids = HashSet::new();
+        for (entity_id, entity) in self.get_all_entities()? {
+            if !selected.contains(&entity.get_group_id(ENTITY_VERSION_LATEST)?) {
+                continue;
+            }
+            entity_ids.push(entity_id);
+            names_ids.insert(entity_id);
+            names_ids.extend(entity.collect_names_ids());
+            passwords_ids.extend(entity.collect_passwords_ids());
+            history_group_ids.extend(entity.collect_group_ids().into_iter().filter(|id|!selected.contains(id)));
+            user_ids.extend(entity.collect_user_ids());
+            tag_ids.extend(entity.collect_tag_ids());
+            template_ids.extend(entity.collect_template_ids());
+        }
+        entity_ids.sort();
+        let database = PmanDatabase::new2(password_hash, password2_hash, key_file_contents)?;
+        {
+            let mut source = self.file.lock().unwrap();
+            let mut target = database.file.lock().unwrap();
+            target.set_history_length(source.get_history_length()?)?;
+            copy_list_items(&source, &mut target, &[GROUPS_ID], GROUPS_ID, &selected)?;
+            copy_list_items(&source, &mut target, &[GROUPS_ID, RETIRED_GROUPS_ID], RETIRED_GROUPS_ID, &history_group_ids)?;
+            copy_list_items(&source, &mut target, &[USERS_ID], USERS_ID, &user_ids)?;
+            copy_list_items(&source, &mut target, &[RETIRED_USERS_ID], RETIRED_USERS_ID, &user_ids)?;
+            copy_list_items(&source, &mut target, &[TAGS_ID], TAGS_ID, &tag_ids)?;
+            copy_list_items(&source, &mut target, &[TEMPLATES_ID], TEMPLATES_ID, &template_ids)?;
+            let parents = get_id_map(&source, GROUP_PARENTS_ID)?.into_iter()
+                .filter(|(id, parent_id)|selected.contains(id) && selected.contains(parent_id))
+                .collect();
+            set_id_map(&mut target, GROUP_PARENTS_ID, parents)?;
+            let policies = get_id_map(&source, GROUP_POLICIES_ID)?.into_iter()
+                .filter(|(id, _max_age)|selected.contains(id))
+                .collect();
+            set_id_map(&mut target, GROUP_POLICIES_ID, policies)?;
+            for id in names_ids {
+                let value: Vec<u8> = source.get_from_names(id)?;
+                target.set_in_names(id, value)?;
+            }
+            for id in passwords_ids {
+                let value: SecureBytes = source.get_from_passwords(id)?;
+                target.set_in_passwords(id, value)?;
+            }
+            if !entity_ids.is_empty() {
+                target.set_in_names(ENTITIES_ID, entity_ids)?;
+            }
+        }
+        Ok(database)
+    }
+
+    // returns entities that are expired or expire within given number of days, sorted by expiry time
+    pub fn get_expiring(&self, days: u32) -> Result<Vec<ExpiringEntity>, Error> {
+        self.get_expiring_at(get_current_timestamp(), days)
+    }
+
+    fn get_expiring_at(&self, timestamp: u64, days: u32) -> Result<Vec<ExpiringEntity>, Error> {
+        let file = self.file.lock().unwrap();
+        let parents = get_id_map(&file, GROUP_PARENTS_ID)?;
+        let policies = get_id_map(&file, GROUP_POLICIES_ID)?;
+        drop(file);
+        let limit = timestamp + days as u64 * 86400;
+        let mut result = Vec::new();
+        for (entity_id, entity) in self.get_all_entities()? {
+            if entity.is_note() {
+                continue;
+            }
+            let password_changed_at = entity.get_password_changed_at();
+            let max_age_days = match entity.get_max_age_days(ENTITY_VERSION_LATEST)? {
+                Some(d) => Some(d),
+                None => get_group_ancestors(&parents, entity.get_group_id(ENTITY_VERSION_LATEST)?)?
+                    .into_iter()
+                    .find_map(|id|policies.get(&id).map(|d|*d))
+            };
+            let max_age_expires_at =
+                max_age_days.map(|d|password_changed_at + d as u64 * 86400);
+            let expires_at = match (entity.get_expires_at(ENTITY_VERSION_LATEST)?, max_age_expires_at) {
+                (Some(e1), Some(e2)) => e1.min(e2),
+                (Some(e), None) | (None, Some(e)) => e,
+                (None, None) => continue
+            };
+            if expires_at <= limit {
+                result.push(ExpiringEntity{entity_id, password_changed_at, expires_at});
+            }
+        }
+        result.sort_by_key(|e|(e.expires_at, e.entity_id));
+        Ok(result)
+    }
+
+    // group id -> group path like Parent/Child
+    fn get_group_paths(&self) -> Result<HashMap<u32, String>, Error> {
+        let file = self.file.lock().unwrap();
+        let groups: HashMap<u32, String> = file.get_indirect_from_names(GROUPS_ID)?;
+        let parents = get_id_map(&file, GROUP_PARENTS_ID)?;
+        let mut result = HashMap::new();
+        for id in groups.keys() {
+            let path: Vec<&str> = get_group_ancestors(&parents, *id)?.into_iter().rev()
+                .map(|group_id|groups.get(&group_id).map(|name|name.as_str()).unwrap_or(""))
+                .collect();
+            result.insert(*id, path.join("/"));
+        }
+        Ok(result)
+    }
+}
+
+// entity values for search queries, user, tag and group names are resolved for the latest entity version
+struct EntitySearchTarget<'a> {
+    entity: &'a PmanDatabaseEntity,
+    users: &'a HashMap<u32, DatabaseUser>,
+    tags: &'a HashMap<u32, String>,
+    group_paths: &'a HashMap<u32, String>
+}
+
+impl<'a> SearchTarget for EntitySearchTarget<'a> {
+    fn get_text_values(&self, field: TextField) -> Result<Vec<String>, Error> {
+        match field {
+            TextField::Name => Ok(vec![self.entity.get_name()?]),
+            TextField::Url => Ok(self.entity.get_urls(ENTITY_VERSION_LATEST)?.into_iter().map(|u|u.url).collect()),
+            TextField::User => Ok(self.users.get(&self.entity.get_user_id(ENTITY_VERSION_LATEST)?)
+                .map(|u|vec![u.name.clone(), u.email.clone(), u.display_name.clone()])
+                .unwrap_or(Vec::new())),
+            TextField::Group => Ok(self.group_paths.get(&self.entity.get_group_id(ENTITY_VERSION_LATEST)?)
+                .map(|p|vec![p.clone()])
+                .unwrap_or(Vec::new())),
+            TextField::Tag => Ok(self.entity.get_tag_ids(ENTITY_VERSION_LATEST)?.into_iter()
+                .filter_map(|id|self.tags.get(&id).cloned())
+                .collect())
+        }
+    }
+
+    // secret property values are not decrypted, so search does not create reveal audit records
+    fn get_properties(&self) -> Result<Vec<(String, Option<String>)>, Error> {
+        let mut result = Vec::new();
+        for property in self.entity.get_property_names(ENTITY_VERSION_LATEST)? {
+            let value = if property.secret { None } else {
+                Some(self.entity.get_property_value(ENTITY_VERSION_LATEST, property.id)?)
+            };
+            result.push((property.name, value));
+        }
+        Ok(result)
+    }
+
+    fn get_timestamp(&self, field: DateField) -> Result<u64, Error> {
+        match field {
+            DateField::Created => Ok(self.entity.get_entity_created_at()),
+            DateField::Modified => self.entity.get_created_at(ENTITY_VERSION_LATEST)
+        }
+    }
+}
+
+struct IntegrityChecker {
+    issues: Vec<IntegrityIssue>,
+    names_ids: HashSet<u32>,
+    passwords_ids: HashSet<u32>,
+    undecryptable_names_ids: HashSet<u32>,
+    undecryptable_passwords_ids: HashSet<u32>,
+    referenced_names_ids: HashSet<u32>,
+    referenced_passwords_ids: HashSet<u32>,
+    // list id -> (list must be rebuilt, valid ids)
+    lists: HashMap<u32, (bool, Vec<u32>)>,
+    // map id -> valid map contents for id maps that must be rebuilt
+    id_maps: HashMap<u32, HashMap<u32, u32>>
+}
+
+impl IntegrityChecker {
+    fn new(file: &MutexGuard<PmanDatabaseFile>) -> Result<IntegrityChecker, Error> {
+        let mut checker = IntegrityChecker{
+            issues: Vec::new(),
+            names_ids: file.get_names_ids()?.into_iter().collect(),
+            passwords_ids: file.get_passwords_ids()?.into_iter().collect(),
+            undecryptable_names_ids: HashSet::new(),
+            undecryptable_passwords_ids: HashSet::new(),
+            referenced_names_ids: HashSet::new(),
+            referenced_passwords_ids: HashSet::new(),
+            lists: HashMap::new(),
+            id_maps: HashMap::new()
+        };
+        for id in &checker.names_ids {
+            if file.check_names_record(*id).is_err() {
+                checker.undecryptable_names_ids.insert(*id);
+            }
+        }
+        for id in &checker.passwords_ids {
+            if file.check_passwords_record(*id).is_err() {
+                checker.undecryptable_passwords_ids.insert(*id);
+            }
+        }
+        let mut undecryptable: Vec<u32> = checker.undecryptable_names_ids.iter()
+            .chain(checker.undecryptable_passwords_ids.iter()).map(|id|*id).collect();
+        undecryptable.sort();
+        for id in undecryptable {
+            checker.add_issue(IntegrityIssueType::UndecryptableRecord, id,
+                              format!("record {} cannot be decrypted", id));
+        }
+        Ok(checker)
+    }
+
+    fn add_issue(&mut self, issue_type: IntegrityIssueType, record_id: u32, description: String) {
+        self.issues.push(IntegrityIssue{issue_type, record_id, description});
+    }
+
+    fn is_valid_name_id(&self, id: u32) -> bool {
+        self.names_ids.contains(&id) && !self.undecryptable_names_ids.contains(&id)
+    }
+
+    fn is_valid_password_id(&self, id: u32) -> bool {
+        self.passwords_ids.contains(&id) && !self.undecryptable_passwords_ids.contains(&id)
+    }
+
+    // returns valid list items, items that cannot be decoded as T are dropped
+    fn check_list<T: ByteValue>(&mut self, file: &MutexGuard<PmanDatabaseFile>, list_id: u32,
+                                list_name: &str) -> Result<HashMap<u32, T>, Error> {
+        let mut rebuild = self.undecryptable_names_ids.contains(&list_id);
+        let items: Vec<u32> = if self.is_valid_name_id(list_id) {
+            match file.get_from_names(list_id) {
+                Ok(v) => v,
+                Err(_) => {
+                    self.add_issue(IntegrityIssueType::UndecryptableRecord, list_id,
+                                   format!("{} list cannot be decoded", list_name));
+                    rebuild = true;
+                    Vec::new()
+                }
+            }
+        } else { Vec::new() };
+        let mut result = HashMap::new();
+        let mut valid = Vec::new();
+        for id in &items {
+            if result.contains_key(id) {
+                self.add_issue(IntegrityIssueType::DanglingReference, *id,
+                               format!("duplicate id {} in {} list", id, list_name));
+                continue;
+            }
+            if !self.is_valid_name_id(*id) {
+                if !self.undecryptable_names_ids.contains(id) {
+                    self.add_issue(IntegrityIssueType::DanglingReference, *id,
+                                   format!("{} list references missing record {}", list_name, id));
+                }
+                continue;
+            }
+            match file.get_from_names::<T>(*id) {
+                Ok(v) => {
+                    result.insert(*id, v);
+                    valid.push(*id);
+                    self.referenced_names_ids.insert(*id);
+                }
+                Err(_) => self.add_issue(IntegrityIssueType::UndecryptableRecord, *id,
+                                         format!("{} list item {} cannot be decoded", list_name, id))
+            }
+        }
+        rebuild |= items != valid;
+        self.lists.insert(list_id, (rebuild, valid));
+        Ok(result)
+    }
+
+    // drops links of missing groups, links to missing parents and links that create cycles
+    fn check_group_parents(&mut self, file: &MutexGuard<PmanDatabaseFile>, group_ids: &HashSet<u32>) {
+        let parents = match get_id_map(file, GROUP_PARENTS_ID) {
+            Ok(p) => p,
+            Err(_) => {
+                self.add_issue(IntegrityIssueType::UndecryptableRecord, GROUP_PARENTS_ID,
+                               "group parents list cannot be decoded".to_string());
+                self.id_maps.insert(GROUP_PARENTS_ID, HashMap::new());
+                return;
+            }
+        };
+        let mut ids: Vec<u32> = parents.keys().map(|id|*id).collect();
+        ids.sort();
+        let mut valid = HashMap::new();
+        for id in &ids {
+            let parent_id = *parents.get(id).unwrap();
+            if group_ids.contains(id) && group_ids.contains(&parent_id) {
+                valid.insert(*id, parent_id);
+            } else {
+                self.add_issue(IntegrityIssueType::DanglingReference, *id,
+                               format!("group parents list references missing group {} or {}", id, parent_id));
+            }
+        }
+        for id in &ids {
+            if valid.contains_key(id) && get_group_ancestors(&valid, *id).is_err() {
+                valid.remove(id);
+                self.add_issue(IntegrityIssueType::DanglingReference, *id,
+                               format!("group {} is its own ancestor", id));
+            }
+        }
+        if valid != parents {
+            self.id_maps.insert(GROUP_PARENTS_ID, valid);
+        }
+    }
+
+    // drops policies of missing groups and zero max age values
+    fn check_group_policies(&mut self, file: &MutexGuard<PmanDatabaseFile>, group_ids: &HashSet<u32>) {
+        let policies = match get_id_map(file, GROUP_POLICIES_ID) {
+            Ok(p) => p,
+            Err(_) => {
+                self.add_issue(IntegrityIssueType::UndecryptableRecord, GROUP_POLICIES_ID,
+                               "group policies list cannot be decoded".to_string());
+                self.id_maps.insert(GROUP_POLICIES_ID, HashMap::new());
+                return;
+            }
+        };
+        let mut ids: Vec<u32> = policies.keys().map(|id|*id).collect();
+        ids.sort();
+        let mut valid = HashMap::new();
+        for id in ids {
+            let max_age_days = *policies.get(&id).unwrap();
+            if !group_ids.contains(&id) {
+                self.add_issue(IntegrityIssueType::DanglingReference, id,
+                               format!("group policies list references missing group {}", id));
+            } else if max_age_days == 0 {
+                self.add_issue(IntegrityIssueType::DanglingReference, id,
+                               format!("group {} has zero password max age", id));
+            } else {
+                valid.insert(id, max_age_days);
+            }
+        }
+        if valid != policies {
+            self.id_maps.insert(GROUP_POLICIES_ID, valid);
+        }
+    }
+
+    // drops trash items that are missing, cannot be decoded or are also in entities list
+    fn check_trash(&mut self, file: &MutexGuard<PmanDatabaseFile>,
+                   entities: &HashMap<u32, PmanDatabaseEntity>) -> HashMap<u32, PmanDatabaseEntity> {
+        let trash = match get_id_map(file, TRASH_ID) {
+            Ok(t) => t,
+            Err(_) => {
+                self.add_issue(IntegrityIssueType::UndecryptableRecord, TRASH_ID,
+                               "trash list cannot be decoded".to_string());
+                self.id_maps.insert(TRASH_ID, HashMap::new());
+                return HashMap::new();
+            }
+        };
+        let mut ids: Vec<u32> = trash.keys().map(|id|*id).collect();
+        ids.sort();
+        let mut valid = HashMap::new();
+        let mut result = HashMap::new();
+        for id in ids {
+            if entities.contains_key(&id) {
+                self.add_issue(IntegrityIssueType::DanglingReference, id,
+                               format!("entity {} is both in entities list and trash", id));
+                continue;
+            }
+            if !self.is_valid_name_id(id) {
+                self.add_issue(IntegrityIssueType::DanglingReference, id,
+                               format!("trash references missing record {}", id));
+                continue;
+            }
+            match file.get_from_names::<PmanDatabaseEntity>(id) {
+                Ok(entity) => {
+                    self.referenced_names_ids.insert(id);
+                    valid.insert(id, *trash.get(&id).unwrap());
+                    result.insert(id, entity);
+                }
+                Err(_) => self.add_issue(IntegrityIssueType::UndecryptableRecord, id,
+                                         format!("trash item {} cannot be decoded", id))
+            }
+        }
+        if valid != trash {
+            self.id_maps.insert(TRASH_ID, valid);
+        }
+        result
+    }
+
+    fn check_entity(&mut self, entity_id: u32, entity: &PmanDatabaseEntity, group_ids: &HashSet<u32>,
+                    user_ids: &HashSet<u32>, tag_ids: &HashSet<u32>, template_ids: &HashSet<u32>) {
+        let mut names_ids = entity.collect_names_ids();
+        names_ids.sort();
+        for id in names_ids {
+            self.referenced_names_ids.insert(id);
+            if !self.names_ids.contains(&id) {
+                self.add_issue(IntegrityIssueType::DanglingReference, entity_id,
+                               format!("entity {} references missing names record {}", entity_id, id));
+            }
+        }
+        let mut passwords_ids = entity.collect_passwords_ids();
+        passwords_ids.sort();
+        for id in passwords_ids {
+            self.referenced_passwords_ids.insert(id);
+            if !self.passwords_ids.contains(&id) {
+                self.add_issue(IntegrityIssueType::DanglingReference, entity_id,
+                               format!("entity {} references missing passwords record {}", entity_id, id));
+            }
+        }
+        for id in entity.collect_group_ids() {
+            if !group_ids.contains(&id) {
+                self.add_issue(IntegrityIssueType::DanglingReference, entity_id,
+                               format!("entity {} references missing group {}", entity_id, id));
+            }
+        }
+        for id in entity.collect_user_ids() {
+            if !user_ids.contains(&id) {
+                self.add_issue(IntegrityIssueType::DanglingReference, entity_id,
+                               format!("entity {} references missing user {}", entity_id, id));
+            }
+        }
+        let mut entity_tag_ids: Vec<u32> = entity.collect_tag_ids().into_iter().collect();
+        entity_tag_ids.sort();
+        for id in entity_tag_ids {
+            if !tag_ids.contains(&id) {
+                self.add_issue(IntegrityIssueType::DanglingReference, entity_id,
+                               format!("entity {} references missing tag {}", entity_id, id));
+            }
+        }
+        let mut entity_template_ids: Vec<u32> = entity.collect_template_ids().into_iter().collect();
+        entity_template_ids.sort();
+        for id in entity_template_ids {
+            if !template_ids.contains(&id) {
+                self.add_issue(IntegrityIssueType::DanglingReference, entity_id,
+                               format!("entity {} references missing template {}", entity_id, id));
+            }
+        }
+    }
+
+    fn check_orphans(&mut self) {
+        let mut orphans: Vec<(u32, &str)> = self.names_ids.iter()
+            .filter(|id|**id >= FIRST_RECORD_ID && !self.referenced_names_ids.contains(id))
+            .map(|id|(*id, "names"))
+            .chain(self.passwords_ids.iter()
+                .filter(|id|**id >= FIRST_RECORD_ID && !self.referenced_passwords_ids.contains(id))
+                .map(|id|(*id, "passwords")))
+            .collect();
+        orphans.sort();
+        for (id, map_name) in orphans {
+            self.add_issue(IntegrityIssueType::OrphanedRecord, id,
+                           format!("{} record {} is not referenced", map_name, id));
+        }
     }
 
-    fn check_exists(&self, list_id: u32, id: u32, error_message: &str) -> Result<(), Error> {
-        let items: Vec<u32> = self.file.lock().unwrap().get_from_names(list_id)?;
-        if items.into_iter().find(|i| *i == id).is_none() {
-            return Err(Error::new(ErrorKind::NotFound, error_message));
+    // rebuilds index lists and removes orphaned and undecryptable records
+    fn repair(&self, file: &mut MutexGuard<PmanDatabaseFile>) -> Result<(), Error> {
+        for (list_id, (rebuild, valid)) in &self.lists {
+            if !rebuild {
+                continue;
+            }
+            if valid.is_empty() {
+                if self.names_ids.contains(list_id) {
+                    file.remove_from_names(list_id)?;
+                }
+            } else {
+                file.set_in_names(*list_id, valid.clone())?;
+            }
+        }
+        for (map_id, map) in &self.id_maps {
+            set_id_map(file, *map_id, map.clone())?;
+        }
+        for id in &self.names_ids {
+            if *id >= FIRST_RECORD_ID &&
+                (!self.referenced_names_ids.contains(id) || self.undecryptable_names_ids.contains(id)) {
+                file.remove_from_names(id)?;
+            }
+        }
+        for id in &self.passwords_ids {
+            if *id >= FIRST_RECORD_ID &&
+                (!self.referenced_passwords_ids.contains(id) || self.undecryptable_passwords_ids.contains(id)) {
+                file.remove_from_passwords(id)?;
+            }
         }
         Ok(())
     }
+}
 
-    fn check_group_exists(&self, group_id: u32) -> Result<(), Error> {
-        self.check_exists(GROUPS_ID, group_id, "group not found")
+// ids - map name id -> value id
+fn check_name_is_unique(file: &mut MutexGuard<PmanDatabaseFile>, ids: &HashMap<u32, u32>,
+                        name: String, error_message: &str) -> Result<(), Error> {
+    for (k, _v) in ids {
+        let n: String = file.get_from_names(*k)?;
+        if n == name {
+            return Err(Error::new(ErrorKind::AlreadyExists, error_message));
+        }
     }
+    Ok(())
+}
 
-    fn check_user_exists(&self, user_id: u32) -> Result<(), Error> {
-        self.check_exists(USERS_ID, user_id, "user not found")
+// removes entity record with all its history records
+fn destroy_entity(file: &mut MutexGuard<PmanDatabaseFile>, entity_id: u32) -> Result<(), Error> {
+    let entity: PmanDatabaseEntity = file.get_from_names(entity_id)?;
+    for id in entity.collect_names_ids() {
+        file.remove_from_names(&id)?;
+    }
+    for id in entity.collect_passwords_ids() {
+        file.remove_from_passwords(&id)?;
     }
+    file.remove_from_names(&entity_id)
+}
 
-    fn check_entity_exists(&self, entity_id: u32) -> Result<(), Error> {
-        self.check_exists(ENTITIES_ID, entity_id, "entity not found")
+fn destroy_trash_items(file: &mut MutexGuard<PmanDatabaseFile>, entity_ids: Vec<u32>) -> Result<(), Error> {
+    if entity_ids.is_empty() {
+        return Ok(());
+    }
+    let mut trash = get_id_map(file, TRASH_ID)?;
+    for entity_id in entity_ids {
+        let name = get_audit_object_name(file, AuditObjectType::Entity, entity_id)?;
+        destroy_entity(file, entity_id)?;
+        trash.remove(&entity_id);
+        add_audit_record(file, AuditEventType::Remove, AuditObjectType::Entity, entity_id, name,
+                         "destroyed".to_string())?;
     }
+    set_id_map(file, TRASH_ID, trash)
+}
 
-    fn remove_from_list(&self, list_id: u32, id: u32) -> Result<(), Error> {
-        let mut file = self.file.lock().unwrap();
-        let mut indexes: Vec<u32> = file.get_from_names(list_id)?;
-        for i in 0..indexes.len() {
-            if indexes[i] == id {
-                file.remove_from_names(&id)?;
-                indexes.remove(i);
-                return if indexes.is_empty() {
-                    file.remove_from_names(&list_id)
-                } else {
-                    file.set_in_names(list_id, indexes)
-                }
-            }
+fn get_audit_object_name(file: &MutexGuard<PmanDatabaseFile>, object_type: AuditObjectType,
+                         object_id: u32) -> Result<String, Error> {
+    match object_type {
+        AuditObjectType::Entity => {
+            let entity: PmanDatabaseEntity = file.get_from_names(object_id)?;
+            file.get_from_names(entity.get_name_id())
         }
-        Err(build_not_found_error())
+        AuditObjectType::User => Ok(file.get_from_names::<DatabaseUser>(object_id)?.name),
+        AuditObjectType::Group => file.get_from_names(object_id),
+        AuditObjectType::Database => Ok(String::new())
     }
+}
 
-    fn get_names_records_count(&self) -> Result<usize, Error> {
-        self.file.lock().unwrap().get_names_records_count()
+fn get_trash_retention(file: &MutexGuard<PmanDatabaseFile>) -> Result<u32, Error> {
+    match file.get_from_names::<Vec<u32>>(TRASH_RETENTION_ID) {
+        Ok(v) if v.len() == 1 => Ok(v[0]),
+        Ok(_) => Err(build_corrupted_data_error("get_trash_retention")),
+        Err(e) if e.kind() == ErrorKind::NotFound => Ok(DEFAULT_TRASH_RETENTION_DAYS),
+        Err(e) => Err(e)
     }
+}
 
-    fn get_passwords_records_count(&self) -> Result<usize, Error> {
-        self.file.lock().unwrap().get_passwords_records_count()
+// new properties are ordered by template fields and get field type and secret flag from the template,
+// properties of entities without template are secret text properties ordered by name
+fn add_new_properties(file: &mut MutexGuard<PmanDatabaseFile>, template: Option<&EntityTemplate>,
+                      properties: HashMap<String, String>, result: &mut Vec<EntityProperty>) -> Result<(), Error> {
+    let fields = template.map(|t|t.fields.as_slice()).unwrap_or(&[]);
+    let mut properties: Vec<(usize, String, String)> = properties.into_iter()
+        .map(|(name, value)|(fields.iter().position(|f|f.name == name).unwrap_or(fields.len()), name, value))
+        .collect();
+    properties.sort();
+    for (index, name, value) in properties {
+        let field = fields.get(index);
+        let ids = result.iter().map(|p|(p.name_id, p.value_id)).collect();
+        check_name_is_unique(file, &ids, name.clone(), "duplicate property name")?;
+        let (field_type, secret) = field.map(|f|(f.field_type, f.secret)).unwrap_or((FieldType::Text, true));
+        let name_id = file.add_to_names(name)?;
+        let value_id = add_property_value(file, secret, value)?;
+        result.push(EntityProperty{name_id, value_id, field_type, secret});
     }
+    Ok(())
+}
 
-    fn check_entity_name(&self, group_id: u32, name: String) -> Result<(), Error> {
-        for (_key, value) in self.get_entities(group_id)? {
-            if value.get_name()? == name {
-                return Err(Error::new(ErrorKind::AlreadyExists, "entity with given name already exists"));
+fn add_property_value(file: &mut MutexGuard<PmanDatabaseFile>, secret: bool, value: String) -> Result<u32, Error> {
+    if secret {
+        file.add_to_passwords(value)
+    } else {
+        file.add_to_names(value)
+    }
+}
+
+fn get_template(file: &MutexGuard<PmanDatabaseFile>, template_id: u32) -> Result<EntityTemplate, Error> {
+    if let Some(template) = get_built_in_templates().into_iter().find(|t|t.id == template_id) {
+        return Ok(template);
+    }
+    let ids: Vec<u32> = match file.get_from_names(TEMPLATES_ID) {
+        Ok(ids) => ids,
+        Err(e) if e.kind() == ErrorKind::NotFound => Vec::new(),
+        Err(e) => return Err(e)
+    };
+    if !ids.contains(&template_id) {
+        return Err(Error::new(ErrorKind::NotFound, "template not found"));
+    }
+    let template: Template = file.get_from_names(template_id)?;
+    Ok(template.to_entity_template(template_id))
+}
+
+// copies list items with given ids from source lists to the target list, record ids are kept
+fn copy_list_items(source: &MutexGuard<PmanDatabaseFile>, target: &mut MutexGuard<PmanDatabaseFile>,
+                   source_list_ids: &[u32], target_list_id: u32, ids: &HashSet<u32>) -> Result<(), Error> {
+    let mut items = Vec::new();
+    for list_id in source_list_ids {
+        let indexes: Vec<u32> = match source.get_from_names(*list_id) {
+            Ok(v) => v,
+            Err(e) if e.kind() == ErrorKind::NotFound => continue,
+            Err(e) => return Err(e)
+        };
+        for id in indexes.into_iter().filter(|id|ids.contains(id)) {
+            let value: Vec<u8> = source.get_from_names(id)?;
+            target.set_in_names(id, value)?;
+            items.push(id);
+        }
+    }
+    if items.is_empty() {
+        return Ok(());
+    }
+    target.set_in_names(target_list_id, items)
+}
+
+// id maps are stored as lists of (key, value) pairs
+fn get_id_map(file: &MutexGuard<PmanDatabaseFile>, record_id: u32) -> Result<HashMap<u32, u32>, Error> {
+    let pairs: Vec<u32> = match file.get_from_names(record_id) {
+        Ok(v) => v,
+        Err(e) => {
+            if e.kind() == ErrorKind::NotFound {
+                return Ok(HashMap::new());
+            } else {
+                return Err(e);
             }
         }
-        Ok(())
+    };
+    if pairs.len() % 2 != 0 {
+        return Err(build_corrupted_data_error("get_id_map"));
     }
+    Ok(pairs.chunks(2).map(|p|(p[0], p[1])).collect())
+}
 
-    fn get_entity(&self, entity_id: u32) -> Result<PmanDatabaseEntity, Error> {
-        self.check_entity_exists(entity_id)?;
-        let mut entity: PmanDatabaseEntity = self.file.lock().unwrap().get_from_names(entity_id)?;
-        entity.set_database_file(self.file.clone());
-        Ok(entity)
+fn set_id_map(file: &mut MutexGuard<PmanDatabaseFile>, record_id: u32, map: HashMap<u32, u32>) -> Result<(), Error> {
+    if map.is_empty() {
+        return match file.remove_from_names(&record_id) {
+            Err(e) if e.kind() != ErrorKind::NotFound => Err(e),
+            _ => Ok(())
+        };
     }
+    let mut ids: Vec<u32> = map.keys().map(|id|*id).collect();
+    ids.sort();
+    let pairs: Vec<u32> = ids.into_iter().flat_map(|id|[id, *map.get(&id).unwrap()]).collect();
+    file.set_in_names(record_id, pairs)
+}
 
-    pub fn set_file1_location_qs3(&self, file_name: String, s3_key: Vec<u8>) -> Result<(), Error> {
-        self.file.lock().unwrap().set_file1_location_qs3(file_name, s3_key)
+// returns group id followed by all its ancestors
+fn get_group_ancestors(parents: &HashMap<u32, u32>, group_id: u32) -> Result<Vec<u32>, Error> {
+    let mut result = vec![group_id];
+    let mut id = group_id;
+    while let Some(parent_id) = parents.get(&id) {
+        if result.contains(parent_id) {
+            return Err(build_corrupted_data_error("get_group_ancestors"));
+        }
+        result.push(*parent_id);
+        id = *parent_id;
     }
+    Ok(result)
+}
 
-    pub fn set_file2_location_qs3(&self, file_name: String, s3_key: Vec<u8>) -> Result<(), Error> {
-        self.file.lock().unwrap().set_file2_location_qs3(file_name, s3_key)
+// returns group id and all its descendants
+fn get_group_subtree(parents: &HashMap<u32, u32>, group_id: u32) -> Vec<u32> {
+    let mut result = vec![group_id];
+    let mut i = 0;
+    while i < result.len() {
+        let id = result[i];
+        for (child_id, parent_id) in parents {
+            if *parent_id == id && !result.contains(child_id) {
+                result.push(*child_id);
+            }
+        }
+        i += 1;
     }
+    result
 }
 
-fn check_property_name(file: &mut MutexGuard<PmanDatabaseFile>, properties: &HashMap<u32, u32>,
-                       name: String) -> Result<(), Error> {
-    for (k, _v) in properties {
-        let n: String = file.get_from_names(*k)?;
-        if n == name {
-            return Err(Error::new(ErrorKind::AlreadyExists, "duplicate property name"));
+// group names are unique among the group siblings
+fn check_group_name(file: &mut MutexGuard<PmanDatabaseFile>, parents: &HashMap<u32, u32>,
+                    parent_id: Option<u32>, name: &String) -> Result<(), Error> {
+    if name.is_empty() || name.contains('/') {
+        return Err(Error::new(ErrorKind::InvalidInput, "invalid group name"));
+    }
+    let indexes: Vec<u32> = match file.get_from_names(GROUPS_ID) {
+        Ok(v) => v,
+        Err(e) => {
+            if e.kind() == ErrorKind::NotFound {
+                return Ok(());
+            } else {
+                return Err(e);
+            }
         }
+    };
+    let siblings: HashSet<u32> = indexes.into_iter()
+        .filter(|id|parents.get(id).map(|p|*p) == parent_id)
+        .collect();
+    let names: HashMap<u32, String> = file.mget_from_names(siblings)?;
+    if names.into_iter().find(|(_id, n)|*n == *name).is_some() {
+        return Err(Error::new(ErrorKind::AlreadyExists, "item with the same name already exists"));
     }
     Ok(())
 }
@@ -408,6 +2154,14 @@ fn string_validator(indexes: &Vec<u32>, file: &mut MutexGuard<PmanDatabaseFile>,
     Ok(())
 }
 
+fn user_validator(indexes: &Vec<u32>, file: &mut MutexGuard<PmanDatabaseFile>, value: &DatabaseUser) -> Result<(), Error> {
+    let data: HashMap<u32, DatabaseUser> = file.mget_from_names(indexes.clone().into_iter().collect())?;
+    if data.into_values().any(|user|user.name == value.name) {
+        return Err(Error::new(ErrorKind::AlreadyExists, "item with the same name already exists"));
+    }
+    Ok(())
+}
+
 fn no_validator<T: ByteValue>(_indexes: &Vec<u32>, _file: &mut MutexGuard<PmanDatabaseFile>, _value: &T) -> Result<(), Error> {
     Ok(())
 }
@@ -416,13 +2170,26 @@ fn no_validator<T: ByteValue>(_indexes: &Vec<u32>, _file: &mut MutexGuard<PmanDa
 mod tests {
     use std::collections::HashMap;
     use std::io::{Error, ErrorKind};
+    use std::sync::Arc;
     use rand::{Rng, RngCore};
     use rand::distributions::{Alphanumeric, DistString};
     use rand::rngs::{OsRng, ThreadRng};
-    use crate::pman::database_entity::ENTITY_VERSION_LATEST;
-    use crate::pman::pman_database::PmanDatabase;
-    use crate::pman::pman_database_file::DEFAULT_HISTORY_LENGTH;
-    use crate::structs_interfaces::{PasswordDatabase, PasswordDatabaseEntity};
+    use crate::pman::database_entity::{ENTITY_VERSION_LATEST, advance_test_clock, get_current_timestamp, PmanDatabaseEntity};
+    use crate::pman::pman_database::{DEFAULT_TRASH_RETENTION_DAYS, ENTITIES_ID, get_id_map, GROUP_PARENTS_ID, GROUP_POLICIES_ID, GROUPS_ID,
+                                     PmanDatabase, RETIRED_GROUPS_ID, RETIRED_USERS_ID, USERS_ID};
+    use sha2::{Digest, Sha256};
+    use crate::pman::pman_database_file::{DATABASE_VERSION_LATEST, DEFAULT_HISTORY_LENGTH};
+    use crate::pman::templates::{TEMPLATE_BANK_ACCOUNT, TEMPLATE_CREDIT_CARD};
+    use crate::pman::audit_log::MAX_AUDIT_RECORDS;
+    use crate::structs_interfaces::{AuditEventType, AuditObjectType, AuditSettings, CryptoEngine, EntityKind, FieldType, IntegrityIssueType, KeySlotType, PasswordDatabase,
+                                    PasswordDatabaseEntity, PropertyLayout, TemplateField, EntityUrl, UrlMatchMode, DatabaseUser, EntitySortKey};
+
+    const V1_0_MAIN_DATA: &[u8] = include_bytes!("../../test_data/v1_0.pdbf");
+    const V1_0_DATA1: &[u8] = include_bytes!("../../test_data/v1_0.pdbf.1");
+    const V1_0_DATA2: &[u8] = include_bytes!("../../test_data/v1_0.pdbf.2");
+    const V2_0_MAIN_DATA: &[u8] = include_bytes!("../../test_data/v2_0.pdbf");
+    const V2_0_DATA1: &[u8] = include_bytes!("../../test_data/v2_0.pdbf.1");
+    const V2_0_DATA2: &[u8] = include_bytes!("../../test_data/v2_0.pdbf.2");
 
     #[derive(Clone)]
     struct TestEntityHistory {
@@ -607,7 +2374,8 @@ mod tests {
                                                 user_ids[e.get_latest_user_index()],
                                                 e.get_latest_password(),
                                                 e.get_latest_url(),
-                                                e.get_latest_properties())?;
+                                                e.get_latest_properties(),
+                                                None)?;
             entity_ids.push(entity_id);
         }
         Ok(TestDatabase{
@@ -619,6 +2387,21 @@ mod tests {
         })
     }
 
+    // saves the database and opens the saved data
+    fn reopen_database(test_database: &TestDatabase) -> Result<PmanDatabase, Error> {
+        reopen_database_with_hashes(&test_database.database, test_database.test_data.hash1_vec.clone(),
+                                    test_database.test_data.hash2_vec.clone())
+    }
+
+    fn reopen_database_with_hashes(database: &PmanDatabase, hash1: Vec<u8>, hash2: Vec<u8>) -> Result<PmanDatabase, Error> {
+        let (data1, data2) = database.save_to_data()?;
+        let mut result = PmanDatabase::new_from_file2(data1.unwrap())?;
+        result.pre_open(hash1, Some(hash2), Some(Vec::new()))?;
+        let (d2, d3) = data2.unwrap();
+        result.open_from_data(d2, d3)?;
+        Ok(result)
+    }
+
     #[test]
     fn test_database() -> Result<(), Error> {
         let test_data = build_test_data();
@@ -668,6 +2451,988 @@ mod tests {
         cleanup_database(test_database)
     }
 
+    #[test]
+    fn test_entity_otp() -> Result<(), Error> {
+        let test_data = build_test_data();
+        let test_database = build_database(test_data)?;
+        let entity_id = test_database.entity_ids[0];
+        // RFC 6238 test secret
+        test_database.database.set_entity_otp(entity_id,
+            Some("otpauth://totp/Amazon?secret=GEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQ&digits=8".to_string()))?;
+        test_database.database.modify_entity(entity_id, None, None,
+                                             Some("otp password".to_string()), None, false,
+                                             HashMap::new(), HashMap::new())?;
+        let database = reopen_database(&test_database)?;
+        let entity = database.get_entity(entity_id)?;
+        assert_eq!(entity.get_max_version(), 2);
+        let code = entity.get_otp_code(ENTITY_VERSION_LATEST, 59)?.unwrap();
+        assert_eq!(code.code, "94287082".to_string());
+        assert_eq!(code.valid_for, Some(1));
+        assert!(entity.get_otp_code(2, 59)?.is_none());
+        database.set_entity_otp(entity_id, None)?;
+        assert!(database.get_entity(entity_id)?.get_otp_code(ENTITY_VERSION_LATEST, 59)?.is_none());
+        assert!(database.set_entity_otp(entity_id, Some("otpauth://totp/a?digits=8".to_string())).is_err());
+        assert!(database.next_hotp_code(entity_id).is_err());
+        // RFC 4226 test secret
+        database.set_entity_otp(entity_id,
+            Some("otpauth://hotp/Amazon?secret=GEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQ&counter=1".to_string()))?;
+        let entity = database.get_entity(entity_id)?;
+        let max_version = entity.get_max_version();
+        let password = entity.get_password(1)?;
+        assert_eq!(database.next_hotp_code(entity_id)?.code, "287082".to_string());
+        assert_eq!(database.next_hotp_code(entity_id)?.code, "359152".to_string());
+        let entity = database.get_entity(entity_id)?;
+        assert_eq!(entity.get_otp_code(ENTITY_VERSION_LATEST, 0)?.unwrap().code, "969429".to_string());
+        // counter is stored in place, password history is kept
+        for _ in 0..5 {
+            database.next_hotp_code(entity_id)?;
+        }
+        let entity = database.get_entity(entity_id)?;
+        assert_eq!(entity.get_max_version(), max_version);
+        assert_eq!(entity.get_password(1)?, password);
+        assert_eq!(entity.get_otp_code(ENTITY_VERSION_LATEST, 0)?.unwrap().code, "399871".to_string());
+        Ok(())
+    }
+
+    #[test]
+    fn test_encryption_cascade() -> Result<(), Error> {
+        let test_data = build_test_data();
+        let test_database = build_database(test_data)?;
+        assert!(test_database.database.set_encryption(0, vec![CryptoEngine::AES]).is_err());
+        assert!(test_database.database.set_encryption(2, vec![CryptoEngine::Chacha20]).is_err());
+        test_database.database.set_encryption(0, vec![CryptoEngine::AesCtr, CryptoEngine::Chacha20])?;
+        test_database.database.set_encryption(1, vec![CryptoEngine::Chacha20, CryptoEngine::AesCtr])?;
+        let database = reopen_database(&test_database)?;
+        let entity_id = test_database.entity_ids[0];
+        assert_eq!(database.get_entity(entity_id)?.get_password(ENTITY_VERSION_LATEST)?,
+                   test_database.database.get_entity(entity_id)?.get_password(ENTITY_VERSION_LATEST)?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_check_integrity() -> Result<(), Error> {
+        let mut test_data = build_test_data();
+        test_data.entities.push(TestEntity::new("Google".to_string(), "google password".to_string(),
+                                                None, 0, 1, HashMap::new()));
+        let test_database = build_database(test_data)?;
+        let database = &test_database.database;
+        assert!(database.check_integrity(false)?.is_empty());
+
+        let mut file = database.file.lock().unwrap();
+        let orphan_name_id = file.add_to_names("orphan".to_string())?;
+        let orphan_password_id = file.add_to_passwords("orphan".to_string())?;
+        let mut groups: Vec<u32> = file.get_from_names(GROUPS_ID)?;
+        groups.push(12345);
+        file.set_in_names(GROUPS_ID, groups)?;
+        let entity: PmanDatabaseEntity = file.get_from_names(test_database.entity_ids[1])?;
+        file.set_in_names(entity.get_name_id(), "Amazon".to_string())?;
+        drop(file);
+
+        let issues = database.check_integrity(false)?;
+        assert_eq!(issues.len(), 4);
+        let find_issue = |issue_type: IntegrityIssueType, record_id: u32|
+            issues.iter().find(|i|i.issue_type == issue_type && i.record_id == record_id).is_some();
+        assert!(find_issue(IntegrityIssueType::OrphanedRecord, orphan_name_id));
+        assert!(find_issue(IntegrityIssueType::OrphanedRecord, orphan_password_id));
+        assert!(find_issue(IntegrityIssueType::DanglingReference, 12345));
+        assert!(find_issue(IntegrityIssueType::DuplicateName, test_database.entity_ids[1]));
+
+        // removing entity from index list makes all its records orphaned
+        let mut file = database.file.lock().unwrap();
+        file.set_in_names(ENTITIES_ID, vec![test_database.entity_ids[0]])?;
+        drop(file);
+        assert_eq!(database.check_integrity(true)?.len(), 6);
+        assert!(database.check_integrity(false)?.is_empty());
+        assert_eq!(database.get_groups()?.len(), 3);
+        let entity = database.get_entity(test_database.entity_ids[0])?;
+        assert_eq!(entity.get_password(ENTITY_VERSION_LATEST)?, "some password".to_string());
+        assert!(database.get_entity(test_database.entity_ids[1]).is_err());
+        let (data1, data2) = database.save_to_data()?;
+        assert!(data1.is_some() && data2.is_some());
+        Ok(())
+    }
+
+    #[test]
+    fn test_check_integrity_truncated_record() -> Result<(), Error> {
+        let test_data = build_test_data();
+        let test_database = build_database(test_data)?;
+        let database = &test_database.database;
+        let entity_id = test_database.entity_ids[0];
+        let password_id = database.get_entity(entity_id)?.get_password_id();
+        // aes record length must be a multiple of the block size
+        database.file.lock().unwrap().set_raw_in_passwords(password_id, vec![1u8; 20])?;
+        assert!(database.get_entity(entity_id)?.get_password(ENTITY_VERSION_LATEST).is_err());
+        let issues = database.check_integrity(false)?;
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].issue_type, IntegrityIssueType::UndecryptableRecord);
+        assert_eq!(issues[0].record_id, password_id);
+        Ok(())
+    }
+
+    #[test]
+    fn test_attachments() -> Result<(), Error> {
+        let test_data = build_test_data();
+        let test_database = build_database(test_data)?;
+        let entity_id = test_database.entity_ids[0];
+        let data = vec![0u8, 1, 2, 255];
+        let attachment_id = test_database.database.add_attachment(entity_id, "cert.pem".to_string(), data.clone())?;
+        assert!(test_database.database.add_attachment(entity_id, "cert.pem".to_string(), Vec::new()).is_err());
+        test_database.database.add_attachment(entity_id, "license.txt".to_string(), Vec::new())?;
+        test_database.database.remove_attachment(entity_id, attachment_id)?;
+        assert!(test_database.database.remove_attachment(entity_id, attachment_id).is_err());
+        let database = reopen_database(&test_database)?;
+        let entity = database.get_entity(entity_id)?;
+        assert_eq!(entity.get_max_version(), 3);
+        let names = entity.get_attachment_names(ENTITY_VERSION_LATEST)?;
+        assert_eq!(names.len(), 1);
+        assert!(names.contains_key("license.txt"));
+        assert!(database.get_attachment(entity_id, ENTITY_VERSION_LATEST, attachment_id).is_err());
+        // removed attachment is still available in entity history
+        assert_eq!(entity.get_attachment_names(1)?.get("cert.pem"), Some(&attachment_id));
+        assert_eq!(database.get_attachment(entity_id, 1, attachment_id)?, data);
+        // attachment versions share the history length with password versions
+        let original_password = entity.get_password(entity.get_max_version())?;
+        database.set_entity_history_length(entity_id, Some(3))?;
+        database.modify_entity(entity_id, None, None, Some("new password".to_string()), None, false,
+                               HashMap::new(), HashMap::new())?;
+        database.add_attachment(entity_id, "a.txt".to_string(), Vec::new())?;
+        database.add_attachment(entity_id, "b.txt".to_string(), Vec::new())?;
+        let entity = database.get_entity(entity_id)?;
+        assert_eq!(entity.get_max_version(), 2);
+        assert_eq!(entity.get_attachment_names(1)?.len(), 2);
+        assert_eq!(entity.get_password(1)?, "new password".to_string());
+        assert_eq!(entity.get_password(2)?, original_password);
+        assert!(database.check_integrity(false)?.is_empty());
+        Ok(())
+    }
+
+    fn build_fixture_password_hash(password: &str) -> Vec<u8> {
+        let mut hasher = Sha256::new();
+        hasher.update(password);
+        Vec::from(hasher.finalize().as_slice())
+    }
+
+    fn check_fixture_database(database: &PmanDatabase) -> Result<(), Error> {
+        let groups = database.get_groups()?;
+        assert_eq!(groups.len(), 1);
+        assert_eq!(groups[0].name, "Internet".to_string());
+        assert_eq!(groups[0].entities_count, 1);
+        let users = database.get_users()?;
+        assert_eq!(users.len(), 1);
+        let entities = database.get_entities(groups[0].id)?;
+        assert_eq!(entities.len(), 1);
+        let (_id, entity) = entities.into_iter().next().unwrap();
+        assert_eq!(entity.get_name()?, "Amazon".to_string());
+        assert_eq!(entity.get_max_version(), 1);
+        assert_eq!(entity.get_password(0)?, "new password".to_string());
+        assert_eq!(entity.get_password(1)?, "some password".to_string());
+        assert_eq!(entity.get_url(0)?, Some("amazon.com".to_string()));
+        let names = entity.get_property_names(0)?;
+        let pin = names.iter().find(|p|p.name == "PIN").unwrap();
+        assert_eq!(entity.get_property_value(0, pin.id)?, "12345".to_string());
+        Ok(())
+    }
+
+    #[test]
+    fn test_tags() -> Result<(), Error> {
+        let mut test_data = build_test_data();
+        test_data.entities.push(TestEntity::new("Google".to_string(), "google password".to_string(),
+                                                None, 0, 1, HashMap::new()));
+        let test_database = build_database(test_data)?;
+        let database = &test_database.database;
+        let entity_id1 = test_database.entity_ids[0];
+        let entity_id2 = test_database.entity_ids[1];
+        let max_version = database.get_entity(entity_id1)?.get_max_version();
+        let work_id = database.add_tag(entity_id1, "work".to_string())?;
+        let tfa_id = database.add_tag(entity_id1, "2fa".to_string())?;
+        assert_eq!(database.add_tag(entity_id1, "work".to_string())?, work_id);
+        assert_eq!(database.add_tag(entity_id2, "work".to_string())?, work_id);
+        assert!(database.add_tag(entity_id2, "".to_string()).is_err());
+        assert_eq!(database.get_tags()?.len(), 2);
+        assert_eq!(database.get_entities_by_tags(vec![work_id])?.len(), 2);
+        let entities = database.get_entities_by_tags(vec![work_id, tfa_id])?;
+        assert_eq!(entities.len(), 1);
+        assert!(entities.contains_key(&entity_id1));
+        assert_eq!(database.get_entity(entity_id1)?.get_tag_ids(ENTITY_VERSION_LATEST)?.len(), 2);
+        // tags are changed in place, no version is added
+        assert_eq!(database.get_entity(entity_id1)?.get_max_version(), max_version);
+
+        database.remove_tag(entity_id2, work_id)?;
+        assert!(database.remove_tag(entity_id2, work_id).is_err());
+        assert_eq!(database.get_entities_by_tags(vec![work_id])?.len(), 1);
+        // tag is still used by entity history
+        database.modify_entity(entity_id1, None, None, Some("new password".to_string()), None, false,
+                               HashMap::new(), HashMap::new())?;
+        database.remove_tag(entity_id1, tfa_id)?;
+        assert_eq!(database.get_tags()?.len(), 2);
+        assert!(database.get_entity(entity_id1)?.get_tag_ids(1)?.contains(&tfa_id));
+        // unused tag is removed from tags list
+        let temp_id = database.add_tag(entity_id2, "temp".to_string())?;
+        database.remove_tag(entity_id2, temp_id)?;
+        assert_eq!(database.get_tags()?.len(), 2);
+        assert!(database.check_integrity(false)?.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_group_tree() -> Result<(), Error> {
+        let test_data = build_test_data();
+        let test_database = build_database(test_data)?;
+        let database = &test_database.database;
+        let root_id = test_database.group_ids[0];
+        let cloud_id = database.add_child_group(root_id, "Cloud".to_string())?;
+        let aws_id = database.add_child_group(cloud_id, "AWS".to_string())?;
+        assert!(database.add_child_group(root_id, "Cloud".to_string()).is_err());
+        assert!(database.add_child_group(root_id, "A/B".to_string()).is_err());
+        // names are unique among siblings only
+        let other_cloud_id = database.add_child_group(test_database.group_ids[1], "Cloud".to_string())?;
+        let entity_id = database.add_entity(aws_id, "aws".to_string(), test_database.user_ids[0],
+                                            "password".to_string(), None, HashMap::new(), None)?;
+        let groups = database.get_groups()?;
+        let find_group = |id: u32|groups.iter().find(|g|g.id == id).unwrap();
+        assert_eq!(find_group(aws_id).parent_id, Some(cloud_id));
+        assert_eq!(find_group(cloud_id).parent_id, Some(root_id));
+        assert_eq!(find_group(root_id).parent_id, None);
+        assert_eq!(find_group(cloud_id).entities_count, 0);
+        assert_eq!(find_group(cloud_id).total_entities_count, 1);
+        assert_eq!(find_group(root_id).total_entities_count, find_group(root_id).entities_count + 1);
+
+        assert!(database.move_group(root_id, Some(aws_id)).is_err());
+        assert!(database.move_group(cloud_id, Some(test_database.group_ids[1])).is_err());
+        database.move_group(cloud_id, None)?;
+        database.move_group(other_cloud_id, Some(aws_id))?;
+        let groups = database.get_groups()?;
+        assert_eq!(groups.iter().find(|g|g.id == cloud_id).unwrap().parent_id, None);
+        assert!(database.check_integrity(false)?.is_empty());
+
+        assert!(database.remove_group(cloud_id).is_err());
+        database.remove_entity(entity_id)?;
+        database.remove_group(cloud_id)?;
+        assert!(database.check_exists(GROUPS_ID, aws_id, "").is_err());
+        assert!(database.check_exists(GROUPS_ID, other_cloud_id, "").is_err());
+        assert!(get_id_map(&database.file.lock().unwrap(), GROUP_PARENTS_ID)?.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_replace_user_and_merge_groups() -> Result<(), Error> {
+        let test_data = build_test_data();
+        let test_database = build_database(test_data)?;
+        let database = &test_database.database;
+        let user_id1 = database.add_user("old@a.com".to_string())?;
+        let user_id2 = database.add_user("new@a.com".to_string())?;
+        assert!(database.rename_user(user_id1, "new@a.com".to_string()).is_err());
+        database.rename_user(user_id1, "old@b.com".to_string())?;
+        assert_eq!(database.get_users()?.get(&user_id1).map(|u|u.name.clone()), Some("old@b.com".to_string()));
+        let group_id = database.add_group("Old".to_string())?;
+        let child_id = database.add_child_group(group_id, "Child".to_string())?;
+        let target_id = database.add_group("Target".to_string())?;
+        let entity_id1 = database.add_entity(group_id, "e1".to_string(), user_id1, "password".to_string(),
+                                             None, HashMap::new(), None)?;
+        let entity_id2 = database.add_entity(target_id, "e2".to_string(), user_id1, "password".to_string(),
+                                             None, HashMap::new(), None)?;
+
+        // history versions keep the retired user
+        assert!(database.replace_user(user_id1, user_id1, false).is_err());
+        database.replace_user(user_id1, user_id2, false)?;
+        assert!(!database.get_users()?.contains_key(&user_id1));
+        assert!(database.check_exists(RETIRED_USERS_ID, user_id1, "").is_ok());
+        let entity = database.get_entity(entity_id1)?;
+        assert_eq!(entity.get_user_id(ENTITY_VERSION_LATEST)?, user_id2);
+        assert_eq!(entity.get_user_id(1)?, user_id1);
+        assert!(database.check_integrity(false)?.is_empty());
+
+        // rewritten history does not reference the removed user
+        let user_id3 = database.add_user("user3@a.com".to_string())?;
+        database.replace_user(user_id2, user_id3, true)?;
+        assert!(database.check_exists(USERS_ID, user_id2, "").is_err());
+        assert!(database.check_exists(RETIRED_USERS_ID, user_id2, "").is_err());
+        let entity = database.get_entity(entity_id2)?;
+        assert_eq!(entity.get_max_version(), 1);
+        assert_eq!(entity.get_user_id(ENTITY_VERSION_LATEST)?, user_id3);
+
+        let conflict_id = database.add_entity(target_id, "e1".to_string(), user_id3, "password".to_string(),
+                                              None, HashMap::new(), None)?;
+        assert!(database.merge_groups(group_id, target_id, true).is_err());
+        database.remove_entity(conflict_id)?;
+        assert!(database.merge_groups(group_id, child_id, true).is_err());
+        assert!(database.merge_groups(group_id, group_id, true).is_err());
+        database.merge_groups(group_id, target_id, true)?;
+        assert!(database.check_exists(GROUPS_ID, group_id, "").is_err());
+        assert!(database.check_exists(RETIRED_GROUPS_ID, group_id, "").is_err());
+        let groups = database.get_groups()?;
+        assert_eq!(groups.iter().find(|g|g.id == child_id).unwrap().parent_id, Some(target_id));
+        let entity = database.get_entity(entity_id1)?;
+        assert_eq!(entity.get_group_id(ENTITY_VERSION_LATEST)?, target_id);
+        assert_eq!(entity.get_group_id(1)?, target_id);
+        assert!(database.check_integrity(false)?.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_list_entities() -> Result<(), Error> {
+        let test_data = build_test_data();
+        let test_database = build_database(test_data)?;
+        let database = &test_database.database;
+        let user_id = test_database.user_ids[0];
+        let group_id = database.add_group("A group".to_string())?;
+        let group_id2 = database.add_group("B group".to_string())?;
+        let mut ids = Vec::new();
+        for name in ["delta", "Alpha", "charlie", "bravo"] {
+            ids.push(database.add_entity(group_id, name.to_string(), user_id, "password".to_string(),
+                                         None, HashMap::new(), None)?);
+        }
+        let other_id = database.add_entity(group_id2, "alpha2".to_string(), user_id, "password".to_string(),
+                                           None, HashMap::new(), None)?;
+        // "B group/A child" sorts after "B group" by path but before "A group" by name
+        let child_id = database.add_child_group(group_id2, "A child".to_string())?;
+        let child_entity_id = database.add_entity(child_id, "alpha3".to_string(), user_id, "password".to_string(),
+                                                  None, HashMap::new(), None)?;
+        let page_ids = |page: (u32, Vec<(u32, Box<dyn PasswordDatabaseEntity + Send>)>)|
+            (page.0, page.1.into_iter().map(|(id, _e)|id).collect::<Vec<u32>>());
+        assert_eq!(page_ids(database.list_entities(Some(group_id), EntitySortKey::Name, false, 0, 2)?),
+                   (4, vec![ids[1], ids[3]]));
+        assert_eq!(page_ids(database.list_entities(Some(group_id), EntitySortKey::Name, false, 2, 10)?),
+                   (4, vec![ids[2], ids[0]]));
+        assert_eq!(page_ids(database.list_entities(Some(group_id), EntitySortKey::Name, true, 0, 1)?),
+                   (4, vec![ids[0]]));
+        // audit log writes keep the index
+        let index = database.get_entity_index()?;
+        database.set_audit_settings(AuditSettings{max_records: 5, log_reveals: true})?;
+        database.get_entity(ids[0])?.get_password(ENTITY_VERSION_LATEST)?;
+        assert!(Arc::ptr_eq(&index, &database.get_entity_index()?));
+        // index is refreshed after changes
+        advance_test_clock(1);
+        database.modify_entity(ids[2], None, None, Some("password2".to_string()), None, false,
+                               HashMap::new(), HashMap::new())?;
+        advance_test_clock(1);
+        database.modify_entity(ids[1], None, None, Some("password2".to_string()), None, false,
+                               HashMap::new(), HashMap::new())?;
+        assert_eq!(page_ids(database.list_entities(Some(group_id), EntitySortKey::ModifiedAt, true, 0, 2)?),
+                   (4, vec![ids[1], ids[2]]));
+        assert_eq!(page_ids(database.search_entities("ALPHA".to_string(), EntitySortKey::Group, true, 0, 10)?),
+                   (3, vec![child_entity_id, other_id, ids[1]]));
+        // search query syntax is the same as in search
+        assert_eq!(page_ids(database.search_entities("group:\"b group\" NOT alpha3".to_string(), EntitySortKey::Name, false, 0, 10)?),
+                   (1, vec![other_id]));
+        assert!(database.search_entities("(alpha".to_string(), EntitySortKey::Name, false, 0, 10).is_err());
+        database.remove_entity(ids[0])?;
+        assert_eq!(database.list_entities(Some(group_id), EntitySortKey::CreatedAt, false, 0, 10)?.0, 3);
+        assert_eq!(database.list_entities(None, EntitySortKey::Name, false, 0, 100)?.0 as usize,
+                   test_database.entity_ids.len() + 5);
+        assert!(database.list_entities(Some(12345), EntitySortKey::Name, false, 0, 10).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_search_query() -> Result<(), Error> {
+        let test_data = build_test_data();
+        let test_database = build_database(test_data)?;
+        let database = &test_database.database;
+        let user_id = test_database.user_ids[0];
+        database.update_user(user_id, DatabaseUser{
+            name: "jdoe".to_string(),
+            email: "john@a.com".to_string(),
+            display_name: "John Doe".to_string(),
+            fields: HashMap::new()
+        })?;
+        let group_id = database.add_group("Web".to_string())?;
+        let child_id = database.add_child_group(group_id, "Shops".to_string())?;
+        let shop_id = database.add_entity(child_id, "Shop".to_string(), user_id, "password".to_string(),
+                                          Some("https://shop.example.com".to_string()), HashMap::new(), None)?;
+        database.add_tag(shop_id, "work".to_string())?;
+        let bank_id = database.add_entity(group_id, "Bank".to_string(), test_database.user_ids[1], "password".to_string(),
+                                          None, HashMap::from([("Bank".to_string(), "Big bank".to_string()),
+                                                               ("Account number".to_string(), "123".to_string()),
+                                                               ("PIN".to_string(), "1234".to_string())]),
+                                          Some(TEMPLATE_BANK_ACCOUNT))?;
+        let search_ids = |query: &str| -> Result<Vec<u32>, Error> {
+            let mut ids: Vec<u32> = database.search(query.to_string())?.into_values()
+                .flat_map(|entities|entities.into_keys())
+                .collect();
+            ids.sort();
+            Ok(ids)
+        };
+        assert_eq!(search_ids("url:example")?, vec![shop_id]);
+        assert_eq!(search_ids("user:\"john doe\" group:web/shops tag:WORK")?, vec![shop_id]);
+        assert_eq!(search_ids("group:web NOT tag:work")?, vec![bank_id]);
+        assert_eq!(search_ids("prop:bank=big OR url:shop.example")?, vec![shop_id, bank_id]);
+        assert_eq!(search_ids("prop:pin group:web")?, vec![bank_id]);
+        // secret property values are not searched
+        assert!(search_ids("prop:pin=1234")?.is_empty());
+        assert_eq!(search_ids("group:web created>2000-01-01 modified<2100-01-01")?, vec![shop_id, bank_id]);
+        assert!(search_ids("shop created<2000-01-01")?.is_empty());
+        assert!(database.search("url:".to_string()).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_export_to_new_database() -> Result<(), Error> {
+        let test_data = build_test_data();
+        let test_database = build_database(test_data)?;
+        let database = &test_database.database;
+        let group_id = database.add_group("Shared".to_string())?;
+        let child_id = database.add_child_group(group_id, "Child".to_string())?;
+        let other_group_id = test_database.group_ids[1];
+        let user_id1 = test_database.user_ids[0];
+        let user_id2 = test_database.user_ids[1];
+        let entity_id1 = database.add_entity(group_id, "e1".to_string(), user_id1, "password1".to_string(),
+                                             Some("a.com".to_string()),
+                                             HashMap::from([("PIN".to_string(), "1234".to_string())]), None)?;
+        let tag_id = database.add_tag(entity_id1, "team".to_string())?;
+        let entity_id2 = database.add_entity(other_group_id, "e2".to_string(), user_id2, "password2".to_string(),
+                                             None, HashMap::new(), None)?;
+        database.modify_entity(entity_id2, Some(child_id), None, Some("password3".to_string()), None, false,
+                               HashMap::new(), HashMap::new())?;
+        let names_count = database.get_names_records_count()?;
+
+        assert!(database.export_to_new_database(Vec::new(), Vec::new(), Vec::new(), Vec::new()).is_err());
+        let hash1 = vec![1u8; 32];
+        let hash2 = vec![2u8; 32];
+        let exported = database.export_to_new_database(vec![group_id, child_id], hash1.clone(), hash2.clone(),
+                                                       Vec::new())?;
+        exported.set_argon2(0, 1, 6, 1)?;
+        exported.set_argon2(1, 1, 6, 1)?;
+        assert_eq!(database.get_names_records_count()?, names_count);
+        assert!(exported.check_integrity(false)?.is_empty());
+
+        // save and open the exported database
+        let opened = reopen_database_with_hashes(&exported, hash1, hash2)?;
+        let groups = opened.get_groups()?;
+        assert_eq!(groups.len(), 2);
+        assert_eq!(groups.iter().find(|g|g.id == child_id).unwrap().parent_id, Some(group_id));
+        let users = opened.get_users()?;
+        assert_eq!(users.len(), 2);
+        assert_eq!(users.get(&user_id2).map(|u|u.name.clone()), Some(test_database.test_data.user_names[1].clone()));
+        assert_eq!(opened.get_tags()?, HashMap::from([(tag_id, "team".to_string())]));
+        let entity1 = opened.get_entity(entity_id1)?;
+        assert_eq!(entity1.get_password(ENTITY_VERSION_LATEST)?, "password1");
+        assert_eq!(entity1.get_url(ENTITY_VERSION_LATEST)?, Some("a.com".to_string()));
+        let pin_id = entity1.get_property_names(ENTITY_VERSION_LATEST)?[0].id;
+        assert_eq!(entity1.get_property_value(ENTITY_VERSION_LATEST, pin_id)?, "1234");
+        // history keeps the group that was not exported
+        let entity2 = opened.get_entity(entity_id2)?;
+        assert_eq!(entity2.get_password(1)?, "password2");
+        assert_eq!(entity2.get_group_id(1)?, other_group_id);
+        assert!(opened.check_exists(RETIRED_GROUPS_ID, other_group_id, "").is_ok());
+        assert!(opened.check_integrity(false)?.is_empty());
+        // new records do not reuse exported ids
+        let new_group_id = opened.add_group("New".to_string())?;
+        assert!(new_group_id > entity_id2);
+        Ok(())
+    }
+
+    #[test]
+    fn test_update_user() -> Result<(), Error> {
+        let test_data = build_test_data();
+        let test_database = build_database(test_data)?;
+        let database = &test_database.database;
+        let user_id = test_database.user_ids[0];
+        let user = DatabaseUser{
+            name: "jdoe".to_string(),
+            email: "john@a.com".to_string(),
+            display_name: "John Doe".to_string(),
+            fields: HashMap::from([("team".to_string(), "ops".to_string())])
+        };
+        assert!(database.update_user(user_id, DatabaseUser::new(test_database.test_data.user_names[1].clone())).is_err());
+        assert!(database.update_user(user_id, DatabaseUser::new("".to_string())).is_err());
+        assert!(database.add_user("".to_string()).is_err());
+        database.update_user(user_id, user.clone())?;
+        assert_eq!(database.get_users()?.get(&user_id), Some(&user));
+        database.rename_user(user_id, "john".to_string())?;
+        let renamed = database.get_users()?.remove(&user_id).unwrap();
+        assert_eq!(renamed.name, "john".to_string());
+        assert_eq!(renamed.display_name, user.display_name);
+        assert!(database.check_integrity(false)?.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_expiry() -> Result<(), Error> {
+        let test_data = build_test_data();
+        let test_database = build_database(test_data)?;
+        let database = &test_database.database;
+        let root_id = test_database.group_ids[0];
+        let child_id = database.add_child_group(root_id, "Child".to_string())?;
+        let entity_id1 = test_database.entity_ids[0];
+        let entity_id2 = database.add_entity(child_id, "child".to_string(), test_database.user_ids[0],
+                                             "password".to_string(), None, HashMap::new(), None)?;
+        let now = get_current_timestamp();
+        assert!(database.get_expiring_at(now, 1000)?.is_empty());
+        assert!(database.set_group_max_age(root_id, Some(0)).is_err());
+        database.set_group_max_age(root_id, Some(30))?;
+        let groups = database.get_groups()?;
+        assert_eq!(groups.iter().find(|g|g.id == root_id).unwrap().max_age_days, Some(30));
+        // group policy applies to subgroups
+        let expiring = database.get_expiring_at(now + 31 * 86400, 0)?;
+        assert!(expiring.iter().any(|e|e.entity_id == entity_id2));
+        assert!(database.get_expiring_at(now, 10)?.is_empty());
+        assert_eq!(database.get_expiring_at(now, 31)?.len(), expiring.len());
+
+        // entity max age overrides group policy, explicit expiry wins when earlier
+        let created_at = database.get_entity(entity_id2)?.get_created_at(ENTITY_VERSION_LATEST)?;
+        advance_test_clock(10);
+        database.set_entity_expiry(entity_id2, None, Some(100))?;
+        // expiry is changed in place and is not a password change
+        let entity = database.get_entity(entity_id2)?;
+        assert_eq!(entity.get_max_version(), 0);
+        assert_eq!(entity.get_created_at(ENTITY_VERSION_LATEST)?, created_at);
+        assert_eq!(entity.get_password_changed_at(), created_at);
+        assert!(database.get_expiring_at(now, 31)?.iter().all(|e|e.entity_id != entity_id2));
+        database.set_entity_expiry(entity_id2, Some(now + 86400), Some(100))?;
+        let expiring = database.get_expiring_at(now, 2)?;
+        assert_eq!(expiring.len(), 1);
+        assert_eq!(expiring[0].entity_id, entity_id2);
+        assert_eq!(expiring[0].expires_at, now + 86400);
+
+        // modifications without password change keep password change time
+        let entity = database.get_entity(entity_id1)?;
+        let changed_at = entity.get_password_changed_at();
+        database.modify_entity(entity_id1, None, None, None, None, false, HashMap::new(), HashMap::new())?;
+        assert_eq!(database.get_entity(entity_id1)?.get_password_changed_at(), changed_at);
+        database.set_group_max_age(root_id, None)?;
+        assert_eq!(database.get_expiring_at(now, 31)?.len(), 1);
+        database.remove_entity(entity_id2)?;
+        database.set_group_max_age(child_id, Some(10))?;
+        database.remove_group(child_id)?;
+        assert!(get_id_map(&database.file.lock().unwrap(), GROUP_POLICIES_ID)?.is_empty());
+        assert!(database.check_integrity(false)?.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_notes() -> Result<(), Error> {
+        let test_data = build_test_data();
+        let test_database = build_database(test_data)?;
+        let database = &test_database.database;
+        let group_id = test_database.group_ids[0];
+        let body = "line 1\nline 2".to_string();
+        let note_id = database.add_note(group_id, "note".to_string(), body.clone())?;
+        assert!(database.add_note(group_id, "note".to_string(), "".to_string()).is_err());
+        let note = database.get_entity(note_id)?;
+        assert_eq!(note.get_kind(), EntityKind::Note);
+        assert_eq!(note.get_password(ENTITY_VERSION_LATEST)?, body);
+        assert_eq!(database.get_entity(test_database.entity_ids[0])?.get_kind(), EntityKind::Password);
+        assert!(database.modify_entity(note_id, None, Some(test_database.user_ids[0]), None, None, false,
+                                       HashMap::new(), HashMap::new()).is_err());
+        assert!(database.set_entity_expiry(note_id, None, Some(10)).is_err());
+        database.modify_entity(note_id, None, None, Some("line 3".to_string()), None, false,
+                               HashMap::new(), HashMap::new())?;
+        let database = reopen_database(&test_database)?;
+        let note = database.get_entity(note_id)?;
+        assert_eq!(note.get_kind(), EntityKind::Note);
+        assert_eq!(note.get_password(ENTITY_VERSION_LATEST)?, "line 3".to_string());
+        assert_eq!(note.get_password(1)?, body);
+        // notes do not reference users
+        database.remove_entity(test_database.entity_ids[0])?;
+        database.remove_user(test_database.user_ids[0])?;
+        assert!(database.check_integrity(false)?.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_trash() -> Result<(), Error> {
+        let test_data = build_test_data();
+        let test_database = build_database(test_data)?;
+        let database = &test_database.database;
+        let group_id = test_database.group_ids[0];
+        let user_id = test_database.user_ids[0];
+        let entity_id = database.add_entity(group_id, "trashed".to_string(), user_id, "password".to_string(),
+                                            None, HashMap::new(), None)?;
+        database.remove_entity(entity_id)?;
+        assert!(database.get_entity(entity_id).is_err());
+        let trash = database.get_trash()?;
+        assert_eq!(trash.len(), 1);
+        assert_eq!((trash[0].entity_id, trash[0].name.clone(), trash[0].group_id),
+                   (entity_id, "trashed".to_string(), group_id));
+        assert!(database.check_integrity(false)?.is_empty());
+
+        // entity with the same name blocks restore
+        let entity_id2 = database.add_entity(group_id, "trashed".to_string(), user_id, "password2".to_string(),
+                                             None, HashMap::new(), None)?;
+        assert!(database.restore_entity(entity_id).is_err());
+        database.remove_entity(entity_id2)?;
+        database.restore_entity(entity_id)?;
+        assert!(database.restore_entity(entity_id).is_err());
+        assert_eq!(database.get_entity(entity_id)?.get_password(ENTITY_VERSION_LATEST)?, "password".to_string());
+        assert_eq!(database.get_trash()?.len(), 1);
+
+        // expired items are purged
+        database.remove_entity(entity_id)?;
+        assert_eq!(database.get_trash_retention()?, DEFAULT_TRASH_RETENTION_DAYS);
+        database.set_trash_retention(1)?;
+        database.purge_trash(get_current_timestamp() + 86400)?;
+        assert!(database.get_trash()?.is_empty());
+        assert!(database.check_integrity(false)?.is_empty());
+
+        database.remove_entity(test_database.entity_ids[0])?;
+        database.empty_trash()?;
+        assert!(database.get_trash()?.is_empty());
+        assert!(database.check_integrity(false)?.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_audit_log() -> Result<(), Error> {
+        let test_data = build_test_data();
+        let test_database = build_database(test_data)?;
+        let database = &test_database.database;
+        let group_id = test_database.group_ids[0];
+        let user_id = test_database.user_ids[0];
+        // audit log is disabled by default
+        database.add_user("auditor".to_string())?;
+        assert!(database.get_audit_log(None, None)?.is_empty());
+        assert!(database.set_audit_settings(AuditSettings{max_records: MAX_AUDIT_RECORDS + 1, log_reveals: false}).is_err());
+
+        database.set_audit_settings(AuditSettings{max_records: 5, log_reveals: false})?;
+        database.set_device_label("laptop".to_string())?;
+        let entity_id = database.add_entity(group_id, "audited".to_string(), user_id, "password1".to_string(),
+                                            None, HashMap::from([("PIN".to_string(), "1".to_string())]), None)?;
+        database.modify_entity(entity_id, None, None, Some("password2".to_string()), None, false,
+                               HashMap::new(), HashMap::new())?;
+        database.rename_entity(entity_id, "audited2".to_string())?;
+        let entity = database.get_entity(entity_id)?;
+        entity.get_password(ENTITY_VERSION_LATEST)?;
+        let log = database.get_audit_log(None, Some(entity_id))?;
+        assert_eq!(log.iter().map(|e|(e.event_type, e.object_id, e.object_name.clone(), e.details.clone()))
+                       .collect::<Vec<(AuditEventType, u32, String, String)>>(),
+                   vec![(AuditEventType::Add, entity_id, "audited".to_string(), "".to_string()),
+                        (AuditEventType::Modify, entity_id, "audited".to_string(), "password".to_string()),
+                        (AuditEventType::Rename, entity_id, "audited2".to_string(), "audited".to_string())]);
+        assert!(log.iter().all(|e|e.device_label == "laptop".to_string() && e.object_type == AuditObjectType::Entity));
+
+        // reveals are logged only when enabled
+        database.set_audit_settings(AuditSettings{max_records: 5, log_reveals: true})?;
+        database.save_to_data()?;
+        assert!(!database.is_audit_pending());
+        entity.get_password(1)?;
+        assert!(database.is_audit_pending());
+        let pin = entity.get_property_names(ENTITY_VERSION_LATEST)?.into_iter().next().unwrap();
+        entity.get_property_value(ENTITY_VERSION_LATEST, pin.id)?;
+        let log = database.get_audit_log(None, Some(entity_id))?;
+        assert_eq!(log.len(), 4);
+        assert_eq!((log[2].event_type, log[2].details.clone()), (AuditEventType::Reveal, "password (version 1)".to_string()));
+        assert_eq!((log[3].event_type, log[3].details.clone()), (AuditEventType::Reveal, "PIN".to_string()));
+
+        // oldest records are removed when the log is full
+        database.remove_entity(entity_id)?;
+        let log = database.get_audit_log(None, None)?;
+        assert_eq!(log.len(), 5);
+        assert_eq!(log[0].event_type, AuditEventType::Rename);
+        assert_eq!((log[1].object_type, log[1].details.clone()),
+                   (AuditObjectType::Database, "audit settings: max records 5 -> 5, log reveals false -> true".to_string()));
+        assert_eq!((log[4].event_type, log[4].object_name.clone()), (AuditEventType::Remove, "audited2".to_string()));
+        assert!(database.get_audit_log(Some(log[4].timestamp + 1), None)?.is_empty());
+        assert!(database.check_integrity(false)?.is_empty());
+
+        // settings changes are logged, disabling the log keeps existing records
+        database.set_audit_settings(AuditSettings{max_records: 0, log_reveals: false})?;
+        let log = database.get_audit_log(None, None)?;
+        assert_eq!(log.len(), 6);
+        assert_eq!((log[5].event_type, log[5].object_type, log[5].details.clone()),
+                   (AuditEventType::Modify, AuditObjectType::Database,
+                    "audit settings: max records 5 -> 0, log reveals true -> false".to_string()));
+        database.add_user("not audited".to_string())?;
+        assert_eq!(database.get_audit_log(None, None)?.len(), 6);
+        assert!(database.check_integrity(false)?.is_empty());
+
+        // shrunk log is trimmed by the next added record
+        database.set_audit_settings(AuditSettings{max_records: 3, log_reveals: false})?;
+        assert_eq!(database.get_audit_log(None, None)?.len(), 7);
+        database.add_user("audited".to_string())?;
+        let log = database.get_audit_log(None, None)?;
+        assert_eq!(log.len(), 3);
+        assert_eq!(log[1].details, "audit settings: max records 0 -> 3, log reveals false -> false".to_string());
+
+        // trash retention changes are logged
+        database.set_trash_retention(7)?;
+        let log = database.get_audit_log(None, None)?;
+        assert_eq!((log[2].event_type, log[2].object_type, log[2].details.clone()),
+                   (AuditEventType::Modify, AuditObjectType::Database,
+                    format!("trash retention: {} -> 7 days", DEFAULT_TRASH_RETENTION_DAYS)));
+        database.set_history_length(4)?;
+        assert_eq!(database.get_audit_log(None, None)?[2].details,
+                   format!("history length: {} -> 4", DEFAULT_HISTORY_LENGTH));
+        Ok(())
+    }
+
+    #[test]
+    fn test_restore_entity_version() -> Result<(), Error> {
+        let test_data = build_test_data();
+        let test_database = build_database(test_data)?;
+        let database = &test_database.database;
+        let group_id = test_database.group_ids[0];
+        let user_id = test_database.user_ids[0];
+        let entity_id = database.add_entity(group_id, "versioned".to_string(), user_id, "password1".to_string(),
+                                            Some("a.com".to_string()), HashMap::from([("PIN".to_string(), "1".to_string())]), None)?;
+        database.modify_entity(entity_id, None, None, Some("password2".to_string()), Some("b.com".to_string()), true,
+                               HashMap::new(), HashMap::new())?;
+        assert!(database.restore_entity_version(entity_id, 0).is_err());
+        assert!(database.restore_entity_version(entity_id, 2).is_err());
+        let names_count = database.get_names_records_count()?;
+        let passwords_count = database.get_passwords_records_count()?;
+        database.restore_entity_version(entity_id, 1)?;
+        // restored version reuses existing records
+        assert_eq!(database.get_names_records_count()?, names_count);
+        assert_eq!(database.get_passwords_records_count()?, passwords_count);
+        let entity = database.get_entity(entity_id)?;
+        assert_eq!(entity.get_max_version(), 2);
+        assert_eq!(entity.get_password(ENTITY_VERSION_LATEST)?, "password1".to_string());
+        assert_eq!(entity.get_url(ENTITY_VERSION_LATEST)?, Some("a.com".to_string()));
+        assert_eq!(entity.get_password(1)?, "password2".to_string());
+        let pin = entity.get_property_names(ENTITY_VERSION_LATEST)?.into_iter().next().unwrap();
+        assert_eq!(entity.get_property_value(ENTITY_VERSION_LATEST, pin.id)?, "1".to_string());
+        assert!(database.check_integrity(false)?.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_entity_urls() -> Result<(), Error> {
+        let test_data = build_test_data();
+        let test_database = build_database(test_data)?;
+        let database = &test_database.database;
+        let group_id = test_database.group_ids[0];
+        let user_id = test_database.user_ids[0];
+        let entity_id = database.add_entity(group_id, "urls".to_string(), user_id, "password".to_string(),
+                                            Some("https://example.com".to_string()), HashMap::new(), None)?;
+        let entity_id2 = database.add_entity(group_id, "urls2".to_string(), user_id, "password".to_string(),
+                                             Some("https://login.example.com/signin".to_string()), HashMap::new(), None)?;
+        let entity = database.get_entity(entity_id)?;
+        assert_eq!(entity.get_urls(ENTITY_VERSION_LATEST)?,
+                   vec![EntityUrl{url: "https://example.com".to_string(), match_mode: UrlMatchMode::BaseDomain}]);
+        let urls = vec![
+            EntityUrl{url: "https://example.com".to_string(), match_mode: UrlMatchMode::Host},
+            EntityUrl{url: r"^https://app\.test/".to_string(), match_mode: UrlMatchMode::Regex}
+        ];
+        assert!(database.set_entity_urls(entity_id, vec![EntityUrl{url: "(".to_string(), match_mode: UrlMatchMode::Regex}]).is_err());
+        let names_count = database.get_names_records_count()?;
+        database.set_entity_urls(entity_id, urls.clone())?;
+        // unchanged url keeps its record
+        assert_eq!(database.get_names_records_count()?, names_count + 1);
+        let entity = database.get_entity(entity_id)?;
+        assert_eq!(entity.get_urls(ENTITY_VERSION_LATEST)?, urls);
+        assert_eq!(entity.get_url(ENTITY_VERSION_LATEST)?, Some("https://example.com".to_string()));
+        // urls are changed in place, no version is added
+        assert_eq!(entity.get_max_version(), 0);
+
+        let matches = database.find_by_url("https://app.test/home".to_string())?;
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].entity_id, entity_id);
+        assert_eq!(matches[0].match_mode, UrlMatchMode::Regex);
+        // entity2 matches the host, entity matches base domain only
+        let matches = database.find_by_url("https://login.example.com/other".to_string())?;
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].entity_id, entity_id2);
+        let matches = database.find_by_url("https://www.example.com".to_string())?;
+        assert_eq!(matches.len(), 1);
+        database.set_entity_urls(entity_id, vec![EntityUrl{url: "example.com".to_string(), match_mode: UrlMatchMode::BaseDomain}])?;
+        // records of replaced urls are removed
+        assert_eq!(database.get_names_records_count()?, names_count);
+        let matches = database.find_by_url("https://login.example.com/signin".to_string())?;
+        assert_eq!(matches.iter().map(|m|m.entity_id).collect::<Vec<u32>>(), vec![entity_id2, entity_id]);
+
+        database.set_entity_urls(entity_id, Vec::new())?;
+        let entity = database.get_entity(entity_id)?;
+        assert!(entity.get_urls(ENTITY_VERSION_LATEST)?.is_empty());
+        assert_eq!(entity.get_url(ENTITY_VERSION_LATEST)?, None);
+        let note_id = database.add_note(group_id, "note".to_string(), "body".to_string())?;
+        assert!(database.set_entity_urls(note_id, urls).is_err());
+        assert!(database.check_integrity(false)?.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_history_length() -> Result<(), Error> {
+        let test_data = build_test_data();
+        let test_database = build_database(test_data)?;
+        let database = &test_database.database;
+        let entity_id = database.add_entity(test_database.group_ids[0], "history".to_string(), test_database.user_ids[0],
+                                            "password0".to_string(), None, HashMap::new(), None)?;
+        for i in 1..5 {
+            database.modify_entity(entity_id, None, None, Some(format!("password{}", i)), None, false,
+                                   HashMap::new(), HashMap::new())?;
+        }
+        assert!(database.set_history_length(1).is_err());
+        database.set_history_length(3)?;
+        // latest versions and the original version are kept
+        let entity = database.get_entity(entity_id)?;
+        assert_eq!(entity.get_max_version(), 2);
+        assert_eq!(entity.get_password(0)?, "password4".to_string());
+        assert_eq!(entity.get_password(1)?, "password3".to_string());
+        assert_eq!(entity.get_password(2)?, "password0".to_string());
+        assert!(database.check_integrity(false)?.is_empty());
+
+        database.set_entity_history_length(entity_id, Some(4))?;
+        for i in 5..8 {
+            database.modify_entity(entity_id, None, None, Some(format!("password{}", i)), None, false,
+                                   HashMap::new(), HashMap::new())?;
+        }
+        assert_eq!(database.get_entity(entity_id)?.get_max_version(), 3);
+        assert_eq!(database.get_entity(entity_id)?.get_history_length(), Some(4));
+
+        let created_at = database.get_entity(entity_id)?.get_created_at(3)?;
+        advance_test_clock(10);
+        database.modify_entity(entity_id, None, None, Some("password8".to_string()), None, false,
+                               HashMap::new(), HashMap::new())?;
+        database.purge_entity_history(entity_id)?;
+        let entity = database.get_entity(entity_id)?;
+        assert_eq!(entity.get_max_version(), 0);
+        assert_eq!(entity.get_password(0)?, "password8".to_string());
+        assert_eq!(entity.get_entity_created_at(), created_at);
+        assert_eq!(entity.get_password_changed_at(), entity.get_created_at(0)?);
+        assert_ne!(entity.get_password_changed_at(), created_at);
+        assert!(database.restore_entity_version(entity_id, 1).is_err());
+        assert!(database.check_integrity(false)?.is_empty());
+
+        let database = reopen_database(&test_database)?;
+        assert_eq!(database.get_history_length()?, 3);
+        assert_eq!(database.get_entity(entity_id)?.get_history_length(), Some(4));
+        assert_eq!(database.get_entity(entity_id)?.get_entity_created_at(), created_at);
+        Ok(())
+    }
+
+    #[test]
+    fn test_entity_templates() -> Result<(), Error> {
+        let test_data = build_test_data();
+        let test_database = build_database(test_data)?;
+        let database = &test_database.database;
+        let group_id = test_database.group_ids[0];
+        let user_id = test_database.user_ids[0];
+        let fields = vec![
+            TemplateField{name: "Router".to_string(), field_type: FieldType::Url, secret: false, required: true},
+            TemplateField{name: "Admin email".to_string(), field_type: FieldType::Email, secret: false, required: false}
+        ];
+        let template_id = database.add_template("Router".to_string(), fields.clone())?;
+        assert!(database.add_template("Router".to_string(), fields).is_err());
+        assert!(database.add_template("Server".to_string(), Vec::new()).is_err());
+        let templates = database.get_templates()?;
+        assert!(templates.iter().find(|t|t.id == TEMPLATE_CREDIT_CARD).unwrap().built_in);
+        assert_eq!(templates.last().unwrap().id, template_id);
+
+        let properties = HashMap::from([("Router".to_string(), "http://192.168.0.1".to_string()),
+                                        ("Admin email".to_string(), "admin".to_string())]);
+        assert!(database.add_entity(group_id, "router".to_string(), user_id, "password".to_string(), None,
+                                    properties, Some(template_id)).is_err());
+        let properties = HashMap::from([("Router".to_string(), "http://192.168.0.1".to_string())]);
+        let entity_id = database.add_entity(group_id, "router".to_string(), user_id, "password".to_string(), None,
+                                            properties, Some(template_id))?;
+        assert_eq!(database.get_entity(entity_id)?.get_template_id(ENTITY_VERSION_LATEST)?, Some(template_id));
+        let router_id = database.get_entity(entity_id)?.get_property_names(ENTITY_VERSION_LATEST)?
+            .into_iter().find(|p|p.name == "Router").unwrap().id;
+        // required field cannot be removed and unknown fields are rejected
+        assert!(database.modify_entity(entity_id, None, None, None, None, false, HashMap::new(),
+                                       HashMap::from([(router_id, None)])).is_err());
+        assert!(database.modify_entity(entity_id, None, None, None, None, false,
+                                       HashMap::from([("PIN".to_string(), "1".to_string())]), HashMap::new()).is_err());
+        database.modify_entity(entity_id, None, None, None, None, false,
+                               HashMap::from([("Admin email".to_string(), "admin@a.com".to_string())]), HashMap::new())?;
+        assert!(database.remove_template(template_id).is_err());
+        assert!(database.remove_template(TEMPLATE_CREDIT_CARD).is_err());
+        assert!(database.check_integrity(false)?.is_empty());
+        database.remove_entity(entity_id)?;
+        database.remove_template(template_id)?;
+        assert_eq!(database.get_templates()?.len(), templates.len() - 1);
+        Ok(())
+    }
+
+    #[test]
+    fn test_property_layout() -> Result<(), Error> {
+        let test_data = build_test_data();
+        let test_database = build_database(test_data)?;
+        let database = &test_database.database;
+        let group_id = test_database.group_ids[0];
+        let user_id = test_database.user_ids[0];
+        let properties = HashMap::from([("CVV".to_string(), "123".to_string()),
+                                        ("Cardholder".to_string(), "John Doe".to_string()),
+                                        ("Expiry date".to_string(), "12/30".to_string()),
+                                        ("Card number".to_string(), "4111111111111111".to_string())]);
+        let entity_id = database.add_entity(group_id, "visa".to_string(), user_id, "password".to_string(), None,
+                                            properties, Some(TEMPLATE_CREDIT_CARD))?;
+        // properties follow template field order and flags
+        let names = database.get_entity(entity_id)?.get_property_names(ENTITY_VERSION_LATEST)?;
+        let layout: Vec<(String, bool)> = names.iter().map(|p|(p.name.clone(), p.secret)).collect();
+        assert_eq!(layout, vec![("Card number".to_string(), true), ("Cardholder".to_string(), false),
+                                ("Expiry date".to_string(), false), ("CVV".to_string(), true)]);
+
+        let mut new_layout: Vec<PropertyLayout> = names.iter().rev()
+            .map(|p|PropertyLayout{id: p.id, field_type: p.field_type, secret: !p.secret})
+            .collect();
+        assert!(database.set_property_layout(entity_id, new_layout[1..].to_vec()).is_err());
+        new_layout[1].field_type = FieldType::Date;
+        assert!(database.set_property_layout(entity_id, new_layout.clone()).is_err());
+        new_layout[1].field_type = FieldType::Text;
+        database.set_property_layout(entity_id, new_layout.clone())?;
+        let entity = database.get_entity(entity_id)?;
+        let names2 = entity.get_property_names(ENTITY_VERSION_LATEST)?;
+        assert_eq!(names2.iter().map(|p|p.id).collect::<Vec<u32>>(),
+                   new_layout.iter().map(|p|p.id).collect::<Vec<u32>>());
+        for property in &names {
+            let property2 = names2.iter().find(|p|p.id == property.id).unwrap();
+            assert_eq!(property2.secret, !property.secret);
+            assert_eq!(entity.get_property_value(ENTITY_VERSION_LATEST, property.id)?,
+                       entity.get_property_value(1, property.id)?);
+        }
+
+        // properties without template are secret text properties ordered by name
+        let properties = HashMap::from([("b".to_string(), "2".to_string()), ("a".to_string(), "1".to_string())]);
+        let entity_id = database.add_entity(group_id, "plain".to_string(), user_id, "password".to_string(), None,
+                                            properties, None)?;
+        let names = database.get_entity(entity_id)?.get_property_names(ENTITY_VERSION_LATEST)?;
+        assert_eq!(names.iter().map(|p|p.name.clone()).collect::<Vec<String>>(), vec!["a".to_string(), "b".to_string()]);
+        assert!(names.iter().all(|p|p.secret && p.field_type == FieldType::Text));
+        assert!(database.check_integrity(false)?.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_upgrade_from_version1() -> Result<(), Error> {
+        let hash1_vec = build_fixture_password_hash("password1");
+        let hash2_vec = build_fixture_password_hash("password2");
+        let mut database = PmanDatabase::new_from_file2(V1_0_MAIN_DATA.to_vec())?;
+        database.pre_open(hash1_vec.clone(), Some(hash2_vec.clone()), Some(Vec::new()))?;
+        assert_eq!(database.get_version()?.stored_version, 0x100);
+        database.open_from_data(V1_0_DATA1.to_vec(), V1_0_DATA2.to_vec())?;
+        check_fixture_database(&database)?;
+        let version = database.get_version()?;
+        assert_eq!(version.stored_version, 0x100);
+        assert_eq!(version.current_version, DATABASE_VERSION_LATEST);
+        let recovery_key = database.add_recovery_key()?;
+        let (data1, data2) = database.save_to_data()?;
+        assert_eq!(database.get_version()?.stored_version, DATABASE_VERSION_LATEST);
+        assert!(data1.is_some());
+        assert!(data2.is_some());
+        let main_data = data1.unwrap();
+        let (d2, d3) = data2.unwrap();
+
+        let mut database2 = PmanDatabase::new_from_file2(main_data.clone())?;
+        database2.pre_open(hash1_vec, Some(hash2_vec), Some(Vec::new()))?;
+        database2.open_from_data(d2.clone(), d3.clone())?;
+        check_fixture_database(&database2)?;
+        assert_eq!(database2.get_key_slots()?.len(), 2);
+
+        let mut database3 = PmanDatabase::new_from_file2(main_data)?;
+        database3.pre_open_with_key_slot(KeySlotType::RecoveryKey, recovery_key, Some(Vec::new()))?;
+        database3.open_from_data(d2, d3)?;
+        check_fixture_database(&database3)
+    }
+
+    #[test]
+    fn test_open_version2() -> Result<(), Error> {
+        let mut database = PmanDatabase::new_from_file2(V2_0_MAIN_DATA.to_vec())?;
+        database.pre_open(build_fixture_password_hash("password1"),
+                          Some(build_fixture_password_hash("password2")), Some(Vec::new()))?;
+        database.open_from_data(V2_0_DATA1.to_vec(), V2_0_DATA2.to_vec())?;
+        check_fixture_database(&database)?;
+        let version = database.get_version()?;
+        assert_eq!(version.stored_version, 0x200);
+        assert_eq!(version.current_version, 0x200);
+        // nothing to upgrade, main file is not rewritten
+        let (data1, data2) = database.save_to_data()?;
+        assert!(data1.is_none());
+        assert!(data2.is_some());
+        Ok(())
+    }
+
     fn check_database(database: &TestDatabase) -> Result<(), Error> {
         // groups
         let groups = database.database.get_groups()?;
@@ -689,12 +3454,12 @@ mod tests {
         let users = database.database.get_users()?;
         assert_eq!(users.len(), database.user_ids.len());
         assert_eq!(users.len(), database.test_data.user_names.len());
-        for (id, name) in users {
+        for (id, user) in users {
             let mut found = false;
             for i in 0..database.user_ids.len() {
                 if id == database.user_ids[i] {
                     found = true;
-                    assert_eq!(name, database.test_data.user_names[i]);
+                    assert_eq!(user.name, database.test_data.user_names[i]);
                     break;
                 }
             }
@@ -726,10 +3491,10 @@ mod tests {
                     let names = en.get_property_names(i)?;
                     let props = ten.get_properties(i);
                     assert_eq!(names.len(), props.len());
-                    for (name, id) in names {
-                        let value = props.get(&name);
+                    for property in names {
+                        let value = props.get(&property.name);
                         assert!(value.is_some());
-                        let pvalue = en.get_property_value(i, id)?;
+                        let pvalue = en.get_property_value(i, property.id)?;
                         assert_eq!(*value.unwrap(), pvalue);
                     }
                 }
@@ -952,7 +3717,7 @@ mod tests {
         let pdb: &PmanDatabase = db.database.as_any().downcast_ref().unwrap();
         let property_names: HashMap<u32, String> = pdb.get_entity(id)?
             .get_property_names(ENTITY_VERSION_LATEST)?
-            .into_iter().map(|(k, v)|(v, k)).collect();
+            .into_iter().map(|p|(p.id, p.name)).collect();
         let property_ids: Vec<u32> = property_names.iter()
             .map(|(k, _v)|*k)
             .collect();
@@ -997,7 +3762,7 @@ mod tests {
         let pdb: &PmanDatabase = db.database.as_any().downcast_ref().unwrap();
         let property_names: HashMap<u32, String> = pdb.get_entity(id)?
             .get_property_names(ENTITY_VERSION_LATEST)?
-            .into_iter().map(|(k, v)|(v, k)).collect();
+            .into_iter().map(|p|(p.id, p.name)).collect();
         let property_ids: Vec<u32> = property_names.iter()
             .map(|(k, _v)|*k)
             .collect();
@@ -1078,7 +3843,8 @@ mod tests {
                                user_id,
                                password.clone(),
                                url.clone(),
-                               properties.clone())?;
+                               properties.clone(),
+                               None)?;
         db.entity_ids.push(id);
         db.test_data.entities.push(TestEntity::new(
             name,
@@ -17,14 +17,14 @@ main database file structure
 |--|-header -> id_value_map
 |  |     database version
 |  |     password1_hash_algorithm properties (supported: argon2)
-|  |     map1_encryption1_algorithm properties (supported: chacha20)
+|  |     map1_encryption1_algorithm properties (supported: chacha20, aes-ctr, cascade of them)
 |  |     map1_encryption2_algorithm properties (supported: aes)
 |  | encryption_algorithm_properties (iv) for sha256(password1_hash+password2_hash), names_map_info_encryption1_algorithm
 |--| common data structure
 |  | map1 contains
 |  |     file1 locations
 |  |     password2_hash_algorithm properties (supported: argon2)
-|  |     map2_encryption1_algorithm properties (supported: chacha20)
+|  |     map2_encryption1_algorithm properties (supported: chacha20, aes-ctr, cascade of them)
 |  |     map2_encryption2_algorithm properties (supported: aes)
 |  | map2 contains
 |  |     file2 locations
@@ -32,6 +32,17 @@ main database file structure
 names&passwords file structure -> common data structure
 file is divided to two parts -> file1 & file2 and saved in different locations in cloud
 
+database version 2.0:
+map1 and map2 encryption keys are random master keys, they are stored in key slots (see key_slots.rs)
+|--|-header -> id_value_map
+|  |     key slots (password pair, recovery key, key file, member)
+|  |     members (name and member key slot id)
+password1_hash_algorithm properties are used as default key slot kdf properties,
+password2_hash_algorithm properties are not used
+
+cascade encryption: every algorithm of the cascade uses own key (hmacsha256 of the layer key)
+and own iv, ivs are stored one after another in the cascade order
+
 */
 
 use std::collections::{HashMap, HashSet};
@@ -43,16 +54,24 @@ use rand::RngCore;
 use rand::rngs::OsRng;
 use sha2::{Sha256, Digest};
 use crate::crypto::{AesProcessor, ChachaProcessor, CryptoProcessor, NoEncryptionProcessor};
-use crate::error_builders::{build_corrupted_data_error, build_unsupported_algorithm_error};
+use crate::error_builders::{build_corrupted_data_error, build_not_found_error, build_unsupported_algorithm_error};
 use crate::pman::id_value_map::id_value_map::{ByteValue, IdValueMap};
+use crate::pman::database_entity::check_history_length;
 use crate::pman::ids::{DATABASE_VERSION_ID, ENCRYPTION_ALGORITHM1_PROPERTIES_ID,
                        ENCRYPTION_ALGORITHM2_PROPERTIES_ID, FILE_LOCATION_ID,
                        HASH_ALGORITHM_PROPERTIES_ID, HISTORY_LENGTH_ID};
 use crate::pman::data_file::{build_qs3_file_location, DataFile, validate_data_hash};
+use crate::pman::key_slots::{add_key_slot, add_member, build_passwords_secret, build_recovery_key, get_key_slots,
+                             get_members, is_member_key_slot, KEY_SLOT_PASSWORDS, KEY_SLOT_RECOVERY_KEY, MasterKeys,
+                             remove_key_slot, remove_member, replace_key_slots, rotate_key_slots, unlock_key_slots};
+use crate::secure_memory::{SecureBox, SecureBytes, wipe};
+use crate::structs_interfaces::{CryptoEngine, DatabaseMember};
 
 const DATABASE_VERSION_MIN: u16 = 0x100; // 1.0
-const DATABASE_VERSION_MAX: u16 = 0x100; // 1.0
+const DATABASE_VERSION_MAX: u16 = DATABASE_VERSION_LATEST;
 const DATABASE_VERSION_1: u16 = 0x100; // 1.0
+const DATABASE_VERSION_2: u16 = 0x200; // 2.0
+pub const DATABASE_VERSION_LATEST: u16 = DATABASE_VERSION_2;
 pub const DEFAULT_HISTORY_LENGTH: u8 = 5;
 pub const HASH_ALGORITHM_ARGON2: u8 = 1;
 pub const DEFAULT_ARGON2_ITERATIONS: u8 = 2;
@@ -60,21 +79,31 @@ pub const DEFAULT_ARGON2_MEMORY: u16 = 64;
 pub const DEFAULT_ARGON2_PARALLELISM: u8 = 6;
 pub const ENCRYPTION_ALGORITHM_AES: u8 = 2;
 pub const ENCRYPTION_ALGORITHM_CHACHA20: u8 = 3;
+pub const ENCRYPTION_ALGORITHM_AES_CTR: u8 = 4;
+pub const ENCRYPTION_ALGORITHM_CASCADE: u8 = 5;
 pub const FILE_LOCATION_QS3: u8 = 1;
 
+type DatabaseMigration = fn(&mut PmanDatabaseProperties) -> Result<(), Error>;
+
+// (from version, to version, migration), migrations are applied one by one on open,
+// new version is written on save
+const DATABASE_MIGRATIONS: [(u16, u16, DatabaseMigration); 1] = [
+    (DATABASE_VERSION_1, DATABASE_VERSION_2, PmanDatabaseProperties::upgrade_to_version2)
+];
+
 pub struct PmanDatabaseProperties {
-    password_hash: Vec<u8>,
-    password2_hash: Vec<u8>,
-    map1_encryption_key1: [u8; 32],
-    map1_encryption_key2: [u8; 32],
-    map2_encryption_key1: [u8; 32],
-    map2_encryption_key2: [u8; 32],
+    // None when database was opened using recovery key or key file
+    passwords: Option<(SecureBytes, SecureBytes)>,
+    keys: SecureBox<MasterKeys>,
+    // version of the loaded file
+    stored_version: u16,
+    version: u16,
     header: IdValueMap,
     main_data: DataFile,
     names_passwords_data: Option<DataFile>,
     is_updated: bool,
-    alg1: u8,
-    alg21: u8,
+    alg1: Vec<u8>,
+    alg21: Vec<u8>,
     processor12: Arc<dyn CryptoProcessor + Send + Sync>,
     processor22: Arc<dyn CryptoProcessor + Send + Sync>,
     history_length: usize
@@ -83,40 +112,36 @@ pub struct PmanDatabaseProperties {
 pub struct PmanDatabaseFile {
     data: Option<Vec<u8>>,
     data_length: usize,
-    properties: Option<PmanDatabaseProperties>
+    properties: Option<PmanDatabaseProperties>,
+    // device label for audit records, not stored in the database
+    device_label: String,
+    // audit records were added after the last save
+    audit_pending: bool,
+    // incremented on every names records change, used to invalidate in-memory indexes
+    names_version: u64
 }
 
 impl PmanDatabaseProperties {
     fn new(password_hash: Vec<u8>, password2_hash: Vec<u8>)
         -> Result<PmanDatabaseProperties, Error> {
         let mut h = IdValueMap::new(NoEncryptionProcessor::new())?;
-        h.add_with_id(DATABASE_VERSION_ID, DATABASE_VERSION_1.to_le_bytes().to_vec()).unwrap();
+        h.add_with_id(DATABASE_VERSION_ID, DATABASE_VERSION_LATEST.to_le_bytes().to_vec()).unwrap();
         h.add_with_id(HASH_ALGORITHM_PROPERTIES_ID, default_argon2_properties()).unwrap();
         h.add_with_id(ENCRYPTION_ALGORITHM1_PROPERTIES_ID, default_chacha_properties()).unwrap();
         h.add_with_id(ENCRYPTION_ALGORITHM2_PROPERTIES_ID, default_aes_properties()).unwrap();
         h.add_with_id(HISTORY_LENGTH_ID, vec![DEFAULT_HISTORY_LENGTH]).unwrap();
 
         let (alg1, alg2) = get_encryption_algorithms(&h)?;
-        let a1 = alg1[0];
-        // initially generating random key - it will be overwritten on save
-        let mut map1_encryption_key1 = [0u8; 32];
-        OsRng.fill_bytes(&mut map1_encryption_key1);
-        let mut map1_encryption_key2 = [0u8; 32];
-        OsRng.fill_bytes(&mut map1_encryption_key2);
+        // master keys, password pair key slot will be created on save
+        let keys = MasterKeys::new();
         let processor12 =
-            build_encryption_processor(alg2, map1_encryption_key2)?;
+            build_encryption_processor(alg2, keys.map1_encryption_key2)?;
 
         let map1 = DataFile::build_names_passwords_file_info(processor12.clone())?;
 
         let (alg21, alg22) = get_encryption_algorithms(&map1)?;
-        let a2 = alg21[0];
-        // initially generating random key - it will be overwritten on save
-        let mut map2_encryption_key1 = [0u8; 32];
-        OsRng.fill_bytes(&mut map2_encryption_key1);
-        let mut map2_encryption_key2 = [0u8; 32];
-        OsRng.fill_bytes(&mut map2_encryption_key2);
         let processor22 =
-            build_encryption_processor(alg22, map2_encryption_key2)?;
+            build_encryption_processor(alg22, keys.map2_encryption_key2)?;
 
         let mut main_data = DataFile::new(map1, processor22.clone())?;
         main_data.build_file2_info()?;
@@ -125,18 +150,16 @@ impl PmanDatabaseProperties {
             Some(DataFile::new(IdValueMap::new(processor12.clone())?,
                                processor22.clone())?);
         Ok(PmanDatabaseProperties{
-            password_hash,
-            password2_hash,
-            map1_encryption_key1,
-            map1_encryption_key2,
-            map2_encryption_key1,
-            map2_encryption_key2,
+            passwords: Some((SecureBytes::new(password_hash), SecureBytes::new(password2_hash))),
+            keys,
+            stored_version: DATABASE_VERSION_LATEST,
+            version: DATABASE_VERSION_LATEST,
             header: h,
             main_data,
             names_passwords_data,
             is_updated: true,
-            alg1: a1,
-            alg21: a2,
+            alg1,
+            alg21,
             processor12,
             processor22,
             history_length: DEFAULT_HISTORY_LENGTH as usize
@@ -147,39 +170,71 @@ impl PmanDatabaseProperties {
                 password2_hash: Vec<u8>)
         -> Result<PmanDatabaseProperties, Error> {
         let (h, offset) = IdValueMap::load(data, 0, NoEncryptionProcessor::new())?;
-        let _v = validate_database_version(&h)?;
+        let version = validate_database_version(&h)?;
+        let keys = if version == DATABASE_VERSION_1 { None } else {
+            let secret = build_passwords_secret(&password_hash, &password2_hash);
+            Some(unlock_key_slots(&h, KEY_SLOT_PASSWORDS, &secret)?)
+        };
+        PmanDatabaseProperties::load(data, data_length, h, offset, version, keys.as_deref(),
+                                     Some((SecureBytes::new(password_hash), SecureBytes::new(password2_hash))))
+    }
+
+    fn pre_open_with_key_slot(data: &mut Vec<u8>, data_length: usize, slot_type: u8, secret: Vec<u8>)
+        -> Result<PmanDatabaseProperties, Error> {
+        let (h, offset) = IdValueMap::load(data, 0, NoEncryptionProcessor::new())?;
+        let version = validate_database_version(&h)?;
+        if version == DATABASE_VERSION_1 {
+            return Err(Error::new(ErrorKind::Unsupported, "key slots are not supported by database version"));
+        }
+        let keys = unlock_key_slots(&h, slot_type, &secret)?;
+        PmanDatabaseProperties::load(data, data_length, h, offset, version, Some(&keys), None)
+    }
+
+    // keys = None - version 1.0 database, keys are built from passwords
+    fn load(data: &mut Vec<u8>, data_length: usize, h: IdValueMap, offset: usize, version: u16,
+            keys: Option<&MasterKeys>, passwords: Option<(SecureBytes, SecureBytes)>)
+        -> Result<PmanDatabaseProperties, Error> {
         let history_length = get_history_length(&h)?;
         let (alg1, alg2) = get_encryption_algorithms(&h)?;
-        let a1 = alg1[0];
-        let (map1_encryption_key1, map1_encryption_key2) =
-            build_encryption_keys(&h, &password_hash, &password2_hash)?;
+        // keys are written directly into locked memory
+        let mut master_keys = MasterKeys::empty();
+        match keys {
+            Some(k) => master_keys.copy_from(k),
+            None => {
+                let (password_hash, password2_hash) =
+                    passwords.as_ref().ok_or(build_passwords_required_error())?;
+                (master_keys.map1_encryption_key1, master_keys.map1_encryption_key2) =
+                    build_encryption_keys(&h, password_hash, password2_hash)?;
+            }
+        }
         let processor12 =
-            build_encryption_processor(alg2, map1_encryption_key2)?;
+            build_encryption_processor(alg2, master_keys.map1_encryption_key2)?;
         let mut main_data =
-            DataFile::pre_load(data, offset, data_length, map1_encryption_key1, a1,
+            DataFile::pre_load(data, offset, data_length, master_keys.map1_encryption_key1, &alg1,
                                processor12.clone())?;
 
-        let (map2_encryption_key1, map2_encryption_key2) =
-            main_data.build_encryption_keys(&password2_hash, &password_hash)?;
+        if keys.is_none() {
+            let (password_hash, password2_hash) =
+                passwords.as_ref().ok_or(build_passwords_required_error())?;
+            (master_keys.map2_encryption_key1, master_keys.map2_encryption_key2) =
+                main_data.build_encryption_keys(password2_hash, password_hash)?;
+        }
         let (alg21, alg22) = main_data.get_encryption_algorithms()?;
-        let a2 = alg21[0];
         let processor22 =
-            build_encryption_processor(alg22, map2_encryption_key2)?;
-        main_data.load(data, map2_encryption_key1, a2, processor22.clone())?;
+            build_encryption_processor(alg22, master_keys.map2_encryption_key2)?;
+        main_data.load(data, master_keys.map2_encryption_key1, &alg21, processor22.clone())?;
 
         let properties = PmanDatabaseProperties{
-            password_hash,
-            password2_hash,
-            map1_encryption_key1,
-            map1_encryption_key2,
-            map2_encryption_key1,
-            map2_encryption_key2,
+            passwords,
+            keys: master_keys,
+            stored_version: version,
+            version,
             header: h,
             main_data,
             names_passwords_data: None,
             is_updated: false,
-            alg1: a1,
-            alg21: a2,
+            alg1,
+            alg21,
             processor12,
             processor22,
             history_length
@@ -194,10 +249,58 @@ impl PmanDatabaseProperties {
         }
         let mut data = join_data(data1, data2)?;
         let l = validate_data_hash(&data)?;
-        let mut npdata = DataFile::pre_load(&mut data, 0, l, self.map1_encryption_key1,
-                                        self.alg1, self.processor12.clone())?;
-        npdata.load(&mut data, self.map2_encryption_key1, self.alg21, self.processor22.clone())?;
+        let mut npdata = DataFile::pre_load(&mut data, 0, l, self.keys.map1_encryption_key1,
+                                        &self.alg1, self.processor12.clone())?;
+        npdata.load(&mut data, self.keys.map2_encryption_key1, &self.alg21, self.processor22.clone())?;
+        wipe(&mut data);
         self.names_passwords_data = Some(npdata);
+        self.upgrade()
+    }
+
+    fn upgrade(&mut self) -> Result<(), Error> {
+        while self.version < DATABASE_VERSION_LATEST {
+            let (_, to_version, migration) = DATABASE_MIGRATIONS.iter()
+                .find(|(from_version, _, _)|*from_version == self.version)
+                .ok_or(Error::new(ErrorKind::Unsupported, "no migration for database version"))?;
+            migration(self)?;
+            self.header.set(DATABASE_VERSION_ID, to_version.to_le_bytes().to_vec())?;
+            self.version = *to_version;
+            self.is_updated = true;
+        }
+        Ok(())
+    }
+
+    // new master keys are generated, all the data will be re-encrypted on save
+    fn upgrade_to_version2(&mut self) -> Result<(), Error> {
+        if self.passwords.is_none() {
+            return Err(build_passwords_required_error());
+        }
+        self.keys = MasterKeys::new();
+        Ok(())
+    }
+
+    // upgraded database is loaded from saved data to make sure that it can be opened
+    fn verify_saved_data(&self, data1: &Vec<u8>, data2: &(Vec<u8>, Vec<u8>)) -> Result<(), Error> {
+        let mut data = data1.clone();
+        let l = validate_data_hash(&data)?;
+        let mut properties = match &self.passwords {
+            Some((password_hash, password2_hash)) =>
+                PmanDatabaseProperties::pre_open(&mut data, l, password_hash.to_vec(),
+                                                 password2_hash.to_vec())?,
+            None => {
+                let (h, offset) = IdValueMap::load(&mut data, 0, NoEncryptionProcessor::new())?;
+                let version = validate_database_version(&h)?;
+                PmanDatabaseProperties::load(&mut data, l, h, offset, version, Some(&self.keys), None)?
+            }
+        };
+        wipe(&mut data);
+        if properties.version != self.version {
+            return Err(build_corrupted_data_error("verify_saved_data"));
+        }
+        properties.open(data2.0.clone(), data2.1.clone())?;
+        if properties.is_updated {
+            return Err(build_corrupted_data_error("verify_saved_data"));
+        }
         Ok(())
     }
 
@@ -210,31 +313,38 @@ impl PmanDatabaseProperties {
 
             // main header
             modify_header_algorithm_properties(&mut self.header)?;
+            if let Some((password_hash, password2_hash)) = &self.passwords {
+                let secret = build_passwords_secret(password_hash, password2_hash);
+                replace_key_slots(&mut self.header, KEY_SLOT_PASSWORDS, &secret, &self.keys)?;
+            }
             self.header.save(&mut output, None)?;
 
             // names info
             self.main_data.modify_header_algorithm_properties()?;
             let (alg1, alg2) = get_encryption_algorithms(&mut self.header)?;
-            self.alg1 = alg1[0];
-            (self.map1_encryption_key1, self.map1_encryption_key2) =
-                build_encryption_keys(&mut self.header, &self.password_hash, &self.password2_hash)?;
-            self.processor12 = build_encryption_processor(alg2, self.map1_encryption_key2)?;
+            self.alg1 = alg1;
+            self.processor12 = build_encryption_processor(alg2, self.keys.map1_encryption_key2)?;
 
             let (alg21, alg22) = self.main_data.get_encryption_algorithms()?;
-            self.alg21 = alg21[0];
-            (self.map2_encryption_key1, self.map2_encryption_key2) =
-                self.main_data.build_encryption_keys(&self.password2_hash, &self.password_hash)?;
-            self.processor22 = build_encryption_processor(alg22, self.map2_encryption_key2)?;
+            self.alg21 = alg21;
+            self.processor22 = build_encryption_processor(alg22, self.keys.map2_encryption_key2)?;
 
-            self.main_data.save(&mut output, self.map1_encryption_key1, self.alg1, Some(self.processor12.clone()),
-                                self.map2_encryption_key1, self.alg21, Some(self.processor22.clone()))?;
+            self.main_data.save(&mut output, self.keys.map1_encryption_key1, &self.alg1, Some(self.processor12.clone()),
+                                self.keys.map2_encryption_key1, &self.alg21, Some(self.processor22.clone()))?;
             Some(output)
         } else {None};
         let mut output2 = Vec::new();
         self.names_passwords_data.as_mut().unwrap()
-            .save(&mut output2, self.map1_encryption_key1, self.alg1, Some(self.processor12.clone()),
-                  self.map2_encryption_key1, self.alg21, Some(self.processor22.clone()))?;
+            .save(&mut output2, self.keys.map1_encryption_key1, &self.alg1, Some(self.processor12.clone()),
+                  self.keys.map2_encryption_key1, &self.alg21, Some(self.processor22.clone()))?;
         let data2 = split_data(output2);
+        if self.stored_version != self.version {
+            match (&data1, &data2) {
+                (Some(d1), Some(d2)) => self.verify_saved_data(d1, d2)?,
+                _ => return Err(build_corrupted_data_error("save"))
+            }
+            self.stored_version = self.version;
+        }
         Ok((data1, data2))
     }
 
@@ -294,6 +404,14 @@ impl PmanDatabaseProperties {
         Err(build_names_passwords_file_not_initialized_error())
     }
 
+    #[cfg(test)]
+    fn set_raw_in_passwords(&mut self, id: u32, value: Vec<u8>) -> Result<(), Error> {
+        if let Some(p) = &mut self.names_passwords_data {
+            return p.set_raw_in_passwords(id, value);
+        }
+        Err(build_names_passwords_file_not_initialized_error())
+    }
+
     fn remove_from_names(&mut self, id: &u32) -> Result<(), Error> {
         if let Some(p) = &mut self.names_passwords_data {
             p.remove_from_names(id);
@@ -324,10 +442,50 @@ impl PmanDatabaseProperties {
         Err(build_names_passwords_file_not_initialized_error())
     }
 
+    fn get_names_ids(&self) -> Result<Vec<u32>, Error> {
+        if let Some(p) = &self.names_passwords_data {
+            return Ok(p.get_names_ids());
+        }
+        Err(build_names_passwords_file_not_initialized_error())
+    }
+
+    fn get_passwords_ids(&self) -> Result<Vec<u32>, Error> {
+        if let Some(p) = &self.names_passwords_data {
+            return p.get_passwords_ids();
+        }
+        Err(build_names_passwords_file_not_initialized_error())
+    }
+
+    fn check_names_record(&self, id: u32) -> Result<(), Error> {
+        if let Some(p) = &self.names_passwords_data {
+            return p.check_names_record(id);
+        }
+        Err(build_names_passwords_file_not_initialized_error())
+    }
+
+    fn check_passwords_record(&self, id: u32) -> Result<(), Error> {
+        if let Some(p) = &self.names_passwords_data {
+            return p.check_passwords_record(id);
+        }
+        Err(build_names_passwords_file_not_initialized_error())
+    }
+
+    fn get_version(&self) -> (u16, u16) {
+        (self.stored_version, self.version)
+    }
+
     fn get_history_length(&self) -> usize {
         self.history_length
     }
 
+    fn set_history_length(&mut self, history_length: usize) -> Result<(), Error> {
+        check_history_length(history_length)?;
+        self.set_updated()?;
+        self.header.set(HISTORY_LENGTH_ID, vec![history_length as u8])?;
+        self.history_length = history_length;
+        Ok(())
+    }
+
     fn set_argon2(&mut self, hash_id: usize, iterations: u8, parallelism: u8, memory: u16) -> Result<(), Error> {
         if let Some(p) = &mut self.names_passwords_data {
             p.set_updated();
@@ -342,6 +500,17 @@ impl PmanDatabaseProperties {
         }
     }
 
+    // layer_id 0 - names & passwords data layer, 1 - passwords data layer
+    fn set_encryption(&mut self, layer_id: usize, algorithms: Vec<u8>) -> Result<(), Error> {
+        let properties = build_encryption_layer_properties(algorithms)?;
+        self.set_updated()?;
+        match layer_id {
+            0 => set_encryption_in_header(&mut self.header, properties),
+            1 => self.main_data.set_encryption_in_header(properties),
+            _ => Err(Error::new(ErrorKind::InvalidInput, "wrong layer id"))
+        }
+    }
+
     fn set_file1_location_qs3(&mut self, file_name: String, s3_key: Vec<u8>) -> Result<(), Error> {
         self.is_updated = true;
         self.main_data.set_file_location_qs3(file_name, s3_key)
@@ -355,6 +524,84 @@ impl PmanDatabaseProperties {
     fn get_location_data(&self) -> Result<(Vec<u8>, Vec<u8>), Error> {
         self.main_data.get_location_data()
     }
+
+    fn set_updated(&mut self) -> Result<(), Error> {
+        if let Some(p) = &mut self.names_passwords_data {
+            p.set_updated();
+        } else {
+            return Err(build_names_passwords_file_not_initialized_error());
+        }
+        self.is_updated = true;
+        Ok(())
+    }
+
+    fn get_key_slots(&self) -> Result<HashMap<u32, u8>, Error> {
+        get_key_slots(&self.header)
+    }
+
+    fn add_key_slot(&mut self, slot_type: u8, secret: Vec<u8>) -> Result<u32, Error> {
+        if slot_type == KEY_SLOT_PASSWORDS {
+            return Err(Error::new(ErrorKind::InvalidInput, "passwords key slot is created on save"));
+        }
+        self.set_updated()?;
+        add_key_slot(&mut self.header, slot_type, &secret, &self.keys)
+    }
+
+    fn add_recovery_key(&mut self) -> Result<Vec<u8>, Error> {
+        let recovery_key = build_recovery_key();
+        self.add_key_slot(KEY_SLOT_RECOVERY_KEY, recovery_key.clone())?;
+        Ok(recovery_key)
+    }
+
+    fn remove_key_slot(&mut self, id: u32) -> Result<(), Error> {
+        let slot_type = *self.get_key_slots()?.get(&id).ok_or(build_not_found_error())?;
+        if is_member_key_slot(&self.header, id)? {
+            return Err(Error::new(ErrorKind::InvalidInput, "member key slot is removed by member revoke"));
+        }
+        self.set_updated()?;
+        remove_key_slot(&mut self.header, id)?;
+        if slot_type == KEY_SLOT_PASSWORDS {
+            // otherwise passwords key slot will be created again on save
+            self.passwords = None;
+        }
+        Ok(())
+    }
+
+    fn set_passwords(&mut self, password_hash: Vec<u8>, password2_hash: Vec<u8>) -> Result<(), Error> {
+        self.set_updated()?;
+        self.passwords = Some((SecureBytes::new(password_hash), SecureBytes::new(password2_hash)));
+        Ok(())
+    }
+
+    fn get_members(&self) -> Result<HashMap<u32, DatabaseMember>, Error> {
+        get_members(&self.header)
+    }
+
+    fn add_member(&mut self, name: String, public_key: Vec<u8>) -> Result<u32, Error> {
+        self.set_updated()?;
+        add_member(&mut self.header, name, &public_key, &self.keys)
+    }
+
+    // revoked member may keep old master keys, so new master keys are generated and wrapped to
+    // remaining members, other key slots are removed, passwords key slot is created on save when passwords are known,
+    // returns types of removed key slots that are not recreated
+    fn revoke_member(&mut self, id: u32) -> Result<Vec<u8>, Error> {
+        let members = self.get_members()?;
+        if !members.contains_key(&id) {
+            return Err(Error::new(ErrorKind::NotFound, "member not found"));
+        }
+        if members.len() == 1 && self.passwords.is_none() {
+            return Err(Error::new(ErrorKind::InvalidInput, "at least one key slot is required"));
+        }
+        self.set_updated()?;
+        remove_member(&mut self.header, id)?;
+        self.keys = MasterKeys::new();
+        let mut removed = rotate_key_slots(&mut self.header, &self.keys)?;
+        if self.passwords.is_some() {
+            removed.retain(|t|*t != KEY_SLOT_PASSWORDS);
+        }
+        Ok(removed)
+    }
 }
 
 impl PmanDatabaseFile {
@@ -366,6 +613,9 @@ impl PmanDatabaseFile {
             data: None,
             data_length: 0,
             properties: Some(properties),
+            device_label: String::new(),
+            audit_pending: false,
+            names_version: 0
         })
     }
 
@@ -374,7 +624,10 @@ impl PmanDatabaseFile {
         Ok(PmanDatabaseFile{
             data: Some(data),
             data_length,
-            properties: None
+            properties: None,
+            device_label: String::new(),
+            audit_pending: false,
+            names_version: 0
         })
     }
 
@@ -392,10 +645,25 @@ impl PmanDatabaseFile {
         Ok(())
     }
 
+    pub fn pre_open_with_key_slot(&mut self, slot_type: u8, secret: Vec<u8>) -> Result<(), Error> {
+        if self.properties.is_some() {
+            return Err(Error::new(ErrorKind::AlreadyExists, "database properties already initialised"))
+        }
+        if self.data.is_none() {
+            return Err(Error::new(ErrorKind::NotFound, "data is not initialised"))
+        }
+        let properties =
+            PmanDatabaseProperties::pre_open_with_key_slot(self.data.as_mut().unwrap(),
+                                                           self.data_length, slot_type, secret)?;
+        self.properties = Some(properties);
+        Ok(())
+    }
+
     pub fn open(&mut self, data1: Vec<u8>, data2: Vec<u8>) -> Result<(), Error> {
         if self.properties.is_none() {
             return Err(build_properties_not_initialized_error())
         }
+        self.names_version += 1;
         self.properties.as_mut().unwrap().open(data1, data2)
     }
 
@@ -403,7 +671,9 @@ impl PmanDatabaseFile {
         if self.properties.is_none() {
             return Err(build_properties_not_initialized_error())
         }
-        self.properties.as_mut().unwrap().save()
+        let result = self.properties.as_mut().unwrap().save()?;
+        self.audit_pending = false;
+        Ok(result)
     }
 
     pub fn set_argon2(&mut self, hash_id: usize, iterations: u8, parallelism: u8, memory: u16) -> Result<(), Error> {
@@ -413,6 +683,13 @@ impl PmanDatabaseFile {
         Err(build_properties_not_initialized_error())
     }
 
+    pub fn set_encryption(&mut self, layer_id: usize, algorithms: Vec<u8>) -> Result<(), Error> {
+        if let Some(p) = &mut self.properties {
+            return p.set_encryption(layer_id, algorithms);
+        }
+        Err(build_properties_not_initialized_error())
+    }
+
     pub fn get_from_names<T: ByteValue>(&self, id: u32) -> Result<T, Error> {
         if let Some(p) = &self.properties {
             return p.get_from_names(id);
@@ -435,6 +712,7 @@ impl PmanDatabaseFile {
     }
 
     pub fn add_to_names<T: ByteValue>(&mut self, value: T) -> Result<u32, Error> {
+        self.names_version += 1;
         if let Some(p) = &mut self.properties {
             return p.add_to_names(value);
         }
@@ -442,6 +720,7 @@ impl PmanDatabaseFile {
     }
 
     pub fn set_in_names<T: ByteValue>(&mut self, id: u32, value: T) -> Result<(), Error> {
+        self.names_version += 1;
         if let Some(p) = &mut self.properties {
             return p.set_in_names(id, value);
         }
@@ -469,7 +748,40 @@ impl PmanDatabaseFile {
         Err(build_properties_not_initialized_error())
     }
 
+    // stores the value without encryption, used to simulate corrupted records
+    #[cfg(test)]
+    pub fn set_raw_in_passwords(&mut self, id: u32, value: Vec<u8>) -> Result<(), Error> {
+        if let Some(p) = &mut self.properties {
+            return p.set_raw_in_passwords(id, value);
+        }
+        Err(build_properties_not_initialized_error())
+    }
+
     pub fn remove_from_names(&mut self, id: &u32) -> Result<(), Error> {
+        self.names_version += 1;
+        if let Some(p) = &mut self.properties {
+            return p.remove_from_names(id);
+        }
+        Err(build_properties_not_initialized_error())
+    }
+
+    // the *_unindexed variants leave names_version untouched, they are used for audit log
+    // records which are not part of the entity index
+    pub fn add_to_names_unindexed<T: ByteValue>(&mut self, value: T) -> Result<u32, Error> {
+        if let Some(p) = &mut self.properties {
+            return p.add_to_names(value);
+        }
+        Err(build_properties_not_initialized_error())
+    }
+
+    pub fn set_in_names_unindexed<T: ByteValue>(&mut self, id: u32, value: T) -> Result<(), Error> {
+        if let Some(p) = &mut self.properties {
+            return p.set_in_names(id, value);
+        }
+        Err(build_properties_not_initialized_error())
+    }
+
+    pub fn remove_from_names_unindexed(&mut self, id: &u32) -> Result<(), Error> {
         if let Some(p) = &mut self.properties {
             return p.remove_from_names(id);
         }
@@ -497,6 +809,42 @@ impl PmanDatabaseFile {
         Err(build_properties_not_initialized_error())
     }
 
+    pub fn get_names_ids(&self) -> Result<Vec<u32>, Error> {
+        if let Some(p) = &self.properties {
+            return p.get_names_ids()
+        }
+        Err(build_properties_not_initialized_error())
+    }
+
+    pub fn get_passwords_ids(&self) -> Result<Vec<u32>, Error> {
+        if let Some(p) = &self.properties {
+            return p.get_passwords_ids()
+        }
+        Err(build_properties_not_initialized_error())
+    }
+
+    pub fn check_names_record(&self, id: u32) -> Result<(), Error> {
+        if let Some(p) = &self.properties {
+            return p.check_names_record(id)
+        }
+        Err(build_properties_not_initialized_error())
+    }
+
+    pub fn check_passwords_record(&self, id: u32) -> Result<(), Error> {
+        if let Some(p) = &self.properties {
+            return p.check_passwords_record(id)
+        }
+        Err(build_properties_not_initialized_error())
+    }
+
+    // returns stored and current database versions
+    pub fn get_version(&self) -> Result<(u16, u16), Error> {
+        if let Some(p) = &self.properties {
+            return Ok(p.get_version());
+        }
+        Err(build_properties_not_initialized_error())
+    }
+
     pub fn get_history_length(&self) -> Result<usize, Error> {
         if let Some(p) = &self.properties {
             return Ok(p.get_history_length())
@@ -504,6 +852,38 @@ impl PmanDatabaseFile {
         Err(build_properties_not_initialized_error())
     }
 
+    // existing entity histories are not pruned here
+    pub fn set_history_length(&mut self, history_length: usize) -> Result<(), Error> {
+        if let Some(p) = &mut self.properties {
+            return p.set_history_length(history_length);
+        }
+        Err(build_properties_not_initialized_error())
+    }
+
+    pub fn get_names_version(&self) -> u64 {
+        self.names_version
+    }
+
+    pub fn is_audit_pending(&self) -> bool {
+        self.audit_pending
+    }
+
+    pub fn set_audit_pending(&mut self) {
+        self.audit_pending = true;
+    }
+
+    pub fn get_device_label(&self) -> String {
+        self.device_label.clone()
+    }
+
+    pub fn set_device_label(&mut self, label: String) -> Result<(), Error> {
+        if label.len() > 255 {
+            return Err(Error::new(ErrorKind::InvalidInput, "device label is too long"));
+        }
+        self.device_label = label;
+        Ok(())
+    }
+
     pub fn set_file1_location_qs3(&mut self, file_name: String, s3_key: Vec<u8>) -> Result<(), Error> {
         if let Some(p) = &mut self.properties {
             p.set_file1_location_qs3(file_name, s3_key)
@@ -527,6 +907,79 @@ impl PmanDatabaseFile {
             Err(build_properties_not_initialized_error())
         }
     }
+
+    pub fn get_key_slots(&self) -> Result<HashMap<u32, u8>, Error> {
+        if let Some(p) = &self.properties {
+            p.get_key_slots()
+        } else {
+            Err(build_properties_not_initialized_error())
+        }
+    }
+
+    pub fn add_key_slot(&mut self, slot_type: u8, secret: Vec<u8>) -> Result<u32, Error> {
+        if let Some(p) = &mut self.properties {
+            p.add_key_slot(slot_type, secret)
+        } else {
+            Err(build_properties_not_initialized_error())
+        }
+    }
+
+    pub fn add_recovery_key(&mut self) -> Result<Vec<u8>, Error> {
+        if let Some(p) = &mut self.properties {
+            p.add_recovery_key()
+        } else {
+            Err(build_properties_not_initialized_error())
+        }
+    }
+
+    pub fn remove_key_slot(&mut self, id: u32) -> Result<(), Error> {
+        if let Some(p) = &mut self.properties {
+            p.remove_key_slot(id)
+        } else {
+            Err(build_properties_not_initialized_error())
+        }
+    }
+
+    pub fn set_passwords(&mut self, password_hash: Vec<u8>, password2_hash: Vec<u8>) -> Result<(), Error> {
+        if let Some(p) = &mut self.properties {
+            p.set_passwords(password_hash, password2_hash)
+        } else {
+            Err(build_properties_not_initialized_error())
+        }
+    }
+
+    pub fn get_members(&self) -> Result<HashMap<u32, DatabaseMember>, Error> {
+        if let Some(p) = &self.properties {
+            p.get_members()
+        } else {
+            Err(build_properties_not_initialized_error())
+        }
+    }
+
+    pub fn add_member(&mut self, name: String, public_key: Vec<u8>) -> Result<u32, Error> {
+        if let Some(p) = &mut self.properties {
+            p.add_member(name, public_key)
+        } else {
+            Err(build_properties_not_initialized_error())
+        }
+    }
+
+    pub fn revoke_member(&mut self, id: u32) -> Result<Vec<u8>, Error> {
+        if let Some(p) = &mut self.properties {
+            p.revoke_member(id)
+        } else {
+            Err(build_properties_not_initialized_error())
+        }
+    }
+}
+
+impl Drop for PmanDatabaseFile {
+    // main file data is decrypted in place by pre_open
+    fn drop(&mut self) {
+        if let Some(data) = &mut self.data {
+            wipe(data);
+        }
+    }
 }
 
 pub fn build_properties_not_initialized_error() -> Error {
@@ -537,6 +990,10 @@ pub fn build_names_passwords_file_not_initialized_error() -> Error {
     Error::new(ErrorKind::NotFound, "names&passwords file is not initialised")
 }
 
+fn build_passwords_required_error() -> Error {
+    Error::new(ErrorKind::InvalidInput, "passwords are required for this database version")
+}
+
 fn build_encryption_processor(algorithm_parameters: Vec<u8>, encryption_key: [u8; 32]) -> Result<Arc<dyn CryptoProcessor + Send + Sync>, Error> {
     if algorithm_parameters.len() == 0 {
         return Err(build_corrupted_data_error("build_encryption_processor"))
@@ -557,6 +1014,44 @@ pub fn set_argon2_in_header(header: &mut IdValueMap, iterations: u8, parallelism
     header.set(HASH_ALGORITHM_PROPERTIES_ID, build_argon2_properties(iterations, parallelism, memory, salt))
 }
 
+pub fn set_encryption_in_header(header: &mut IdValueMap, properties: Vec<u8>) -> Result<(), Error> {
+    header.set(ENCRYPTION_ALGORITHM1_PROPERTIES_ID, properties)
+}
+
+// single stream cipher -> [algorithm], cascade -> [ENCRYPTION_ALGORITHM_CASCADE, count, algorithm1, algorithm2, ...]
+pub fn build_encryption_layer_properties(algorithms: Vec<u8>) -> Result<Vec<u8>, Error> {
+    if algorithms.len() == 0 || algorithms.len() > 255 {
+        return Err(Error::new(ErrorKind::InvalidInput, "wrong number of encryption algorithms"));
+    }
+    for algorithm in &algorithms {
+        if *algorithm != ENCRYPTION_ALGORITHM_CHACHA20 && *algorithm != ENCRYPTION_ALGORITHM_AES_CTR {
+            return Err(build_unsupported_algorithm_error());
+        }
+    }
+    if algorithms.len() == 1 {
+        return Ok(algorithms);
+    }
+    let mut result = vec![ENCRYPTION_ALGORITHM_CASCADE, algorithms.len() as u8];
+    result.extend_from_slice(&algorithms);
+    Ok(result)
+}
+
+pub fn crypto_engine_to_algorithm(engine: CryptoEngine) -> Result<u8, Error> {
+    match engine {
+        CryptoEngine::Chacha20 => Ok(ENCRYPTION_ALGORITHM_CHACHA20),
+        CryptoEngine::AesCtr => Ok(ENCRYPTION_ALGORITHM_AES_CTR),
+        // aes block cipher is used only for records encryption
+        CryptoEngine::AES => Err(build_unsupported_algorithm_error())
+    }
+}
+
+pub fn get_cascade_algorithms(properties: &Vec<u8>) -> Result<Vec<u8>, Error> {
+    if properties.len() < 4 || properties[1] as usize != properties.len() - 2 {
+        return Err(build_corrupted_data_error("get_cascade_algorithms"));
+    }
+    Ok(properties[2..].to_vec())
+}
+
 pub fn build_aes_processor(parameters: Vec<u8>, key: [u8; 32]) -> Result<Arc<dyn CryptoProcessor + Send + Sync>, Error> {
     if parameters.len() != 1 {
         return Err(build_corrupted_data_error("build_aes_processor"));
@@ -593,6 +1088,8 @@ fn modify_algorithm_properties(mut properties: Vec<u8>) -> Result<Vec<u8>, Error
         },
         ENCRYPTION_ALGORITHM_AES => Ok(properties),
         ENCRYPTION_ALGORITHM_CHACHA20 => Ok(properties),
+        ENCRYPTION_ALGORITHM_AES_CTR => Ok(properties),
+        ENCRYPTION_ALGORITHM_CASCADE => Ok(properties),
         _ => Err(Error::new(ErrorKind::Unsupported, "unsupported algorithm"))
     }
 }
@@ -620,8 +1117,9 @@ pub fn build_encryption_keys(header: &IdValueMap, password_hash: &Vec<u8>,
     let mut hasher = Sha256::new();
     hasher.update(password_hash);
     hasher.update(password2_hash);
-    let hash = hasher.finalize();
-    let passwords_hash = Vec::from(hash.as_slice());
+    let mut hash = hasher.finalize();
+    let passwords_hash = SecureBytes::new(Vec::from(hash.as_slice()));
+    wipe(hash.as_mut_slice());
     match alg[0] {
         HASH_ALGORITHM_ARGON2 => {
             let key1 = build_argon2_key(alg.clone(), &passwords_hash)?;
@@ -632,7 +1130,7 @@ pub fn build_encryption_keys(header: &IdValueMap, password_hash: &Vec<u8>,
     }
 }
 
-fn validate_database_version(header: &IdValueMap) -> Result<usize, Error> {
+fn validate_database_version(header: &IdValueMap) -> Result<u16, Error> {
     let version_bytes: Vec<u8> = header.get(DATABASE_VERSION_ID)?;
     if version_bytes.len() != 2 {
         return Err(build_corrupted_data_error("validate_database_version"))
@@ -643,7 +1141,7 @@ fn validate_database_version(header: &IdValueMap) -> Result<usize, Error> {
     if version < DATABASE_VERSION_MIN || version > DATABASE_VERSION_MAX {
         return Err(Error::new(ErrorKind::Unsupported, "unsupported database version"))
     }
-    Ok(version as usize)
+    Ok(version)
 }
 
 pub fn default_aes_properties() -> Vec<u8> {
@@ -686,7 +1184,7 @@ pub fn build_argon2_properties(iterations: u8, parallelism: u8, memory: u16, sal
     result
 }
 
-fn set_argon2_salt(input: &mut Vec<u8>, salt: [u8; 16]) -> Result<(), Error> {
+pub fn set_argon2_salt(input: &mut Vec<u8>, salt: [u8; 16]) -> Result<(), Error> {
     if input.len() != 21 {
         Err(build_corrupted_data_error("set_argon2_salt"))
     } else {
@@ -706,6 +1204,12 @@ pub fn build_chacha_salt() -> [u8; 12] {
     result
 }
 
+pub fn build_aes_ctr_salt() -> [u8; 16] {
+    let mut result = [0u8; 16];
+    OsRng.fill_bytes(&mut result);
+    result
+}
+
 fn split_data(data: Vec<u8>) -> Option<(Vec<u8>, Vec<u8>)> {
     if data.len() == 0 {
         return None;
@@ -746,6 +1250,8 @@ mod tests {
     use rand::{Rng, RngCore};
     use rand::distributions::Standard;
     use rand::rngs::OsRng;
+    use crate::pman::key_slots::{build_key_file_secret, build_member_key_pair, KEY_SLOT_KEY_FILE, KEY_SLOT_MEMBER,
+                                 KEY_SLOT_PASSWORDS, KEY_SLOT_RECOVERY_KEY};
     use crate::pman::pman_database_file::{join_data, PmanDatabaseFile, split_data};
 
     #[test]
@@ -766,6 +1272,109 @@ mod tests {
         db2.open(d2, d3)
     }
 
+    #[test]
+    fn test_key_slots() -> Result<(), Error> {
+        let mut hash1 = [0u8; 32];
+        OsRng.fill_bytes(&mut hash1);
+        let mut hash2 = [0u8; 32];
+        OsRng.fill_bytes(&mut hash2);
+        let hash1_vec = Vec::from(hash1);
+        let hash2_vec = Vec::from(hash2);
+        let key_file = vec![1u8, 2, 3, 4, 5];
+        let mut db = PmanDatabaseFile::new(hash1_vec.clone(), hash2_vec.clone())?;
+        db.set_argon2(0, 1, 6, 1)?;
+        let recovery_key = db.add_recovery_key()?;
+        db.add_key_slot(KEY_SLOT_KEY_FILE, build_key_file_secret(&key_file))?;
+        let (data1, data2) = db.save()?;
+        let main_data = data1.unwrap();
+        let (d2, d3) = data2.unwrap();
+
+        // open with recovery key and change passwords
+        let mut db2 = PmanDatabaseFile::prepare(main_data.clone())?;
+        db2.pre_open_with_key_slot(KEY_SLOT_RECOVERY_KEY, recovery_key)?;
+        db2.open(d2.clone(), d3.clone())?;
+        assert_eq!(db2.get_key_slots()?.len(), 3);
+        db2.set_passwords(hash2_vec.clone(), hash1_vec.clone())?;
+        let (new_data1, new_data2) = db2.save()?;
+        let new_main_data = new_data1.unwrap();
+        let (nd2, nd3) = new_data2.unwrap();
+
+        // old passwords do not work
+        let mut db3 = PmanDatabaseFile::prepare(new_main_data.clone())?;
+        assert!(db3.pre_open(hash1_vec.clone(), hash2_vec.clone()).is_err());
+
+        // new passwords
+        let mut db4 = PmanDatabaseFile::prepare(new_main_data.clone())?;
+        db4.pre_open(hash2_vec, hash1_vec)?;
+        db4.open(nd2.clone(), nd3.clone())?;
+        assert_eq!(db4.get_key_slots()?.len(), 3);
+
+        // key file
+        let mut db5 = PmanDatabaseFile::prepare(new_main_data)?;
+        db5.pre_open_with_key_slot(KEY_SLOT_KEY_FILE, build_key_file_secret(&key_file))?;
+        db5.open(nd2, nd3)
+    }
+
+    #[test]
+    fn test_members() -> Result<(), Error> {
+        let mut hash1 = [0u8; 32];
+        OsRng.fill_bytes(&mut hash1);
+        let mut hash2 = [0u8; 32];
+        OsRng.fill_bytes(&mut hash2);
+        let hash1_vec = Vec::from(hash1);
+        let hash2_vec = Vec::from(hash2);
+        let alice = build_member_key_pair();
+        let bob = build_member_key_pair();
+        let mut db = PmanDatabaseFile::new(hash1_vec.clone(), hash2_vec.clone())?;
+        db.set_argon2(0, 1, 6, 1)?;
+        let recovery_key = db.add_recovery_key()?;
+        let alice_id = db.add_member("alice".to_string(), alice.public_key.clone())?;
+        let bob_id = db.add_member("bob".to_string(), bob.public_key.clone())?;
+        assert!(db.add_member("alice".to_string(), build_member_key_pair().public_key).is_err());
+        assert!(db.add_member("carol".to_string(), bob.public_key.clone()).is_err());
+        assert!(db.add_member("carol".to_string(), vec![1, 2, 3]).is_err());
+        let record_id = db.add_to_names("shared".to_string())?;
+        let (data1, data2) = db.save()?;
+        let main_data = data1.unwrap();
+        let (d2, d3) = data2.unwrap();
+
+        // bob opens the database with own private key and revokes alice
+        let mut db2 = PmanDatabaseFile::prepare(main_data.clone())?;
+        db2.pre_open_with_key_slot(KEY_SLOT_MEMBER, bob.private_key.clone())?;
+        db2.open(d2, d3)?;
+        let members = db2.get_members()?;
+        assert_eq!(members.len(), 2);
+        assert_eq!(members.get(&alice_id).map(|m|m.public_key.clone()), Some(alice.public_key.clone()));
+        assert_eq!(db2.get_key_slots()?.len(), 4);
+        assert!(db2.remove_key_slot(*db2.get_key_slots()?.iter()
+            .find(|(_id, t)|**t == KEY_SLOT_MEMBER).unwrap().0).is_err());
+        // passwords are unknown, so passwords key slot is not recreated on save
+        assert_eq!(db2.revoke_member(alice_id)?, vec![KEY_SLOT_PASSWORDS, KEY_SLOT_RECOVERY_KEY]);
+        // the only member cannot be revoked when passwords are unknown
+        assert!(db2.revoke_member(bob_id).is_err());
+        assert_eq!(db2.get_key_slots()?.len(), 1);
+        let (new_data1, new_data2) = db2.save()?;
+        let new_main_data = new_data1.unwrap();
+        let (nd2, nd3) = new_data2.unwrap();
+
+        // master keys are rotated
+        let mut db3 = PmanDatabaseFile::prepare(new_main_data.clone())?;
+        assert!(db3.pre_open_with_key_slot(KEY_SLOT_MEMBER, alice.private_key).is_err());
+        let mut db4 = PmanDatabaseFile::prepare(new_main_data.clone())?;
+        assert!(db4.pre_open_with_key_slot(KEY_SLOT_RECOVERY_KEY, recovery_key).is_err());
+        let mut db5 = PmanDatabaseFile::prepare(new_main_data.clone())?;
+        assert!(db5.pre_open(hash1_vec, hash2_vec).is_err());
+        let mut db6 = PmanDatabaseFile::prepare(new_main_data)?;
+        db6.pre_open_with_key_slot(KEY_SLOT_MEMBER, bob.private_key)?;
+        db6.open(nd2, nd3)?;
+        assert_eq!(db6.get_from_names::<String>(record_id)?, "shared");
+        assert_eq!(db6.get_members()?.len(), 1);
+
+        // passwords key slot is recreated on save when passwords are known
+        assert_eq!(db.revoke_member(alice_id)?, vec![KEY_SLOT_RECOVERY_KEY]);
+        Ok(())
+    }
+
     #[test]
     fn test_split_join() -> Result<(), Error> {
         let mut rng = rand::thread_rng();
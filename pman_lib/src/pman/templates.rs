@@ -0,0 +1,299 @@
+/*
+
+entity templates
+|-TEMPLATES_ID -> list of custom template ids (names file)
+|-template id -> template
+|     name length (u8), name
+|     fields count (u8)
+|     for each field: field type (u8), flags (u8, 1 - secret, 2 - required), name length (u8), name
+
+built-in templates have fixed ids below FIRST_RECORD_ID and are not stored in the database,
+template fields are validated against entity properties (property name = field name)
+
+*/
+
+use std::collections::{HashMap, HashSet};
+use std::io::{Error, ErrorKind};
+use crate::error_builders::build_corrupted_data_error;
+use crate::pman::id_value_map::id_value_map::ByteValue;
+use crate::structs_interfaces::{EntityTemplate, FieldType, TemplateField};
+
+const FIELD_TYPE_TEXT: u8 = 1;
+const FIELD_TYPE_URL: u8 = 2;
+const FIELD_TYPE_EMAIL: u8 = 3;
+const FIELD_TYPE_PHONE: u8 = 4;
+const FIELD_TYPE_DATE: u8 = 5;
+const FIELD_TYPE_MULTILINE: u8 = 6;
+
+const FIELD_FLAG_SECRET: u8 = 1;
+const FIELD_FLAG_REQUIRED: u8 = 2;
+
+pub const MAX_TEMPLATE_FIELDS: usize = 50;
+
+pub const TEMPLATE_CREDIT_CARD: u32 = 1;
+pub const TEMPLATE_BANK_ACCOUNT: u32 = 2;
+pub const TEMPLATE_WIFI_NETWORK: u32 = 3;
+pub const TEMPLATE_SERVER: u32 = 4;
+pub const TEMPLATE_API_KEY: u32 = 5;
+
+pub struct Template {
+    name: String,
+    fields: Vec<TemplateField>
+}
+
+impl ByteValue for Template {
+    fn from_bytes(source: Vec<u8>) -> Result<Box<Template>, Error> {
+        let (name, mut offset) = string_from_bytes(&source, 0)?;
+        if source.len() <= offset {
+            return Err(build_corrupted_data_error("Template.from_bytes1"));
+        }
+        let mut length = source[offset];
+        offset += 1;
+        let mut fields = Vec::new();
+        while length > 0 {
+            if source.len() < offset + 2 {
+                return Err(build_corrupted_data_error("Template.from_bytes2"));
+            }
+            let field_type = field_type_from_u8(source[offset])?;
+            let flags = source[offset + 1];
+            let (field_name, new_offset) = string_from_bytes(&source, offset + 2)?;
+            offset = new_offset;
+            fields.push(TemplateField{
+                name: field_name,
+                field_type,
+                secret: flags & FIELD_FLAG_SECRET != 0,
+                required: flags & FIELD_FLAG_REQUIRED != 0
+            });
+            length -= 1;
+        }
+        if offset != source.len() {
+            return Err(build_corrupted_data_error("Template.from_bytes3"));
+        }
+        Ok(Box::new(Template{ name, fields }))
+    }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut result = Vec::new();
+        string_to_bytes(&self.name, &mut result);
+        result.push(self.fields.len() as u8);
+        for field in &self.fields {
+            result.push(field_type_to_u8(field.field_type));
+            let mut flags = 0;
+            if field.secret {
+                flags |= FIELD_FLAG_SECRET;
+            }
+            if field.required {
+                flags |= FIELD_FLAG_REQUIRED;
+            }
+            result.push(flags);
+            string_to_bytes(&field.name, &mut result);
+        }
+        result
+    }
+}
+
+impl Template {
+    pub fn new(name: String, fields: Vec<TemplateField>) -> Result<Template, Error> {
+        check_name(&name, "invalid template name")?;
+        if fields.is_empty() || fields.len() > MAX_TEMPLATE_FIELDS {
+            return Err(Error::new(ErrorKind::InvalidInput, "invalid template fields count"));
+        }
+        let mut names = HashSet::new();
+        for field in &fields {
+            check_name(&field.name, "invalid template field name")?;
+            if !names.insert(field.name.clone()) {
+                return Err(Error::new(ErrorKind::InvalidInput, "duplicate template field name"));
+            }
+        }
+        Ok(Template{ name, fields })
+    }
+
+    pub fn get_name(&self) -> &String {
+        &self.name
+    }
+
+    pub fn to_entity_template(self, id: u32) -> EntityTemplate {
+        EntityTemplate{ id, name: self.name, built_in: false, fields: self.fields }
+    }
+}
+
+pub fn get_built_in_templates() -> Vec<EntityTemplate> {
+    vec![
+        build_built_in_template(TEMPLATE_CREDIT_CARD, "Credit card", vec![
+            ("Card number", FieldType::Text, true, true),
+            ("Cardholder", FieldType::Text, false, true),
+            ("Expiry date", FieldType::Text, false, true),
+            ("CVV", FieldType::Text, true, false),
+            ("PIN", FieldType::Text, true, false)
+        ]),
+        build_built_in_template(TEMPLATE_BANK_ACCOUNT, "Bank account", vec![
+            ("Bank", FieldType::Text, false, true),
+            ("Account number", FieldType::Text, false, true),
+            ("IBAN", FieldType::Text, false, false),
+            ("SWIFT", FieldType::Text, false, false),
+            ("Phone", FieldType::Phone, false, false),
+            ("PIN", FieldType::Text, true, false)
+        ]),
+        build_built_in_template(TEMPLATE_WIFI_NETWORK, "Wi-Fi network", vec![
+            ("SSID", FieldType::Text, false, true),
+            ("Security", FieldType::Text, false, false)
+        ]),
+        build_built_in_template(TEMPLATE_SERVER, "Server", vec![
+            ("Hostname", FieldType::Text, false, true),
+            ("Port", FieldType::Text, false, false),
+            ("SSH key", FieldType::Multiline, true, false)
+        ]),
+        build_built_in_template(TEMPLATE_API_KEY, "API key", vec![
+            ("API key", FieldType::Text, true, true),
+            ("Endpoint", FieldType::Url, false, false),
+            ("Expires", FieldType::Date, false, false)
+        ])
+    ]
+}
+
+fn build_built_in_template(id: u32, name: &str, fields: Vec<(&str, FieldType, bool, bool)>) -> EntityTemplate {
+    EntityTemplate{
+        id,
+        name: name.to_string(),
+        built_in: true,
+        fields: fields.into_iter()
+            .map(|(name, field_type, secret, required)|TemplateField{
+                name: name.to_string(), field_type, secret, required
+            })
+            .collect()
+    }
+}
+
+// properties - map property name -> property value
+pub fn validate_properties(template: &EntityTemplate, properties: &HashMap<String, String>) -> Result<(), Error> {
+    let fields: HashMap<&String, &TemplateField> = template.fields.iter().map(|f|(&f.name, f)).collect();
+    for (name, value) in properties {
+        let field = fields.get(name)
+            .ok_or(Error::new(ErrorKind::InvalidInput, format!("field {} is not defined in template {}", name, template.name)))?;
+        if !value.is_empty() && !validate_field_value(field.field_type, value) {
+            return Err(Error::new(ErrorKind::InvalidInput, format!("invalid {} value", name)));
+        }
+    }
+    for field in &template.fields {
+        if field.required && properties.get(&field.name).map(|v|v.is_empty()).unwrap_or(true) {
+            return Err(Error::new(ErrorKind::InvalidInput, format!("field {} is required", field.name)));
+        }
+    }
+    Ok(())
+}
+
+pub fn validate_field_value(field_type: FieldType, value: &String) -> bool {
+    match field_type {
+        FieldType::Text => !value.contains('\n'),
+        FieldType::Multiline => true,
+        FieldType::Url => !value.contains(char::is_whitespace),
+        FieldType::Email => {
+            let parts: Vec<&str> = value.split('@').collect();
+            parts.len() == 2 && !parts[0].is_empty() && !parts[1].is_empty() &&
+                !value.contains(char::is_whitespace)
+        }
+        FieldType::Phone => value.chars().any(|c|c.is_ascii_digit()) &&
+            value.chars().all(|c|c.is_ascii_digit() || "+-() ".contains(c)),
+        FieldType::Date => validate_date(value)
+    }
+}
+
+// date format: YYYY-MM-DD
+fn validate_date(value: &String) -> bool {
+    let parts: Vec<&str> = value.split('-').collect();
+    if parts.len() != 3 || parts[0].len() != 4 || parts[1].len() != 2 || parts[2].len() != 2 ||
+        !value.chars().all(|c|c.is_ascii_digit() || c == '-') {
+        return false;
+    }
+    let month: u32 = parts[1].parse().unwrap_or(0);
+    let day: u32 = parts[2].parse().unwrap_or(0);
+    month >= 1 && month <= 12 && day >= 1 && day <= 31
+}
+
+pub fn field_type_to_u8(field_type: FieldType) -> u8 {
+    match field_type {
+        FieldType::Text => FIELD_TYPE_TEXT,
+        FieldType::Url => FIELD_TYPE_URL,
+        FieldType::Email => FIELD_TYPE_EMAIL,
+        FieldType::Phone => FIELD_TYPE_PHONE,
+        FieldType::Date => FIELD_TYPE_DATE,
+        FieldType::Multiline => FIELD_TYPE_MULTILINE
+    }
+}
+
+pub fn field_type_from_u8(field_type: u8) -> Result<FieldType, Error> {
+    match field_type {
+        FIELD_TYPE_TEXT => Ok(FieldType::Text),
+        FIELD_TYPE_URL => Ok(FieldType::Url),
+        FIELD_TYPE_EMAIL => Ok(FieldType::Email),
+        FIELD_TYPE_PHONE => Ok(FieldType::Phone),
+        FIELD_TYPE_DATE => Ok(FieldType::Date),
+        FIELD_TYPE_MULTILINE => Ok(FieldType::Multiline),
+        _ => Err(build_corrupted_data_error("field_type_from_u8"))
+    }
+}
+
+fn check_name(name: &String, error_message: &str) -> Result<(), Error> {
+    if name.is_empty() || name.len() > 255 {
+        return Err(Error::new(ErrorKind::InvalidInput, error_message));
+    }
+    Ok(())
+}
+
+fn string_to_bytes(value: &String, output: &mut Vec<u8>) {
+    output.push(value.len() as u8);
+    output.extend_from_slice(value.as_bytes());
+}
+
+fn string_from_bytes(source: &Vec<u8>, offset: usize) -> Result<(String, usize), Error> {
+    if source.len() <= offset {
+        return Err(build_corrupted_data_error("string_from_bytes1"));
+    }
+    let length = source[offset] as usize;
+    let start = offset + 1;
+    if source.len() < start + length {
+        return Err(build_corrupted_data_error("string_from_bytes2"));
+    }
+    let value = String::from_utf8(source[start..start+length].to_vec())
+        .map_err(|_e|build_corrupted_data_error("string_from_bytes3"))?;
+    Ok((value, start + length))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::io::Error;
+    use crate::pman::id_value_map::id_value_map::ByteValue;
+    use crate::pman::templates::{get_built_in_templates, Template, TEMPLATE_API_KEY, validate_properties};
+    use crate::structs_interfaces::{FieldType, TemplateField};
+
+    #[test]
+    fn test_templates() -> Result<(), Error> {
+        let fields = vec![
+            TemplateField{name: "Login url".to_string(), field_type: FieldType::Url, secret: false, required: true},
+            TemplateField{name: "Recovery codes".to_string(), field_type: FieldType::Multiline, secret: true, required: false}
+        ];
+        assert!(Template::new("".to_string(), fields.clone()).is_err());
+        assert!(Template::new("Site".to_string(), vec![fields[0].clone(), fields[0].clone()]).is_err());
+        let template = Template::new("Site".to_string(), fields.clone())?;
+        let template2 = Template::from_bytes(template.to_bytes())?.to_entity_template(100);
+        assert_eq!(template2.name, "Site".to_string());
+        assert_eq!(template2.fields, fields);
+        assert!(Template::from_bytes(vec![4, 65]).is_err());
+
+        let mut properties = HashMap::from([("Recovery codes".to_string(), "1\n2".to_string())]);
+        assert!(validate_properties(&template2, &properties).is_err());
+        properties.insert("Login url".to_string(), "https://a.com".to_string());
+        validate_properties(&template2, &properties)?;
+        properties.insert("Other".to_string(), "".to_string());
+        assert!(validate_properties(&template2, &properties).is_err());
+
+        let api_key = get_built_in_templates().into_iter().find(|t|t.id == TEMPLATE_API_KEY).unwrap();
+        let mut properties = HashMap::from([("API key".to_string(), "123".to_string()),
+                                            ("Expires".to_string(), "2025-13-01".to_string())]);
+        assert!(validate_properties(&api_key, &properties).is_err());
+        properties.insert("Expires".to_string(), "2025-12-01".to_string());
+        validate_properties(&api_key, &properties)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,140 @@
+/*
+
+database users
+|-USERS_ID -> list of user ids (names file)
+|-user id -> user
+|     old format: login name
+|     new format: 0 (u8), login name, email, display name: length (u16), string
+|                 fields count (u8), for each field sorted by name: name, value: length (u16), string
+
+login name cannot be empty and cannot start with zero byte, so old format records are read as users with login name only
+
+*/
+
+use std::collections::HashMap;
+use std::io::{Error, ErrorKind};
+use crate::error_builders::build_corrupted_data_error;
+use crate::pman::id_value_map::id_value_map::ByteValue;
+use crate::structs_interfaces::DatabaseUser;
+
+const USER_FORMAT_MARKER: u8 = 0;
+
+pub const MAX_USER_FIELDS: usize = 50;
+
+impl ByteValue for DatabaseUser {
+    fn from_bytes(source: Vec<u8>) -> Result<Box<DatabaseUser>, Error> {
+        if source.first() != Some(&USER_FORMAT_MARKER) {
+            let name = String::from_utf8(source)
+                .map_err(|_e|build_corrupted_data_error("DatabaseUser.from_bytes1"))?;
+            return Ok(Box::new(DatabaseUser::new(name)));
+        }
+        let (name, offset) = string_from_bytes(&source, 1)?;
+        let (email, offset) = string_from_bytes(&source, offset)?;
+        let (display_name, mut offset) = string_from_bytes(&source, offset)?;
+        if source.len() <= offset {
+            return Err(build_corrupted_data_error("DatabaseUser.from_bytes2"));
+        }
+        let mut length = source[offset];
+        offset += 1;
+        let mut fields = HashMap::new();
+        while length > 0 {
+            let (field_name, new_offset) = string_from_bytes(&source, offset)?;
+            let (value, new_offset) = string_from_bytes(&source, new_offset)?;
+            offset = new_offset;
+            fields.insert(field_name, value);
+            length -= 1;
+        }
+        if offset != source.len() {
+            return Err(build_corrupted_data_error("DatabaseUser.from_bytes3"));
+        }
+        Ok(Box::new(DatabaseUser{ name, email, display_name, fields }))
+    }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut result = vec![USER_FORMAT_MARKER];
+        string_to_bytes(&self.name, &mut result);
+        string_to_bytes(&self.email, &mut result);
+        string_to_bytes(&self.display_name, &mut result);
+        let mut fields: Vec<(&String, &String)> = self.fields.iter().collect();
+        fields.sort();
+        result.push(fields.len() as u8);
+        for (name, value) in fields {
+            string_to_bytes(name, &mut result);
+            string_to_bytes(value, &mut result);
+        }
+        result
+    }
+}
+
+impl DatabaseUser {
+    pub fn new(name: String) -> DatabaseUser {
+        DatabaseUser{ name, email: String::new(), display_name: String::new(), fields: HashMap::new() }
+    }
+}
+
+pub fn validate_user(user: &DatabaseUser) -> Result<(), Error> {
+    if user.name.is_empty() || user.name.starts_with('\0') {
+        return Err(Error::new(ErrorKind::InvalidInput, "invalid user name"));
+    }
+    if user.fields.len() > MAX_USER_FIELDS {
+        return Err(Error::new(ErrorKind::InvalidInput, "too many user fields"));
+    }
+    if user.fields.keys().any(|name|name.is_empty()) {
+        return Err(Error::new(ErrorKind::InvalidInput, "invalid user field name"));
+    }
+    let strings = [&user.name, &user.email, &user.display_name].into_iter()
+        .chain(user.fields.iter().flat_map(|(name, value)|[name, value]));
+    for value in strings {
+        if value.len() > u16::MAX as usize {
+            return Err(Error::new(ErrorKind::InvalidInput, "user field is too long"));
+        }
+    }
+    Ok(())
+}
+
+fn string_to_bytes(value: &String, output: &mut Vec<u8>) {
+    output.extend_from_slice(&(value.len() as u16).to_le_bytes());
+    output.extend_from_slice(value.as_bytes());
+}
+
+fn string_from_bytes(source: &Vec<u8>, offset: usize) -> Result<(String, usize), Error> {
+    if source.len() < offset + 2 {
+        return Err(build_corrupted_data_error("string_from_bytes1"));
+    }
+    let length = u16::from_le_bytes([source[offset], source[offset + 1]]) as usize;
+    let start = offset + 2;
+    if source.len() < start + length {
+        return Err(build_corrupted_data_error("string_from_bytes2"));
+    }
+    let value = String::from_utf8(source[start..start+length].to_vec())
+        .map_err(|_e|build_corrupted_data_error("string_from_bytes3"))?;
+    Ok((value, start + length))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::io::Error;
+    use crate::pman::id_value_map::id_value_map::ByteValue;
+    use crate::pman::users::validate_user;
+    use crate::structs_interfaces::DatabaseUser;
+
+    #[test]
+    fn test_database_user() -> Result<(), Error> {
+        let old = DatabaseUser::from_bytes("user@a.com".to_string().to_bytes())?;
+        assert_eq!(*old, DatabaseUser::new("user@a.com".to_string()));
+        let user = DatabaseUser{
+            name: "jdoe".to_string(),
+            email: "john@a.com".to_string(),
+            display_name: "John Doe".to_string(),
+            fields: HashMap::from([("phone".to_string(), "123".to_string()), ("team".to_string(), "ops".to_string())])
+        };
+        validate_user(&user)?;
+        assert_eq!(*DatabaseUser::from_bytes(user.to_bytes())?, user);
+        let mut bytes = user.to_bytes();
+        bytes.push(0);
+        assert!(DatabaseUser::from_bytes(bytes).is_err());
+        assert!(validate_user(&DatabaseUser::new("".to_string())).is_err());
+        Ok(())
+    }
+}
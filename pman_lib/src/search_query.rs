@@ -0,0 +1,369 @@
+use std::io::{Error, ErrorKind};
+use chrono::NaiveDate;
+
+/*
+
+search query language:
+word             - entity name contains the word
+"some phrase"    - entity name contains the phrase, quotes can be used inside qualified terms too
+name:value       - entity name contains the value
+url:value        - one of entity urls contains the value
+user:value       - entity user login name, email or display name contains the value
+group:value      - entity group path (like Work/Cloud) contains the value
+tag:value        - one of entity tags contains the value
+prop:name        - entity has the property with given name
+prop:name=value  - entity property with given name contains the value, secret property values are not searched
+created<date, created<=date, created=date, created>=date, created>date - entity creation date comparison,
+modified... - same for the last modification date, date format is YYYY-MM-DD (UTC)
+a b, a AND b     - both terms match
+a OR b           - any of terms match
+NOT a            - term does not match
+(...)            - grouping
+
+NOT has the highest precedence, then AND, then OR. operators are case-sensitive, so lowercase and/or/not are words.
+all text comparisons are case-insensitive.
+
+*/
+
+const SECONDS_PER_DAY: u64 = 86400;
+
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum TextField {
+    Name,
+    Url,
+    User,
+    Group,
+    Tag
+}
+
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum DateField {
+    Created,
+    Modified
+}
+
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum DateOperator {
+    Less,
+    LessOrEqual,
+    Equal,
+    GreaterOrEqual,
+    Greater
+}
+
+#[derive(PartialEq, Debug)]
+pub enum SearchQuery {
+    // field, lowercase value
+    Text(TextField, String),
+    // lowercase property name, lowercase value
+    Property(String, Option<String>),
+    // field, operator, day start timestamp
+    Date(DateField, DateOperator, u64),
+    And(Box<SearchQuery>, Box<SearchQuery>),
+    Or(Box<SearchQuery>, Box<SearchQuery>),
+    Not(Box<SearchQuery>)
+}
+
+// entity values used by search query
+pub trait SearchTarget {
+    fn get_text_values(&self, field: TextField) -> Result<Vec<String>, Error>;
+    // property name, value pairs, value is None for secret properties
+    fn get_properties(&self) -> Result<Vec<(String, Option<String>)>, Error>;
+    fn get_timestamp(&self, field: DateField) -> Result<u64, Error>;
+}
+
+impl SearchQuery {
+    pub fn matches(&self, target: &dyn SearchTarget) -> Result<bool, Error> {
+        match self {
+            SearchQuery::Text(field, value) => Ok(target.get_text_values(*field)?.iter()
+                .any(|v|v.to_lowercase().contains(value))),
+            SearchQuery::Property(name, value) => Ok(target.get_properties()?.iter()
+                .any(|(n, v)|n.to_lowercase() == *name && match value {
+                    None => true,
+                    Some(value) => v.as_ref().map(|v|v.to_lowercase().contains(value)).unwrap_or(false)
+                })),
+            SearchQuery::Date(field, operator, day_start) => {
+                let timestamp = target.get_timestamp(*field)?;
+                let day_end = day_start + SECONDS_PER_DAY;
+                Ok(match operator {
+                    DateOperator::Less => timestamp < *day_start,
+                    DateOperator::LessOrEqual => timestamp < day_end,
+                    DateOperator::Equal => timestamp >= *day_start && timestamp < day_end,
+                    DateOperator::GreaterOrEqual => timestamp >= *day_start,
+                    DateOperator::Greater => timestamp >= day_end
+                })
+            },
+            SearchQuery::And(a, b) => Ok(a.matches(target)? && b.matches(target)?),
+            SearchQuery::Or(a, b) => Ok(a.matches(target)? || b.matches(target)?),
+            SearchQuery::Not(a) => Ok(!a.matches(target)?)
+        }
+    }
+}
+
+#[derive(PartialEq, Debug)]
+enum Token {
+    // characters with quoted flags
+    Word(Vec<(char, bool)>),
+    And,
+    Or,
+    Not,
+    Open,
+    Close
+}
+
+pub fn parse_search_query(query: &str) -> Result<SearchQuery, Error> {
+    let tokens = tokenize(query)?;
+    if tokens.is_empty() {
+        return Err(build_query_error("empty search query"));
+    }
+    let mut parser = Parser{ tokens, position: 0 };
+    let result = parser.parse_or()?;
+    if parser.position != parser.tokens.len() {
+        return Err(build_query_error("unexpected token in search query"));
+    }
+    Ok(result)
+}
+
+fn tokenize(query: &str) -> Result<Vec<Token>, Error> {
+    let mut tokens = Vec::new();
+    let mut word = Vec::new();
+    let mut quoted = false;
+    for c in query.chars() {
+        if quoted {
+            if c == '"' {
+                quoted = false;
+            } else {
+                word.push((c, true));
+            }
+            continue;
+        }
+        match c {
+            '"' => quoted = true,
+            '(' | ')' => {
+                push_word(&mut tokens, &mut word);
+                tokens.push(if c == '(' { Token::Open } else { Token::Close });
+            },
+            c if c.is_whitespace() => push_word(&mut tokens, &mut word),
+            _ => word.push((c, false))
+        }
+    }
+    if quoted {
+        return Err(build_query_error("unterminated quote in search query"));
+    }
+    push_word(&mut tokens, &mut word);
+    Ok(tokens)
+}
+
+fn push_word(tokens: &mut Vec<Token>, word: &mut Vec<(char, bool)>) {
+    if word.is_empty() {
+        return;
+    }
+    let token = if word.iter().any(|(_c, quoted)|*quoted) {
+        Token::Word(word.clone())
+    } else {
+        match word.iter().map(|(c, _q)|*c).collect::<String>().as_str() {
+            "AND" => Token::And,
+            "OR" => Token::Or,
+            "NOT" => Token::Not,
+            _ => Token::Word(word.clone())
+        }
+    };
+    tokens.push(token);
+    word.clear();
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    position: usize
+}
+
+impl Parser {
+    fn parse_or(&mut self) -> Result<SearchQuery, Error> {
+        let mut result = self.parse_and()?;
+        while self.tokens.get(self.position) == Some(&Token::Or) {
+            self.position += 1;
+            result = SearchQuery::Or(Box::new(result), Box::new(self.parse_and()?));
+        }
+        Ok(result)
+    }
+
+    // adjacent terms are joined with AND
+    fn parse_and(&mut self) -> Result<SearchQuery, Error> {
+        let mut result = self.parse_not()?;
+        loop {
+            match self.tokens.get(self.position) {
+                Some(Token::And) => self.position += 1,
+                Some(Token::Word(_)) | Some(Token::Not) | Some(Token::Open) => {},
+                _ => return Ok(result)
+            }
+            result = SearchQuery::And(Box::new(result), Box::new(self.parse_not()?));
+        }
+    }
+
+    fn parse_not(&mut self) -> Result<SearchQuery, Error> {
+        if self.tokens.get(self.position) == Some(&Token::Not) {
+            self.position += 1;
+            return Ok(SearchQuery::Not(Box::new(self.parse_not()?)));
+        }
+        self.parse_primary()
+    }
+
+    fn parse_primary(&mut self) -> Result<SearchQuery, Error> {
+        let token = self.tokens.get(self.position)
+            .ok_or(build_query_error("unexpected end of search query"))?;
+        self.position += 1;
+        match token {
+            Token::Open => {
+                let result = self.parse_or()?;
+                if self.tokens.get(self.position) != Some(&Token::Close) {
+                    return Err(build_query_error("closing bracket expected in search query"));
+                }
+                self.position += 1;
+                Ok(result)
+            },
+            Token::Word(word) => parse_term(word),
+            _ => Err(build_query_error("unexpected token in search query"))
+        }
+    }
+}
+
+fn parse_term(word: &[(char, bool)]) -> Result<SearchQuery, Error> {
+    if let Some(index) = find_unquoted(word, |c|c == ':') {
+        let qualifier = to_string(&word[..index]).to_lowercase();
+        let value = &word[index+1..];
+        let field = match qualifier.as_str() {
+            "name" => Some(TextField::Name),
+            "url" => Some(TextField::Url),
+            "user" => Some(TextField::User),
+            "group" => Some(TextField::Group),
+            "tag" => Some(TextField::Tag),
+            "prop" => return parse_property_term(value),
+            _ => None
+        };
+        if let Some(f) = field {
+            return Ok(SearchQuery::Text(f, to_value(value)?));
+        }
+    }
+    if let Some(index) = find_unquoted(word, |c|c == '<' || c == '>' || c == '=') {
+        let field = match to_string(&word[..index]).to_lowercase().as_str() {
+            "created" => Some(DateField::Created),
+            "modified" => Some(DateField::Modified),
+            _ => None
+        };
+        if let Some(f) = field {
+            return parse_date_term(f, &word[index..]);
+        }
+    }
+    Ok(SearchQuery::Text(TextField::Name, to_value(word)?))
+}
+
+fn parse_property_term(value: &[(char, bool)]) -> Result<SearchQuery, Error> {
+    match find_unquoted(value, |c|c == '=') {
+        Some(index) => Ok(SearchQuery::Property(to_value(&value[..index])?, Some(to_value(&value[index+1..])?))),
+        None => Ok(SearchQuery::Property(to_value(value)?, None))
+    }
+}
+
+fn parse_date_term(field: DateField, value: &[(char, bool)]) -> Result<SearchQuery, Error> {
+    let text = to_string(value);
+    let (operator, date) = [("<=", DateOperator::LessOrEqual), (">=", DateOperator::GreaterOrEqual),
+                            ("<", DateOperator::Less), (">", DateOperator::Greater), ("=", DateOperator::Equal)]
+        .into_iter()
+        .find_map(|(prefix, operator)|text.strip_prefix(prefix).map(|d|(operator, d)))
+        .ok_or(build_query_error("date comparison operator expected in search query"))?;
+    let timestamp = NaiveDate::parse_from_str(date, "%Y-%m-%d")
+        .map_err(|_e|build_query_error("invalid date in search query, YYYY-MM-DD expected"))?
+        .and_hms_opt(0, 0, 0).unwrap()
+        .and_utc()
+        .timestamp();
+    Ok(SearchQuery::Date(field, operator, timestamp.max(0) as u64))
+}
+
+fn find_unquoted(word: &[(char, bool)], predicate: impl Fn(char) -> bool) -> Option<usize> {
+    word.iter().position(|(c, quoted)|!*quoted && predicate(*c))
+}
+
+fn to_string(word: &[(char, bool)]) -> String {
+    word.iter().map(|(c, _q)|*c).collect()
+}
+
+fn to_value(word: &[(char, bool)]) -> Result<String, Error> {
+    if word.is_empty() {
+        return Err(build_query_error("empty value in search query"));
+    }
+    Ok(to_string(word).to_lowercase())
+}
+
+fn build_query_error(message: &str) -> Error {
+    Error::new(ErrorKind::InvalidInput, message)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Error;
+    use crate::search_query::{DateField, DateOperator, parse_search_query, SearchQuery, SearchTarget, TextField};
+
+    struct TestTarget;
+
+    impl SearchTarget for TestTarget {
+        fn get_text_values(&self, field: TextField) -> Result<Vec<String>, Error> {
+            Ok(match field {
+                TextField::Name => vec!["Amazon Prime".to_string()],
+                TextField::Url => vec!["https://amazon.com".to_string(), "https://aws.amazon.com".to_string()],
+                TextField::User => vec!["jdoe".to_string(), "john@a.com".to_string()],
+                TextField::Group => vec!["Internet/Shops".to_string()],
+                TextField::Tag => Vec::new()
+            })
+        }
+
+        fn get_properties(&self) -> Result<Vec<(String, Option<String>)>, Error> {
+            Ok(vec![("PIN".to_string(), None), ("Card type".to_string(), Some("Visa Gold".to_string()))])
+        }
+
+        fn get_timestamp(&self, field: DateField) -> Result<u64, Error> {
+            // 2024-01-01 12:00 and 2024-03-01 00:00
+            Ok(if field == DateField::Created { 1704110400 } else { 1709251200 })
+        }
+    }
+
+    fn matches(query: &str) -> Result<bool, Error> {
+        parse_search_query(query)?.matches(&TestTarget)
+    }
+
+    #[test]
+    fn test_parse_search_query() -> Result<(), Error> {
+        assert_eq!(parse_search_query("a b OR NOT c")?, SearchQuery::Or(
+            Box::new(SearchQuery::And(Box::new(SearchQuery::Text(TextField::Name, "a".to_string())),
+                                      Box::new(SearchQuery::Text(TextField::Name, "b".to_string())))),
+            Box::new(SearchQuery::Not(Box::new(SearchQuery::Text(TextField::Name, "c".to_string()))))));
+        assert_eq!(parse_search_query("\"url:x\" \"OR\"")?, SearchQuery::And(
+            Box::new(SearchQuery::Text(TextField::Name, "url:x".to_string())),
+            Box::new(SearchQuery::Text(TextField::Name, "or".to_string()))));
+        assert_eq!(parse_search_query("prop:\"Card type\"=\"a=b\"")?,
+                   SearchQuery::Property("card type".to_string(), Some("a=b".to_string())));
+        assert_eq!(parse_search_query("modified>=2024-01-01")?,
+                   SearchQuery::Date(DateField::Modified, DateOperator::GreaterOrEqual, 1704067200));
+        assert_eq!(parse_search_query("other:x")?, SearchQuery::Text(TextField::Name, "other:x".to_string()));
+        for query in ["", "(a", "a)", "a OR", "url:", "created<2024-13-01", "created<>2024-01-01", "\"a"] {
+            assert!(parse_search_query(query).is_err(), "{}", query);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_search_query_match() -> Result<(), Error> {
+        assert!(matches("prime")?);
+        assert!(matches("\"amazon prime\"")?);
+        assert!(!matches("amazon aws")?);
+        assert!(matches("amazon (aws OR url:aws)")?);
+        assert!(matches("user:JOHN group:shops NOT tag:work")?);
+        assert!(matches("prop:pin")?);
+        assert!(!matches("prop:pin=1")?);
+        assert!(matches("prop:\"card type\"=gold")?);
+        assert!(matches("created=2024-01-01 created<2024-01-02 created>2023-12-31")?);
+        assert!(!matches("created<2024-01-01")?);
+        assert!(!matches("created>2024-01-01")?);
+        assert!(matches("modified>=2024-03-01 modified<=2024-03-01")?);
+        Ok(())
+    }
+}
@@ -0,0 +1,233 @@
+use std::collections::BTreeMap;
+use std::io::Error;
+use std::mem::{ManuallyDrop, MaybeUninit};
+use std::ops::{Deref, DerefMut};
+use std::sync::Mutex;
+use std::sync::atomic::{compiler_fence, Ordering};
+
+/*
+
+Secure memory: pages holding decrypted data and keys are locked in RAM (mlock), excluded from core dumps
+(MADV_DONTDUMP on linux) and wiped when freed.
+Small allocations may share pages with each other, so pages are reference counted and unlocked only
+when the last secure allocation on the page is freed.
+Locking is best effort: when mlock fails (for example because of RLIMIT_MEMLOCK) data is still wiped on free.
+
+*/
+
+// page address -> number of secure allocations on the page
+static LOCKED_PAGES: Mutex<BTreeMap<usize, u32>> = Mutex::new(BTreeMap::new());
+
+// value is stored on the heap, memory owned by T itself is not locked
+pub struct SecureBox<T> {
+    value: ManuallyDrop<Box<T>>
+}
+
+impl<T> SecureBox<T> {
+    pub fn new(value: T) -> SecureBox<T> {
+        let value = Box::new(value);
+        lock_memory(&*value as *const T as usize, std::mem::size_of::<T>());
+        SecureBox { value: ManuallyDrop::new(value) }
+    }
+}
+
+impl<T> Deref for SecureBox<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.value
+    }
+}
+
+impl<T> DerefMut for SecureBox<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.value
+    }
+}
+
+impl<T> Drop for SecureBox<T> {
+    fn drop(&mut self) {
+        let value = unsafe { ManuallyDrop::take(&mut self.value) };
+        let ptr = Box::into_raw(value);
+        unsafe { std::ptr::drop_in_place(ptr) };
+        let address = ptr as usize;
+        wipe_memory(address, std::mem::size_of::<T>());
+        unlock_memory(address, std::mem::size_of::<T>());
+        // frees the memory without dropping the value once more
+        drop(unsafe { Box::from_raw(ptr as *mut MaybeUninit<T>) });
+    }
+}
+
+impl<T: Clone> Clone for SecureBox<T> {
+    fn clone(&self) -> SecureBox<T> {
+        SecureBox::new((**self.value).clone())
+    }
+}
+
+// immutable byte buffer
+pub struct SecureBytes {
+    data: Vec<u8>
+}
+
+impl SecureBytes {
+    pub fn new(data: Vec<u8>) -> SecureBytes {
+        lock_memory(data.as_ptr() as usize, data.capacity());
+        SecureBytes { data }
+    }
+}
+
+impl Deref for SecureBytes {
+    type Target = Vec<u8>;
+
+    fn deref(&self) -> &Vec<u8> {
+        &self.data
+    }
+}
+
+impl Drop for SecureBytes {
+    fn drop(&mut self) {
+        wipe_memory(self.data.as_ptr() as usize, self.data.capacity());
+        unlock_memory(self.data.as_ptr() as usize, self.data.capacity());
+    }
+}
+
+impl Clone for SecureBytes {
+    fn clone(&self) -> SecureBytes {
+        SecureBytes::new(self.data.clone())
+    }
+}
+
+impl PartialEq for SecureBytes {
+    fn eq(&self, other: &SecureBytes) -> bool {
+        self.data == other.data
+    }
+}
+
+pub fn wipe(data: &mut [u8]) {
+    wipe_memory(data.as_mut_ptr() as usize, data.len());
+}
+
+fn wipe_memory(address: usize, length: usize) {
+    let ptr = address as *mut u8;
+    for i in 0..length {
+        unsafe { std::ptr::write_volatile(ptr.add(i), 0) };
+    }
+    compiler_fence(Ordering::SeqCst);
+}
+
+fn get_page_size() -> usize {
+    #[cfg(unix)]
+    {
+        unsafe { libc::sysconf(libc::_SC_PAGESIZE) as usize }
+    }
+    #[cfg(not(unix))]
+    {
+        4096
+    }
+}
+
+fn get_pages(address: usize, length: usize) -> Vec<usize> {
+    if length == 0 {
+        return Vec::new();
+    }
+    let page_size = get_page_size();
+    let first = address & !(page_size - 1);
+    let last = (address + length - 1) & !(page_size - 1);
+    (first..=last).step_by(page_size).collect()
+}
+
+fn lock_memory(address: usize, length: usize) {
+    let mut pages = LOCKED_PAGES.lock().unwrap();
+    for page in get_pages(address, length) {
+        let counter = pages.entry(page).or_insert(0);
+        if *counter == 0 {
+            lock_page(page);
+        }
+        *counter += 1;
+    }
+}
+
+fn unlock_memory(address: usize, length: usize) {
+    let mut pages = LOCKED_PAGES.lock().unwrap();
+    for page in get_pages(address, length) {
+        if let Some(counter) = pages.get_mut(&page) {
+            *counter -= 1;
+            if *counter == 0 {
+                pages.remove(&page);
+                unlock_page(page);
+            }
+        }
+    }
+}
+
+#[cfg(unix)]
+fn lock_page(page: usize) {
+    let page_size = get_page_size();
+    unsafe {
+        libc::mlock(page as *const libc::c_void, page_size);
+        #[cfg(target_os = "linux")]
+        libc::madvise(page as *mut libc::c_void, page_size, libc::MADV_DONTDUMP);
+    }
+}
+
+#[cfg(unix)]
+fn unlock_page(page: usize) {
+    let page_size = get_page_size();
+    unsafe {
+        libc::munlock(page as *const libc::c_void, page_size);
+        #[cfg(target_os = "linux")]
+        libc::madvise(page as *mut libc::c_void, page_size, libc::MADV_DODUMP);
+    }
+}
+
+#[cfg(not(unix))]
+fn lock_page(_page: usize) {
+}
+
+#[cfg(not(unix))]
+fn unlock_page(_page: usize) {
+}
+
+// disables core dumps for the current process
+#[cfg(unix)]
+pub fn disable_core_dumps() -> Result<(), Error> {
+    let limit = libc::rlimit { rlim_cur: 0, rlim_max: 0 };
+    if unsafe { libc::setrlimit(libc::RLIMIT_CORE, &limit) } != 0 {
+        return Err(Error::last_os_error());
+    }
+    #[cfg(target_os = "linux")]
+    if unsafe { libc::prctl(libc::PR_SET_DUMPABLE, 0, 0, 0, 0) } != 0 {
+        return Err(Error::last_os_error());
+    }
+    Ok(())
+}
+
+#[cfg(not(unix))]
+pub fn disable_core_dumps() -> Result<(), Error> {
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::secure_memory::{get_pages, LOCKED_PAGES, SecureBox, SecureBytes, wipe};
+
+    #[test]
+    fn test_secure_memory() {
+        let bytes = SecureBytes::new(vec![1, 2, 3, 4]);
+        let pages = get_pages(bytes.as_ptr() as usize, bytes.capacity());
+        assert!(pages.iter().all(|p| LOCKED_PAGES.lock().unwrap().contains_key(p)));
+        let bytes2 = bytes.clone();
+        assert!(bytes == bytes2);
+        drop(bytes);
+        drop(bytes2);
+
+        let mut key = SecureBox::new([5u8; 32]);
+        key[0] = 6;
+        assert_eq!(key[0], 6);
+        assert_eq!(key[1], 5);
+
+        let mut data = vec![1u8, 2, 3];
+        wipe(&mut data);
+        assert_eq!(data, vec![0, 0, 0]);
+    }
+}
@@ -1,6 +1,7 @@
 use std::any::Any;
 use std::collections::HashMap;
 use std::io::Error;
+use crate::otp::OtpCode;
 use crate::structs_interfaces::PasswordDatabaseType::Pman;
 
 pub trait PasswordDatabaseEntity {
@@ -10,16 +11,40 @@ pub trait PasswordDatabaseEntity {
     fn get_group_id(&self, version: u32) -> Result<u32, Error>;
     fn get_password(&self, version: u32) -> Result<String, Error>;
     fn get_url(&self, version: u32) -> Result<Option<String>, Error>;
-    fn get_property_names(&self, version: u32) -> Result<HashMap<String, u32>, Error>;
+    // entity url (when present) followed by additional urls
+    fn get_urls(&self, version: u32) -> Result<Vec<EntityUrl>, Error>;
+    // properties in display order
+    fn get_property_names(&self, version: u32) -> Result<Vec<PropertyInfo>, Error>;
     fn get_property_value(&self, version: u32, index: u32) -> Result<String, Error>;
 
     fn get_created_at(&self, version: u32) -> Result<u64, Error>;
+    // map attachment name -> attachment id
+    fn get_attachment_names(&self, version: u32) -> Result<HashMap<String, u32>, Error>;
+    fn get_tag_ids(&self, version: u32) -> Result<Vec<u32>, Error>;
+    // explicit password expiry timestamp in seconds
+    fn get_expires_at(&self, version: u32) -> Result<Option<u64>, Error>;
+    // password max age policy, group policy is used when None
+    fn get_max_age_days(&self, version: u32) -> Result<Option<u32>, Error>;
+    // notes keep their body in password field and have no user and url
+    fn get_kind(&self) -> EntityKind;
+    // template that properties were validated against
+    fn get_template_id(&self, version: u32) -> Result<Option<u32>, Error>;
+    // entity history length, None - database default
+    fn get_history_length(&self) -> Option<u32>;
+    // returns None when entity has no otp field
+    fn get_otp_code(&self, version: u32, timestamp: u64) -> Result<Option<OtpCode>, Error>;
 }
 
 pub struct DatabaseGroup {
     pub name: String,
     pub id: u32,
-    pub entities_count: u32
+    // None for root groups
+    pub parent_id: Option<u32>,
+    pub entities_count: u32,
+    // entities count including all descendant groups
+    pub total_entities_count: u32,
+    // password max age policy for group entities, inherited from the parent group when None
+    pub max_age_days: Option<u32>
 }
 
 impl DatabaseGroup {
@@ -31,9 +56,21 @@ impl DatabaseGroup {
         self.id
     }
 
+    pub fn get_parent_id(&self) -> Option<u32> {
+        self.parent_id
+    }
+
     pub fn get_entities_count(&self) -> u32 {
         self.entities_count
     }
+
+    pub fn get_total_entities_count(&self) -> u32 {
+        self.total_entities_count
+    }
+
+    pub fn get_max_age_days(&self) -> Option<u32> {
+        self.max_age_days
+    }
 }
 
 pub trait PasswordDatabase {
@@ -46,35 +83,275 @@ pub trait PasswordDatabase {
     // open - opens database using download results.
     fn open(&self) -> Result<(), Error>;
     fn get_groups(&self) -> Result<Vec<DatabaseGroup>, Error>;
-    fn get_users(&self) -> Result<HashMap<u32, String>, Error>;
+    fn get_users(&self) -> Result<HashMap<u32, DatabaseUser>, Error>;
     fn get_entities(&self, group_id: u32) -> Result<HashMap<u32, Box<dyn PasswordDatabaseEntity + Send>>, Error>;
     fn add_user(&self, name: String) -> Result<u32, Error>;
     fn remove_user(&self, id: u32) -> Result<(), Error>;
+    fn rename_user(&self, user_id: u32, new_name: String) -> Result<(), Error>;
+    // replaces login name, email, display name and fields of the user
+    fn update_user(&self, user_id: u32, user: DatabaseUser) -> Result<(), Error>;
+    // removes user and assigns its entities to the replacement user, rewrite_history = false adds new entity versions
+    // and keeps history versions that reference the removed user
+    fn replace_user(&self, user_id: u32, replacement_id: u32, rewrite_history: bool) -> Result<(), Error>;
+    // search_string is a query like 'amazon url:aws user:john prop:pin created>=2024-01-01 OR NOT tag:work',
+    // see search_query.rs for the syntax
     fn search(&self, search_string: String) -> Result<HashMap<u32, HashMap<u32, Box<dyn PasswordDatabaseEntity + Send>>>, Error>;
+    // entities of the group (all groups when group_id is None) ordered by sort_key,
+    // returns total entities count and entities in range offset..offset+limit
+    fn list_entities(&self, group_id: Option<u32>, sort_key: EntitySortKey, descending: bool, offset: u32, limit: u32)
+        -> Result<(u32, Vec<(u32, Box<dyn PasswordDatabaseEntity + Send>)>), Error>;
+    // same as list_entities for entities matching search_string, the query syntax is the same as in search
+    fn search_entities(&self, search_string: String, sort_key: EntitySortKey, descending: bool, offset: u32, limit: u32)
+        -> Result<(u32, Vec<(u32, Box<dyn PasswordDatabaseEntity + Send>)>), Error>;
     fn add_group(&self, name: String) -> Result<u32, Error>;
+    fn add_child_group(&self, parent_id: u32, name: String) -> Result<u32, Error>;
     fn rename_group(&self, group_id: u32, new_name: String) -> Result<(), Error>;
+    // moves group with all its descendants, new_parent_id = None moves group to the root level
+    fn move_group(&self, group_id: u32, new_parent_id: Option<u32>) -> Result<(), Error>;
+    // empty descendant groups are removed too
     fn remove_group(&self, id: u32) -> Result<(), Error>;
+    // moves entities and subgroups of the group to the target group and removes the group,
+    // rewrite_history works the same way as in replace_user
+    fn merge_groups(&self, group_id: u32, target_group_id: u32, rewrite_history: bool) -> Result<(), Error>;
     fn remove_entity(&self, entity_id: u32) -> Result<(), Error>;
     fn rename_entity(&self, entity_id: u32, new_name: String) -> Result<(), Error>;
+    // properties are validated against the template when template_id is provided
     fn add_entity(&self, group_id: u32, name: String, user_id: u32, password: String,
-                  url: Option<String>, properties: HashMap<String, String>) -> Result<u32, Error>;
+                  url: Option<String>, properties: HashMap<String, String>,
+                  template_id: Option<u32>) -> Result<u32, Error>;
+    fn add_note(&self, group_id: u32, name: String, body: String) -> Result<u32, Error>;
 
     fn modify_entity(&self, entity_id: u32, new_group_id: Option<u32>,
                      new_user_id: Option<u32>, new_password: Option<String>, new_url: Option<String>,
                      change_url: bool, new_properties: HashMap<String, String>,
                      modified_properties: HashMap<u32, Option<String>>) -> Result<(), Error>;
+    // layout should contain all entity properties in the new order
+    fn set_property_layout(&self, entity_id: u32, layout: Vec<PropertyLayout>) -> Result<(), Error>;
+    // copies fields of the given history version to the new latest version
+    fn restore_entity_version(&self, entity_id: u32, version: u32) -> Result<(), Error>;
+    // first url replaces entity url, empty list removes all urls
+    fn set_entity_urls(&self, entity_id: u32, urls: Vec<EntityUrl>) -> Result<(), Error>;
+    // attachment changes add entity versions, so they share the history length with password changes,
+    // removed attachments stay available in older versions
+    fn add_attachment(&self, entity_id: u32, name: String, data: Vec<u8>) -> Result<u32, Error>;
+    fn get_attachment(&self, entity_id: u32, version: u32, attachment_id: u32) -> Result<Vec<u8>, Error>;
+    fn remove_attachment(&self, entity_id: u32, attachment_id: u32) -> Result<(), Error>;
+    // map tag id -> tag name
+    fn get_tags(&self) -> Result<HashMap<u32, String>, Error>;
+    // tag is created when it does not exist, returns tag id
+    fn add_tag(&self, entity_id: u32, tag: String) -> Result<u32, Error>;
+    // tag is removed from tags list when it is not used by any entity
+    fn remove_tag(&self, entity_id: u32, tag_id: u32) -> Result<(), Error>;
+    // returns entities that have all the given tags
+    fn get_entities_by_tags(&self, tag_ids: Vec<u32>) -> Result<HashMap<u32, Box<dyn PasswordDatabaseEntity + Send>>, Error>;
+    // returns entities that have urls matching the given url, best matches first
+    fn find_by_url(&self, url: String) -> Result<Vec<UrlMatch>, Error>;
     fn save(&self) -> Result<Option<Vec<u8>>, Error>;
 
     fn as_any(&self) -> &dyn Any;
+    fn as_mut_any(&mut self) -> &mut dyn Any;
 }
 
 pub enum HashAlgorithm {
     Argon2
 }
 
+#[derive(PartialEq, Clone, Copy, Debug)]
 pub enum CryptoEngine {
     AES,
-    Chacha20
+    Chacha20,
+    AesCtr
+}
+
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum KeySlotType {
+    Passwords,
+    RecoveryKey,
+    KeyFile,
+    Member
+}
+
+// database member that unlocks the database with own x25519 private key
+#[derive(PartialEq, Clone, Debug)]
+pub struct DatabaseMember {
+    pub name: String,
+    pub public_key: Vec<u8>
+}
+
+#[derive(PartialEq, Clone, Debug)]
+pub struct MemberKeyPair {
+    pub private_key: Vec<u8>,
+    pub public_key: Vec<u8>
+}
+
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum EntityKind {
+    Password,
+    Note
+}
+
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum FieldType {
+    Text,
+    Url,
+    Email,
+    Phone,
+    Date,
+    Multiline
+}
+
+#[derive(PartialEq, Clone, Debug)]
+pub struct PropertyInfo {
+    pub id: u32,
+    pub name: String,
+    pub field_type: FieldType,
+    // secret property values are stored in passwords file, visible ones in names file
+    pub secret: bool
+}
+
+#[derive(PartialEq, Clone, Debug)]
+pub struct PropertyLayout {
+    pub id: u32,
+    pub field_type: FieldType,
+    pub secret: bool
+}
+
+#[derive(PartialEq, Clone, Debug)]
+pub struct TemplateField {
+    pub name: String,
+    pub field_type: FieldType,
+    pub secret: bool,
+    pub required: bool
+}
+
+#[derive(Clone, Debug)]
+pub struct EntityTemplate {
+    pub id: u32,
+    pub name: String,
+    // built-in templates cannot be removed
+    pub built_in: bool,
+    pub fields: Vec<TemplateField>
+}
+
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum IntegrityIssueType {
+    DanglingReference,
+    OrphanedRecord,
+    DuplicateName,
+    UndecryptableRecord
+}
+
+#[derive(Clone, Debug)]
+pub struct IntegrityIssue {
+    pub issue_type: IntegrityIssueType,
+    pub record_id: u32,
+    pub description: String
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct ExpiringEntity {
+    pub entity_id: u32,
+    // timestamp of the current password creation
+    pub password_changed_at: u64,
+    pub expires_at: u64
+}
+
+#[derive(Clone, Debug)]
+pub struct TrashedEntity {
+    pub entity_id: u32,
+    pub name: String,
+    // original group, entity is restored into it
+    pub group_id: u32,
+    pub deleted_at: u64
+}
+
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum EntitySortKey {
+    Name,
+    CreatedAt,
+    ModifiedAt,
+    // group name, then entity name
+    Group
+}
+
+#[derive(PartialEq, Clone, Debug)]
+pub struct DatabaseUser {
+    // login name, unique in the database
+    pub name: String,
+    pub email: String,
+    pub display_name: String,
+    pub fields: HashMap<String, String>
+}
+
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum UrlMatchMode {
+    Exact,
+    Host,
+    BaseDomain,
+    Prefix,
+    Regex
+}
+
+#[derive(PartialEq, Clone, Debug)]
+pub struct EntityUrl {
+    pub url: String,
+    pub match_mode: UrlMatchMode
+}
+
+#[derive(Clone, Debug)]
+pub struct UrlMatch {
+    pub entity_id: u32,
+    pub group_id: u32,
+    // matched entity url
+    pub url: String,
+    pub match_mode: UrlMatchMode
+}
+
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum AuditEventType {
+    Add,
+    Modify,
+    Rename,
+    Remove,
+    Restore,
+    Reveal
+}
+
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum AuditObjectType {
+    Group,
+    User,
+    Entity,
+    Database
+}
+
+#[derive(PartialEq, Clone, Debug)]
+pub struct AuditEvent {
+    pub timestamp: u64,
+    pub event_type: AuditEventType,
+    pub object_type: AuditObjectType,
+    pub object_id: u32,
+    // object name at the time of the event
+    pub object_name: String,
+    // changed fields, previous name or revealed property name
+    pub details: String,
+    pub device_label: String
+}
+
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub struct AuditSettings {
+    // 0 - audit log is disabled
+    pub max_records: u32,
+    // log password and property value reveals
+    pub log_reveals: bool
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct DatabaseVersion {
+    // version of the loaded file
+    pub stored_version: u16,
+    // version that will be written on save
+    pub current_version: u16
 }
 
 #[derive(PartialEq)]
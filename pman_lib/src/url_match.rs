@@ -0,0 +1,153 @@
+use std::io::{Error, ErrorKind};
+use regex_lite::Regex;
+use crate::structs_interfaces::UrlMatchMode;
+
+/*
+
+entity url match modes:
+Exact - url is equal to the entity url, trailing slash is ignored
+Host - url host is equal to the entity url host, scheme and port are ignored
+BaseDomain - url belongs to the same registrable domain as the entity url (a.b.com matches b.com)
+Prefix - url starts with the entity url, the prefix must end with '/' or be followed by '/', '?', '#'
+         or the end of url, so https://bank.com does not match https://bank.com.evil.net
+Regex - url matches the entity url regular expression
+
+public suffix list is not used, base domain is the last two host labels or the last three labels
+when the host ends with a generic second level label and a country code (like co.uk or com.au)
+
+*/
+
+// match scores, more specific match modes rank higher
+const SCORE_EXACT: u32 = 5;
+const SCORE_REGEX: u32 = 4;
+const SCORE_PREFIX: u32 = 3;
+const SCORE_HOST: u32 = 2;
+const SCORE_BASE_DOMAIN: u32 = 1;
+
+const SECOND_LEVEL_LABELS: [&str; 7] = ["co", "com", "net", "org", "gov", "edu", "ac"];
+
+pub fn validate_url_pattern(pattern: &String, mode: UrlMatchMode) -> Result<(), Error> {
+    if pattern.is_empty() {
+        return Err(Error::new(ErrorKind::InvalidInput, "empty url"));
+    }
+    match mode {
+        UrlMatchMode::Regex => Regex::new(pattern)
+            .map(|_r|())
+            .map_err(|e|Error::new(ErrorKind::InvalidInput, format!("invalid url regex: {}", e))),
+        UrlMatchMode::Host | UrlMatchMode::BaseDomain => get_host(pattern)
+            .map(|_h|())
+            .ok_or(Error::new(ErrorKind::InvalidInput, "url host expected")),
+        UrlMatchMode::Exact | UrlMatchMode::Prefix => Ok(())
+    }
+}
+
+// returns match score or None when url does not match the pattern
+pub fn match_url(pattern: &String, mode: UrlMatchMode, url: &String) -> Option<u32> {
+    match mode {
+        UrlMatchMode::Exact => if pattern.trim_end_matches('/') == url.trim_end_matches('/') {
+            Some(SCORE_EXACT)
+        } else { None },
+        UrlMatchMode::Regex => match Regex::new(pattern) {
+            Ok(r) if r.is_match(url) => Some(SCORE_REGEX),
+            _ => None
+        },
+        UrlMatchMode::Prefix => if is_prefix_match(pattern, url) { Some(SCORE_PREFIX) } else { None },
+        UrlMatchMode::Host => {
+            let host = get_host(url)?;
+            if get_host(pattern)? == host { Some(SCORE_HOST) } else { None }
+        }
+        UrlMatchMode::BaseDomain => {
+            let host = get_host(url)?;
+            let pattern_host = get_host(pattern)?;
+            if pattern_host == host {
+                Some(SCORE_HOST)
+            } else if get_base_domain(&pattern_host) == get_base_domain(&host) {
+                Some(SCORE_BASE_DOMAIN)
+            } else { None }
+        }
+    }
+}
+
+fn is_prefix_match(pattern: &str, url: &str) -> bool {
+    match url.strip_prefix(pattern) {
+        Some(rest) => pattern.ends_with('/') || rest.is_empty() ||
+            rest.starts_with(|c|c == '/' || c == '?' || c == '#'),
+        None => false
+    }
+}
+
+// lowercase host without scheme, user info and port, scheme is optional
+fn get_host(url: &str) -> Option<String> {
+    let without_scheme = match url.find("://") {
+        Some(index) => &url[index + 3..],
+        None => url
+    };
+    let authority = without_scheme.split(|c|c == '/' || c == '?' || c == '#').next().unwrap();
+    let authority = match authority.rfind('@') {
+        Some(index) => &authority[index + 1..],
+        None => authority
+    };
+    let host = if authority.starts_with('[') {
+        // ipv6 address
+        &authority[..authority.find(']')? + 1]
+    } else {
+        authority.split(':').next().unwrap()
+    };
+    let host = host.trim_end_matches('.').to_lowercase();
+    if host.is_empty() { None } else { Some(host) }
+}
+
+fn get_base_domain(host: &String) -> String {
+    if host.starts_with('[') || host.chars().all(|c|c.is_ascii_digit() || c == '.') {
+        return host.clone();
+    }
+    let labels: Vec<&str> = host.split('.').collect();
+    let count = if labels.len() >= 3 && labels[labels.len() - 1].len() == 2 &&
+        SECOND_LEVEL_LABELS.contains(&labels[labels.len() - 2]) { 3 } else { 2 };
+    if labels.len() <= count {
+        return host.clone();
+    }
+    labels[labels.len() - count..].join(".")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::structs_interfaces::UrlMatchMode;
+    use crate::url_match::{get_base_domain, get_host, match_url, validate_url_pattern};
+
+    #[test]
+    fn test_url_match() {
+        assert_eq!(get_host("https://user@Login.Example.com:8443/path?q=1"), Some("login.example.com".to_string()));
+        assert_eq!(get_host("example.com/path"), Some("example.com".to_string()));
+        assert_eq!(get_host("http://[::1]:8080/"), Some("[::1]".to_string()));
+        assert_eq!(get_host("https:///path"), None);
+        assert_eq!(get_base_domain(&"a.b.example.com".to_string()), "example.com".to_string());
+        assert_eq!(get_base_domain(&"login.bank.co.uk".to_string()), "bank.co.uk".to_string());
+        assert_eq!(get_base_domain(&"192.168.1.1".to_string()), "192.168.1.1".to_string());
+
+        let url = "https://login.example.com/signin".to_string();
+        let check = |pattern: &str, mode: UrlMatchMode|match_url(&pattern.to_string(), mode, &url);
+        assert_eq!(check("https://login.example.com/signin/", UrlMatchMode::Exact), Some(5));
+        assert_eq!(check("https://login.example.com/", UrlMatchMode::Exact), None);
+        assert_eq!(check(r"^https://[a-z]+\.example\.com/", UrlMatchMode::Regex), Some(4));
+        assert_eq!(check("https://login.example.com/", UrlMatchMode::Prefix), Some(3));
+        assert_eq!(check("https://login.example.com", UrlMatchMode::Prefix), Some(3));
+        assert_eq!(check("https://login.example.com/signin", UrlMatchMode::Prefix), Some(3));
+        assert_eq!(check("https://login.example.com/sign", UrlMatchMode::Prefix), None);
+        assert_eq!(match_url(&"https://bank.com".to_string(), UrlMatchMode::Prefix,
+                             &"https://bank.com.evil.net/login".to_string()), None);
+        assert_eq!(match_url(&"https://bank.com".to_string(), UrlMatchMode::Prefix,
+                             &"https://bank.com?next=1".to_string()), Some(3));
+        assert_eq!(check("http://login.example.com", UrlMatchMode::Host), Some(2));
+        assert_eq!(check("example.com", UrlMatchMode::Host), None);
+        assert_eq!(check("example.com", UrlMatchMode::BaseDomain), Some(1));
+        assert_eq!(check("login.example.com", UrlMatchMode::BaseDomain), Some(2));
+        assert_eq!(check("example.org", UrlMatchMode::BaseDomain), None);
+        assert_eq!(match_url(&"bank.co.uk".to_string(), UrlMatchMode::BaseDomain,
+                             &"https://other.co.uk".to_string()), None);
+
+        assert!(validate_url_pattern(&"(".to_string(), UrlMatchMode::Regex).is_err());
+        assert!(validate_url_pattern(&"".to_string(), UrlMatchMode::Exact).is_err());
+        assert!(validate_url_pattern(&"https://".to_string(), UrlMatchMode::Host).is_err());
+    }
+}